@@ -1,22 +1,157 @@
 // HTTP client for communicating with the Sentinel backend API
 
+use crate::api::retry::{is_retryable, retry_with_backoff, BackoffPolicy, ConnectionState};
 use crate::types::*;
 use anyhow::{Context, Result};
 use futures::{Stream, StreamExt};
-use reqwest::Client;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{Client, Url};
+use std::future::Future;
 use std::pin::Pin;
 use std::time::Duration;
+use tokio::sync::watch;
 
 /// API client for Sentinel Orchestrator backend
 pub struct ApiClient {
     client: Client,
     base_url: String,
     api_key: Option<String>,
+    /// Reachability observed by the last retried call, published to subscribers
+    connection: watch::Sender<ConnectionState>,
+}
+
+/// Normalize a user-supplied base URL
+///
+/// Trims surrounding whitespace, defaults the scheme to `http` when absent,
+/// strips trailing slashes and validates that the result parses with a host.
+///
+/// # Arguments
+/// * `base_url` - Raw base URL, e.g. `localhost:3000` or `http://host:3000/`
+///
+/// # Returns
+/// * `Ok(String)` - Normalized base URL without a trailing slash
+/// * `Err` - If the URL cannot be parsed or has no host
+pub fn normalize_base_url(base_url: &str) -> Result<String> {
+    let trimmed = base_url.trim();
+    if trimmed.is_empty() {
+        anyhow::bail!("Invalid API base URL: URL is empty");
+    }
+
+    let with_scheme = if trimmed.contains("://") {
+        trimmed.to_string()
+    } else {
+        format!("http://{}", trimmed)
+    };
+    let normalized = with_scheme.trim_end_matches('/').to_string();
+
+    let parsed =
+        Url::parse(&normalized).with_context(|| format!("Invalid API base URL '{}'", base_url))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        anyhow::bail!(
+            "Invalid API base URL '{}': unsupported scheme '{}'",
+            base_url,
+            parsed.scheme()
+        );
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        anyhow::bail!("Invalid API base URL '{}': missing host", base_url);
+    }
+
+    Ok(normalized)
+}
+
+/// Find the end of the last complete server-sent event in raw stream bytes
+///
+/// # Returns
+/// The index just past the blank line (`\n\n` or `\r\n\r\n`) ending the last complete
+/// event, or `None` if no event is complete yet
+fn last_event_end(buffer: &[u8]) -> Option<usize> {
+    (1..buffer.len())
+        .rev()
+        .find(|&i| {
+            buffer[i] == b'\n'
+                && (buffer[i - 1] == b'\n' || (i >= 2 && buffer[i - 2..i] == *b"\n\r"))
+        })
+        .map(|i| i + 1)
+}
+
+/// Remove complete server-sent events from `buffer` and return their data
+///
+/// Events end with a blank line; an incomplete trailing event stays in the buffer.
+/// Multiple `data:` lines are joined with newlines, and events without data
+/// (such as keep-alive comments) are skipped.
+///
+/// # Note
+/// The buffer holds raw bytes so a multi-byte character split across network chunks
+/// is decoded only once both halves have arrived; line breaks never occur inside a
+/// UTF-8 character, so complete events always decode whole.
+pub fn take_sse_events(buffer: &mut Vec<u8>) -> Vec<String> {
+    let Some(end) = last_event_end(buffer) else {
+        return Vec::new();
+    };
+    let complete: Vec<u8> = buffer.drain(..end).collect();
+    let complete = String::from_utf8_lossy(&complete).replace("\r\n", "\n");
+
+    complete
+        .split("\n\n")
+        .filter_map(|event| {
+            let data: Vec<&str> = event
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(|data| data.strip_prefix(' ').unwrap_or(data))
+                .collect();
+            (!data.is_empty()).then(|| data.join("\n"))
+        })
+        .collect()
+}
+
+/// Data of a chat stream event that marks the end of the stream
+const STREAM_DONE_MARKER: &str = "[DONE]";
+
+/// Extract the text delta from one chat completion stream event
+///
+/// Accepts OpenAI-style chunks (`{"choices":[{"delta":{"content":"..."}}]}`) and
+/// falls back to treating the event data as plain text.
+///
+/// # Returns
+/// * `Some(String)` - Text to append to the reply (possibly empty)
+/// * `None` - The event is the `[DONE]` end-of-stream marker
+pub fn parse_chat_stream_event(data: &str) -> Option<String> {
+    if data.trim() == STREAM_DONE_MARKER {
+        return None;
+    }
+    match serde_json::from_str::<serde_json::Value>(data) {
+        // A chunk without content (e.g. the role-only first chunk) adds no text
+        Ok(chunk) if chunk.get("choices").is_some() => Some(
+            chunk["choices"][0]["delta"]["content"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+        ),
+        _ => Some(data.to_string()),
+    }
+}
+
+/// Remove complete chat stream events from `buffer` and return their text deltas
+///
+/// # Returns
+/// Tuple of (deltas, done), where `done` is set once the `[DONE]` marker was seen;
+/// events after the marker are ignored.
+pub fn take_chat_deltas(buffer: &mut Vec<u8>) -> (Vec<String>, bool) {
+    let mut deltas = Vec::new();
+    for event in take_sse_events(buffer) {
+        match parse_chat_stream_event(&event) {
+            Some(delta) => deltas.push(delta),
+            None => return (deltas, true),
+        }
+    }
+    (deltas, false)
 }
 
 impl ApiClient {
     /// Create a new API client
     pub fn new(base_url: String) -> Result<Self> {
+        let base_url = normalize_base_url(&base_url)?;
         let client = Client::builder()
             .timeout(Duration::from_secs(30))
             .build()
@@ -26,11 +161,13 @@ impl ApiClient {
             client,
             base_url,
             api_key: None,
+            connection: watch::Sender::new(ConnectionState::default()),
         })
     }
 
     /// Create a new API client with authentication
     pub fn with_api_key(base_url: String, api_key: String) -> Result<Self> {
+        let base_url = normalize_base_url(&base_url)?;
         let client = Client::builder()
             .timeout(Duration::from_secs(30))
             .build()
@@ -40,6 +177,7 @@ impl ApiClient {
             client,
             base_url,
             api_key: Some(api_key),
+            connection: watch::Sender::new(ConnectionState::default()),
         })
     }
 
@@ -52,139 +190,394 @@ impl ApiClient {
         }
     }
 
-    /// Get health status
-    pub async fn health(&self) -> Result<HealthStatus> {
-        let url = format!("{}/health", self.base_url);
-        let request = self.client.get(&url);
-        let response = self
-            .add_auth_header(request)
-            .send()
-            .await
-            .context("Failed to send health check request")?;
-
-        let status = response
-            .json::<HealthStatus>()
-            .await
-            .context("Failed to parse health status")?;
+    /// Subscribe to the backend reachability observed by retried calls
+    ///
+    /// Retries publish their state here as they happen, so the UI can show reconnect
+    /// progress without waiting on whichever task is making the request.
+    pub fn subscribe_connection(&self) -> watch::Receiver<ConnectionState> {
+        self.connection.subscribe()
+    }
 
-        Ok(status)
+    fn set_connection_state(&self, state: ConnectionState) {
+        self.connection.send_replace(state);
     }
 
-    /// Get readiness status
-    pub async fn ready(&self) -> Result<HealthStatus> {
-        let url = format!("{}/health/ready", self.base_url);
-        let request = self.client.get(&url);
-        let response = self
-            .add_auth_header(request)
-            .send()
-            .await
-            .context("Failed to send readiness check request")?;
+    /// Run an operation with retries, tracking the connection state
+    ///
+    /// The state is `Reconnecting` while connection failures are retried, `Down` once
+    /// they are exhausted, and `Connected` whenever the backend answers, even with an
+    /// API error.
+    async fn with_retries<T, Op, Fut>(
+        &self,
+        policy: &BackoffPolicy,
+        operation: Op,
+        mut on_retry: impl FnMut(u32, Duration),
+    ) -> Result<T>
+    where
+        Op: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let result = retry_with_backoff(policy, operation, |attempt, delay| {
+            self.set_connection_state(ConnectionState::Reconnecting {
+                attempt,
+                max_attempts: policy.max_attempts,
+            });
+            on_retry(attempt, delay);
+        })
+        .await;
 
-        let status = response
-            .json::<HealthStatus>()
-            .await
-            .context("Failed to parse readiness status")?;
+        self.set_connection_state(match &result {
+            Err(e) if is_retryable(e) => ConnectionState::Down,
+            _ => ConnectionState::Connected,
+        });
+        result
+    }
 
-        Ok(status)
+    /// Get health status, retrying connection failures with a short backoff
+    pub async fn health(&self) -> Result<HealthStatus> {
+        self.with_retries(&BackoffPolicy::short(), || self.fetch_health(), |_, _| {})
+            .await
     }
 
-    /// Get liveness status
-    pub async fn live(&self) -> Result<HealthStatus> {
-        let url = format!("{}/health/live", self.base_url);
+    /// Make a single health check request
+    async fn fetch_health(&self) -> Result<HealthStatus> {
+        let url = format!("{}/health", self.base_url);
         let request = self.client.get(&url);
         let response = self
             .add_auth_header(request)
             .send()
             .await
-            .context("Failed to send liveness check request")?;
+            .context("Failed to send health check request")?;
 
         let status = response
             .json::<HealthStatus>()
             .await
-            .context("Failed to parse liveness status")?;
+            .context("Failed to parse health status")?;
 
         Ok(status)
     }
 
-    /// Create a chat completion (non-streaming)
-    pub async fn chat_completion(
+    /// Stream a chat completion
+    ///
+    /// Returns a stream of text deltas from the LLM response. A server that answers
+    /// with a plain JSON completion instead of server-sent events yields the whole
+    /// reply as a single chunk.
+    pub async fn stream_chat_completion(
         &self,
         request: ChatCompletionRequest,
-    ) -> Result<ChatCompletionResponse> {
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<String, anyhow::Error>> + Send>>> {
         let url = format!("{}/v1/chat/completions", self.base_url);
-        let request_builder = self.client.post(&url).json(&request);
+        let mut stream_request = request;
+        stream_request.stream = true;
+
+        let request_builder = self.client.post(&url).json(&stream_request);
         let response = self
             .add_auth_header(request_builder)
             .send()
             .await
-            .context("Failed to send chat completion request")?;
+            .context("Failed to send streaming chat completion request")?;
 
         let status = response.status();
         if !status.is_success() {
+            // For error responses, try to parse error message
+            // We need to clone status before consuming response
             let status_code = status.as_u16();
-            let error: ErrorResponse = response
-                .json()
+            // Consume response for error parsing - we can't use it after this
+            let error_msg = match response.json::<ErrorResponse>().await {
+                Ok(error) => format!("API error: {} - {}", error.code, error.message),
+                Err(_) => format!("HTTP error: {}", status_code),
+            };
+            anyhow::bail!("{}", error_msg);
+        }
+
+        let is_event_stream = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+        if !is_event_stream {
+            let completion = response
+                .json::<ChatCompletionResponse>()
                 .await
-                .unwrap_or_else(|_| ErrorResponse {
-                    code: "unknown".to_string(),
-                    message: format!("HTTP {}", status_code),
-                    details: None,
-                });
-            anyhow::bail!("API error: {} - {}", error.code, error.message);
+                .context("Failed to parse chat completion response")?;
+            return Ok(Box::pin(futures::stream::once(async move {
+                Ok(completion.message.content)
+            })));
         }
 
-        let completion = response
-            .json::<ChatCompletionResponse>()
-            .await
-            .context("Failed to parse chat completion response")?;
+        // Server-sent events: emit text deltas until the [DONE] marker or the stream ends
+        let deltas = response
+            .bytes_stream()
+            .scan((Vec::new(), false), |(buffer, done), chunk| {
+                if *done {
+                    return futures::future::ready(None);
+                }
+                let deltas = match chunk {
+                    Ok(bytes) => {
+                        buffer.extend_from_slice(&bytes);
+                        let (deltas, finished) = take_chat_deltas(buffer);
+                        *done = finished;
+                        deltas.into_iter().map(Ok).collect()
+                    }
+                    Err(e) => vec![Err(anyhow::Error::new(e).context("Stream error"))],
+                };
+                futures::future::ready(Some(futures::stream::iter(deltas)))
+            })
+            .flatten();
 
-        Ok(completion)
+        Ok(Box::pin(deltas))
     }
 
-    /// Stream a chat completion
-    /// Returns a stream of text chunks from the LLM response
-    pub async fn stream_chat_completion(
+    /// Open a chat completion stream, retrying connection failures with backoff
+    ///
+    /// Only opening the stream is retried; an error while reading it is yielded by the
+    /// stream so the caller can keep the partial reply.
+    ///
+    /// # Arguments
+    /// * `request` - The chat completion request to (re)send
+    /// * `policy` - Backoff policy controlling delays and the attempt cap
+    /// * `on_retry` - Called with (next attempt number, delay) before each reconnect
+    pub async fn open_chat_stream_with_reconnect(
         &self,
         request: ChatCompletionRequest,
+        policy: &BackoffPolicy,
+        on_retry: impl FnMut(u32, Duration),
     ) -> Result<Pin<Box<dyn Stream<Item = Result<String, anyhow::Error>> + Send>>> {
-        let url = format!("{}/v1/chat/completions", self.base_url);
-        let mut stream_request = request;
-        stream_request.stream = true;
+        self.with_retries(
+            policy,
+            || self.stream_chat_completion(request.clone()),
+            on_retry,
+        )
+        .await
+    }
 
-        let request_builder = self.client.post(&url).json(&stream_request);
+    /// Stream server log lines from the admin log stream endpoint
+    ///
+    /// Yields the server's buffered recent lines first, then new lines as they are
+    /// logged. Requires an admin API key.
+    pub async fn stream_logs(
+        &self,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<String, anyhow::Error>> + Send>>> {
+        let url = format!("{}/v1/logs/stream", self.base_url);
         let response = self
-            .add_auth_header(request_builder)
+            .add_auth_header(self.client.get(&url))
             .send()
             .await
-            .context("Failed to send streaming chat completion request")?;
+            .context("Failed to send log stream request")?;
 
         let status = response.status();
         if !status.is_success() {
-            // For error responses, try to parse error message
-            // We need to clone status before consuming response
-            let status_code = status.as_u16();
-            // Consume response for error parsing - we can't use it after this
             let error_msg = match response.json::<ErrorResponse>().await {
                 Ok(error) => format!("API error: {} - {}", error.code, error.message),
-                Err(_) => format!("HTTP error: {}", status_code),
+                Err(_) => format!("HTTP error: {}", status.as_u16()),
             };
             anyhow::bail!("{}", error_msg);
         }
 
-        // For Server-Sent Events (SSE) or chunked responses
-        // Parse the stream line by line
-        let stream = response
+        let lines = response
             .bytes_stream()
-            .map(|result| {
-                result
-                    .map(|bytes| {
-                        // Try to parse as UTF-8, handling partial chunks
-                        String::from_utf8_lossy(bytes.as_ref()).to_string()
-                    })
-                    .map_err(|e| anyhow::anyhow!("Stream error: {}", e))
-            });
+            .scan(Vec::new(), |buffer, chunk| {
+                let lines = match chunk {
+                    Ok(bytes) => {
+                        buffer.extend_from_slice(&bytes);
+                        take_sse_events(buffer).into_iter().map(Ok).collect()
+                    }
+                    Err(e) => vec![Err(anyhow::Error::new(e).context("Log stream error"))],
+                };
+                futures::future::ready(Some(futures::stream::iter(lines)))
+            })
+            .flatten();
+
+        Ok(Box::pin(lines))
+    }
+
+    /// Stream a chat completion and collect the full response text
+    ///
+    /// Fails if the connection drops before the stream completes.
+    pub async fn collect_chat_completion(&self, request: ChatCompletionRequest) -> Result<String> {
+        let mut stream = self.stream_chat_completion(request).await?;
+        let mut content = String::new();
+        while let Some(chunk) = stream.next().await {
+            content.push_str(&chunk?);
+        }
+        Ok(content)
+    }
 
-        Ok(Box::pin(stream))
+    /// Stream a chat completion, reconnecting with exponential backoff if the connection drops
+    ///
+    /// A dropped stream is resumed by resending the whole conversation, so any partial
+    /// response from the failed attempt is discarded.
+    ///
+    /// # Arguments
+    /// * `request` - The chat completion request to (re)send
+    /// * `policy` - Backoff policy controlling delays and the attempt cap
+    /// * `on_retry` - Called with (next attempt number, delay) before each reconnect
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Full response text from the first attempt that completes
+    /// * `Err` - The API error, or the last connection error once attempts are exhausted
+    pub async fn stream_chat_completion_with_reconnect(
+        &self,
+        request: ChatCompletionRequest,
+        policy: &BackoffPolicy,
+        on_retry: impl FnMut(u32, Duration),
+    ) -> Result<String> {
+        self.with_retries(
+            policy,
+            || self.collect_chat_completion(request.clone()),
+            on_retry,
+        )
+        .await
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_defaults_scheme_to_http() {
+        assert_eq!(
+            normalize_base_url("localhost:3000").unwrap(),
+            "http://localhost:3000"
+        );
+    }
+
+    #[test]
+    fn test_normalize_strips_trailing_slash() {
+        assert_eq!(
+            normalize_base_url("http://localhost:3000/").unwrap(),
+            "http://localhost:3000"
+        );
+        assert_eq!(
+            normalize_base_url("  https://api.example.com/sentinel//  ").unwrap(),
+            "https://api.example.com/sentinel"
+        );
+    }
+
+    #[test]
+    fn test_normalize_rejects_invalid_urls() {
+        assert!(normalize_base_url("").is_err());
+        assert!(normalize_base_url("   ").is_err());
+        assert!(normalize_base_url("http://").is_err());
+        assert!(normalize_base_url("http://local host:3000").is_err());
+        assert!(normalize_base_url("localhost:notaport").is_err());
+        assert!(normalize_base_url("ftp://localhost:3000").is_err());
+    }
+
+    #[test]
+    fn test_client_constructors_normalize_base_url() {
+        let client = ApiClient::new("localhost:3000/".to_string()).unwrap();
+        assert_eq!(client.base_url, "http://localhost:3000");
+
+        let client =
+            ApiClient::with_api_key("127.0.0.1:8080".to_string(), "sk-test".to_string()).unwrap();
+        assert_eq!(client.base_url, "http://127.0.0.1:8080");
+
+        assert!(ApiClient::new("http://".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_take_sse_events_keeps_partial_event() {
+        let mut buffer =
+            b"data: [12:00:01] INFO first\n\n: keep-alive\n\ndata: [12:00:02] WA".to_vec();
+
+        assert_eq!(take_sse_events(&mut buffer), vec!["[12:00:01] INFO first"]);
+        assert_eq!(buffer, b"data: [12:00:02] WA");
+
+        buffer.extend_from_slice(b"RN second\r\ndata: more\r\n\r\n");
+        assert_eq!(
+            take_sse_events(&mut buffer),
+            vec!["[12:00:02] WARN second\nmore"]
+        );
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_take_sse_events_joins_character_split_across_chunks() {
+        let event = "data: caf\u{e9} \u{2713}\n\n".as_bytes();
+        // Split inside the two-byte "é"
+        let split = event.iter().position(|&b| b == 0xc3).unwrap() + 1;
+        let mut buffer = event[..split].to_vec();
+
+        assert!(take_sse_events(&mut buffer).is_empty());
+        buffer.extend_from_slice(&event[split..]);
+        assert_eq!(take_sse_events(&mut buffer), vec!["caf\u{e9} \u{2713}"]);
+    }
+
+    #[test]
+    fn test_parse_chat_stream_event_formats() {
+        assert_eq!(
+            parse_chat_stream_event(r#"{"choices":[{"delta":{"content":"Hel"}}]}"#),
+            Some("Hel".to_string())
+        );
+        assert_eq!(
+            parse_chat_stream_event(r#"{"choices":[{"delta":{}}]}"#),
+            Some(String::new())
+        );
+        assert_eq!(
+            parse_chat_stream_event("plain text"),
+            Some("plain text".to_string())
+        );
+        assert_eq!(parse_chat_stream_event("[DONE]"), None);
+    }
+
+    #[test]
+    fn test_take_chat_deltas_stops_at_done_marker() {
+        let mut buffer = b"data: one\n\ndata: two\n\ndata: [DONE]\n\ndata: late\n\n".to_vec();
+        let (deltas, done) = take_chat_deltas(&mut buffer);
+        assert_eq!(deltas, vec!["one", "two"]);
+        assert!(done);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_backend_is_marked_down() {
+        // Nothing listens on port 1, so every attempt fails to connect
+        let client = ApiClient::new("127.0.0.1:1".to_string()).unwrap();
+        assert_eq!(*client.connection.borrow(), ConnectionState::Connected);
+        let policy = BackoffPolicy {
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+            multiplier: 1,
+            max_attempts: 2,
+        };
+        let mut observed = Vec::new();
+
+        let result = client
+            .with_retries(
+                &policy,
+                || client.fetch_health(),
+                |_, _| observed.push(*client.connection.borrow()),
+            )
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            observed,
+            vec![ConnectionState::Reconnecting {
+                attempt: 2,
+                max_attempts: 2
+            }]
+        );
+        assert_eq!(*client.connection.borrow(), ConnectionState::Down);
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_observe_connection_state() {
+        let client = ApiClient::new("127.0.0.1:1".to_string()).unwrap();
+        let mut connection = client.subscribe_connection();
+        let policy = BackoffPolicy {
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+            multiplier: 1,
+            max_attempts: 2,
+        };
+
+        let _ = client
+            .with_retries(&policy, || client.fetch_health(), |_, _| {})
+            .await;
+
+        assert!(connection.has_changed().unwrap());
+        assert_eq!(*connection.borrow_and_update(), ConnectionState::Down);
+    }
+}
@@ -1,4 +1,4 @@
 pub mod client;
+pub mod retry;
 
 pub use client::ApiClient;
-
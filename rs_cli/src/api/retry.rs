@@ -0,0 +1,308 @@
+// Reconnection with exponential backoff for API calls
+// Only connection-level failures are retried; API errors are surfaced immediately
+
+use anyhow::Result;
+use std::future::Future;
+use std::time::Duration;
+
+/// Default number of attempts (including the first) before giving up
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// Backoff policy for reconnecting a dropped stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackoffPolicy {
+    /// Delay before the first retry
+    pub initial_delay: Duration,
+    /// Upper bound on any single delay
+    pub max_delay: Duration,
+    /// Factor the delay grows by after each retry
+    pub multiplier: u32,
+    /// Total attempts, including the first, before surfacing the error
+    pub max_attempts: u32,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            multiplier: 2,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// Short policy for quick calls such as health checks
+    pub fn short() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(1),
+            multiplier: 2,
+            max_attempts: 3,
+        }
+    }
+
+    /// Delay to wait before the given retry (1-based)
+    ///
+    /// # Arguments
+    /// * `retry` - Retry number, where 1 is the first retry after the initial attempt
+    pub fn delay_for_retry(&self, retry: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .checked_pow(retry.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.initial_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+/// Reachability of the backend as observed by the last API call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConnectionState {
+    /// The backend answered (possibly with an API error)
+    #[default]
+    Connected,
+    /// A connection failure is being retried
+    Reconnecting {
+        /// Attempt about to be made (the first retry is attempt 2)
+        attempt: u32,
+        /// Total attempts before giving up
+        max_attempts: u32,
+    },
+    /// Retries were exhausted without reaching the backend
+    Down,
+}
+
+impl ConnectionState {
+    /// Short label for display
+    pub fn label(&self) -> String {
+        match self {
+            ConnectionState::Connected => "Connected".to_string(),
+            ConnectionState::Reconnecting {
+                attempt,
+                max_attempts,
+            } => format!("Reconnecting (attempt {}/{})", attempt, max_attempts),
+            ConnectionState::Down => "Down".to_string(),
+        }
+    }
+}
+
+/// What to do after an attempt failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Wait for the delay, then make another attempt
+    Retry(Duration),
+    /// Surface the error
+    GiveUp,
+}
+
+/// Decide whether to retry after a failed attempt
+///
+/// # Arguments
+/// * `policy` - Backoff policy controlling delays and the attempt cap
+/// * `attempt` - Number of the attempt that just failed (1-based)
+/// * `retryable` - Whether the failure was a connection-level error (see [`is_retryable`])
+pub fn retry_decision(policy: &BackoffPolicy, attempt: u32, retryable: bool) -> RetryDecision {
+    if retryable && attempt < policy.max_attempts.max(1) {
+        RetryDecision::Retry(policy.delay_for_retry(attempt))
+    } else {
+        RetryDecision::GiveUp
+    }
+}
+
+/// Whether an error means the backend could not be reached at all
+///
+/// Narrower than [`is_retryable`]: a backend that answered with a malformed or
+/// truncated body was reachable, so only connect failures and timeouts count.
+pub fn is_unreachable(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .any(|e| e.is_connect() || e.is_timeout())
+}
+
+/// Whether an error is a connection-level failure worth reconnecting for
+///
+/// HTTP-level API errors (bad request, auth failures) are not retried.
+pub fn is_retryable(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .any(|e| e.is_connect() || e.is_timeout() || e.is_request() || e.is_body() || e.is_decode())
+}
+
+/// Run an operation, retrying retryable failures with exponential backoff
+///
+/// # Arguments
+/// * `policy` - Backoff policy controlling delays and the attempt cap
+/// * `operation` - Operation to run; called once per attempt
+/// * `on_retry` - Called with (next attempt number, delay) before each retry
+///
+/// # Returns
+/// * `Ok(T)` - Result of the first successful attempt
+/// * `Err` - The first non-retryable error, or the last error once attempts are exhausted
+pub async fn retry_with_backoff<T, Op, Fut>(
+    policy: &BackoffPolicy,
+    mut operation: Op,
+    mut on_retry: impl FnMut(u32, Duration),
+) -> Result<T>
+where
+    Op: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+
+    loop {
+        let error = match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+        let retryable = is_retryable(&error);
+        match retry_decision(policy, attempt, retryable) {
+            RetryDecision::Retry(delay) => {
+                attempt += 1;
+                on_retry(attempt, delay);
+                tokio::time::sleep(delay).await;
+            }
+            RetryDecision::GiveUp if retryable => {
+                return Err(error.context(format!("Giving up after {} attempts", attempt)));
+            }
+            RetryDecision::GiveUp => return Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ApiClient;
+    use crate::types::{CanonicalMessage, ChatCompletionRequest, Role};
+
+    fn fast_policy(max_attempts: u32) -> BackoffPolicy {
+        BackoffPolicy {
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(4),
+            multiplier: 2,
+            max_attempts,
+        }
+    }
+
+    fn chat_request() -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            messages: vec![CanonicalMessage::new(Role::User, "Hello".to_string())],
+            model: None,
+            temperature: None,
+            max_tokens: None,
+            stream: true,
+        }
+    }
+
+    /// Delays waited between attempts, in milliseconds
+    fn delay_sequence(policy: &BackoffPolicy) -> Vec<u64> {
+        (1..policy.max_attempts)
+            .map(|retry| policy.delay_for_retry(retry).as_millis() as u64)
+            .collect()
+    }
+
+    #[test]
+    fn test_backoff_sequence_doubles_and_caps() {
+        let policy = BackoffPolicy::default();
+        assert_eq!(delay_sequence(&policy), vec![500, 1000, 2000, 4000]);
+
+        let capped = BackoffPolicy {
+            max_attempts: 8,
+            ..BackoffPolicy::default()
+        };
+        assert_eq!(
+            delay_sequence(&capped),
+            vec![500, 1000, 2000, 4000, 8000, 8000, 8000]
+        );
+
+        // Huge retry counts saturate at the cap instead of overflowing
+        assert_eq!(capped.delay_for_retry(100), Duration::from_secs(8));
+    }
+
+    #[test]
+    fn test_retry_decision() {
+        let policy = fast_policy(3);
+
+        assert_eq!(
+            retry_decision(&policy, 1, true),
+            RetryDecision::Retry(Duration::from_millis(1))
+        );
+        assert_eq!(
+            retry_decision(&policy, 2, true),
+            RetryDecision::Retry(Duration::from_millis(2))
+        );
+        // Attempts exhausted
+        assert_eq!(retry_decision(&policy, 3, true), RetryDecision::GiveUp);
+        // API errors are never retried
+        assert_eq!(retry_decision(&policy, 1, false), RetryDecision::GiveUp);
+        // A zero attempt cap still allows the single initial attempt
+        assert_eq!(
+            retry_decision(&fast_policy(0), 1, true),
+            RetryDecision::GiveUp
+        );
+    }
+
+    #[test]
+    fn test_connection_state_labels() {
+        assert_eq!(ConnectionState::default().label(), "Connected");
+        assert_eq!(
+            ConnectionState::Reconnecting {
+                attempt: 2,
+                max_attempts: 3
+            }
+            .label(),
+            "Reconnecting (attempt 2/3)"
+        );
+        assert_eq!(ConnectionState::Down.label(), "Down");
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_repeated_connection_failures() {
+        // Nothing listens on port 1, so every attempt fails to connect
+        let client = ApiClient::new("127.0.0.1:1".to_string()).unwrap();
+        let policy = fast_policy(3);
+        let mut attempts = 0;
+        let mut retries = Vec::new();
+
+        let result = retry_with_backoff(
+            &policy,
+            || {
+                attempts += 1;
+                client.collect_chat_completion(chat_request())
+            },
+            |attempt, delay| retries.push((attempt, delay)),
+        )
+        .await;
+
+        let error = result.unwrap_err();
+        assert!(format!("{:#}", error).contains("Giving up after 3 attempts"));
+        assert_eq!(attempts, 3);
+        assert_eq!(
+            retries,
+            vec![(2, Duration::from_millis(1)), (3, Duration::from_millis(2))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_errors_are_not_retried() {
+        let mut attempts = 0;
+
+        let result: Result<()> = retry_with_backoff(
+            &fast_policy(5),
+            || {
+                attempts += 1;
+                async { Err(anyhow::anyhow!("API error: invalid_request - bad input")) }
+            },
+            |_, _| panic!("should not retry"),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+}
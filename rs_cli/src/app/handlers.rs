@@ -1,71 +1,124 @@
 // Event handlers for different modes
 
+use crate::api::retry::BackoffPolicy;
 use crate::app::AppState;
 use crate::types::*;
 use anyhow::Result;
 use futures::StreamExt;
+use std::sync::Arc;
+use tokio::sync::{mpsc, RwLock};
 
 /// Handle sending a chat message and streaming the response
-pub async fn handle_chat_message(state: &mut AppState, message: String) -> Result<()> {
+///
+/// Runs in the background: the state lock is only held briefly per update, so the UI
+/// redraws the in-flight reply as chunks arrive. If the stream fails part way, the
+/// partial reply is kept in the conversation and the error is returned.
+pub async fn handle_chat_message(state: Arc<RwLock<AppState>>, message: String) -> Result<()> {
     if message.trim().is_empty() {
         return Ok(());
     }
 
-    // Create user message
-    let user_msg = CanonicalMessage::new(Role::User, message.clone());
-    state.add_message(user_msg);
-
-    // Create request
-    let request = ChatCompletionRequest {
-        messages: state.messages.clone(),
-        model: None,
-        temperature: None,
-        max_tokens: None,
-        stream: true, // Use streaming
+    let (api_client, request) = {
+        let mut state = state.write().await;
+        state.add_message(CanonicalMessage::new(Role::User, message));
+        state.begin_streaming();
+        let request = ChatCompletionRequest {
+            messages: state.messages.clone(),
+            model: None,
+            temperature: None,
+            max_tokens: None,
+            stream: true,
+        };
+        (state.api_client.clone(), request)
     };
 
-    // Stream the response
-    let mut stream = state
-        .api_client
-        .stream_chat_completion(request)
-        .await
-        .map_err(|e| {
-            state.set_error(format!("Failed to start streaming: {}", e));
-            e
-        })?;
-
-    // Create assistant message that we'll build up
-    let mut assistant_content = String::new();
-    let assistant_id = MessageId::new();
-
-    // Collect stream chunks
-    while let Some(chunk_result) = stream.next().await {
-        match chunk_result {
-            Ok(chunk) => {
-                assistant_content.push_str(&chunk);
+    // Open the stream, reconnecting with backoff if the backend is unreachable.
+    // Retries are reported through a channel because the callback cannot await the lock.
+    let policy = BackoffPolicy::default();
+    let (retry_tx, mut retry_rx) = mpsc::unbounded_channel();
+    let retry_reporter = tokio::spawn({
+        let state = state.clone();
+        let max_attempts = policy.max_attempts;
+        async move {
+            while let Some((attempt, delay)) = retry_rx.recv().await {
+                let mut state = state.write().await;
+                state.reconnect_status = Some(format!(
+                    "Reconnecting… (attempt {}/{})",
+                    attempt, max_attempts
+                ));
+                add_debug_log(
+                    &mut state,
+                    "WARN",
+                    format!(
+                        "Chat stream dropped, reconnecting in {:?} (attempt {}/{})",
+                        delay, attempt, max_attempts
+                    ),
+                );
             }
+        }
+    });
+    let opened = api_client
+        .open_chat_stream_with_reconnect(request, &policy, move |attempt, delay| {
+            let _ = retry_tx.send((attempt, delay));
+        })
+        .await;
+    // The sender was dropped with the callback, so the reporter drains and exits
+    let _ = retry_reporter.await;
+    state.write().await.reconnect_status = None;
+
+    let mut chunks = match opened {
+        Ok(chunks) => chunks,
+        Err(e) => {
+            state.write().await.finish_streaming();
+            return Err(e);
+        }
+    };
+    while let Some(chunk) = chunks.next().await {
+        let mut state = state.write().await;
+        match chunk {
+            Ok(delta) => state.append_stream_chunk(&delta),
             Err(e) => {
-                state.set_error(format!("Stream error: {}", e));
-                break;
+                state.finish_streaming();
+                return Err(e.context("Stream interrupted"));
             }
         }
     }
 
-    // Create final assistant message
-    if !assistant_content.trim().is_empty() {
-        let assistant_msg = CanonicalMessage {
-            id: assistant_id,
-            role: Role::Assistant,
-            content: assistant_content,
-            timestamp: chrono::Utc::now(),
-            metadata: std::collections::HashMap::new(),
-        };
-        state.add_message(assistant_msg);
-    }
+    state.write().await.finish_streaming();
+    Ok(())
+}
 
+/// Refresh the system health status
+///
+/// Runs in the background like [`handle_chat_message`]: the request (and any
+/// reconnect attempts) happens without holding the state lock, so the status screen
+/// keeps redrawing the connection state while the backend is retried.
+pub async fn refresh_health(state: Arc<RwLock<AppState>>) -> Result<()> {
+    let api_client = state.read().await.api_client.clone();
+    let health = api_client.health().await?;
+    state.write().await.health = Some(health);
     Ok(())
 }
 
+/// Copy the most recent assistant message to the system clipboard
+///
+/// Sets a transient status on success or when there is nothing to copy; clipboard
+/// failures (e.g. no display server) are shown as an error.
+pub fn copy_last_assistant_message(state: &mut AppState) {
+    let Some(content) = state
+        .last_assistant_message()
+        .map(|message| message.content.clone())
+    else {
+        state.status = Some("No assistant message to copy".to_string());
+        return;
+    };
+
+    match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(content)) {
+        Ok(()) => state.status = Some("Copied".to_string()),
+        Err(e) => state.set_error(format!("Failed to copy to clipboard: {}", e)),
+    }
+}
+
 /// Handle investigation query
 pub async fn handle_investigation(state: &mut AppState, query: String) -> Result<()> {
     if query.trim().is_empty() {
@@ -82,15 +135,21 @@ pub async fn handle_investigation(state: &mut AppState, query: String) -> Result
     Ok(())
 }
 
+/// Maximum number of debug log lines kept
+const MAX_DEBUG_LOGS: usize = 100;
+
 /// Add a debug log entry
 pub fn add_debug_log(state: &mut AppState, level: &str, message: String) {
     let timestamp = chrono::Utc::now().format("%H:%M:%S").to_string();
-    let log_entry = format!("[{}] {}: {}", timestamp, level, message);
-    state.debug_logs.push(log_entry);
+    push_debug_line(state, format!("[{}] {}: {}", timestamp, level, message));
+}
 
-    // Keep only last 100 logs
-    if state.debug_logs.len() > 100 {
+/// Add an already formatted line (e.g. from the server log stream) to the debug logs
+pub fn push_debug_line(state: &mut AppState, line: String) {
+    state.debug_logs.push(line);
+
+    // Keep only the most recent logs
+    if state.debug_logs.len() > MAX_DEBUG_LOGS {
         state.debug_logs.remove(0);
     }
 }
-
@@ -0,0 +1,64 @@
+// Background health polling for the system status screen
+// Refreshes health on a timer, only while the status screen is shown
+
+use crate::app::poll_gate::PollGate;
+use crate::app::{add_debug_log, AppState};
+use crate::modes::Mode;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+/// Default interval between health polls on the status screen, in seconds
+pub const DEFAULT_STATUS_POLL_SECS: u64 = 5;
+
+/// Decides when to poll `/health` and runs polls in the background
+pub struct HealthPoller {
+    /// Polls only in `SystemStatus` mode, one at a time, at most once per interval
+    gate: PollGate,
+}
+
+impl HealthPoller {
+    /// Create a poller that refreshes health every `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            gate: PollGate::new(Mode::SystemStatus, interval),
+        }
+    }
+
+    /// Start a background poll if one is due
+    ///
+    /// The request runs on a spawned task without holding the state lock; only the
+    /// result is written back, so the UI keeps drawing while the backend responds.
+    pub fn tick(&mut self, mode: Mode, state: &Arc<RwLock<AppState>>) {
+        let Some(in_flight) = self.gate.try_start(mode) else {
+            return;
+        };
+
+        let state = state.clone();
+        tokio::spawn(async move {
+            let _in_flight = in_flight;
+            let api_client = state.read().await.api_client.clone();
+            let result = api_client.health().await;
+            let mut state = state.write().await;
+            match result {
+                Ok(health) => state.health = Some(health),
+                Err(e) => add_debug_log(&mut state, "WARN", format!("Health poll failed: {:#}", e)),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_polls_only_on_status_screen() {
+        let poller = HealthPoller::new(Duration::from_secs(5));
+        let now = Instant::now();
+
+        assert!(poller.gate.is_due(Mode::SystemStatus, now));
+        assert!(!poller.gate.is_due(Mode::Chat, now));
+    }
+}
@@ -0,0 +1,199 @@
+// Editable single-line input buffer
+// Tracks a cursor so text can be edited anywhere, not just at the end
+
+/// Text being typed in chat/investigation mode, with a cursor position
+///
+/// The cursor is a character index in `0..=len`, so multi-byte characters move and
+/// delete as a single unit.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct InputBuffer {
+    /// Current text
+    text: String,
+    /// Cursor position in characters from the start of `text`
+    cursor: usize,
+}
+
+impl InputBuffer {
+    /// Create an empty buffer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the current text
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the cursor position in characters
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Check whether the buffer contains only whitespace
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// Number of characters in the buffer
+    fn char_len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    /// Byte offset of the character at `index` (or the end of the text)
+    fn byte_offset(&self, index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(index)
+            .map_or(self.text.len(), |(offset, _)| offset)
+    }
+
+    /// Insert a character at the cursor and move the cursor past it
+    pub fn insert(&mut self, c: char) {
+        let offset = self.byte_offset(self.cursor);
+        self.text.insert(offset, c);
+        self.cursor += 1;
+    }
+
+    /// Delete the character before the cursor (no-op at the start)
+    pub fn backspace(&mut self) {
+        if self.cursor == 0 {
+            return;
+        }
+        self.cursor -= 1;
+        let offset = self.byte_offset(self.cursor);
+        self.text.remove(offset);
+    }
+
+    /// Delete the character under the cursor (no-op at the end)
+    pub fn delete(&mut self) {
+        if self.cursor < self.char_len() {
+            let offset = self.byte_offset(self.cursor);
+            self.text.remove(offset);
+        }
+    }
+
+    /// Move the cursor one character left
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    /// Move the cursor one character right
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.char_len());
+    }
+
+    /// Move the cursor to the start of the text
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    /// Move the cursor to the end of the text
+    pub fn move_end(&mut self) {
+        self.cursor = self.char_len();
+    }
+
+    /// Clear the text and reset the cursor
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+    }
+
+    /// Take the text out of the buffer, leaving it empty
+    pub fn take(&mut self) -> String {
+        self.cursor = 0;
+        std::mem::take(&mut self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer(text: &str) -> InputBuffer {
+        let mut buffer = InputBuffer::new();
+        text.chars().for_each(|c| buffer.insert(c));
+        buffer
+    }
+
+    #[test]
+    fn test_insert_appends_and_advances_cursor() {
+        let buffer = buffer("hello");
+        assert_eq!(buffer.as_str(), "hello");
+        assert_eq!(buffer.cursor(), 5);
+    }
+
+    #[test]
+    fn test_cursor_movement_is_clamped() {
+        let mut buffer = buffer("abc");
+        buffer.move_right();
+        assert_eq!(buffer.cursor(), 3);
+
+        buffer.move_home();
+        assert_eq!(buffer.cursor(), 0);
+        buffer.move_left();
+        assert_eq!(buffer.cursor(), 0);
+
+        buffer.move_right();
+        assert_eq!(buffer.cursor(), 1);
+        buffer.move_end();
+        assert_eq!(buffer.cursor(), 3);
+    }
+
+    #[test]
+    fn test_insert_mid_line() {
+        let mut buffer = buffer("helo");
+        buffer.move_left();
+        buffer.insert('l');
+        assert_eq!(buffer.as_str(), "hello");
+        assert_eq!(buffer.cursor(), 4);
+    }
+
+    #[test]
+    fn test_backspace_and_delete_at_cursor() {
+        let mut buffer = buffer("abcd");
+        buffer.move_home();
+        buffer.move_right();
+        buffer.move_right();
+
+        buffer.backspace();
+        assert_eq!(buffer.as_str(), "acd");
+        assert_eq!(buffer.cursor(), 1);
+
+        buffer.delete();
+        assert_eq!(buffer.as_str(), "ad");
+        assert_eq!(buffer.cursor(), 1);
+
+        // No-ops at the edges
+        buffer.move_end();
+        buffer.delete();
+        assert_eq!(buffer.as_str(), "ad");
+        buffer.move_home();
+        buffer.backspace();
+        assert_eq!(buffer.as_str(), "ad");
+        assert_eq!(buffer.cursor(), 0);
+    }
+
+    #[test]
+    fn test_multibyte_characters_edit_as_one_unit() {
+        let mut buffer = buffer("héllo…");
+        buffer.backspace();
+        assert_eq!(buffer.as_str(), "héllo");
+
+        buffer.move_home();
+        buffer.move_right();
+        buffer.delete();
+        assert_eq!(buffer.as_str(), "hllo");
+        buffer.insert('é');
+        assert_eq!(buffer.as_str(), "héllo");
+        assert_eq!(buffer.cursor(), 2);
+    }
+
+    #[test]
+    fn test_take_empties_buffer() {
+        let mut buffer = buffer("send me");
+        assert!(!buffer.is_blank());
+        assert_eq!(buffer.take(), "send me");
+        assert_eq!(buffer, InputBuffer::new());
+        assert!(buffer.is_blank());
+    }
+}
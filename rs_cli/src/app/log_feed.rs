@@ -0,0 +1,89 @@
+// Background feed of server logs into the debugging screen
+// Subscribes to the server log stream the first time the debugging screen is opened
+
+use crate::app::poll_gate::PollGate;
+use crate::app::{add_debug_log, push_debug_line, AppState};
+use crate::modes::Mode;
+use futures::StreamExt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+/// Minimum time between attempts to (re)connect to the log stream
+pub const LOG_STREAM_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Keeps a background subscription to the server log stream while debugging
+pub struct LogFeed {
+    /// Connects only in `Debugging` mode, when not already connected, and at most
+    /// once per retry interval so an unavailable endpoint is not hammered
+    gate: PollGate,
+}
+
+impl LogFeed {
+    /// Create an idle log feed
+    pub fn new() -> Self {
+        Self {
+            gate: PollGate::new(Mode::Debugging, LOG_STREAM_RETRY_INTERVAL),
+        }
+    }
+
+    /// Connect to the log stream in the background if due
+    ///
+    /// Lines are appended to `state.debug_logs` as they arrive; the subscription
+    /// continues in other modes until the stream ends.
+    pub fn tick(&mut self, mode: Mode, state: &Arc<RwLock<AppState>>) {
+        let Some(active) = self.gate.try_start(mode) else {
+            return;
+        };
+
+        let state = state.clone();
+        tokio::spawn(async move {
+            let _active = active;
+            let api_client = state.read().await.api_client.clone();
+            match api_client.stream_logs().await {
+                Ok(mut lines) => {
+                    while let Some(line) = lines.next().await {
+                        let mut state = state.write().await;
+                        match line {
+                            Ok(line) => push_debug_line(&mut state, line),
+                            Err(e) => {
+                                add_debug_log(&mut state, "ERROR", format!("{:#}", e));
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    let mut state = state.write().await;
+                    add_debug_log(
+                        &mut state,
+                        "ERROR",
+                        format!("Failed to open server log stream: {:#}", e),
+                    );
+                }
+            }
+        });
+    }
+}
+
+impl Default for LogFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_connects_only_in_debugging_mode() {
+        let feed = LogFeed::new();
+        let now = Instant::now();
+
+        assert!(feed.gate.is_due(Mode::Debugging, now));
+        assert!(!feed.gate.is_due(Mode::Chat, now));
+        assert!(!feed.gate.is_due(Mode::SystemStatus, now));
+    }
+}
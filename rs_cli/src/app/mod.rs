@@ -1,6 +1,9 @@
 pub mod handlers;
+pub mod health_poll;
+pub mod input;
+pub mod log_feed;
+pub mod poll_gate;
 pub mod state;
 
 pub use handlers::*;
 pub use state::AppState;
-
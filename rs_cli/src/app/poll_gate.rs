@@ -0,0 +1,110 @@
+// Gating for background tasks tied to one screen
+// Shared by the health poller and the log feed so both start work the same way
+
+use crate::modes::Mode;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Decides when a background task may start
+///
+/// A task starts only while its screen is shown, never while a previous run is still
+/// in flight, and at most once per interval.
+pub struct PollGate {
+    /// Mode whose screen the task serves
+    mode: Mode,
+    /// Minimum time between starts
+    interval: Duration,
+    /// When the last run was started
+    last_start: Option<Instant>,
+    /// Set while a run is in flight
+    in_flight: Arc<AtomicBool>,
+}
+
+impl PollGate {
+    /// Create a gate for a task serving `mode`, started at most once per `interval`
+    pub fn new(mode: Mode, interval: Duration) -> Self {
+        Self {
+            mode,
+            interval,
+            last_start: None,
+            in_flight: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Whether a run should start now
+    pub fn is_due(&self, mode: Mode, now: Instant) -> bool {
+        mode == self.mode
+            && !self.in_flight.load(Ordering::Acquire)
+            && self
+                .last_start
+                .is_none_or(|last| now.saturating_duration_since(last) >= self.interval)
+    }
+
+    /// Start a run if one is due
+    ///
+    /// # Returns
+    /// A guard to move into the background task; the run counts as in flight until it
+    /// is dropped. `None` if no run is due.
+    pub fn try_start(&mut self, mode: Mode) -> Option<InFlight> {
+        let now = Instant::now();
+        if !self.is_due(mode, now) {
+            return None;
+        }
+        self.last_start = Some(now);
+        self.in_flight.store(true, Ordering::Release);
+        Some(InFlight(self.in_flight.clone()))
+    }
+}
+
+/// Marks a gated run as in flight until dropped
+pub struct InFlight(Arc<AtomicBool>);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_due_only_in_its_mode() {
+        let gate = PollGate::new(Mode::SystemStatus, Duration::from_secs(5));
+        let now = Instant::now();
+
+        assert!(gate.is_due(Mode::SystemStatus, now));
+        for mode in [
+            Mode::MainMenu,
+            Mode::Chat,
+            Mode::Investigation,
+            Mode::Debugging,
+        ] {
+            assert!(!gate.is_due(mode, now));
+        }
+    }
+
+    #[test]
+    fn test_due_at_most_once_per_interval() {
+        let mut gate = PollGate::new(Mode::SystemStatus, Duration::from_secs(5));
+        let start = Instant::now();
+        gate.last_start = Some(start);
+
+        assert!(!gate.is_due(Mode::SystemStatus, start + Duration::from_secs(4)));
+        assert!(gate.is_due(Mode::SystemStatus, start + Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_not_due_until_in_flight_run_is_dropped() {
+        let mut gate = PollGate::new(Mode::Debugging, Duration::ZERO);
+
+        let run = gate.try_start(Mode::Debugging).unwrap();
+        assert!(!gate.is_due(Mode::Debugging, Instant::now()));
+        assert!(gate.try_start(Mode::Debugging).is_none());
+
+        drop(run);
+        assert!(gate.is_due(Mode::Debugging, Instant::now()));
+    }
+}
@@ -1,9 +1,9 @@
 // Application state management
 
 use crate::api::ApiClient;
+use crate::app::input::InputBuffer;
 use crate::modes::Mode;
 use crate::types::*;
-use anyhow::Result;
 use std::sync::Arc;
 
 /// Application state
@@ -16,8 +16,12 @@ pub struct AppState {
     pub menu_selection: usize,
     /// Conversation history
     pub messages: Vec<CanonicalMessage>,
+    /// Assistant reply being streamed, shown after `messages` until it completes
+    pub streaming: Option<CanonicalMessage>,
+    /// Index of the first message shown in the chat window
+    pub chat_scroll: usize,
     /// Current input buffer (for chat/investigation)
-    pub input: String,
+    pub input: InputBuffer,
     /// Investigation results
     pub investigation_results: Vec<String>,
     /// Debug logs
@@ -26,6 +30,10 @@ pub struct AppState {
     pub health: Option<HealthStatus>,
     /// Error message to display
     pub error: Option<String>,
+    /// Reconnection indicator while a dropped stream is being retried
+    pub reconnect_status: Option<String>,
+    /// Transient notice (e.g. "Copied"), cleared on the next key press
+    pub status: Option<String>,
     /// Whether the app should exit
     pub should_exit: bool,
 }
@@ -38,11 +46,15 @@ impl AppState {
             mode: Mode::MainMenu,
             menu_selection: 0,
             messages: Vec::new(),
-            input: String::new(),
+            streaming: None,
+            chat_scroll: 0,
+            input: InputBuffer::new(),
             investigation_results: Vec::new(),
             debug_logs: Vec::new(),
             health: None,
             error: None,
+            reconnect_status: None,
+            status: None,
             should_exit: false,
         }
     }
@@ -52,6 +64,49 @@ impl AppState {
         self.messages.push(message);
     }
 
+    /// Start an empty in-flight assistant reply
+    pub fn begin_streaming(&mut self) {
+        self.streaming = Some(CanonicalMessage::new(Role::Assistant, String::new()));
+    }
+
+    /// Append a streamed text delta to the in-flight assistant reply
+    pub fn append_stream_chunk(&mut self, chunk: &str) {
+        if let Some(message) = &mut self.streaming {
+            message.content.push_str(chunk);
+        }
+    }
+
+    /// Move the in-flight assistant reply into the conversation
+    ///
+    /// Also used when the stream fails, so the partial reply is kept; a reply with no
+    /// text is dropped.
+    pub fn finish_streaming(&mut self) {
+        if let Some(message) = self.streaming.take() {
+            if !message.content.trim().is_empty() {
+                self.add_message(message);
+            }
+        }
+    }
+
+    /// Scroll the chat window back by `lines` messages
+    pub fn scroll_chat_up(&mut self, lines: usize) {
+        self.chat_scroll = self.chat_scroll.saturating_sub(lines);
+    }
+
+    /// Scroll the chat window forward by `lines` messages, keeping the last one visible
+    pub fn scroll_chat_down(&mut self, lines: usize) {
+        let last = self.messages.len().saturating_sub(1);
+        self.chat_scroll = (self.chat_scroll + lines).min(last);
+    }
+
+    /// Get the most recent assistant message in the conversation, if any
+    pub fn last_assistant_message(&self) -> Option<&CanonicalMessage> {
+        self.messages
+            .iter()
+            .rev()
+            .find(|message| message.role == Role::Assistant)
+    }
+
     /// Clear error
     pub fn clear_error(&mut self) {
         self.error = None;
@@ -61,19 +116,94 @@ impl AppState {
     pub fn set_error(&mut self, error: String) {
         self.error = Some(error);
     }
+}
 
-    /// Update health status
-    pub async fn update_health(&mut self) -> Result<()> {
-        match self.api_client.health().await {
-            Ok(status) => {
-                self.health = Some(status);
-                Ok(())
-            }
-            Err(e) => {
-                self.set_error(format!("Failed to fetch health: {}", e));
-                Err(e)
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::client::take_chat_deltas;
+
+    fn state_with(messages: Vec<CanonicalMessage>) -> AppState {
+        let api_client = ApiClient::new("http://localhost:3000".to_string()).unwrap();
+        let mut state = AppState::new(Arc::new(api_client));
+        state.messages = messages;
+        state
+    }
+
+    #[test]
+    fn test_last_assistant_message_skips_later_user_messages() {
+        let state = state_with(vec![
+            CanonicalMessage::new(Role::User, "first question".to_string()),
+            CanonicalMessage::new(Role::Assistant, "first answer".to_string()),
+            CanonicalMessage::new(Role::User, "second question".to_string()),
+            CanonicalMessage::new(Role::Assistant, "second answer".to_string()),
+            CanonicalMessage::new(Role::User, "unanswered".to_string()),
+        ]);
+
+        let message = state.last_assistant_message().unwrap();
+        assert_eq!(message.content, "second answer");
+    }
+
+    #[test]
+    fn test_last_assistant_message_none_without_replies() {
+        assert!(state_with(Vec::new()).last_assistant_message().is_none());
+
+        let state = state_with(vec![CanonicalMessage::new(Role::User, "hello".to_string())]);
+        assert!(state.last_assistant_message().is_none());
+    }
+
+    #[test]
+    fn test_chat_scroll_is_clamped_to_messages() {
+        let mut state = state_with(
+            (0..5)
+                .map(|i| CanonicalMessage::new(Role::User, format!("message {}", i)))
+                .collect(),
+        );
+
+        state.scroll_chat_up(3);
+        assert_eq!(state.chat_scroll, 0);
+        state.scroll_chat_down(3);
+        assert_eq!(state.chat_scroll, 3);
+        state.scroll_chat_down(3);
+        assert_eq!(state.chat_scroll, 4);
+        state.scroll_chat_up(1);
+        assert_eq!(state.chat_scroll, 3);
+    }
+
+    #[test]
+    fn test_streamed_chunks_accumulate_into_assistant_message() {
+        let mut state = state_with(vec![CanonicalMessage::new(Role::User, "hello".to_string())]);
+        state.begin_streaming();
+
+        // Events split across network chunks, including a partial trailing event
+        let mut buffer = Vec::new();
+        for chunk in [
+            "data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\nda",
+            "ta: {\"choices\":[{\"delta\":{\"content\":\"lo!\"}}]}\n\n",
+            ": keep-alive\n\ndata: [DONE]\n\n",
+        ] {
+            buffer.extend_from_slice(chunk.as_bytes());
+            let (deltas, _) = take_chat_deltas(&mut buffer);
+            deltas
+                .iter()
+                .for_each(|delta| state.append_stream_chunk(delta));
         }
+        // The partial reply stays out of the conversation until the stream finishes
+        assert_eq!(state.messages.len(), 1);
+        assert_eq!(state.streaming.as_ref().unwrap().content, "Hello!");
+
+        state.finish_streaming();
+        assert!(state.streaming.is_none());
+        assert_eq!(state.last_assistant_message().unwrap().content, "Hello!");
     }
-}
 
+    #[test]
+    fn test_finish_streaming_drops_empty_reply() {
+        let mut state = state_with(Vec::new());
+        state.begin_streaming();
+        state.finish_streaming();
+
+        assert!(state.streaming.is_none());
+        assert!(state.messages.is_empty());
+    }
+}
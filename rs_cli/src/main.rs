@@ -7,55 +7,298 @@ mod modes;
 mod types;
 mod ui;
 
-use crate::app::{handle_chat_message, AppState};
+use crate::api::retry::{is_unreachable, BackoffPolicy, ConnectionState};
 use crate::api::ApiClient;
+use crate::app::health_poll::{HealthPoller, DEFAULT_STATUS_POLL_SECS};
+use crate::app::log_feed::LogFeed;
+use crate::app::{
+    copy_last_assistant_message, handle_chat_message, handle_investigation, refresh_health,
+    AppState,
+};
 use crate::modes::Mode;
+use crate::types::{CanonicalMessage, ChatCompletionRequest, Role};
 use crate::ui::*;
 use anyhow::{Context, Result};
 use clap::Parser;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{
-    backend::CrosstermBackend,
-    Terminal,
-};
-use std::io;
+use ratatui::{backend::CrosstermBackend, layout::Rect, Terminal};
+use serde::Deserialize;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{watch, RwLock};
+
+/// Backend URL used when neither a flag nor the config file sets one
+const DEFAULT_URL: &str = "http://localhost:3000";
+
+/// Environment variable holding the API key
+const API_KEY_ENV: &str = "SENTINEL_API_KEY";
+
+/// Exit code for failures without a more specific code
+const EXIT_FAILURE: u8 = 1;
+
+/// Exit code for invalid configuration (config file, backend URL, missing terminal)
+const EXIT_CONFIG: u8 = 2;
+
+/// Exit code when the backend cannot be reached
+const EXIT_CONNECTION: u8 = 3;
+
+/// Failure that ends the CLI, grouped by the exit code it produces
+#[derive(Debug, thiserror::Error)]
+enum CliError {
+    /// Invalid configuration, e.g. an unreadable config file or a bad backend URL
+    #[error("Configuration error: {0:#}")]
+    Config(anyhow::Error),
+    /// The backend could not be reached
+    #[error("Could not reach the backend: {0:#}")]
+    Connection(anyhow::Error),
+    /// Any other failure
+    #[error("{0:#}")]
+    Other(anyhow::Error),
+}
+
+impl CliError {
+    /// Classify a failed backend request by whether the backend could not be reached
+    fn from_request(error: anyhow::Error) -> Self {
+        if is_unreachable(&error) {
+            Self::Connection(error)
+        } else {
+            Self::Other(error)
+        }
+    }
+
+    /// Get the process exit code for this error
+    fn exit_code(&self) -> u8 {
+        match self {
+            Self::Config(_) => EXIT_CONFIG,
+            Self::Connection(_) => EXIT_CONNECTION,
+            Self::Other(_) => EXIT_FAILURE,
+        }
+    }
+}
 
 /// Sentinel Orchestrator CLI
 #[derive(Parser, Debug)]
 #[command(name = "sentinel-cli")]
 #[command(about = "Interactive CLI for Sentinel Orchestrator", long_about = None)]
 struct Args {
-    /// Backend API base URL
-    #[arg(short, long, default_value = "http://localhost:3000")]
-    url: String,
+    /// Backend API base URL [default: http://localhost:3000]
+    #[arg(short, long)]
+    url: Option<String>,
 
     /// API key for authentication (or set SENTINEL_API_KEY env var)
     #[arg(short = 'k', long)]
     api_key: Option<String>,
+
+    /// Config file path [default: ~/.config/sentinel/config.toml]
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Send a single chat message, print the reply and exit without starting the TUI
+    #[arg(short, long)]
+    prompt: Option<String>,
+
+    /// Seconds between health refreshes on the system status screen
+    #[arg(long, default_value_t = DEFAULT_STATUS_POLL_SECS, value_parser = clap::value_parser!(u64).range(1..))]
+    status_interval: u64,
+}
+
+/// How the CLI runs for this invocation
+#[derive(Debug, PartialEq)]
+enum Launch {
+    /// Interactive terminal UI
+    Tui,
+    /// Send the given prompt, print the reply and exit
+    Prompt(String),
+    /// Read the prompt from piped stdin, print the reply and exit
+    Stdin,
+    /// No interactive terminal and nothing to send
+    NoTerminal,
+}
+
+/// Choose how to run from the `--prompt` flag and whether stdin/stdout are terminals
+///
+/// The TUI needs raw mode, which only works on an interactive terminal, so without one
+/// the CLI falls back to one-shot prompt mode instead of failing to enable raw mode.
+fn choose_launch(
+    prompt: Option<String>,
+    stdin_is_terminal: bool,
+    stdout_is_terminal: bool,
+) -> Launch {
+    match prompt {
+        Some(prompt) => Launch::Prompt(prompt),
+        None if stdin_is_terminal && stdout_is_terminal => Launch::Tui,
+        None if !stdin_is_terminal => Launch::Stdin,
+        None => Launch::NoTerminal,
+    }
+}
+
+/// Send one chat message and print the reply to stdout
+async fn run_prompt(api_client: &ApiClient, prompt: String) -> Result<()> {
+    if prompt.trim().is_empty() {
+        anyhow::bail!("Prompt is empty");
+    }
+    let request = ChatCompletionRequest {
+        messages: vec![CanonicalMessage::new(Role::User, prompt)],
+        model: None,
+        temperature: None,
+        max_tokens: None,
+        stream: true,
+    };
+    let reply = api_client
+        .stream_chat_completion_with_reconnect(request, &BackoffPolicy::default(), |_, _| {})
+        .await?;
+    println!("{}", reply);
+    Ok(())
+}
+
+/// Defaults read from the CLI config file
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+struct CliConfig {
+    /// Backend API base URL
+    url: Option<String>,
+    /// API key for authentication
+    api_key: Option<String>,
+    /// Mode to start in (e.g. "chat" or "system_status")
+    mode: Option<Mode>,
+}
+
+impl CliConfig {
+    /// Default config file location: `$XDG_CONFIG_HOME/sentinel/config.toml`, falling
+    /// back to `~/.config/sentinel/config.toml`
+    fn default_path() -> Option<PathBuf> {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .map(|dir| dir.join("sentinel").join("config.toml"))
+    }
+
+    /// Load the config file
+    ///
+    /// A missing file yields the built-in defaults unless `required` is set
+    /// (the path was given explicitly with `--config`).
+    fn load(path: &Path, required: bool) -> Result<Self> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !required => {
+                return Ok(Self::default())
+            }
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        toml::from_str(&contents).with_context(|| format!("Invalid config file {}", path.display()))
+    }
+}
+
+/// Startup settings after resolving flags, config file and environment
+#[derive(Debug, PartialEq)]
+struct Settings {
+    url: String,
+    api_key: Option<String>,
+    mode: Mode,
+}
+
+/// Resolve startup settings: flags take precedence over the config file, which takes
+/// precedence over environment variables and built-in defaults
+fn resolve_settings(args: Args, config: CliConfig, env_api_key: Option<String>) -> Settings {
+    Settings {
+        url: args
+            .url
+            .or(config.url)
+            .unwrap_or_else(|| DEFAULT_URL.to_string()),
+        api_key: args.api_key.or(config.api_key).or(env_api_key),
+        mode: config.mode.unwrap_or(Mode::MainMenu),
+    }
+}
+
+/// Chat messages scrolled per mouse wheel step
+const CHAT_SCROLL_STEP: usize = 3;
+
+/// Open the mode for the selected main menu entry
+///
+/// # Returns
+/// `true` if the entry was Quit and the app should exit
+fn activate_menu_selection(state: &mut AppState) -> bool {
+    match state.menu_selection {
+        0 => state.mode = Mode::Chat,
+        1 => state.mode = Mode::Investigation,
+        2 => state.mode = Mode::Debugging,
+        3 => state.mode = Mode::SystemStatus,
+        4 => {
+            state.should_exit = true;
+            return true;
+        }
+        _ => {}
+    }
+    false
+}
+
+/// Raw mode and alternate screen, restored when dropped
+///
+/// Created as soon as raw mode is on, so the terminal is restored even if a later
+/// setup step (entering the alternate screen, creating the `Terminal`) fails.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    /// Enable raw mode, enter the alternate screen and capture the mouse
+    fn enter() -> Result<Self> {
+        enable_raw_mode().context("Failed to enable raw mode")?;
+        let guard = Self;
+        execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)
+            .context("Failed to enter alternate screen")?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture);
+    }
 }
 
 /// Main application
 struct App {
     state: Arc<RwLock<AppState>>,
     terminal: Terminal<CrosstermBackend<io::Stdout>>,
+    health_poller: HealthPoller,
+    log_feed: LogFeed,
+    /// Backend connection state, published by the API client as requests are retried
+    connection: watch::Receiver<ConnectionState>,
+    /// Screen rects of the main menu entries from the last draw, for mouse clicks
+    menu_item_rects: Vec<Rect>,
+    /// Restores the terminal; declared last so it is dropped after `terminal`
+    _terminal_guard: TerminalGuard,
 }
 
 impl App {
-    fn new(state: Arc<RwLock<AppState>>) -> Result<Self> {
-        enable_raw_mode().context("Failed to enable raw mode")?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)
-            .context("Failed to enter alternate screen")?;
-        let backend = CrosstermBackend::new(stdout);
+    fn new(
+        state: Arc<RwLock<AppState>>,
+        connection: watch::Receiver<ConnectionState>,
+        status_interval: Duration,
+    ) -> Result<Self> {
+        let terminal_guard = TerminalGuard::enter()?;
+        let backend = CrosstermBackend::new(io::stdout());
         let terminal = Terminal::new(backend).context("Failed to create terminal")?;
 
-        Ok(Self { state, terminal })
+        Ok(Self {
+            state,
+            terminal,
+            health_poller: HealthPoller::new(status_interval),
+            log_feed: LogFeed::new(),
+            connection,
+            menu_item_rects: Vec::new(),
+            _terminal_guard: terminal_guard,
+        })
     }
 
     async fn run(&mut self) -> Result<()> {
@@ -63,12 +306,15 @@ impl App {
             self.draw().await?;
 
             if crossterm::event::poll(std::time::Duration::from_millis(50))? {
-                if let Event::Key(key) = event::read()? {
-                    if key.kind == KeyEventKind::Press {
-                        if self.handle_key(key.code).await? {
-                            break;
-                        }
+                let exit = match event::read()? {
+                    Event::Key(key) if key.kind == KeyEventKind::Press => {
+                        self.handle_key(key).await?
                     }
+                    Event::Mouse(mouse) => self.handle_mouse(mouse).await,
+                    _ => false,
+                };
+                if exit {
+                    break;
                 }
             }
 
@@ -76,6 +322,10 @@ impl App {
             if state.should_exit {
                 break;
             }
+            let mode = state.mode;
+            drop(state);
+            self.health_poller.tick(mode, &self.state);
+            self.log_feed.tick(mode, &self.state);
         }
 
         Ok(())
@@ -83,23 +333,43 @@ impl App {
 
     async fn draw(&mut self) -> Result<()> {
         let state = self.state.read().await;
+        let connection = *self.connection.borrow();
+        let mut menu_item_rects = Vec::new();
 
         self.terminal.draw(|f| {
             match state.mode {
                 Mode::MainMenu => {
-                    render_main_menu(f, state.menu_selection);
+                    menu_item_rects = render_main_menu(f, state.menu_selection);
                 }
                 Mode::Chat => {
-                    render_chat(f, &state.messages, &state.input);
+                    // Show the in-flight reply after the conversation while it streams
+                    let transcript = ChatTranscript {
+                        messages: &state.messages,
+                        streaming: state.streaming.as_ref(),
+                    };
+                    render_chat(
+                        f,
+                        transcript,
+                        state.chat_scroll,
+                        state.input.as_str(),
+                        state.input.cursor(),
+                        state.reconnect_status.as_deref(),
+                        state.status.as_deref(),
+                    );
                 }
                 Mode::Investigation => {
-                    render_investigation(f, &state.input, &state.investigation_results);
+                    render_investigation(
+                        f,
+                        state.input.as_str(),
+                        state.input.cursor(),
+                        &state.investigation_results,
+                    );
                 }
                 Mode::Debugging => {
                     render_debugging(f, &state.debug_logs);
                 }
                 Mode::SystemStatus => {
-                    render_system_status(f, &state.health);
+                    render_system_status(f, &state.health, connection);
                 }
             }
 
@@ -108,17 +378,43 @@ impl App {
             }
         })?;
 
+        self.menu_item_rects = menu_item_rects;
         Ok(())
     }
 
-    async fn handle_key(&mut self, key: KeyCode) -> Result<bool> {
+    /// Handle a mouse event: click a main menu entry or scroll the chat
+    ///
+    /// # Returns
+    /// `true` if the app should exit
+    async fn handle_mouse(&mut self, mouse: MouseEvent) -> bool {
         let mut state = self.state.write().await;
 
-        match key {
+        match (state.mode, mouse.kind) {
+            (Mode::MainMenu, MouseEventKind::Down(MouseButton::Left)) => {
+                if let Some(index) = menu_item_at(&self.menu_item_rects, mouse.column, mouse.row) {
+                    state.menu_selection = index;
+                    return activate_menu_selection(&mut state);
+                }
+            }
+            (Mode::Chat, MouseEventKind::ScrollUp) => state.scroll_chat_up(CHAT_SCROLL_STEP),
+            (Mode::Chat, MouseEventKind::ScrollDown) => state.scroll_chat_down(CHAT_SCROLL_STEP),
+            _ => {}
+        }
+
+        false
+    }
+
+    async fn handle_key(&mut self, key: KeyEvent) -> Result<bool> {
+        let mut state = self.state.write().await;
+        state.status = None;
+
+        match key.code {
             KeyCode::Char('q') => {
                 state.should_exit = true;
                 return Ok(true);
             }
+            // Esc dismisses an error popup before it leaves the current mode
+            KeyCode::Esc if state.error.is_some() => state.clear_error(),
             KeyCode::Esc => {
                 if state.mode != Mode::MainMenu {
                     state.mode = Mode::MainMenu;
@@ -139,77 +435,71 @@ impl App {
                 };
                 state.input.clear();
             }
-            KeyCode::Up => {
-                match state.mode {
-                    Mode::MainMenu => {
-                        if state.menu_selection > 0 {
-                            state.menu_selection -= 1;
-                        } else {
-                            state.menu_selection = 4; // Wrap to last item
-                        }
-                    }
-                    _ => {}
+            KeyCode::Up if state.mode == Mode::MainMenu => {
+                if state.menu_selection > 0 {
+                    state.menu_selection -= 1;
+                } else {
+                    state.menu_selection = 4; // Wrap to last item
                 }
             }
-            KeyCode::Down => {
-                match state.mode {
-                    Mode::MainMenu => {
-                        if state.menu_selection < 4 {
-                            state.menu_selection += 1;
-                        } else {
-                            state.menu_selection = 0; // Wrap to first item
-                        }
-                    }
-                    _ => {}
+            KeyCode::Down if state.mode == Mode::MainMenu => {
+                if state.menu_selection < 4 {
+                    state.menu_selection += 1;
+                } else {
+                    state.menu_selection = 0; // Wrap to first item
                 }
             }
             KeyCode::Enter => {
                 match state.mode {
-                    Mode::MainMenu => {
-                        // Handle menu selection
-                        match state.menu_selection {
-                            0 => state.mode = Mode::Chat,
-                            1 => state.mode = Mode::Investigation,
-                            2 => state.mode = Mode::Debugging,
-                            3 => state.mode = Mode::SystemStatus,
-                            4 => {
-                                state.should_exit = true;
-                                return Ok(true);
+                    Mode::MainMenu => return Ok(activate_menu_selection(&mut state)),
+                    // Send chat message; one reply streams at a time
+                    Mode::Chat if !state.input.is_blank() && state.streaming.is_none() => {
+                        let message = state.input.take();
+
+                        // Stream the reply in the background so the UI keeps redrawing
+                        let app_state = self.state.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = handle_chat_message(app_state.clone(), message).await {
+                                app_state
+                                    .write()
+                                    .await
+                                    .set_error(format!("Failed to send message: {:#}", e));
                             }
-                            _ => {}
-                        }
+                        });
                     }
-                    Mode::Chat => {
-                        // Send chat message
-                        if !state.input.trim().is_empty() {
-                            let message = state.input.clone();
-                            state.input.clear();
-                            
-                            // Handle chat message with streaming
-                            if let Err(e) = handle_chat_message(&mut *state, message).await {
-                                state.set_error(format!("Failed to send message: {}", e));
-                            }
+                    Mode::Investigation if !state.input.is_blank() => {
+                        let query = state.input.take();
+                        if let Err(e) = handle_investigation(&mut state, query).await {
+                            state.set_error(format!("Investigation failed: {:#}", e));
                         }
                     }
                     Mode::SystemStatus => {
-                        // Refresh health status
-                        if let Err(e) = state.update_health().await {
-                            state.set_error(format!("Failed to update health: {}", e));
-                        }
+                        // Refresh health status in the background so the UI keeps redrawing
+                        let app_state = self.state.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = refresh_health(app_state.clone()).await {
+                                app_state
+                                    .write()
+                                    .await
+                                    .set_error(format!("Failed to update health: {:#}", e));
+                            }
+                        });
                     }
                     _ => {}
                 }
             }
-            KeyCode::Backspace => {
-                if state.mode == Mode::Chat || state.mode == Mode::Investigation {
-                    state.input.pop();
-                }
-            }
-            KeyCode::Char(c) => {
-                if state.mode == Mode::Chat || state.mode == Mode::Investigation {
-                    state.input.push(c);
-                }
+            KeyCode::Char('y')
+                if state.mode == Mode::Chat && key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                copy_last_assistant_message(&mut state);
             }
+            KeyCode::Backspace if state.mode.accepts_input() => state.input.backspace(),
+            KeyCode::Delete if state.mode.accepts_input() => state.input.delete(),
+            KeyCode::Left if state.mode.accepts_input() => state.input.move_left(),
+            KeyCode::Right if state.mode.accepts_input() => state.input.move_right(),
+            KeyCode::Home if state.mode.accepts_input() => state.input.move_home(),
+            KeyCode::End if state.mode.accepts_input() => state.input.move_end(),
+            KeyCode::Char(c) if state.mode.accepts_input() => state.input.insert(c),
             _ => {}
         }
 
@@ -217,38 +507,237 @@ impl App {
     }
 }
 
-impl Drop for App {
-    fn drop(&mut self) {
-        let _ = disable_raw_mode();
-        let _ = execute!(
-            io::stdout(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        );
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            // The terminal is already restored: any `App` was dropped inside `run`
+            eprintln!("sentinel-cli: {}", e);
+            ExitCode::from(e.exit_code())
+        }
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let args = Args::parse();
+/// Parse arguments, resolve settings and run the TUI or a one-shot prompt
+async fn run() -> Result<(), CliError> {
+    let mut args = Args::parse();
+    let status_interval = Duration::from_secs(args.status_interval);
+    let launch = choose_launch(
+        args.prompt.take(),
+        io::stdin().is_terminal(),
+        io::stdout().is_terminal(),
+    );
 
-    // Get API key from argument or environment variable
-    let api_key = args.api_key.or_else(|| std::env::var("SENTINEL_API_KEY").ok());
+    // Load the config file, then resolve flags > config file > environment
+    let config = match &args.config {
+        Some(path) => CliConfig::load(path, true),
+        None => match CliConfig::default_path() {
+            Some(path) => CliConfig::load(&path, false),
+            None => Ok(CliConfig::default()),
+        },
+    }
+    .map_err(CliError::Config)?;
+    let settings = resolve_settings(args, config, std::env::var(API_KEY_ENV).ok());
 
     // Initialize API client
-    let api_client = Arc::new(if let Some(key) = api_key {
-        ApiClient::with_api_key(args.url, key).context("Failed to create API client")?
-    } else {
-        ApiClient::new(args.url).context("Failed to create API client")?
-    });
+    let api_client = Arc::new(
+        if let Some(key) = settings.api_key {
+            ApiClient::with_api_key(settings.url, key)
+        } else {
+            ApiClient::new(settings.url)
+        }
+        .context("Failed to create API client")
+        .map_err(CliError::Config)?,
+    );
+
+    // Without an interactive terminal, answer a single prompt instead of starting the TUI
+    match launch {
+        Launch::Tui => {}
+        Launch::Prompt(prompt) => {
+            return run_prompt(&api_client, prompt)
+                .await
+                .map_err(CliError::from_request)
+        }
+        Launch::Stdin => {
+            let mut prompt = String::new();
+            io::stdin()
+                .read_to_string(&mut prompt)
+                .context("Failed to read prompt from stdin")
+                .map_err(CliError::Other)?;
+            return run_prompt(&api_client, prompt)
+                .await
+                .map_err(CliError::from_request);
+        }
+        Launch::NoTerminal => {
+            return Err(CliError::Config(anyhow::anyhow!(
+                "The interactive UI needs a terminal. Run sentinel-cli in a terminal, \
+                 pass --prompt \"<message>\", or pipe a message on stdin"
+            )))
+        }
+    }
 
     // Initialize app state
-    let state = Arc::new(RwLock::new(AppState::new(api_client)));
+    let connection = api_client.subscribe_connection();
+    let mut app_state = AppState::new(api_client);
+    app_state.mode = settings.mode;
+    let state = Arc::new(RwLock::new(app_state));
 
     // Create and run app
-    let mut app = App::new(state).context("Failed to create app")?;
-    app.run().await.context("Failed to run app")?;
+    let mut app = App::new(state, connection, status_interval)
+        .context("Failed to create app")
+        .map_err(CliError::Other)?;
+    app.run()
+        .await
+        .context("Failed to run app")
+        .map_err(CliError::Other)?;
 
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(cli: &[&str]) -> Args {
+        Args::parse_from(std::iter::once("sentinel-cli").chain(cli.iter().copied()))
+    }
+
+    fn file_config() -> CliConfig {
+        toml::from_str(
+            r#"
+            url = "http://file:3000"
+            api_key = "sk-file"
+            mode = "system_status"
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_flags_override_config_file_and_env() {
+        let settings = resolve_settings(
+            args(&["--url", "http://flag:3000", "--api-key", "sk-flag"]),
+            file_config(),
+            Some("sk-env".to_string()),
+        );
+
+        assert_eq!(settings.url, "http://flag:3000");
+        assert_eq!(settings.api_key.as_deref(), Some("sk-flag"));
+        assert_eq!(settings.mode, Mode::SystemStatus);
+    }
+
+    #[test]
+    fn test_config_file_overrides_env() {
+        let settings = resolve_settings(args(&[]), file_config(), Some("sk-env".to_string()));
+
+        assert_eq!(settings.url, "http://file:3000");
+        assert_eq!(settings.api_key.as_deref(), Some("sk-file"));
+    }
+
+    #[test]
+    fn test_env_and_builtin_defaults_without_config_file() {
+        let settings =
+            resolve_settings(args(&[]), CliConfig::default(), Some("sk-env".to_string()));
+
+        assert_eq!(
+            settings,
+            Settings {
+                url: DEFAULT_URL.to_string(),
+                api_key: Some("sk-env".to_string()),
+                mode: Mode::MainMenu,
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_config_file_uses_defaults_unless_required() {
+        let path = Path::new("/nonexistent/sentinel/config.toml");
+
+        assert_eq!(CliConfig::load(path, false).unwrap(), CliConfig::default());
+        assert!(CliConfig::load(path, true).is_err());
+    }
+
+    #[test]
+    fn test_non_terminal_falls_back_to_prompt_mode() {
+        // Piped stdin (e.g. CI) reads the prompt instead of enabling raw mode
+        assert_eq!(choose_launch(None, false, false), Launch::Stdin);
+        assert_eq!(choose_launch(None, false, true), Launch::Stdin);
+        // Output redirected with nothing to send
+        assert_eq!(choose_launch(None, true, false), Launch::NoTerminal);
+        // An explicit prompt never starts the TUI
+        assert_eq!(
+            choose_launch(Some("hi".to_string()), true, true),
+            Launch::Prompt("hi".to_string())
+        );
+        assert_eq!(choose_launch(None, true, true), Launch::Tui);
+    }
+
+    #[test]
+    fn test_unknown_config_keys_are_rejected() {
+        assert!(toml::from_str::<CliConfig>("colour = \"blue\"").is_err());
+    }
+
+    #[test]
+    fn test_errors_map_to_distinct_exit_codes() {
+        let config = CliError::Config(anyhow::anyhow!("bad url"));
+        let connection = CliError::Connection(anyhow::anyhow!("refused"));
+        let other = CliError::Other(anyhow::anyhow!("boom"));
+
+        assert_eq!(config.exit_code(), EXIT_CONFIG);
+        assert_eq!(connection.exit_code(), EXIT_CONNECTION);
+        assert_eq!(other.exit_code(), EXIT_FAILURE);
+        assert_eq!(config.to_string(), "Configuration error: bad url");
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_backend_is_a_connection_error() {
+        // Nothing listens on port 1, so the request fails to connect
+        let error = reqwest::get("http://127.0.0.1:1/health").await.unwrap_err();
+        let error = CliError::from_request(anyhow::Error::new(error).context("Request failed"));
+        assert_eq!(error.exit_code(), EXIT_CONNECTION);
+
+        let error = CliError::from_request(anyhow::anyhow!("API error 400: bad request"));
+        assert_eq!(error.exit_code(), EXIT_FAILURE);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_response_is_not_a_connection_error() {
+        use tokio::io::AsyncWriteExt;
+
+        // A reachable backend that answers with a body that is not JSON
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/health", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 8\r\n\r\nnot json")
+                .await
+                .unwrap();
+        });
+
+        let error = reqwest::get(&url)
+            .await
+            .unwrap()
+            .json::<serde_json::Value>()
+            .await
+            .unwrap_err();
+        assert!(error.is_decode());
+        let error = CliError::from_request(anyhow::Error::new(error));
+        assert_eq!(error.exit_code(), EXIT_FAILURE);
+    }
+
+    #[test]
+    fn test_invalid_config_file_is_a_config_error() {
+        let dir = std::env::temp_dir().join(format!("sentinel-cli-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        std::fs::write(&path, "url = 3").unwrap();
+
+        let error = CliConfig::load(&path, true)
+            .map_err(CliError::Config)
+            .unwrap_err();
+        assert_eq!(error.exit_code(), EXIT_CONFIG);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,6 +1,9 @@
 // Application modes
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Mode {
     MainMenu,
     Chat,
@@ -10,26 +13,8 @@ pub enum Mode {
 }
 
 impl Mode {
-    /// Get all available modes
-    pub fn all() -> Vec<Self> {
-        vec![
-            Mode::MainMenu,
-            Mode::Chat,
-            Mode::Investigation,
-            Mode::Debugging,
-            Mode::SystemStatus,
-        ]
-    }
-
-    /// Get mode name
-    pub fn name(&self) -> &'static str {
-        match self {
-            Mode::MainMenu => "Main Menu",
-            Mode::Chat => "Chat",
-            Mode::Investigation => "Investigation",
-            Mode::Debugging => "Debugging",
-            Mode::SystemStatus => "System Status",
-        }
+    /// Check whether typed characters go to the input buffer in this mode
+    pub fn accepts_input(&self) -> bool {
+        matches!(self, Mode::Chat | Mode::Investigation)
     }
 }
-
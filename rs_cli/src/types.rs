@@ -29,23 +29,6 @@ impl std::fmt::Display for MessageId {
     }
 }
 
-/// Unique identifier for an agent
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(transparent)]
-pub struct AgentId(pub Uuid);
-
-impl AgentId {
-    pub fn new() -> Self {
-        Self(Uuid::new_v4())
-    }
-}
-
-impl std::fmt::Display for AgentId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
-
 /// Role of a message participant
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -55,16 +38,6 @@ pub enum Role {
     System,
 }
 
-/// Agent state in the state machine
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum AgentState {
-    Idle,
-    Thinking,
-    ToolCall,
-    Reflecting,
-}
-
 /// Canonical message format
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CanonicalMessage {
@@ -136,15 +109,6 @@ pub struct TokenUsage {
     pub total_tokens: u32,
 }
 
-/// Agent status information
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct AgentStatus {
-    pub id: AgentId,
-    pub state: AgentState,
-    pub last_activity: DateTime<Utc>,
-    pub messages_processed: u64,
-}
-
 /// Error response format
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ErrorResponse {
@@ -153,4 +117,3 @@ pub struct ErrorResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<HashMap<String, String>>,
 }
-
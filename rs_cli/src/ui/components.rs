@@ -1,5 +1,6 @@
 // UI components for the TUI
 
+use crate::api::retry::ConnectionState;
 use crate::types::*;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -9,18 +10,83 @@ use ratatui::{
     Frame,
 };
 
-/// Render the main menu
-pub fn render_main_menu(f: &mut Frame, selected: usize) {
-    let menu_items = vec![
-        "Chat Mode",
-        "Investigation Mode",
-        "Debugging Mode",
-        "System Status",
-        "Quit",
-    ];
+/// Placeholder shown in the chat window before any messages are exchanged
+pub const EMPTY_CHAT_PLACEHOLDER: &str = "No messages yet — type below and press Enter";
+
+/// Placeholder shown on the status screen before health has been checked
+pub const EMPTY_STATUS_PLACEHOLDER: &str = "Press Enter to check system health";
 
-    let items_len = menu_items.len();
-    let items: Vec<ListItem> = menu_items
+/// Conversation shown in the chat window
+#[derive(Clone, Copy)]
+pub struct ChatTranscript<'a> {
+    /// Completed messages
+    pub messages: &'a [CanonicalMessage],
+    /// Assistant reply still streaming, shown after `messages`
+    pub streaming: Option<&'a CanonicalMessage>,
+}
+
+impl<'a> ChatTranscript<'a> {
+    /// Iterate over the completed messages followed by the streaming reply
+    fn iter(self) -> impl Iterator<Item = &'a CanonicalMessage> {
+        self.messages.iter().chain(self.streaming)
+    }
+}
+
+/// Pick the chat placeholder when there are no messages to show
+fn chat_placeholder(transcript: ChatTranscript<'_>) -> Option<&'static str> {
+    transcript
+        .iter()
+        .next()
+        .is_none()
+        .then_some(EMPTY_CHAT_PLACEHOLDER)
+}
+
+/// Pick the status placeholder when health has not been checked yet
+fn status_placeholder(health: &Option<HealthStatus>) -> Option<&'static str> {
+    health.is_none().then_some(EMPTY_STATUS_PLACEHOLDER)
+}
+
+/// Main menu entries, in the order of `AppState::menu_selection`
+const MENU_ITEMS: [&str; 5] = [
+    "Chat Mode",
+    "Investigation Mode",
+    "Debugging Mode",
+    "System Status",
+    "Quit",
+];
+
+/// Screen rows occupied by each menu entry inside the menu's bordered `area`
+///
+/// Entries that do not fit in the area are not rendered and get no rect.
+fn menu_item_rects(area: Rect) -> Vec<Rect> {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    (0..MENU_ITEMS.len().min(inner.height as usize))
+        .map(|i| Rect::new(inner.x, inner.y + i as u16, inner.width, 1))
+        .collect()
+}
+
+/// Find the menu entry at a screen position
+///
+/// # Arguments
+/// * `item_rects` - Entry rects as returned by `render_main_menu`
+/// * `column` - Screen column of the click
+/// * `row` - Screen row of the click
+///
+/// # Returns
+/// Index of the entry under the position, or `None` if it is outside every entry
+pub fn menu_item_at(item_rects: &[Rect], column: u16, row: u16) -> Option<usize> {
+    item_rects.iter().position(|rect| {
+        (rect.left()..rect.right()).contains(&column) && (rect.top()..rect.bottom()).contains(&row)
+    })
+}
+
+/// Render the main menu
+///
+/// # Returns
+/// Screen rect of each visible entry, indexed like the menu, for mouse hit-testing
+pub fn render_main_menu(f: &mut Frame, selected: usize) -> Vec<Rect> {
+    let items_len = MENU_ITEMS.len();
+    let items: Vec<ListItem> = MENU_ITEMS
         .iter()
         .enumerate()
         .map(|(i, item)| {
@@ -42,68 +108,116 @@ pub fn render_main_menu(f: &mut Frame, selected: usize) {
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan)),
         )
-        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+        .highlight_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
 
     let area = centered_rect(40, items_len as u16 + 2, f.size());
     f.render_widget(list, area);
+    menu_item_rects(area)
+}
+
+/// Build the input line with the character under the cursor highlighted
+///
+/// A highlighted space marks the cursor when it sits past the last character.
+fn input_line(input: &str, cursor: usize) -> Line<'_> {
+    let split = input
+        .char_indices()
+        .nth(cursor)
+        .map_or(input.len(), |(offset, _)| offset);
+    let (before, rest) = input.split_at(split);
+    let (at_cursor, after) = match rest.chars().next() {
+        Some(c) => rest.split_at(c.len_utf8()),
+        None => (" ", ""),
+    };
+
+    Line::from(vec![
+        Span::raw(before),
+        Span::styled(at_cursor, Style::default().add_modifier(Modifier::REVERSED)),
+        Span::raw(after),
+    ])
 }
 
 /// Render chat interface
-pub fn render_chat(f: &mut Frame, messages: &[CanonicalMessage], input: &str) {
+///
+/// `reconnect_status` replaces the input title while a dropped stream is being retried;
+/// otherwise a transient `notice` (e.g. "Copied") does. `cursor` is the character
+/// position of the input cursor. The message list starts at index `scroll`.
+pub fn render_chat(
+    f: &mut Frame,
+    transcript: ChatTranscript<'_>,
+    scroll: usize,
+    input: &str,
+    cursor: usize,
+    reconnect_status: Option<&str>,
+    notice: Option<&str>,
+) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Min(1),
-            Constraint::Length(3),
-        ])
+        .constraints([Constraint::Min(1), Constraint::Length(3)])
         .split(f.size());
 
     // Messages area
-    let message_items: Vec<ListItem> = messages
-        .iter()
-        .map(|msg| {
-            let role_color = match msg.role {
-                Role::User => Color::Cyan,
-                Role::Assistant => Color::Green,
-                Role::System => Color::Yellow,
-            };
-
-            let role_text = match msg.role {
-                Role::User => "User",
-                Role::Assistant => "Assistant",
-                Role::System => "System",
-            };
-
-            let timestamp = msg.timestamp.format("%H:%M:%S").to_string();
-            let header = format!("[{}] {}", role_text, timestamp);
-            let content = msg.content.clone();
-
-            ListItem::new(vec![
-                Line::from(vec![
-                    Span::styled(header, Style::default().fg(role_color).add_modifier(Modifier::BOLD)),
-                ]),
-                Line::from(content),
-            ])
-        })
-        .collect();
+    let messages_block = Block::default()
+        .title("Chat")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
 
-    let messages_list = List::new(message_items)
-        .block(
-            Block::default()
-                .title("Chat")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
-        );
-
-    f.render_widget(messages_list, chunks[0]);
+    if let Some(placeholder) = chat_placeholder(transcript) {
+        render_placeholder(f, placeholder, messages_block, chunks[0]);
+    } else {
+        let message_items: Vec<ListItem> = transcript
+            .iter()
+            .skip(scroll)
+            .map(|msg| {
+                let role_color = match msg.role {
+                    Role::User => Color::Cyan,
+                    Role::Assistant => Color::Green,
+                    Role::System => Color::Yellow,
+                };
+
+                let role_text = match msg.role {
+                    Role::User => "User",
+                    Role::Assistant => "Assistant",
+                    Role::System => "System",
+                };
+
+                let timestamp = msg.timestamp.format("%H:%M:%S").to_string();
+                let header = format!("[{}] {}", role_text, timestamp);
+                let content = msg.content.clone();
+
+                ListItem::new(vec![
+                    Line::from(vec![Span::styled(
+                        header,
+                        Style::default().fg(role_color).add_modifier(Modifier::BOLD),
+                    )]),
+                    Line::from(content),
+                ])
+            })
+            .collect();
+
+        let messages_list = List::new(message_items).block(messages_block);
+
+        f.render_widget(messages_list, chunks[0]);
+    }
 
     // Input area
-    let input_paragraph = Paragraph::new(input)
+    let (input_title, input_color) = match (reconnect_status, notice) {
+        (Some(status), _) => (status, Color::Magenta),
+        (None, Some(notice)) => (notice, Color::Green),
+        (None, None) => (
+            "Input (Enter to send, Ctrl+Y to copy reply, Esc to cancel)",
+            Color::Yellow,
+        ),
+    };
+    let input_paragraph = Paragraph::new(input_line(input, cursor))
         .block(
             Block::default()
-                .title("Input (Enter to send, Esc to cancel)")
+                .title(input_title)
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow)),
+                .border_style(Style::default().fg(input_color)),
         )
         .wrap(Wrap { trim: true });
 
@@ -111,17 +225,25 @@ pub fn render_chat(f: &mut Frame, messages: &[CanonicalMessage], input: &str) {
 }
 
 /// Render system status
-pub fn render_system_status(f: &mut Frame, health: &Option<HealthStatus>) {
+pub fn render_system_status(
+    f: &mut Frame,
+    health: &Option<HealthStatus>,
+    connection: ConnectionState,
+) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Min(1),
-        ])
+        .constraints([Constraint::Length(3), Constraint::Min(1)])
         .split(f.size());
 
     // Health status header
-    let status_text = if let Some(health) = health {
+    let status_border = Block::default()
+        .title("System Health")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    if let Some(placeholder) = status_placeholder(health) {
+        render_placeholder(f, placeholder, status_border, chunks[0]);
+    } else if let Some(health) = health {
         let status_color = match health.status {
             HealthState::Healthy | HealthState::Ready => Color::Green,
             HealthState::Alive => Color::Yellow,
@@ -131,47 +253,60 @@ pub fn render_system_status(f: &mut Frame, health: &Option<HealthStatus>) {
         let status_str = format!("{:?}", health.status);
         let timestamp = health.timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string();
 
-        vec![
-            Line::from(vec![
-                Span::styled("Status: ", Style::default().fg(Color::White)),
-                Span::styled(status_str, Style::default().fg(status_color).add_modifier(Modifier::BOLD)),
-                Span::raw(" | "),
-                Span::styled("Last Check: ", Style::default().fg(Color::White)),
-                Span::styled(timestamp, Style::default().fg(Color::Cyan)),
-            ]),
-        ]
-    } else {
-        vec![Line::from(vec![Span::styled(
-            "Status: Not checked",
-            Style::default().fg(Color::Yellow),
-        )])]
-    };
+        let status_text = vec![Line::from(vec![
+            Span::styled("Status: ", Style::default().fg(Color::White)),
+            Span::styled(
+                status_str,
+                Style::default()
+                    .fg(status_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" | "),
+            Span::styled("Last Check: ", Style::default().fg(Color::White)),
+            Span::styled(timestamp, Style::default().fg(Color::Cyan)),
+        ])];
 
-    let status_block = Paragraph::new(status_text)
-        .block(
-            Block::default()
-                .title("System Health")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
-        )
-        .alignment(Alignment::Left);
+        let status_block = Paragraph::new(status_text)
+            .block(status_border)
+            .alignment(Alignment::Left);
 
-    f.render_widget(status_block, chunks[0]);
+        f.render_widget(status_block, chunks[0]);
+    }
 
     // Additional info area
+    let connection_color = match connection {
+        ConnectionState::Connected => Color::Green,
+        ConnectionState::Reconnecting { .. } => Color::Yellow,
+        ConnectionState::Down => Color::Red,
+    };
     let info_text = vec![
-        Line::from(""),
         Line::from(vec![
-            Span::styled("Endpoints:", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled("Connection: ", Style::default().fg(Color::White)),
+            Span::styled(
+                connection.label(),
+                Style::default()
+                    .fg(connection_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
         ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Endpoints:",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]),
         Line::from("  • /health - Health check"),
         Line::from("  • /health/ready - Readiness check"),
         Line::from("  • /health/live - Liveness check"),
         Line::from("  • /v1/chat/completions - Chat API"),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Navigation:", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-        ]),
+        Line::from(vec![Span::styled(
+            "Navigation:",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]),
         Line::from("  • Tab - Switch between modes"),
         Line::from("  • ↑/↓ - Navigate menu"),
         Line::from("  • Enter - Select"),
@@ -192,17 +327,14 @@ pub fn render_system_status(f: &mut Frame, health: &Option<HealthStatus>) {
 }
 
 /// Render investigation mode
-pub fn render_investigation(f: &mut Frame, query: &str, results: &[String]) {
+pub fn render_investigation(f: &mut Frame, query: &str, cursor: usize, results: &[String]) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Min(1),
-        ])
+        .constraints([Constraint::Length(3), Constraint::Min(1)])
         .split(f.size());
 
     // Query input
-    let query_paragraph = Paragraph::new(query)
+    let query_paragraph = Paragraph::new(input_line(query, cursor))
         .block(
             Block::default()
                 .title("Investigation Query")
@@ -219,13 +351,12 @@ pub fn render_investigation(f: &mut Frame, query: &str, results: &[String]) {
         .map(|result| ListItem::new(result.as_str()))
         .collect();
 
-    let results_list = List::new(result_items)
-        .block(
-            Block::default()
-                .title("Investigation Results")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Magenta)),
-        );
+    let results_list = List::new(result_items).block(
+        Block::default()
+            .title("Investigation Results")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
 
     f.render_widget(results_list, chunks[1]);
 }
@@ -248,13 +379,12 @@ pub fn render_debugging(f: &mut Frame, logs: &[String]) {
         })
         .collect();
 
-    let logs_list = List::new(log_items)
-        .block(
-            Block::default()
-                .title("Debug Logs")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red)),
-        );
+    let logs_list = List::new(log_items).block(
+        Block::default()
+            .title("Debug Logs")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red)),
+    );
 
     f.render_widget(logs_list, f.size());
 }
@@ -263,15 +393,17 @@ pub fn render_debugging(f: &mut Frame, logs: &[String]) {
 pub fn render_error(f: &mut Frame, error: &str) {
     let error_text = vec![
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Error:", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
-        ]),
+        Line::from(vec![Span::styled(
+            "Error:",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )]),
         Line::from(""),
         Line::from(error),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Press Esc to dismiss", Style::default().fg(Color::Yellow)),
-        ]),
+        Line::from(vec![Span::styled(
+            "Press Esc to dismiss",
+            Style::default().fg(Color::Yellow),
+        )]),
     ];
 
     let error_block = Paragraph::new(error_text)
@@ -288,6 +420,27 @@ pub fn render_error(f: &mut Frame, error: &str) {
     f.render_widget(error_block, area);
 }
 
+/// Render a bordered block with a single line of dimmed text centered inside it
+fn render_placeholder(f: &mut Frame, text: &str, block: Block, area: Rect) {
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let line_area = Rect {
+        y: inner.y + inner.height.saturating_sub(1) / 2,
+        height: inner.height.min(1),
+        ..inner
+    };
+    let placeholder = Paragraph::new(Span::styled(
+        text,
+        Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::ITALIC),
+    ))
+    .alignment(Alignment::Center);
+
+    f.render_widget(placeholder, line_area);
+}
+
 /// Helper to create a centered rectangle
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
@@ -309,3 +462,152 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    /// Render into an in-memory terminal and return its contents as text
+    fn render_to_string(draw: impl FnOnce(&mut Frame)) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(draw).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    /// Transcript of completed messages with no reply streaming
+    fn transcript(messages: &[CanonicalMessage]) -> ChatTranscript<'_> {
+        ChatTranscript {
+            messages,
+            streaming: None,
+        }
+    }
+
+    #[test]
+    fn test_chat_placeholder_chosen_when_empty() {
+        assert_eq!(
+            chat_placeholder(transcript(&[])),
+            Some(EMPTY_CHAT_PLACEHOLDER)
+        );
+
+        let messages = vec![CanonicalMessage::new(Role::User, "Hello".to_string())];
+        assert_eq!(chat_placeholder(transcript(&messages)), None);
+
+        let partial = CanonicalMessage::new(Role::Assistant, "Hel".to_string());
+        let streaming = ChatTranscript {
+            messages: &[],
+            streaming: Some(&partial),
+        };
+        assert_eq!(chat_placeholder(streaming), None);
+    }
+
+    #[test]
+    fn test_chat_shows_streaming_reply_after_messages() {
+        let messages = vec![CanonicalMessage::new(Role::User, "question".to_string())];
+        let partial = CanonicalMessage::new(Role::Assistant, "partial answer".to_string());
+        let chat = render_to_string(|f| {
+            let transcript = ChatTranscript {
+                messages: &messages,
+                streaming: Some(&partial),
+            };
+            render_chat(f, transcript, 0, "", 0, None, None);
+        });
+
+        let question = chat.find("question").unwrap();
+        let answer = chat.find("partial answer").unwrap();
+        assert!(question < answer);
+    }
+
+    #[test]
+    fn test_status_placeholder_chosen_when_unchecked() {
+        assert_eq!(status_placeholder(&None), Some(EMPTY_STATUS_PLACEHOLDER));
+
+        let health = Some(HealthStatus {
+            status: HealthState::Healthy,
+            timestamp: chrono::Utc::now(),
+        });
+        assert_eq!(status_placeholder(&health), None);
+    }
+
+    #[test]
+    fn test_empty_screens_render_placeholder_text() {
+        let chat = render_to_string(|f| render_chat(f, transcript(&[]), 0, "", 0, None, None));
+        assert!(chat.contains(EMPTY_CHAT_PLACEHOLDER));
+
+        let status =
+            render_to_string(|f| render_system_status(f, &None, ConnectionState::Connected));
+        assert!(status.contains(EMPTY_STATUS_PLACEHOLDER));
+    }
+
+    #[test]
+    fn test_chat_shows_reconnecting_indicator() {
+        let chat = render_to_string(|f| {
+            render_chat(
+                f,
+                transcript(&[]),
+                0,
+                "",
+                0,
+                Some("Reconnecting… (attempt 2/5)"),
+                None,
+            );
+        });
+        assert!(chat.contains("Reconnecting… (attempt 2/5)"));
+        assert!(!chat.contains("Enter to send"));
+    }
+
+    #[test]
+    fn test_system_status_shows_connection_state() {
+        let status = render_to_string(|f| render_system_status(f, &None, ConnectionState::Down));
+        assert!(status.contains("Connection: Down"));
+    }
+
+    #[test]
+    fn test_menu_item_at_maps_rows_inside_the_border() {
+        let rects = menu_item_rects(Rect::new(10, 5, 30, 7));
+        assert_eq!(rects.len(), MENU_ITEMS.len());
+
+        // First entry sits just inside the top-left border
+        assert_eq!(menu_item_at(&rects, 11, 6), Some(0));
+        assert_eq!(menu_item_at(&rects, 38, 6), Some(0));
+        assert_eq!(menu_item_at(&rects, 20, 10), Some(4));
+
+        // Borders and the outside of the menu select nothing
+        assert_eq!(menu_item_at(&rects, 10, 6), None);
+        assert_eq!(menu_item_at(&rects, 39, 6), None);
+        assert_eq!(menu_item_at(&rects, 20, 5), None);
+        assert_eq!(menu_item_at(&rects, 20, 11), None);
+        assert_eq!(menu_item_at(&rects, 0, 0), None);
+    }
+
+    #[test]
+    fn test_menu_item_rects_skip_entries_that_do_not_fit() {
+        let rects = menu_item_rects(Rect::new(0, 0, 20, 4));
+        assert_eq!(rects.len(), 2);
+        assert_eq!(menu_item_at(&rects, 5, 2), Some(1));
+        assert_eq!(menu_item_at(&rects, 5, 3), None);
+
+        assert!(menu_item_rects(Rect::new(0, 0, 20, 2)).is_empty());
+    }
+
+    #[test]
+    fn test_render_main_menu_reports_item_rects() {
+        let mut terminal = Terminal::new(TestBackend::new(80, 100)).unwrap();
+        let mut rects = Vec::new();
+        terminal.draw(|f| rects = render_main_menu(f, 0)).unwrap();
+
+        assert_eq!(rects.len(), MENU_ITEMS.len());
+        let buffer = terminal.backend().buffer();
+        for (i, rect) in rects.iter().enumerate() {
+            let row: String = (rect.left()..rect.right())
+                .map(|x| buffer.get(x, rect.y).symbol())
+                .collect();
+            assert!(row.starts_with(MENU_ITEMS[i]));
+        }
+    }
+}
@@ -1,4 +1,3 @@
 pub mod components;
 
 pub use components::*;
-
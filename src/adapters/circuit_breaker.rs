@@ -0,0 +1,408 @@
+// Circuit breaker for LLM providers
+// Stops calling an upstream that keeps failing, then probes it again after a cooldown
+
+use crate::core::clock::{Clock, SystemClock};
+use crate::core::error::SentinelError;
+use crate::core::traits::LLMProvider;
+use crate::core::types::{CanonicalMessage, CompletionParams};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Default number of consecutive upstream failures that opens the circuit
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// Default time the circuit stays open before a probe is allowed (30 seconds)
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// State of a circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls go through; consecutive upstream failures are counted
+    Closed,
+    /// Calls fail fast without reaching the upstream
+    Open,
+    /// The cooldown has passed; one probe call decides whether to close or reopen
+    HalfOpen,
+}
+
+/// Failure bookkeeping shared by all calls through the breaker
+#[derive(Debug, Default)]
+struct BreakerState {
+    /// Upstream failures since the last success
+    consecutive_failures: u32,
+    /// When the circuit last opened (`None` while closed)
+    opened_at: Option<DateTime<Utc>>,
+    /// When the pending half-open probe started, if one is in flight
+    probe_started_at: Option<DateTime<Utc>>,
+}
+
+/// LLM provider decorator that fails fast while its inner provider keeps failing
+///
+/// Only `SentinelError::ProviderError` counts as an upstream failure; other errors
+/// (e.g. an invalid request) reach the caller without affecting the breaker. After
+/// `failure_threshold` consecutive failures the circuit opens for `cooldown`, then
+/// lets a single probe through: success closes it, failure reopens it.
+pub struct CircuitBreakerProvider {
+    /// Provider being protected
+    inner: Arc<dyn LLMProvider>,
+    /// Name reported in fail-fast errors
+    name: String,
+    /// Consecutive failures that open the circuit
+    failure_threshold: u32,
+    /// Time the circuit stays open before probing
+    cooldown: chrono::Duration,
+    /// Time source for the cooldown
+    clock: Arc<dyn Clock>,
+    /// Failure bookkeeping
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreakerProvider {
+    /// Wrap a provider in a circuit breaker with default settings
+    ///
+    /// # Arguments
+    /// * `inner` - Provider to protect
+    /// * `name` - Provider name reported in fail-fast errors (e.g. "openai")
+    pub fn new(inner: Arc<dyn LLMProvider>, name: impl Into<String>) -> Self {
+        Self {
+            inner,
+            name: name.into(),
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            cooldown: chrono::Duration::from_std(DEFAULT_COOLDOWN).unwrap_or(chrono::Duration::MAX),
+            clock: Arc::new(SystemClock),
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    /// Open the circuit after `failure_threshold` consecutive failures (0 is treated as 1)
+    pub fn with_failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = failure_threshold.max(1);
+        self
+    }
+
+    /// Keep the circuit open for `cooldown` before probing the upstream again
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = chrono::Duration::from_std(cooldown).unwrap_or(chrono::Duration::MAX);
+        self
+    }
+
+    /// Use a custom time source for the cooldown
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Get the current state of the circuit
+    pub fn circuit_state(&self) -> CircuitState {
+        let state = self.state();
+        self.state_at(&state, self.clock.now())
+    }
+
+    /// Lock the bookkeeping, recovering it if a holder panicked
+    ///
+    /// Every update leaves the counters consistent, so poisoned bookkeeping is still valid.
+    fn state(&self) -> MutexGuard<'_, BreakerState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Derive the circuit state from the bookkeeping at `now`
+    fn state_at(&self, state: &BreakerState, now: DateTime<Utc>) -> CircuitState {
+        match state.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if now - opened_at < self.cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Error returned instead of calling the upstream while the circuit is open
+    fn open_error(&self) -> SentinelError {
+        SentinelError::ProviderError {
+            provider: self.name.clone(),
+            detail: "circuit breaker is open after repeated failures".to_string(),
+        }
+    }
+
+    /// Decide whether a call may reach the upstream
+    ///
+    /// # Note
+    /// In the half-open state only one probe is let through; a probe that never
+    /// reports back (e.g. its future was dropped) is replaced after another cooldown.
+    fn try_acquire(&self) -> Result<(), SentinelError> {
+        let mut state = self.state();
+        let now = self.clock.now();
+        match self.state_at(&state, now) {
+            CircuitState::Closed => Ok(()),
+            CircuitState::Open => Err(self.open_error()),
+            CircuitState::HalfOpen => {
+                let probe_pending = state
+                    .probe_started_at
+                    .is_some_and(|started| now - started < self.cooldown);
+                if probe_pending {
+                    return Err(self.open_error());
+                }
+                info!("Circuit for {} half-open, probing upstream", self.name);
+                state.probe_started_at = Some(now);
+                Ok(())
+            }
+        }
+    }
+
+    /// Update the breaker with the outcome of a call that reached the upstream
+    fn record<T>(&self, result: &Result<T, SentinelError>) {
+        let mut state = self.state();
+        let now = self.clock.now();
+        let was_probe = state.probe_started_at.take().is_some();
+
+        match result {
+            Ok(_) => {
+                if state.opened_at.is_some() {
+                    info!("Circuit for {} closed after successful probe", self.name);
+                }
+                *state = BreakerState::default();
+            }
+            Err(SentinelError::ProviderError { .. }) => {
+                state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+                if was_probe || state.consecutive_failures >= self.failure_threshold {
+                    if state.opened_at.is_none() || was_probe {
+                        warn!(
+                            "Circuit for {} opened after {} consecutive failures",
+                            self.name, state.consecutive_failures
+                        );
+                    }
+                    state.opened_at = Some(now);
+                }
+            }
+            // Caller errors say nothing about upstream health
+            Err(_) => {}
+        }
+    }
+
+    /// Run `call` on the inner provider unless the circuit is open
+    async fn guarded<T, Fut>(&self, call: Fut) -> Result<T, SentinelError>
+    where
+        Fut: Future<Output = Result<T, SentinelError>>,
+    {
+        self.try_acquire()?;
+        let result = call.await;
+        self.record(&result);
+        result
+    }
+}
+
+#[async_trait]
+impl LLMProvider for CircuitBreakerProvider {
+    async fn complete(
+        &self,
+        messages: Vec<CanonicalMessage>,
+    ) -> Result<CanonicalMessage, SentinelError> {
+        self.guarded(self.inner.complete(messages)).await
+    }
+
+    async fn complete_with_params(
+        &self,
+        messages: Vec<CanonicalMessage>,
+        params: CompletionParams,
+    ) -> Result<CanonicalMessage, SentinelError> {
+        self.guarded(self.inner.complete_with_params(messages, params))
+            .await
+    }
+
+    /// Open a stream on the inner provider unless the circuit is open
+    ///
+    /// # Note
+    /// Only opening the stream is recorded; errors yielded by an open stream do not
+    /// affect the breaker.
+    async fn stream(
+        &self,
+        messages: Vec<CanonicalMessage>,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = Result<String, SentinelError>> + Send + Unpin>,
+        SentinelError,
+    > {
+        self.guarded(self.inner.stream(messages)).await
+    }
+
+    /// Unhealthy while the circuit is open; otherwise the inner provider's health
+    async fn health_check(&self) -> Result<(), SentinelError> {
+        if self.circuit_state() == CircuitState::Open {
+            return Err(self.open_error());
+        }
+        self.inner.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Role;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// Clock that only moves when the test advances it
+    struct ManualClock(Mutex<DateTime<Utc>>);
+
+    impl ManualClock {
+        fn new() -> Arc<Self> {
+            Arc::new(Self(Mutex::new(Utc::now())))
+        }
+
+        fn advance(&self, by: Duration) {
+            *self.0.lock().unwrap() += chrono::Duration::from_std(by).unwrap();
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> DateTime<Utc> {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    /// Provider whose upstream can be switched between failing and healthy
+    #[derive(Default)]
+    struct FlakyProvider {
+        failing: AtomicBool,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LLMProvider for FlakyProvider {
+        async fn complete(
+            &self,
+            _messages: Vec<CanonicalMessage>,
+        ) -> Result<CanonicalMessage, SentinelError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.failing.load(Ordering::SeqCst) {
+                Err(SentinelError::ProviderError {
+                    provider: "flaky".to_string(),
+                    detail: "503".to_string(),
+                })
+            } else {
+                Ok(CanonicalMessage::new(Role::Assistant, "ok".to_string()))
+            }
+        }
+
+        async fn stream(
+            &self,
+            _messages: Vec<CanonicalMessage>,
+        ) -> Result<
+            Box<dyn futures::Stream<Item = Result<String, SentinelError>> + Send + Unpin>,
+            SentinelError,
+        > {
+            unimplemented!("not used by these tests")
+        }
+    }
+
+    fn prompt() -> Vec<CanonicalMessage> {
+        vec![CanonicalMessage::new(Role::User, "hi".to_string())]
+    }
+
+    fn breaker(inner: Arc<FlakyProvider>, clock: Arc<ManualClock>) -> CircuitBreakerProvider {
+        CircuitBreakerProvider::new(inner, "flaky")
+            .with_failure_threshold(3)
+            .with_cooldown(Duration::from_secs(30))
+            .with_clock(clock)
+    }
+
+    #[tokio::test]
+    async fn test_opens_after_threshold_and_fails_fast() {
+        let inner = Arc::new(FlakyProvider::default());
+        inner.failing.store(true, Ordering::SeqCst);
+        let breaker = breaker(inner.clone(), ManualClock::new());
+
+        for _ in 0..2 {
+            assert!(breaker.complete(prompt()).await.is_err());
+            assert_eq!(breaker.circuit_state(), CircuitState::Closed);
+        }
+        assert!(breaker.complete(prompt()).await.is_err());
+        assert_eq!(breaker.circuit_state(), CircuitState::Open);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+
+        // Open: fails fast without calling the upstream
+        let error = breaker.complete(prompt()).await.unwrap_err();
+        assert!(matches!(
+            error,
+            SentinelError::ProviderError { ref provider, .. } if provider == "flaky"
+        ));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+        assert!(breaker.health_check().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_poisoned_state_keeps_counting_failures() {
+        let inner = Arc::new(FlakyProvider::default());
+        inner.failing.store(true, Ordering::SeqCst);
+        let breaker = breaker(inner.clone(), ManualClock::new());
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _state = breaker.state.lock().unwrap();
+            panic!("poison the breaker");
+        }));
+        assert!(breaker.state.is_poisoned());
+
+        for _ in 0..3 {
+            assert!(breaker.complete(prompt()).await.is_err());
+        }
+        assert_eq!(breaker.circuit_state(), CircuitState::Open);
+    }
+
+    #[tokio::test]
+    async fn test_half_open_probe_closes_on_success() {
+        let inner = Arc::new(FlakyProvider::default());
+        inner.failing.store(true, Ordering::SeqCst);
+        let clock = ManualClock::new();
+        let breaker = breaker(inner.clone(), clock.clone());
+        for _ in 0..3 {
+            let _ = breaker.complete(prompt()).await;
+        }
+
+        clock.advance(Duration::from_secs(29));
+        assert_eq!(breaker.circuit_state(), CircuitState::Open);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(breaker.circuit_state(), CircuitState::HalfOpen);
+
+        inner.failing.store(false, Ordering::SeqCst);
+        breaker.complete(prompt()).await.unwrap();
+        assert_eq!(breaker.circuit_state(), CircuitState::Closed);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_failed_probe_reopens_for_another_cooldown() {
+        let inner = Arc::new(FlakyProvider::default());
+        inner.failing.store(true, Ordering::SeqCst);
+        let clock = ManualClock::new();
+        let breaker = breaker(inner.clone(), clock.clone());
+        for _ in 0..3 {
+            let _ = breaker.complete(prompt()).await;
+        }
+
+        clock.advance(Duration::from_secs(30));
+        assert!(breaker.complete(prompt()).await.is_err());
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 4);
+        assert_eq!(breaker.circuit_state(), CircuitState::Open);
+
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(breaker.circuit_state(), CircuitState::HalfOpen);
+    }
+
+    #[tokio::test]
+    async fn test_success_resets_failure_count() {
+        let inner = Arc::new(FlakyProvider::default());
+        let breaker = breaker(inner.clone(), ManualClock::new());
+
+        inner.failing.store(true, Ordering::SeqCst);
+        for _ in 0..2 {
+            let _ = breaker.complete(prompt()).await;
+        }
+        inner.failing.store(false, Ordering::SeqCst);
+        breaker.complete(prompt()).await.unwrap();
+        inner.failing.store(true, Ordering::SeqCst);
+        for _ in 0..2 {
+            let _ = breaker.complete(prompt()).await;
+        }
+
+        assert_eq!(breaker.circuit_state(), CircuitState::Closed);
+    }
+}
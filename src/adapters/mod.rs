@@ -1,3 +1,5 @@
+pub mod circuit_breaker;
 pub mod openai;
 pub mod qdrant;
+pub mod routing;
 pub mod sled;
@@ -1 +1,775 @@
 // OpenAI client implementation
+// Implements LLMProvider on top of async-openai with retry and backoff
+
+use crate::core::error::SentinelError;
+use crate::core::traits::LLMProvider;
+use crate::core::types::{
+    CanonicalMessage, CompletionParams, Role, DEFAULT_MODEL, MODEL_METADATA_KEY,
+};
+use async_openai::config::OpenAIConfig;
+use async_openai::error::OpenAIError;
+use async_openai::types::{
+    ChatCompletionMessageToolCall, ChatCompletionRequestAssistantMessageArgs,
+    ChatCompletionRequestMessage, ChatCompletionRequestSystemMessageArgs,
+    ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+    CreateChatCompletionRequestArgs, CreateChatCompletionResponse,
+    CreateChatCompletionStreamResponse,
+};
+use async_openai::Client;
+use async_trait::async_trait;
+use futures::StreamExt;
+use rand::Rng;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::{debug, warn};
+
+/// Default number of retries after the initial attempt
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Default base delay before the first retry
+const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound on any single retry delay
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Environment variable overriding the retry base delay, in milliseconds
+const RETRY_BASE_DELAY_ENV: &str = "OPENAI_RETRY_BASE_DELAY_MS";
+
+/// Default maximum number of in-flight requests per provider
+pub const DEFAULT_MAX_CONCURRENCY: usize = 8;
+
+/// Environment variable overriding the maximum number of in-flight requests
+const MAX_CONCURRENCY_ENV: &str = "OPENAI_MAX_CONCURRENCY";
+
+/// Metadata key holding an assistant message's tool calls, as a JSON array in
+/// OpenAI's `tool_calls` wire format
+pub const TOOL_CALLS_METADATA_KEY: &str = "tool_calls";
+
+/// API error types/codes that indicate a transient failure (429 and 5xx)
+const RETRYABLE_API_ERRORS: &[&str] = &[
+    "rate_limit_exceeded",
+    "server_error",
+    "engine_overloaded",
+    "service_unavailable",
+];
+
+/// Check whether an OpenAI error is transient and worth retrying
+///
+/// # Returns
+/// `true` for rate limits, server errors, timeouts, connection failures and dropped
+/// streams; `false` for invalid requests, authentication failures, exhausted quota
+/// and client-side errors.
+pub fn is_retryable(error: &OpenAIError) -> bool {
+    match error {
+        OpenAIError::Reqwest(e) => {
+            e.is_timeout()
+                || e.is_connect()
+                || e.status()
+                    .is_some_and(|status| status.as_u16() == 429 || status.is_server_error())
+        }
+        OpenAIError::ApiError(api_error) => [&api_error.r#type, &api_error.code]
+            .into_iter()
+            .flatten()
+            .any(|kind| RETRYABLE_API_ERRORS.contains(&kind.as_str())),
+        OpenAIError::StreamError(_) => true,
+        OpenAIError::JSONDeserialize(_)
+        | OpenAIError::FileSaveError(_)
+        | OpenAIError::FileReadError(_)
+        | OpenAIError::InvalidArgument(_) => false,
+    }
+}
+
+/// Read the retry base delay from the environment, falling back to the default
+fn retry_base_delay_from_env() -> Duration {
+    env::var(RETRY_BASE_DELAY_ENV)
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_RETRY_BASE_DELAY)
+}
+
+/// Read the request concurrency limit from the environment, falling back to the default
+///
+/// Zero and unparsable values are ignored.
+fn max_concurrency_from_env() -> usize {
+    env::var(MAX_CONCURRENCY_ENV)
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|&limit| limit > 0)
+        .unwrap_or(DEFAULT_MAX_CONCURRENCY)
+}
+
+/// Name reported in `SentinelError::ProviderError` for OpenAI failures
+const PROVIDER_NAME: &str = "openai";
+
+/// Build the error for a failed or unusable OpenAI response
+fn provider_error(detail: impl Into<String>) -> SentinelError {
+    SentinelError::ProviderError {
+        provider: PROVIDER_NAME.to_string(),
+        detail: detail.into(),
+    }
+}
+
+/// Convert an async-openai error into a domain error
+fn map_openai_error(error: OpenAIError) -> SentinelError {
+    match error {
+        OpenAIError::InvalidArgument(reason) => SentinelError::InvalidMessage { reason },
+        other => provider_error(other.to_string()),
+    }
+}
+
+/// OpenAI LLM provider
+pub struct OpenAIProvider {
+    client: Client<OpenAIConfig>,
+    model: String,
+    max_retries: u32,
+    retry_base_delay: Duration,
+    /// Caps in-flight upstream requests; excess calls queue for a permit
+    concurrency: Arc<Semaphore>,
+    max_concurrency: usize,
+}
+
+impl OpenAIProvider {
+    /// Create a new OpenAI provider from environment settings
+    ///
+    /// Reads `OPENAI_API_KEY`, `OPENAI_MODEL` (default `gpt-4o-mini`),
+    /// `OPENAI_RETRY_BASE_DELAY_MS` (default 500) and `OPENAI_MAX_CONCURRENCY` (default 8).
+    ///
+    /// # Returns
+    /// * `Ok(OpenAIProvider)` - Successfully created
+    /// * `Err(SentinelError)` - Error if `OPENAI_API_KEY` is not set
+    pub fn new() -> Result<Self, SentinelError> {
+        let api_key = env::var("OPENAI_API_KEY").map_err(|_| SentinelError::DomainViolation {
+            rule: "OPENAI_API_KEY environment variable is not set".to_string(),
+        })?;
+        let model = env::var("OPENAI_MODEL").unwrap_or_else(|_| DEFAULT_MODEL.to_string());
+        Ok(Self::with_config(&api_key, &model, DEFAULT_MAX_RETRIES))
+    }
+
+    /// Create a new OpenAI provider with custom configuration
+    ///
+    /// # Arguments
+    /// * `api_key` - OpenAI API key
+    /// * `model` - Chat model to use
+    /// * `max_retries` - Retries after the initial attempt for transient errors
+    ///
+    /// # Note
+    /// The retry base delay is read from `OPENAI_RETRY_BASE_DELAY_MS` and the
+    /// concurrency limit from `OPENAI_MAX_CONCURRENCY`.
+    pub fn with_config(api_key: &str, model: &str, max_retries: u32) -> Self {
+        // Disable the client's built-in rate-limit retries so `max_retries` is authoritative
+        let no_retry = backoff::ExponentialBackoffBuilder::new()
+            .with_max_elapsed_time(Some(Duration::ZERO))
+            .build();
+        let client =
+            Client::with_config(OpenAIConfig::new().with_api_key(api_key)).with_backoff(no_retry);
+        let max_concurrency = max_concurrency_from_env();
+
+        Self {
+            client,
+            model: model.to_string(),
+            max_retries,
+            retry_base_delay: retry_base_delay_from_env(),
+            concurrency: Arc::new(Semaphore::new(max_concurrency)),
+            max_concurrency,
+        }
+    }
+
+    /// Override the base delay used for exponential backoff
+    pub fn with_retry_base_delay(mut self, retry_base_delay: Duration) -> Self {
+        self.retry_base_delay = retry_base_delay;
+        self
+    }
+
+    /// Override the maximum number of in-flight requests (at least 1)
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        let max_concurrency = max_concurrency.max(1);
+        self.concurrency = Arc::new(Semaphore::new(max_concurrency));
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    /// Get the configured model
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Get the configured retry count
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Get the maximum number of in-flight requests
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    /// Wait for a free request slot
+    ///
+    /// The slot is released when the returned permit is dropped.
+    async fn acquire_permit(&self) -> Result<OwnedSemaphorePermit, SentinelError> {
+        self.concurrency
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| SentinelError::DomainViolation {
+                rule: "OpenAI request limiter is closed".to_string(),
+            })
+    }
+
+    /// Delay before retry number `retry` (0-based), with equal jitter
+    ///
+    /// The un-jittered delay doubles each retry from the base delay, capped at
+    /// 30 seconds; the result is drawn uniformly from its upper half.
+    fn retry_delay(&self, retry: u32) -> Duration {
+        let exponential = self
+            .retry_base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(MAX_RETRY_DELAY);
+        let half = exponential / 2;
+        half + rand::thread_rng().gen_range(Duration::ZERO..=half)
+    }
+
+    /// Build a chat completion request
+    ///
+    /// Parameters left unset in `params` use the configured model and the API defaults.
+    fn build_request(
+        &self,
+        messages: &[CanonicalMessage],
+        params: &CompletionParams,
+    ) -> Result<CreateChatCompletionRequest, OpenAIError> {
+        let messages = messages
+            .iter()
+            .map(canonical_to_openai_message)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut args = CreateChatCompletionRequestArgs::default();
+        args.model(params.model.as_deref().unwrap_or(&self.model))
+            .messages(messages);
+        if let Some(temperature) = params.temperature {
+            args.temperature(temperature as f32);
+        }
+        if let Some(max_tokens) = params.max_tokens {
+            args.max_tokens(max_tokens);
+        }
+        args.build()
+    }
+}
+
+/// Read the tool calls attached to a message's metadata, if any
+fn tool_calls_from_metadata(
+    message: &CanonicalMessage,
+) -> Result<Option<Vec<ChatCompletionMessageToolCall>>, OpenAIError> {
+    message
+        .metadata
+        .get(TOOL_CALLS_METADATA_KEY)
+        .map(|raw| {
+            serde_json::from_str(raw).map_err(|e| {
+                OpenAIError::InvalidArgument(format!(
+                    "Invalid {} metadata on message {}: {}",
+                    TOOL_CALLS_METADATA_KEY, message.id, e
+                ))
+            })
+        })
+        .transpose()
+}
+
+/// Convert a canonical message into an OpenAI request message
+///
+/// Assistant tool calls are carried in `tool_calls`; the deprecated `function_call`
+/// field is never set.
+fn canonical_to_openai_message(
+    message: &CanonicalMessage,
+) -> Result<ChatCompletionRequestMessage, OpenAIError> {
+    let content = message.content.clone();
+    Ok(match message.role {
+        Role::System => ChatCompletionRequestSystemMessageArgs::default()
+            .content(content)
+            .build()?
+            .into(),
+        Role::User => ChatCompletionRequestUserMessageArgs::default()
+            .content(content)
+            .build()?
+            .into(),
+        Role::Assistant => {
+            let mut args = ChatCompletionRequestAssistantMessageArgs::default();
+            match tool_calls_from_metadata(message)? {
+                Some(tool_calls) => {
+                    // Tool-calling turns often carry no text content
+                    if !content.is_empty() {
+                        args.content(content);
+                    }
+                    args.tool_calls(tool_calls);
+                }
+                None => {
+                    args.content(content);
+                }
+            }
+            args.build()?.into()
+        }
+    })
+}
+
+/// Convert an OpenAI response into a canonical assistant message
+///
+/// The model that served the request is recorded under `MODEL_METADATA_KEY`.
+fn from_response(
+    response: CreateChatCompletionResponse,
+) -> Result<CanonicalMessage, SentinelError> {
+    let model = response.model;
+    let message = response
+        .choices
+        .into_iter()
+        .next()
+        .map(|choice| choice.message)
+        .ok_or_else(|| provider_error("response contained no choices"))?;
+
+    let mut metadata = std::collections::HashMap::new();
+    if let Some(tool_calls) = message.tool_calls.filter(|calls| !calls.is_empty()) {
+        let encoded =
+            serde_json::to_string(&tool_calls).map_err(|e| SentinelError::DomainViolation {
+                rule: format!("Failed to encode tool calls: {}", e),
+            })?;
+        metadata.insert(TOOL_CALLS_METADATA_KEY.to_string(), encoded);
+    }
+
+    let content = match message.content {
+        Some(content) => content,
+        None if metadata.contains_key(TOOL_CALLS_METADATA_KEY) => String::new(),
+        None => return Err(provider_error("response contained no message content")),
+    };
+    metadata.insert(MODEL_METADATA_KEY.to_string(), model);
+    Ok(CanonicalMessage::with_metadata(
+        Role::Assistant,
+        content,
+        metadata,
+    ))
+}
+
+/// Extract the text carried by a streamed chunk's first choice
+///
+/// # Returns
+/// `Some(text)` for a non-empty content delta; `None` for role-only, tool-call,
+/// finish and usage chunks, which carry no text
+fn delta_content(chunk: &CreateChatCompletionStreamResponse) -> Option<String> {
+    chunk
+        .choices
+        .first()
+        .and_then(|choice| choice.delta.content.clone())
+        .filter(|content| !content.is_empty())
+}
+
+#[async_trait]
+impl LLMProvider for OpenAIProvider {
+    async fn complete(
+        &self,
+        messages: Vec<CanonicalMessage>,
+    ) -> Result<CanonicalMessage, SentinelError> {
+        self.complete_with_params(messages, CompletionParams::default())
+            .await
+    }
+
+    async fn complete_with_params(
+        &self,
+        messages: Vec<CanonicalMessage>,
+        params: CompletionParams,
+    ) -> Result<CanonicalMessage, SentinelError> {
+        let request = self
+            .build_request(&messages, &params)
+            .map_err(map_openai_error)?;
+
+        let mut retry = 0;
+        loop {
+            // Hold a slot per attempt so backoff sleeps don't block other callers
+            let permit = self.acquire_permit().await?;
+            let result = self.client.chat().create(request.clone()).await;
+            drop(permit);
+
+            match result {
+                Ok(response) => return from_response(response),
+                Err(e) if retry < self.max_retries && is_retryable(&e) => {
+                    let delay = self.retry_delay(retry);
+                    retry += 1;
+                    warn!(
+                        "OpenAI request failed ({}), retrying in {:?} (retry {}/{})",
+                        e, delay, retry, self.max_retries
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => {
+                    debug!("OpenAI request failed after {} retries: {}", retry, e);
+                    return Err(map_openai_error(e));
+                }
+            }
+        }
+    }
+
+    /// Streams content deltas as they arrive, skipping chunks without text
+    ///
+    /// Streams are not retried: a failure mid-stream is yielded as an error item.
+    /// The request slot is held until the returned stream is dropped.
+    async fn stream(
+        &self,
+        messages: Vec<CanonicalMessage>,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = Result<String, SentinelError>> + Send + Unpin>,
+        SentinelError,
+    > {
+        let request = self
+            .build_request(&messages, &CompletionParams::default())
+            .map_err(map_openai_error)?;
+        let permit = self.acquire_permit().await?;
+        let stream = self
+            .client
+            .chat()
+            .create_stream(request)
+            .await
+            .map_err(map_openai_error)?;
+
+        Ok(Box::new(stream.filter_map(move |chunk| {
+            let _permit = &permit;
+            futures::future::ready(match chunk {
+                Ok(chunk) => delta_content(&chunk).map(Ok),
+                Err(e) => Some(Err(map_openai_error(e))),
+            })
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_openai::error::ApiError;
+
+    fn api_error(r#type: Option<&str>, code: Option<&str>) -> OpenAIError {
+        OpenAIError::ApiError(ApiError {
+            message: "test".to_string(),
+            r#type: r#type.map(str::to_string),
+            param: None,
+            code: code.map(str::to_string),
+        })
+    }
+
+    #[test]
+    fn test_rate_limit_and_server_errors_are_retryable() {
+        assert!(is_retryable(&api_error(
+            Some("requests"),
+            Some("rate_limit_exceeded")
+        )));
+        assert!(is_retryable(&api_error(Some("server_error"), None)));
+        assert!(is_retryable(&api_error(None, Some("engine_overloaded"))));
+        assert!(is_retryable(&OpenAIError::StreamError(
+            "connection reset".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_client_errors_are_not_retryable() {
+        assert!(!is_retryable(&api_error(
+            Some("invalid_request_error"),
+            None
+        )));
+        assert!(!is_retryable(&api_error(
+            Some("invalid_request_error"),
+            Some("invalid_api_key")
+        )));
+        assert!(!is_retryable(&api_error(
+            Some("insufficient_quota"),
+            Some("insufficient_quota")
+        )));
+        assert!(!is_retryable(&OpenAIError::InvalidArgument(
+            "bad".to_string()
+        )));
+        let json_error = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        assert!(!is_retryable(&OpenAIError::JSONDeserialize(json_error)));
+    }
+
+    #[tokio::test]
+    async fn test_connection_errors_are_retryable() {
+        // Nothing listens on port 1, so this fails to connect without network access
+        let error = reqwest::get("http://127.0.0.1:1").await.unwrap_err();
+        assert!(is_retryable(&OpenAIError::Reqwest(error)));
+    }
+
+    #[test]
+    fn test_retry_delay_grows_exponentially_with_jitter() {
+        let provider = OpenAIProvider::with_config("sk-test", "gpt-4o-mini", 3)
+            .with_retry_base_delay(Duration::from_millis(100));
+
+        for (retry, full) in [(0, 100), (1, 200), (2, 400)] {
+            let delay = provider.retry_delay(retry);
+            assert!(delay >= Duration::from_millis(full / 2));
+            assert!(delay <= Duration::from_millis(full));
+        }
+        assert!(provider.retry_delay(30) <= MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn test_with_config_sets_model_and_retries() {
+        let provider = OpenAIProvider::with_config("sk-test", "gpt-4o", 5);
+        assert_eq!(provider.model(), "gpt-4o");
+        assert_eq!(provider.max_retries(), 5);
+    }
+
+    #[test]
+    fn test_with_max_concurrency_is_at_least_one() {
+        let provider = OpenAIProvider::with_config("sk-test", "gpt-4o", 0);
+        assert_eq!(provider.with_max_concurrency(0).max_concurrency(), 1);
+    }
+
+    /// Tracks in-flight requests at a mock upstream
+    #[derive(Default)]
+    struct InFlight {
+        current: std::sync::atomic::AtomicUsize,
+        peak: std::sync::atomic::AtomicUsize,
+    }
+
+    /// Serve a chat completions endpoint that answers after a delay, recording concurrency
+    async fn spawn_delayed_upstream(in_flight: Arc<InFlight>) -> String {
+        use axum::{routing::post, Json, Router};
+        use std::sync::atomic::Ordering;
+
+        let app = Router::new().route(
+            "/chat/completions",
+            post(move || {
+                let in_flight = in_flight.clone();
+                async move {
+                    let now = in_flight.current.fetch_add(1, Ordering::SeqCst) + 1;
+                    in_flight.peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    in_flight.current.fetch_sub(1, Ordering::SeqCst);
+                    Json(serde_json::json!({
+                        "id": "chatcmpl-test",
+                        "object": "chat.completion",
+                        "created": 0,
+                        "model": "gpt-4o-mini",
+                        "choices": [{
+                            "index": 0,
+                            "message": { "role": "assistant", "content": "ok" },
+                            "finish_reason": "stop"
+                        }]
+                    }))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_completions_never_exceed_limit() {
+        let in_flight = Arc::new(InFlight::default());
+        let api_base = spawn_delayed_upstream(in_flight.clone()).await;
+        let mut provider =
+            OpenAIProvider::with_config("sk-test", "gpt-4o-mini", 0).with_max_concurrency(2);
+        provider.client = Client::with_config(
+            OpenAIConfig::new()
+                .with_api_key("sk-test")
+                .with_api_base(api_base),
+        );
+        let provider = Arc::new(provider);
+
+        let calls = (0..6).map(|_| {
+            let provider = provider.clone();
+            tokio::spawn(async move {
+                provider
+                    .complete(vec![CanonicalMessage::new(Role::User, "hi".to_string())])
+                    .await
+            })
+        });
+        for result in futures::future::join_all(calls).await {
+            assert_eq!(result.unwrap().unwrap().content, "ok");
+        }
+
+        let peak = in_flight.peak.load(std::sync::atomic::Ordering::SeqCst);
+        assert!(peak <= 2, "peak concurrency {} exceeded limit", peak);
+        assert_eq!(peak, 2, "excess calls should queue rather than serialize");
+    }
+
+    #[test]
+    fn test_build_request_maps_roles() {
+        let provider = OpenAIProvider::with_config("sk-test", "gpt-4o", 0);
+        let request = provider
+            .build_request(
+                &[
+                    CanonicalMessage::new(Role::System, "be brief".to_string()),
+                    CanonicalMessage::new(Role::User, "hi".to_string()),
+                    CanonicalMessage::new(Role::Assistant, "hello".to_string()),
+                ],
+                &CompletionParams::default(),
+            )
+            .unwrap();
+
+        assert_eq!(request.model, "gpt-4o");
+        assert_eq!(request.temperature, None);
+        assert_eq!(request.max_tokens, None);
+        assert!(matches!(
+            request.messages.as_slice(),
+            [
+                ChatCompletionRequestMessage::System(_),
+                ChatCompletionRequestMessage::User(_),
+                ChatCompletionRequestMessage::Assistant(_),
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_build_request_applies_params() {
+        let provider = OpenAIProvider::with_config("sk-test", "gpt-4o", 0);
+        let params = CompletionParams {
+            model: Some("gpt-4o-mini".to_string()),
+            temperature: Some(0.25),
+            max_tokens: Some(128),
+        };
+        let request = provider
+            .build_request(
+                &[CanonicalMessage::new(Role::User, "hi".to_string())],
+                &params,
+            )
+            .unwrap();
+
+        assert_eq!(request.model, "gpt-4o-mini");
+        assert_eq!(request.temperature, Some(0.25));
+        assert_eq!(request.max_tokens, Some(128));
+    }
+
+    fn assistant_with_tool_call(content: &str) -> CanonicalMessage {
+        let tool_calls = serde_json::json!([{
+            "id": "call_abc123",
+            "type": "function",
+            "function": {"name": "get_weather", "arguments": "{\"city\":\"Oslo\"}"}
+        }]);
+        CanonicalMessage::with_metadata(
+            Role::Assistant,
+            content.to_string(),
+            std::collections::HashMap::from([(
+                TOOL_CALLS_METADATA_KEY.to_string(),
+                tool_calls.to_string(),
+            )]),
+        )
+    }
+
+    #[test]
+    fn test_assistant_tool_call_uses_tool_calls_field() {
+        let message = canonical_to_openai_message(&assistant_with_tool_call("")).unwrap();
+
+        let ChatCompletionRequestMessage::Assistant(assistant) = &message else {
+            panic!("Expected assistant message, got {:?}", message);
+        };
+        let tool_calls = assistant.tool_calls.as_ref().unwrap();
+        assert_eq!(tool_calls.len(), 1);
+        assert_eq!(tool_calls[0].id, "call_abc123");
+        assert_eq!(tool_calls[0].function.name, "get_weather");
+        assert_eq!(assistant.content, None);
+
+        // Wire format uses tool_calls, never the deprecated function_call
+        let json = serde_json::to_value(&message).unwrap();
+        assert!(json.get("tool_calls").is_some());
+        assert!(json.get("function_call").is_none());
+    }
+
+    #[test]
+    fn test_assistant_without_tool_calls_keeps_content() {
+        let message = canonical_to_openai_message(&CanonicalMessage::new(
+            Role::Assistant,
+            "hello".to_string(),
+        ))
+        .unwrap();
+
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["content"], "hello");
+        assert!(json.get("tool_calls").is_none());
+        assert!(json.get("function_call").is_none());
+    }
+
+    #[test]
+    fn test_invalid_tool_calls_metadata_is_rejected() {
+        let message = CanonicalMessage::with_metadata(
+            Role::Assistant,
+            String::new(),
+            std::collections::HashMap::from([(
+                TOOL_CALLS_METADATA_KEY.to_string(),
+                "not json".to_string(),
+            )]),
+        );
+
+        assert!(matches!(
+            canonical_to_openai_message(&message),
+            Err(OpenAIError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_response_tool_calls_round_trip_into_metadata() {
+        let response: CreateChatCompletionResponse = serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "finish_reason": "tool_calls",
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{
+                        "id": "call_abc123",
+                        "type": "function",
+                        "function": {"name": "get_weather", "arguments": "{}"}
+                    }]
+                }
+            }]
+        }))
+        .unwrap();
+
+        let message = from_response(response).unwrap();
+
+        assert_eq!(message.content, "");
+        let tool_calls: Vec<ChatCompletionMessageToolCall> =
+            serde_json::from_str(&message.metadata[TOOL_CALLS_METADATA_KEY]).unwrap();
+        assert_eq!(tool_calls[0].function.name, "get_weather");
+        assert_eq!(message.metadata[MODEL_METADATA_KEY], "gpt-4o");
+    }
+
+    /// Build a streamed chunk from a JSON list of choices
+    fn stream_chunk(choices: serde_json::Value) -> CreateChatCompletionStreamResponse {
+        serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "created": 0,
+            "model": "gpt-4o-mini",
+            "choices": choices
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_delta_content_extracts_text() {
+        let chunk = stream_chunk(serde_json::json!([
+            {"index": 0, "delta": {"content": "Hel"}, "finish_reason": null}
+        ]));
+        assert_eq!(delta_content(&chunk), Some("Hel".to_string()));
+    }
+
+    #[test]
+    fn test_delta_content_skips_empty_deltas() {
+        let role_only = stream_chunk(serde_json::json!([
+            {"index": 0, "delta": {"role": "assistant"}, "finish_reason": null}
+        ]));
+        let empty = stream_chunk(serde_json::json!([
+            {"index": 0, "delta": {"content": ""}, "finish_reason": null}
+        ]));
+        let finished = stream_chunk(serde_json::json!([
+            {"index": 0, "delta": {}, "finish_reason": "stop"}
+        ]));
+        let usage_only = stream_chunk(serde_json::json!([]));
+
+        assert_eq!(delta_content(&role_only), None);
+        assert_eq!(delta_content(&empty), None);
+        assert_eq!(delta_content(&finished), None);
+        assert_eq!(delta_content(&usage_only), None);
+    }
+}
@@ -3,16 +3,16 @@
 
 use crate::core::error::SentinelError;
 use crate::core::traits::VectorStore;
-use crate::core::types::MessageId;
+use crate::core::types::{metadata_byte_size, MessageId, DEFAULT_MAX_METADATA_BYTES};
 use async_trait::async_trait;
 use qdrant_client::qdrant::{
-    vectors_config::Config, CreateCollection, Distance, PointStruct, ScoredPoint, SearchPoints,
-    UpsertPoints, VectorParams, VectorsConfig,
+    vectors_config::Config, Condition, CreateCollection, DeletePoints, Distance, Filter, GetPoints,
+    PointId, PointStruct, ScoredPoint, SearchPoints, UpsertPoints, VectorParams, VectorsConfig,
 };
-use qdrant_client::Qdrant;
+use qdrant_client::{Qdrant, QdrantError};
 use std::collections::HashMap;
 use std::env;
-use tracing::{debug, info, warn};
+use tracing::{debug, info, instrument, warn};
 
 /// Default Qdrant server URL
 const DEFAULT_QDRANT_URL: &str = "http://localhost:6333";
@@ -20,15 +20,87 @@ const DEFAULT_QDRANT_URL: &str = "http://localhost:6333";
 /// Default collection name for long-term memories
 const DEFAULT_COLLECTION_NAME: &str = "sentinel_memories";
 
-/// Default vector dimension (1536 for OpenAI embeddings, 384 for sentence-transformers)
-/// This should match the embedding model being used
-const DEFAULT_VECTOR_DIM: u64 = 1536;
+/// Embedding model assumed when none is configured
+const DEFAULT_EMBEDDING_MODEL: EmbeddingModel = EmbeddingModel::OpenAiAda002;
+
+/// Collection metadata key recording the embedding model a collection was created for
+pub const EMBEDDING_MODEL_METADATA_KEY: &str = "embedding_model";
+
+/// Name reported in `SentinelError::ProviderError` for Qdrant failures
+const PROVIDER_NAME: &str = "qdrant";
+
+/// Build the error for a failed Qdrant request
+fn provider_error(detail: impl Into<String>) -> SentinelError {
+    SentinelError::ProviderError {
+        provider: PROVIDER_NAME.to_string(),
+        detail: detail.into(),
+    }
+}
+
+/// Check whether a Qdrant error reports that a collection already exists
+///
+/// Qdrant answers a duplicate `create_collection` with `InvalidArgument` and a message
+/// like "Wrong input: Collection `name` already exists!" rather than a dedicated code,
+/// so the message is inspected as well as the status code.
+fn is_already_exists_error(error: &QdrantError) -> bool {
+    match error {
+        QdrantError::ResponseError { status } => {
+            status.code() == tonic::Code::AlreadyExists
+                || status
+                    .message()
+                    .to_ascii_lowercase()
+                    .contains("already exists")
+        }
+        _ => false,
+    }
+}
+
+/// Known embedding models and the vector dimension each produces
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmbeddingModel {
+    /// OpenAI `text-embedding-ada-002`
+    OpenAiAda002,
+    /// OpenAI `text-embedding-3-small`
+    OpenAiSmall3,
+    /// sentence-transformers `all-MiniLM-L6-v2`
+    MiniLmL6,
+}
+
+impl EmbeddingModel {
+    /// Dimension of the vectors the model produces
+    pub fn dimension(&self) -> u64 {
+        match self {
+            Self::OpenAiAda002 | Self::OpenAiSmall3 => 1536,
+            Self::MiniLmL6 => 384,
+        }
+    }
+
+    /// Name of the model as used by its provider
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::OpenAiAda002 => "text-embedding-ada-002",
+            Self::OpenAiSmall3 => "text-embedding-3-small",
+            Self::MiniLmL6 => "all-MiniLM-L6-v2",
+        }
+    }
+}
+
+impl std::fmt::Display for EmbeddingModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
 
 /// Qdrant vector store implementation
 pub struct QdrantStore {
     client: Qdrant,
     collection_name: String,
     vector_dim: u64,
+    /// Embedding model the collection is for, when created from a named model
+    model: Option<EmbeddingModel>,
+    max_metadata_bytes: usize,
+    /// Fail searches whose results contain unconvertible point IDs instead of dropping them
+    strict_conversion: bool,
 }
 
 impl QdrantStore {
@@ -41,7 +113,28 @@ impl QdrantStore {
         let url = env::var("QDRANT_URL").unwrap_or_else(|_| DEFAULT_QDRANT_URL.to_string());
         let collection_name =
             env::var("QDRANT_COLLECTION").unwrap_or_else(|_| DEFAULT_COLLECTION_NAME.to_string());
-        Self::with_config(&url, &collection_name, DEFAULT_VECTOR_DIM).await
+        Self::with_model(&url, &collection_name, DEFAULT_EMBEDDING_MODEL).await
+    }
+
+    /// Create a new Qdrant store for a named embedding model
+    ///
+    /// The vector dimension is derived from the model, and the model name is recorded
+    /// in the metadata of a newly created collection.
+    ///
+    /// # Arguments
+    /// * `url` - Qdrant server URL
+    /// * `collection_name` - Name of the collection to use/create
+    /// * `model` - Embedding model producing the stored vectors
+    ///
+    /// # Returns
+    /// * `Ok(QdrantStore)` - Successfully created
+    /// * `Err(SentinelError)` - Error if connection or collection creation fails
+    pub async fn with_model(
+        url: &str,
+        collection_name: &str,
+        model: EmbeddingModel,
+    ) -> Result<Self, SentinelError> {
+        Self::connect(url, collection_name, model.dimension(), Some(model)).await
     }
 
     /// Create a new Qdrant store with custom configuration
@@ -58,25 +151,37 @@ impl QdrantStore {
         url: &str,
         collection_name: &str,
         vector_dim: u64,
+    ) -> Result<Self, SentinelError> {
+        Self::connect(url, collection_name, vector_dim, None).await
+    }
+
+    async fn connect(
+        url: &str,
+        collection_name: &str,
+        vector_dim: u64,
+        model: Option<EmbeddingModel>,
     ) -> Result<Self, SentinelError> {
         let client = Qdrant::from_url(url)
             .build()
-            .map_err(|e| SentinelError::DomainViolation {
-                rule: format!("Failed to connect to Qdrant at {}: {}", url, e),
-            })?;
+            .map_err(|e| provider_error(format!("failed to connect to {}: {}", url, e)))?;
 
         let store = Self {
             client,
             collection_name: collection_name.to_string(),
             vector_dim,
+            model,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            strict_conversion: false,
         };
 
         // Ensure collection exists
         store.ensure_collection().await?;
 
         info!(
-            "Qdrant store initialized: collection={}, vector_dim={}",
-            collection_name, vector_dim
+            "Qdrant store initialized: collection={}, vector_dim={}, model={}",
+            collection_name,
+            vector_dim,
+            model.map_or("unspecified", |model| model.name())
         );
 
         Ok(store)
@@ -113,20 +218,72 @@ impl QdrantStore {
                     ..Default::default()
                 })),
             }),
+            metadata: self
+                .model
+                .map(|model| {
+                    HashMap::from([(
+                        EMBEDDING_MODEL_METADATA_KEY.to_string(),
+                        model.name().into(),
+                    )])
+                })
+                .unwrap_or_default(),
             ..Default::default()
         };
 
-        self.client
-            .create_collection(create_collection)
-            .await
-            .map_err(|e| SentinelError::DomainViolation {
-                rule: format!(
-                    "Failed to create collection {}: {}",
+        match self.client.create_collection(create_collection).await {
+            Ok(_) => {
+                info!("Created Qdrant collection: {}", self.collection_name);
+                Ok(())
+            }
+            // Another instance created it between our check and create
+            Err(e) if is_already_exists_error(&e) => {
+                debug!(
+                    "Collection {} was created concurrently: {}",
                     self.collection_name, e
-                ),
-            })?;
+                );
+                Ok(())
+            }
+            Err(e) => Err(provider_error(format!(
+                "failed to create collection {}: {}",
+                self.collection_name, e
+            ))),
+        }
+    }
+
+    /// Set the maximum total metadata byte size accepted per point
+    pub fn with_max_metadata_bytes(mut self, max_metadata_bytes: usize) -> Self {
+        self.max_metadata_bytes = max_metadata_bytes;
+        self
+    }
+
+    /// Fail searches when any returned point ID cannot be converted to a `MessageId`
+    ///
+    /// By default such points are dropped with a warning; strict mode surfaces them as an
+    /// error so a mismatch between stored IDs and this adapter is not mistaken for "no matches".
+    pub fn with_strict_conversion(mut self, strict: bool) -> Self {
+        self.strict_conversion = strict;
+        self
+    }
 
-        info!("Created Qdrant collection: {}", self.collection_name);
+    /// Validate that a point's metadata fits within the configured byte limit
+    ///
+    /// # Returns
+    /// * `Ok(())` - Metadata is within the limit
+    /// * `Err(SentinelError)` - InvalidMessage naming the message and sizes
+    fn validate_metadata_size(
+        &self,
+        id: MessageId,
+        metadata: &HashMap<String, String>,
+    ) -> Result<(), SentinelError> {
+        let metadata_bytes = metadata_byte_size(metadata);
+        if metadata_bytes > self.max_metadata_bytes {
+            return Err(SentinelError::InvalidMessage {
+                reason: format!(
+                    "Metadata for message {} is {} bytes, exceeding the limit of {} bytes",
+                    id, metadata_bytes, self.max_metadata_bytes
+                ),
+            });
+        }
         Ok(())
     }
 
@@ -163,6 +320,113 @@ impl QdrantStore {
             .collect()
     }
 
+    /// Convert exact-match metadata filters into a Qdrant filter
+    ///
+    /// # Returns
+    /// `None` for an empty map, otherwise a filter with one `must` condition per entry
+    fn metadata_to_filter(&self, filters: &HashMap<String, String>) -> Option<Filter> {
+        if filters.is_empty() {
+            return None;
+        }
+        Some(Filter::must(filters.iter().map(|(key, value)| {
+            Condition::matches(key.clone(), value.clone())
+        })))
+    }
+
+    /// Describe the expected embedding dimension, naming the model when known
+    fn expected_dimension(&self) -> String {
+        match self.model {
+            Some(model) => format!("{} (model {})", self.vector_dim, model),
+            None => self.vector_dim.to_string(),
+        }
+    }
+
+    /// Validate that every embedding in a batch matches the collection dimension
+    ///
+    /// # Returns
+    /// * `Ok(())` - All embeddings have the expected dimension
+    /// * `Err(SentinelError)` - InvalidMessage naming the first offending index
+    fn validate_batch_dimensions(
+        &self,
+        items: &[(MessageId, Vec<f32>, HashMap<String, String>)],
+    ) -> Result<(), SentinelError> {
+        for (index, (id, embedding, _)) in items.iter().enumerate() {
+            if embedding.len() as u64 != self.vector_dim {
+                return Err(SentinelError::InvalidMessage {
+                    reason: format!(
+                        "Embedding dimension mismatch at batch index {} (message {}): expected {}, got {}",
+                        index,
+                        id,
+                        self.expected_dimension(),
+                        embedding.len()
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Convert a Qdrant scored point into a (MessageId, score) pair
+    ///
+    /// # Returns
+    /// `None` if the point has no ID or the ID is not a valid UUID
+    fn scored_point_to_result(&self, point: &ScoredPoint) -> Option<(MessageId, f32)> {
+        let id = point.id.as_ref()?;
+        // Qdrant PointId can be UUID or num - we stored as UUID string
+        match self.extract_uuid_from_point_id(id) {
+            Ok(uuid_str) => self
+                .point_id_to_message_id(&uuid_str)
+                .ok()
+                .map(|message_id| (message_id, point.score)),
+            Err(_) => {
+                warn!("Failed to extract UUID from point ID, skipping");
+                None
+            }
+        }
+    }
+
+    /// Convert the points returned by a search, accounting for any that were dropped
+    ///
+    /// # Arguments
+    /// * `points` - Scored points as returned by Qdrant
+    ///
+    /// # Returns
+    /// * `Ok(Vec<(MessageId, f32)>)` - Converted results; unconvertible points are dropped
+    ///   with a warning naming the returned and converted counts
+    /// * `Err(SentinelError)` - Strict mode is enabled and at least one point was dropped
+    fn convert_scored_points(
+        &self,
+        points: &[ScoredPoint],
+    ) -> Result<Vec<(MessageId, f32)>, SentinelError> {
+        let results: Vec<(MessageId, f32)> = points
+            .iter()
+            .filter_map(|point| self.scored_point_to_result(point))
+            .collect();
+
+        let dropped = points.len() - results.len();
+        if dropped > 0 {
+            warn!(
+                collection = %self.collection_name,
+                returned = points.len(),
+                converted = results.len(),
+                dropped,
+                "Search results dropped due to unconvertible point IDs"
+            );
+            if self.strict_conversion {
+                return Err(SentinelError::DomainViolation {
+                    rule: format!(
+                        "{} of {} search results in collection {} have point IDs that are not MessageIds",
+                        dropped,
+                        points.len(),
+                        self.collection_name
+                    ),
+                });
+            }
+        }
+
+        Ok(results)
+    }
+
     /// Extract UUID string from Qdrant PointId
     /// This handles both UUID and numeric point IDs
     fn extract_uuid_from_point_id(
@@ -196,6 +460,10 @@ impl QdrantStore {
 
 #[async_trait]
 impl VectorStore for QdrantStore {
+    #[instrument(
+        skip(self, embedding, metadata),
+        fields(collection = %self.collection_name, id = %id, dimension = embedding.len())
+    )]
     async fn upsert(
         &self,
         id: MessageId,
@@ -207,11 +475,12 @@ impl VectorStore for QdrantStore {
             return Err(SentinelError::InvalidMessage {
                 reason: format!(
                     "Embedding dimension mismatch: expected {}, got {}",
-                    self.vector_dim,
+                    self.expected_dimension(),
                     embedding.len()
                 ),
             });
         }
+        self.validate_metadata_size(id, &metadata)?;
 
         let point_id = self.message_id_to_point_id(id);
         let payload = self.metadata_to_payload(&metadata);
@@ -227,25 +496,78 @@ impl VectorStore for QdrantStore {
         self.client
             .upsert_points(upsert_request)
             .await
-            .map_err(|e| SentinelError::DomainViolation {
-                rule: format!("Failed to upsert point {}: {}", id, e),
-            })?;
+            .map_err(|e| provider_error(format!("failed to upsert point {}: {}", id, e)))?;
 
         debug!("Upserted embedding for message {}", id);
         Ok(())
     }
 
-    async fn search(
+    #[instrument(
+        skip_all,
+        fields(collection = %self.collection_name, batch_size = items.len())
+    )]
+    async fn upsert_batch(
+        &self,
+        items: Vec<(MessageId, Vec<f32>, HashMap<String, String>)>,
+    ) -> Result<(), SentinelError> {
+        if items.is_empty() {
+            return Ok(());
+        }
+
+        // Reject the whole batch if any embedding has the wrong dimension
+        self.validate_batch_dimensions(&items)?;
+        for (id, _, metadata) in &items {
+            self.validate_metadata_size(*id, metadata)?;
+        }
+
+        let count = items.len();
+        let points: Vec<PointStruct> = items
+            .into_iter()
+            .map(|(id, embedding, metadata)| {
+                let payload = self.metadata_to_payload(&metadata);
+                PointStruct::new(self.message_id_to_point_id(id), embedding, payload)
+            })
+            .collect();
+
+        let upsert_request = UpsertPoints {
+            collection_name: self.collection_name.clone(),
+            points,
+            ..Default::default()
+        };
+
+        self.client
+            .upsert_points(upsert_request)
+            .await
+            .map_err(|e| {
+                provider_error(format!("failed to upsert batch of {} points: {}", count, e))
+            })?;
+
+        debug!("Upserted batch of {} embeddings", count);
+        Ok(())
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            collection = %self.collection_name,
+            limit,
+            filtered = filters.is_some(),
+            returned_count = tracing::field::Empty,
+            result_count = tracing::field::Empty
+        )
+    )]
+    async fn search_filtered(
         &self,
         query_embedding: Vec<f32>,
         limit: usize,
-    ) -> Result<Vec<MessageId>, SentinelError> {
+        filters: Option<HashMap<String, String>>,
+    ) -> Result<Vec<(MessageId, f32)>, SentinelError> {
         // Validate query embedding dimension
         if query_embedding.len() as u64 != self.vector_dim {
             return Err(SentinelError::InvalidMessage {
                 reason: format!(
                     "Query embedding dimension mismatch: expected {}, got {}",
-                    self.vector_dim,
+                    self.expected_dimension(),
                     query_embedding.len()
                 ),
             });
@@ -256,6 +578,7 @@ impl VectorStore for QdrantStore {
             vector: query_embedding,
             limit: limit as u64,
             with_payload: Some(true.into()),
+            filter: filters.as_ref().and_then(|f| self.metadata_to_filter(f)),
             ..Default::default()
         };
 
@@ -263,32 +586,58 @@ impl VectorStore for QdrantStore {
             .client
             .search_points(search_points)
             .await
-            .map_err(|e| SentinelError::DomainViolation {
-                rule: format!("Failed to search vectors: {}", e),
-            })?;
+            .map_err(|e| provider_error(format!("failed to search vectors: {}", e)))?;
 
-        // Convert Qdrant point IDs back to MessageIds
-        let message_ids: Vec<MessageId> = search_result
-            .result
-            .iter()
-            .filter_map(|point: &ScoredPoint| {
-                point.id.as_ref().and_then(|id| {
-                    // Extract UUID from point ID
-                    // Qdrant PointId can be UUID or num - we stored as UUID string
-                    match self.extract_uuid_from_point_id(id) {
-                        Ok(uuid_str) => self.point_id_to_message_id(&uuid_str).ok(),
-                        Err(_) => {
-                            warn!("Failed to extract UUID from point ID, skipping");
-                            None
-                        }
-                    }
-                })
-            })
-            .collect();
+        // Convert Qdrant point IDs back to MessageIds, keeping the similarity score
+        tracing::Span::current().record("returned_count", search_result.result.len());
+        let results = self.convert_scored_points(&search_result.result)?;
+
+        tracing::Span::current().record("result_count", results.len());
+        debug!("Search returned {} results", results.len());
+        Ok(results)
+    }
+
+    #[instrument(skip(self), fields(collection = %self.collection_name, id = %id))]
+    async fn contains(&self, id: MessageId) -> Result<bool, SentinelError> {
+        let get_request = GetPoints {
+            collection_name: self.collection_name.clone(),
+            ids: vec![PointId::from(self.message_id_to_point_id(id))],
+            with_payload: Some(false.into()),
+            with_vectors: Some(false.into()),
+            ..Default::default()
+        };
+
+        let response = self
+            .client
+            .get_points(get_request)
+            .await
+            .map_err(|e| provider_error(format!("failed to look up point {}: {}", id, e)))?;
+
+        Ok(!response.result.is_empty())
+    }
+
+    #[instrument(skip(self), fields(collection = %self.collection_name, id = %id))]
+    async fn delete(&self, id: MessageId) -> Result<(), SentinelError> {
+        let point_id = PointId::from(self.message_id_to_point_id(id));
+
+        let delete_request = DeletePoints {
+            collection_name: self.collection_name.clone(),
+            wait: Some(true),
+            points: Some(vec![point_id].into()),
+            ..Default::default()
+        };
+
+        self.client
+            .delete_points(delete_request)
+            .await
+            .map_err(|e| provider_error(format!("failed to delete point {}: {}", id, e)))?;
 
-        let ids = message_ids;
-        debug!("Search returned {} results", ids.len());
-        Ok(ids)
+        debug!("Deleted embedding for message {}", id);
+        Ok(())
+    }
+
+    fn dimension(&self) -> Option<usize> {
+        Some(self.vector_dim as usize)
     }
 }
 
@@ -307,6 +656,9 @@ mod tests {
             client: Qdrant::from_url("http://localhost:6333").build().unwrap(),
             collection_name: "test".to_string(),
             vector_dim: 1536,
+            model: None,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            strict_conversion: false,
         };
 
         let mut metadata = HashMap::new();
@@ -325,6 +677,9 @@ mod tests {
             client: Qdrant::from_url("http://localhost:6333").build().unwrap(),
             collection_name: "test".to_string(),
             vector_dim: 1536,
+            model: None,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            strict_conversion: false,
         };
 
         let message_id = MessageId::new();
@@ -339,6 +694,9 @@ mod tests {
             client: Qdrant::from_url("http://localhost:6333").build().unwrap(),
             collection_name: "test".to_string(),
             vector_dim: 1536,
+            model: None,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            strict_conversion: false,
         };
 
         let original_id = MessageId::new();
@@ -354,12 +712,329 @@ mod tests {
             client: Qdrant::from_url("http://localhost:6333").build().unwrap(),
             collection_name: "test".to_string(),
             vector_dim: 1536,
+            model: None,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            strict_conversion: false,
         };
 
         let result = store.point_id_to_message_id("invalid-uuid");
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_scored_point_to_result() {
+        let store = QdrantStore {
+            client: Qdrant::from_url("http://localhost:6333").build().unwrap(),
+            collection_name: "test".to_string(),
+            vector_dim: 3,
+            model: None,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            strict_conversion: false,
+        };
+
+        let message_id = MessageId::new();
+        let point = ScoredPoint {
+            id: Some(PointId::from(store.message_id_to_point_id(message_id))),
+            score: 0.87,
+            ..Default::default()
+        };
+        assert_eq!(
+            store.scored_point_to_result(&point),
+            Some((message_id, 0.87))
+        );
+
+        // Numeric and missing IDs are skipped
+        let numeric = ScoredPoint {
+            id: Some(PointId::from(42u64)),
+            score: 0.5,
+            ..Default::default()
+        };
+        assert_eq!(store.scored_point_to_result(&numeric), None);
+        assert_eq!(store.scored_point_to_result(&ScoredPoint::default()), None);
+    }
+
+    /// Two convertible points followed by a numeric-ID point and a point without an ID
+    fn mixed_points(store: &QdrantStore, ids: [MessageId; 2]) -> Vec<ScoredPoint> {
+        vec![
+            ScoredPoint {
+                id: Some(PointId::from(store.message_id_to_point_id(ids[0]))),
+                score: 0.9,
+                ..Default::default()
+            },
+            ScoredPoint {
+                id: Some(PointId::from(7u64)),
+                score: 0.8,
+                ..Default::default()
+            },
+            ScoredPoint {
+                id: Some(PointId::from(store.message_id_to_point_id(ids[1]))),
+                score: 0.7,
+                ..Default::default()
+            },
+            ScoredPoint {
+                score: 0.6,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_convert_scored_points_drops_unconvertible_points() {
+        let store = QdrantStore {
+            client: Qdrant::from_url("http://localhost:6333").build().unwrap(),
+            collection_name: "test".to_string(),
+            vector_dim: 3,
+            model: None,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            strict_conversion: false,
+        };
+        let ids = [MessageId::new(), MessageId::new()];
+
+        let results = store
+            .convert_scored_points(&mixed_points(&store, ids))
+            .unwrap();
+        assert_eq!(results, vec![(ids[0], 0.9), (ids[1], 0.7)]);
+
+        // No points returned is a genuine empty result in either mode
+        assert!(store.convert_scored_points(&[]).unwrap().is_empty());
+        let strict = store.with_strict_conversion(true);
+        assert!(strict.convert_scored_points(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_convert_scored_points_strict_mode_surfaces_discrepancy() {
+        let store = QdrantStore {
+            client: Qdrant::from_url("http://localhost:6333").build().unwrap(),
+            collection_name: "test".to_string(),
+            vector_dim: 3,
+            model: None,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            strict_conversion: false,
+        }
+        .with_strict_conversion(true);
+        let ids = [MessageId::new(), MessageId::new()];
+
+        let error = store
+            .convert_scored_points(&mixed_points(&store, ids))
+            .unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("2 of 4"), "unexpected error: {}", message);
+
+        // Fully convertible results pass in strict mode
+        let convertible = &mixed_points(&store, ids)[..1];
+        assert_eq!(
+            store.convert_scored_points(convertible).unwrap(),
+            vec![(ids[0], 0.9)]
+        );
+    }
+
+    #[test]
+    fn test_metadata_to_filter() {
+        let store = QdrantStore {
+            client: Qdrant::from_url("http://localhost:6333").build().unwrap(),
+            collection_name: "test".to_string(),
+            vector_dim: 3,
+            model: None,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            strict_conversion: false,
+        };
+
+        // No filters means an unfiltered search
+        assert!(store.metadata_to_filter(&HashMap::new()).is_none());
+
+        let filters = HashMap::from([
+            ("agent_id".to_string(), "agent-1".to_string()),
+            ("source".to_string(), "consolidation".to_string()),
+        ]);
+        let filter = store.metadata_to_filter(&filters).unwrap();
+        assert!(filter.should.is_empty());
+        assert!(filter.must_not.is_empty());
+
+        let mut conditions = filter.must.clone();
+        conditions.sort_by_key(|c| format!("{:?}", c));
+        let mut expected = vec![
+            Condition::matches("agent_id", "agent-1".to_string()),
+            Condition::matches("source", "consolidation".to_string()),
+        ];
+        expected.sort_by_key(|c| format!("{:?}", c));
+        assert_eq!(conditions, expected);
+    }
+
+    #[tokio::test]
+    async fn test_upsert_batch_rejects_dimension_mismatch() {
+        let store = QdrantStore {
+            client: Qdrant::from_url("http://localhost:6333").build().unwrap(),
+            collection_name: "test".to_string(),
+            vector_dim: 3,
+            model: None,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            strict_conversion: false,
+        };
+
+        let items = vec![
+            (MessageId::new(), vec![0.1, 0.2, 0.3], HashMap::new()),
+            (MessageId::new(), vec![0.1, 0.2, 0.3], HashMap::new()),
+            (MessageId::new(), vec![0.1, 0.2], HashMap::new()), // Wrong dimension
+        ];
+
+        // Validation fails before any request is sent
+        let result = store.upsert_batch(items).await;
+        match result.unwrap_err() {
+            SentinelError::InvalidMessage { reason } => {
+                assert!(reason.contains("dimension mismatch"));
+                assert!(reason.contains("batch index 2"));
+            }
+            _ => panic!("Expected InvalidMessage error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upsert_rejects_oversized_metadata() {
+        // Nothing listens on this port, so any write attempt would fail with DomainViolation
+        let store = QdrantStore {
+            client: Qdrant::from_url("http://127.0.0.1:1").build().unwrap(),
+            collection_name: "test".to_string(),
+            vector_dim: 3,
+            model: None,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            strict_conversion: false,
+        }
+        .with_max_metadata_bytes(32);
+        let oversized = HashMap::from([("blob".to_string(), "x".repeat(64))]);
+
+        match store
+            .upsert(MessageId::new(), vec![0.1, 0.2, 0.3], oversized.clone())
+            .await
+            .unwrap_err()
+        {
+            SentinelError::InvalidMessage { reason } => {
+                assert!(reason.contains("exceeding the limit of 32 bytes"));
+            }
+            other => panic!("Expected InvalidMessage, got {:?}", other),
+        }
+
+        let items = vec![
+            (MessageId::new(), vec![0.1, 0.2, 0.3], HashMap::new()),
+            (MessageId::new(), vec![0.1, 0.2, 0.3], oversized),
+        ];
+        assert!(matches!(
+            store.upsert_batch(items).await,
+            Err(SentinelError::InvalidMessage { .. })
+        ));
+    }
+
+    #[test]
+    fn test_is_already_exists_error() {
+        let response = |code, message: &str| QdrantError::ResponseError {
+            status: tonic::Status::new(code, message),
+        };
+
+        // What Qdrant returns for a duplicate create
+        assert!(is_already_exists_error(&response(
+            tonic::Code::InvalidArgument,
+            "Wrong input: Collection `sentinel_memories` already exists!",
+        )));
+        assert!(is_already_exists_error(&response(
+            tonic::Code::AlreadyExists,
+            "collection exists",
+        )));
+
+        assert!(!is_already_exists_error(&response(
+            tonic::Code::InvalidArgument,
+            "Wrong input: Vector dimension error: expected dim: 1536, got 384",
+        )));
+        assert!(!is_already_exists_error(&response(
+            tonic::Code::Unavailable,
+            "connection refused",
+        )));
+        assert!(!is_already_exists_error(&QdrantError::ConversionError(
+            "already exists".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_embedding_model_dimensions() {
+        assert_eq!(EmbeddingModel::OpenAiAda002.dimension(), 1536);
+        assert_eq!(EmbeddingModel::OpenAiSmall3.dimension(), 1536);
+        assert_eq!(EmbeddingModel::MiniLmL6.dimension(), 384);
+        assert_eq!(DEFAULT_EMBEDDING_MODEL.dimension(), 1536);
+    }
+
+    #[test]
+    fn test_embedding_model_names() {
+        assert_eq!(
+            EmbeddingModel::OpenAiAda002.name(),
+            "text-embedding-ada-002"
+        );
+        assert_eq!(
+            EmbeddingModel::OpenAiSmall3.name(),
+            "text-embedding-3-small"
+        );
+        assert_eq!(EmbeddingModel::MiniLmL6.to_string(), "all-MiniLM-L6-v2");
+    }
+
+    #[tokio::test]
+    async fn test_dimension_mismatch_names_model() {
+        let model = EmbeddingModel::MiniLmL6;
+        let store = QdrantStore {
+            client: Qdrant::from_url("http://127.0.0.1:1").build().unwrap(),
+            collection_name: "test".to_string(),
+            vector_dim: model.dimension(),
+            model: Some(model),
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            strict_conversion: false,
+        };
+
+        match store
+            .upsert(MessageId::new(), vec![0.1; 1536], HashMap::new())
+            .await
+            .unwrap_err()
+        {
+            SentinelError::InvalidMessage { reason } => {
+                assert!(
+                    reason.contains("expected 384 (model all-MiniLM-L6-v2), got 1536"),
+                    "{}",
+                    reason
+                );
+            }
+            other => panic!("Expected InvalidMessage, got {:?}", other),
+        }
+        match store.search(vec![0.1; 3], 5).await.unwrap_err() {
+            SentinelError::InvalidMessage { reason } => {
+                assert!(reason.contains("all-MiniLM-L6-v2"), "{}", reason);
+            }
+            other => panic!("Expected InvalidMessage, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_dimension_reports_configured_vector_dim() {
+        let store = QdrantStore {
+            client: Qdrant::from_url("http://localhost:6333").build().unwrap(),
+            collection_name: "test".to_string(),
+            vector_dim: 384,
+            model: None,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            strict_conversion: false,
+        };
+
+        assert_eq!(store.dimension(), Some(384));
+    }
+
+    #[tokio::test]
+    async fn test_upsert_batch_empty_is_noop() {
+        let store = QdrantStore {
+            client: Qdrant::from_url("http://localhost:6333").build().unwrap(),
+            collection_name: "test".to_string(),
+            vector_dim: 3,
+            model: None,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            strict_conversion: false,
+        };
+
+        assert!(store.upsert_batch(Vec::new()).await.is_ok());
+    }
+
     // Integration test helper - requires Qdrant running
     #[tokio::test]
     #[ignore] // Ignore by default, run with --ignored flag
@@ -384,6 +1059,122 @@ mod tests {
         assert!(results.contains(&message_id));
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn test_qdrant_batch_upsert_integration() {
+        let store = QdrantStore::with_config("http://localhost:6333", "test_collection", 3)
+            .await
+            .unwrap();
+
+        let ids: Vec<MessageId> = (0..5).map(|_| MessageId::new()).collect();
+        let items = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let mut metadata = HashMap::new();
+                metadata.insert("index".to_string(), i.to_string());
+                (*id, vec![0.1 * (i as f32 + 1.0), 0.2, 0.3], metadata)
+            })
+            .collect();
+
+        // Upsert all points in a single request
+        store.upsert_batch(items).await.unwrap();
+
+        // Search
+        let results = store.search(vec![0.1, 0.2, 0.3], 10).await.unwrap();
+        for id in &ids {
+            assert!(results.contains(id));
+        }
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_qdrant_search_scored_integration() {
+        let store = QdrantStore::with_config("http://localhost:6333", "test_collection", 3)
+            .await
+            .unwrap();
+
+        let items = (0..5)
+            .map(|i| {
+                (
+                    MessageId::new(),
+                    vec![1.0, i as f32 * 0.5, 0.1],
+                    HashMap::new(),
+                )
+            })
+            .collect();
+        store.upsert_batch(items).await.unwrap();
+
+        let results = store.search_scored(vec![1.0, 0.0, 0.1], 5).await.unwrap();
+        assert!(!results.is_empty());
+        // Scores are ordered from most to least similar
+        for pair in results.windows(2) {
+            assert!(pair[0].1 >= pair[1].1);
+        }
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_qdrant_search_filtered_integration() {
+        let store = QdrantStore::with_config("http://localhost:6333", "test_collection", 3)
+            .await
+            .unwrap();
+
+        let agent_a = uuid::Uuid::new_v4().to_string();
+        let agent_b = uuid::Uuid::new_v4().to_string();
+        let id_a = MessageId::new();
+        let id_b = MessageId::new();
+        let embedding = vec![0.7, 0.1, 0.2];
+
+        store
+            .upsert_batch(vec![
+                (
+                    id_a,
+                    embedding.clone(),
+                    HashMap::from([("agent_id".to_string(), agent_a.clone())]),
+                ),
+                (
+                    id_b,
+                    embedding.clone(),
+                    HashMap::from([("agent_id".to_string(), agent_b)]),
+                ),
+            ])
+            .await
+            .unwrap();
+
+        let filters = HashMap::from([("agent_id".to_string(), agent_a)]);
+        let results = store
+            .search_filtered(embedding, 10, Some(filters))
+            .await
+            .unwrap();
+        let ids: Vec<MessageId> = results.into_iter().map(|(id, _)| id).collect();
+
+        assert_eq!(ids, vec![id_a]);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_qdrant_delete_integration() {
+        let store = QdrantStore::with_config("http://localhost:6333", "test_collection", 3)
+            .await
+            .unwrap();
+
+        let message_id = MessageId::new();
+        let embedding = vec![0.4, 0.5, 0.6];
+
+        store
+            .upsert(message_id, embedding.clone(), HashMap::new())
+            .await
+            .unwrap();
+        let results = store.search(embedding.clone(), 10).await.unwrap();
+        assert!(results.contains(&message_id));
+
+        // Delete and confirm search no longer returns the point
+        store.delete(message_id).await.unwrap();
+        let results = store.search(embedding, 10).await.unwrap();
+        assert!(!results.contains(&message_id));
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_embedding_dimension_validation() {
@@ -0,0 +1,346 @@
+// Routing LLM provider
+// Spreads completions over several providers by weight and fails over on upstream errors
+
+use crate::core::error::SentinelError;
+use crate::core::traits::LLMProvider;
+use crate::core::types::{CanonicalMessage, CompletionParams};
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::warn;
+
+/// LLM provider that dispatches to a list of providers by weighted round-robin
+///
+/// Every `total weight` calls, each provider is chosen first `weight` times. When the
+/// chosen provider fails with `SentinelError::ProviderError`, the call is retried on
+/// the following providers in list order (wrapping around); any other error is
+/// returned as is, since another provider would reject the request too.
+///
+/// A provider with weight 0 is never chosen first but still serves as a failover
+/// target, which makes it a backup.
+pub struct RoutingProvider {
+    /// Providers in failover order, with their weights
+    providers: Vec<(Arc<dyn LLMProvider>, u32)>,
+    /// Sum of all weights (always > 0)
+    total_weight: u64,
+    /// Number of calls routed so far
+    next: AtomicU64,
+}
+
+impl RoutingProvider {
+    /// Create a routing provider
+    ///
+    /// # Arguments
+    /// * `providers` - Providers with their weights, in failover order
+    ///
+    /// # Returns
+    /// * `Ok(RoutingProvider)` - Provider routing over the list
+    /// * `Err(SentinelError)` - The list is empty or no provider has a positive weight
+    pub fn new(providers: Vec<(Arc<dyn LLMProvider>, u32)>) -> Result<Self, SentinelError> {
+        if providers.is_empty() {
+            return Err(SentinelError::DomainViolation {
+                rule: "Routing provider needs at least one provider".to_string(),
+            });
+        }
+        let total_weight = providers.iter().map(|(_, weight)| u64::from(*weight)).sum();
+        if total_weight == 0 {
+            return Err(SentinelError::DomainViolation {
+                rule: "Routing provider needs at least one provider with a positive weight"
+                    .to_string(),
+            });
+        }
+        Ok(Self {
+            providers,
+            total_weight,
+            next: AtomicU64::new(0),
+        })
+    }
+
+    /// Get the number of providers routed over
+    pub fn provider_count(&self) -> usize {
+        self.providers.len()
+    }
+
+    /// Pick the index of the provider to try first for the next call
+    fn select(&self) -> usize {
+        let mut slot = self.next.fetch_add(1, Ordering::Relaxed) % self.total_weight;
+        for (index, (_, weight)) in self.providers.iter().enumerate() {
+            let weight = u64::from(*weight);
+            if slot < weight {
+                return index;
+            }
+            slot -= weight;
+        }
+        // The slot is always below the total weight, so this is never reached
+        self.providers.len() - 1
+    }
+
+    /// Error returned when no provider produced a result of its own
+    fn no_provider_error() -> SentinelError {
+        SentinelError::ProviderError {
+            provider: "routing".to_string(),
+            detail: "no provider available".to_string(),
+        }
+    }
+
+    /// Run `call` on the selected provider, failing over to the next ones on
+    /// provider errors
+    ///
+    /// # Returns
+    /// The first success, the first non-provider error, or the last provider error
+    /// once every provider has failed
+    async fn route<T, F, Fut>(&self, call: F) -> Result<T, SentinelError>
+    where
+        F: Fn(Arc<dyn LLMProvider>) -> Fut,
+        Fut: Future<Output = Result<T, SentinelError>>,
+    {
+        let first = self.select();
+        let mut last_error = None;
+
+        for offset in 0..self.providers.len() {
+            let index = (first + offset) % self.providers.len();
+            match call(self.providers[index].0.clone()).await {
+                Ok(value) => return Ok(value),
+                Err(e @ SentinelError::ProviderError { .. }) => {
+                    warn!("Provider {} failed, failing over: {}", index, e);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(Self::no_provider_error))
+    }
+}
+
+#[async_trait]
+impl LLMProvider for RoutingProvider {
+    async fn complete(
+        &self,
+        messages: Vec<CanonicalMessage>,
+    ) -> Result<CanonicalMessage, SentinelError> {
+        self.route(|provider| {
+            let messages = messages.clone();
+            async move { provider.complete(messages).await }
+        })
+        .await
+    }
+
+    async fn complete_with_params(
+        &self,
+        messages: Vec<CanonicalMessage>,
+        params: CompletionParams,
+    ) -> Result<CanonicalMessage, SentinelError> {
+        self.route(|provider| {
+            let messages = messages.clone();
+            let params = params.clone();
+            async move { provider.complete_with_params(messages, params).await }
+        })
+        .await
+    }
+
+    /// Open a stream on the selected provider
+    ///
+    /// # Note
+    /// Failover only covers opening the stream; errors yielded by an open stream are
+    /// passed through to the caller.
+    async fn stream(
+        &self,
+        messages: Vec<CanonicalMessage>,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = Result<String, SentinelError>> + Send + Unpin>,
+        SentinelError,
+    > {
+        self.route(|provider| {
+            let messages = messages.clone();
+            async move { provider.stream(messages).await }
+        })
+        .await
+    }
+
+    /// Healthy while at least one provider is healthy
+    async fn health_check(&self) -> Result<(), SentinelError> {
+        let mut last_error = None;
+        for (provider, _) in &self.providers {
+            match provider.health_check().await {
+                Ok(()) => return Ok(()),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(Self::no_provider_error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Role;
+    use futures::StreamExt;
+    use std::sync::atomic::AtomicUsize;
+
+    /// Provider that counts its calls and either answers with its name or fails
+    struct CountingProvider {
+        name: &'static str,
+        calls: AtomicUsize,
+        error: Option<SentinelError>,
+    }
+
+    impl CountingProvider {
+        fn healthy(name: &'static str) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                calls: AtomicUsize::new(0),
+                error: None,
+            })
+        }
+
+        fn failing(name: &'static str, error: SentinelError) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                calls: AtomicUsize::new(0),
+                error: Some(error),
+            })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl LLMProvider for CountingProvider {
+        async fn complete(
+            &self,
+            _messages: Vec<CanonicalMessage>,
+        ) -> Result<CanonicalMessage, SentinelError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match &self.error {
+                Some(error) => Err(error.clone()),
+                None => Ok(CanonicalMessage::new(
+                    Role::Assistant,
+                    self.name.to_string(),
+                )),
+            }
+        }
+
+        async fn stream(
+            &self,
+            _messages: Vec<CanonicalMessage>,
+        ) -> Result<
+            Box<dyn futures::Stream<Item = Result<String, SentinelError>> + Send + Unpin>,
+            SentinelError,
+        > {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match &self.error {
+                Some(error) => Err(error.clone()),
+                None => Ok(Box::new(futures::stream::iter(vec![Ok(self
+                    .name
+                    .to_string())]))),
+            }
+        }
+    }
+
+    fn upstream_error(provider: &str) -> SentinelError {
+        SentinelError::ProviderError {
+            provider: provider.to_string(),
+            detail: "unavailable".to_string(),
+        }
+    }
+
+    fn prompt() -> Vec<CanonicalMessage> {
+        vec![CanonicalMessage::new(Role::User, "hi".to_string())]
+    }
+
+    #[tokio::test]
+    async fn test_calls_distributed_by_weight() {
+        let primary = CountingProvider::healthy("primary");
+        let secondary = CountingProvider::healthy("secondary");
+        let router = RoutingProvider::new(vec![
+            (primary.clone() as Arc<dyn LLMProvider>, 3),
+            (secondary.clone() as Arc<dyn LLMProvider>, 1),
+        ])
+        .unwrap();
+
+        for _ in 0..400 {
+            router.complete(prompt()).await.unwrap();
+        }
+
+        assert_eq!(primary.calls(), 300);
+        assert_eq!(secondary.calls(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_on_provider_error() {
+        let primary = CountingProvider::failing("primary", upstream_error("primary"));
+        let secondary = CountingProvider::healthy("secondary");
+        let router = RoutingProvider::new(vec![
+            (primary.clone() as Arc<dyn LLMProvider>, 1),
+            (secondary.clone() as Arc<dyn LLMProvider>, 1),
+        ])
+        .unwrap();
+
+        for _ in 0..10 {
+            let response = router.complete(prompt()).await.unwrap();
+            assert_eq!(response.content, "secondary");
+        }
+        // Half the calls pick the primary first and fail over; the rest go straight
+        // to the secondary
+        assert_eq!(primary.calls(), 5);
+        assert_eq!(secondary.calls(), 10);
+
+        let mut stream = router.stream(prompt()).await.unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), "secondary");
+    }
+
+    #[tokio::test]
+    async fn test_other_errors_are_not_failed_over() {
+        let primary = CountingProvider::failing(
+            "primary",
+            SentinelError::InvalidMessage {
+                reason: "empty".to_string(),
+            },
+        );
+        let backup = CountingProvider::healthy("backup");
+        let router = RoutingProvider::new(vec![
+            (primary.clone() as Arc<dyn LLMProvider>, 1),
+            (backup.clone() as Arc<dyn LLMProvider>, 0),
+        ])
+        .unwrap();
+
+        let error = router.complete(prompt()).await.unwrap_err();
+        assert!(matches!(error, SentinelError::InvalidMessage { .. }));
+        assert_eq!(backup.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_all_providers_failing_returns_last_error() {
+        let router = RoutingProvider::new(vec![
+            (
+                CountingProvider::failing("a", upstream_error("a")) as Arc<dyn LLMProvider>,
+                1,
+            ),
+            (
+                CountingProvider::failing("b", upstream_error("b")) as Arc<dyn LLMProvider>,
+                0,
+            ),
+        ])
+        .unwrap();
+
+        let error = router.complete(prompt()).await.unwrap_err();
+        assert_eq!(error, upstream_error("b"));
+    }
+
+    #[test]
+    fn test_requires_positive_total_weight() {
+        assert!(matches!(
+            RoutingProvider::new(Vec::new()),
+            Err(SentinelError::DomainViolation { rule }) if rule == "Routing provider needs at least one provider"
+        ));
+        assert!(RoutingProvider::new(vec![(
+            CountingProvider::healthy("idle") as Arc<dyn LLMProvider>,
+            0
+        )])
+        .is_err());
+    }
+}
@@ -0,0 +1,232 @@
+// Idempotency key cache for chat completions
+// Replays the stored response when a client retries a request with the same key
+
+use crate::core::auth::ApiKeyId;
+use crate::core::types::ChatCompletionResponse;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Header carrying the client-chosen idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Maximum accepted length of an idempotency key
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Default number of cached responses
+pub const DEFAULT_IDEMPOTENCY_CAPACITY: usize = 1024;
+
+/// Default time a cached response can be replayed
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Cache key: idempotency keys are scoped to the API key that sent them
+type CacheKey = (ApiKeyId, String);
+
+/// A cached response and its bookkeeping
+#[derive(Debug)]
+struct CachedResponse {
+    response: ChatCompletionResponse,
+    stored_at: Instant,
+    /// Logical clock value of the last access, for LRU eviction
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<CacheKey, CachedResponse>,
+    clock: u64,
+}
+
+/// Bounded LRU cache of chat completion responses keyed by idempotency key
+#[derive(Debug)]
+pub struct IdempotencyCache {
+    /// Maximum number of cached responses
+    capacity: usize,
+    /// How long a cached response can be replayed
+    ttl: Duration,
+    state: Mutex<CacheState>,
+}
+
+impl IdempotencyCache {
+    /// Create a cache holding at most `capacity` responses for `ttl` each
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Get a cached response that has not expired
+    ///
+    /// # Arguments
+    /// * `key_id` - API key that sent the request
+    /// * `idempotency_key` - Client-chosen idempotency key
+    ///
+    /// # Returns
+    /// The response stored by the first request with this key, if still cached
+    pub fn get(&self, key_id: &ApiKeyId, idempotency_key: &str) -> Option<ChatCompletionResponse> {
+        let mut state = self.state();
+        state.clock += 1;
+        let clock = state.clock;
+        let key = (key_id.clone(), idempotency_key.to_string());
+
+        match state.entries.get_mut(&key) {
+            Some(entry) if entry.stored_at.elapsed() < self.ttl => {
+                entry.last_used = clock;
+                Some(entry.response.clone())
+            }
+            Some(_) => {
+                debug!("Idempotency key {} for {} expired", idempotency_key, key_id);
+                state.entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Store the response for an idempotency key, evicting the least recently used
+    /// entry when the cache is full
+    pub fn insert(
+        &self,
+        key_id: ApiKeyId,
+        idempotency_key: String,
+        response: ChatCompletionResponse,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut state = self.state();
+        state.clock += 1;
+        let clock = state.clock;
+        let key = (key_id, idempotency_key);
+
+        if !state.entries.contains_key(&key) && state.entries.len() >= self.capacity {
+            let ttl = self.ttl;
+            state
+                .entries
+                .retain(|_, entry| entry.stored_at.elapsed() < ttl);
+            if state.entries.len() >= self.capacity {
+                let oldest = state
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    state.entries.remove(&oldest);
+                }
+            }
+        }
+
+        state.entries.insert(
+            key,
+            CachedResponse {
+                response,
+                stored_at: Instant::now(),
+                last_used: clock,
+            },
+        );
+    }
+
+    /// Get the number of cached responses (including expired ones not yet evicted)
+    pub fn len(&self) -> usize {
+        self.state().entries.len()
+    }
+
+    /// Check whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Lock the cache, recovering it if a holder panicked
+    ///
+    /// Every update leaves the entries consistent, so a poisoned cache is still valid.
+    fn state(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for IdempotencyCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_IDEMPOTENCY_CAPACITY, DEFAULT_IDEMPOTENCY_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{CanonicalMessage, Role};
+
+    fn response(content: &str) -> ChatCompletionResponse {
+        ChatCompletionResponse {
+            message: CanonicalMessage::new(Role::Assistant, content.to_string()),
+            model: "test".to_string(),
+            usage: None,
+            session_id: None,
+        }
+    }
+
+    fn key_id(id: &str) -> ApiKeyId {
+        ApiKeyId::new(id.to_string())
+    }
+
+    #[test]
+    fn test_keys_are_scoped_per_api_key() {
+        let cache = IdempotencyCache::default();
+        cache.insert(key_id("tenant-a"), "retry-1".to_string(), response("a"));
+
+        assert_eq!(
+            cache
+                .get(&key_id("tenant-a"), "retry-1")
+                .unwrap()
+                .message
+                .content,
+            "a"
+        );
+        assert!(cache.get(&key_id("tenant-b"), "retry-1").is_none());
+    }
+
+    #[test]
+    fn test_expired_entries_are_not_replayed() {
+        let cache = IdempotencyCache::new(10, Duration::ZERO);
+        cache.insert(key_id("tenant-a"), "retry-1".to_string(), response("a"));
+
+        assert!(cache.get(&key_id("tenant-a"), "retry-1").is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_least_recently_used_entry_is_evicted() {
+        let cache = IdempotencyCache::new(2, DEFAULT_IDEMPOTENCY_TTL);
+        cache.insert(key_id("k"), "first".to_string(), response("1"));
+        cache.insert(key_id("k"), "second".to_string(), response("2"));
+        // Touch "first" so "second" becomes the least recently used
+        assert!(cache.get(&key_id("k"), "first").is_some());
+
+        cache.insert(key_id("k"), "third".to_string(), response("3"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key_id("k"), "first").is_some());
+        assert!(cache.get(&key_id("k"), "second").is_none());
+        assert!(cache.get(&key_id("k"), "third").is_some());
+    }
+
+    #[test]
+    fn test_poisoned_cache_keeps_serving() {
+        let cache = IdempotencyCache::default();
+        cache.insert(key_id("k"), "first".to_string(), response("1"));
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _state = cache.state.lock().unwrap();
+            panic!("poison the cache");
+        }));
+        assert!(cache.state.is_poisoned());
+
+        assert_eq!(
+            cache.get(&key_id("k"), "first").unwrap().message.content,
+            "1"
+        );
+        cache.insert(key_id("k"), "second".to_string(), response("2"));
+        assert_eq!(cache.len(), 2);
+    }
+}
@@ -1,40 +1,398 @@
 // Tower middleware for authentication, authorization, timeout, CORS, and tracing
 
 use axum::{
-    extract::Request,
-    http::{header::AUTHORIZATION, StatusCode},
+    body::{Body, HttpBody},
+    extract::{MatchedPath, Request},
+    http::{header::AUTHORIZATION, HeaderValue, StatusCode},
     middleware::Next,
     response::Response,
 };
-use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tower::ServiceBuilder;
-use tower_http::{cors::CorsLayer, trace::TraceLayer};
+use tower_http::{
+    cors::{AllowOrigin, CorsLayer},
+    trace::TraceLayer,
+};
 use tracing::{error, info, warn};
 
-use crate::core::auth::{ApiKey, ApiKeyId, AuthLevel, AuthResult};
+use crate::core::auth::{verify_signature, ApiKey, ApiKeyId, AuthLevel, AuthResult};
+use crate::core::error::SentinelError;
+use crate::core::types::ApiKeyInfo;
+use crate::telemetry::audit::AuditLogConfig;
+use crate::telemetry::metrics::RequestMetrics;
+
+/// Stored record for an API key: (key_id, auth_level, optional expiry, optional
+/// request signing secret)
+type KeyRecord = (ApiKeyId, AuthLevel, Option<DateTime<Utc>>, Option<String>);
+
+/// Record layout written before request signing was supported
+type LegacyKeyRecord = (ApiKeyId, AuthLevel, Option<DateTime<Utc>>);
+
+/// Header carrying the hex-encoded HMAC-SHA256 request signature
+pub const SIGNATURE_HEADER: &str = "x-signature";
+
+/// Header carrying the Unix timestamp (seconds) covered by the request signature
+pub const SIGNATURE_TIMESTAMP_HEADER: &str = "x-signature-timestamp";
+
+/// Largest request body buffered to verify a signature
+const MAX_SIGNED_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// Prefix of generated API keys
+const GENERATED_KEY_PREFIX: &str = "sk-";
+
+/// Number of random bytes in a generated API key
+const GENERATED_KEY_BYTES: usize = 24;
+
+/// Hex-encoded SHA-256 digest of an API key
+/// The store is keyed by this digest so plaintext keys are never held or persisted
+fn hash_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Read every key record from an API key database
+///
+/// # Note
+/// Records that fail to deserialize are skipped with a warning.
+fn read_persisted_keys(db: &sled::Db) -> Result<HashMap<String, KeyRecord>, SentinelError> {
+    let mut keys = HashMap::new();
+    for result in db.iter() {
+        let (key, bytes) = result.map_err(|e| SentinelError::DomainViolation {
+            rule: format!("Failed to scan API key database: {}", e),
+        })?;
+        let key = String::from_utf8_lossy(&key).into_owned();
+        match decode_record(&bytes) {
+            Ok(record) => {
+                keys.insert(key, record);
+            }
+            Err(e) => warn!("Skipping unreadable API key record: {}", e),
+        }
+    }
+    Ok(keys)
+}
+
+/// Decode a persisted key record, accepting the layout without a signing secret
+fn decode_record(bytes: &[u8]) -> bincode::Result<KeyRecord> {
+    bincode::deserialize::<KeyRecord>(bytes).or_else(|e| {
+        bincode::deserialize::<LegacyKeyRecord>(bytes)
+            .map(|(key_id, auth_level, expires_at)| (key_id, auth_level, expires_at, None))
+            .map_err(|_| e)
+    })
+}
+
+/// Parse API keys from `SENTINEL_API_KEY_<ID>=<KEY>:<LEVEL>` environment variables
+///
+/// # Arguments
+/// * `vars` - Environment variables to scan, e.g. `std::env::vars()`
+///
+/// # Returns
+/// Each valid key with its record; malformed variables are skipped with a warning
+fn read_env_keys(vars: impl IntoIterator<Item = (String, String)>) -> Vec<(String, KeyRecord)> {
+    let mut entries = Vec::new();
+
+    for (key, value) in vars {
+        if let Some(key_id_str) = key.strip_prefix("SENTINEL_API_KEY_") {
+            let key_id = ApiKeyId::new(key_id_str.to_string());
+
+            // Parse value: <key>:<level>
+            let parts: Vec<&str> = value.split(':').collect();
+            if parts.len() != 2 {
+                warn!("Invalid API key format for {}: expected <key>:<level>", key);
+                continue;
+            }
+
+            let api_key = parts[0].to_string();
+            let level_str = parts[1].to_lowercase();
+
+            let auth_level = match level_str.as_str() {
+                "read" => AuthLevel::Read,
+                "write" => AuthLevel::Write,
+                "admin" => AuthLevel::Admin,
+                _ => {
+                    warn!("Invalid auth level for {}: {}", key, level_str);
+                    continue;
+                }
+            };
+
+            // Validate API key format
+            let key_obj = ApiKey::new(api_key.clone());
+            if key_obj.validate_format().is_err() {
+                warn!("Invalid API key format for {}", key);
+                continue;
+            }
+
+            entries.push((api_key, (key_id, auth_level, None, None)));
+        }
+    }
+
+    entries
+}
+
+/// Outcome of `ApiKeyStore::reload`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KeyReloadSummary {
+    /// Number of keys read from the environment and the backing database
+    pub loaded: usize,
+    /// Number of keys revoked because their source no longer lists them
+    pub pruned: usize,
+}
 
 /// API key store for authentication
-/// In production, this would be backed by a database or external service
+/// Purely in-memory by default; `with_persistence` adds a Sled write-through backing
+/// for keys added programmatically or through the admin API
 #[derive(Debug, Clone)]
 pub struct ApiKeyStore {
-    /// Map of API key to (key_id, auth_level)
-    keys: Arc<RwLock<HashMap<String, (ApiKeyId, AuthLevel)>>>,
+    /// Map of API key SHA-256 digest to (key_id, auth_level, expiry)
+    keys: Arc<RwLock<HashMap<String, KeyRecord>>>,
+    /// Digests of the keys most recently loaded from `SENTINEL_API_KEY_*` variables
+    env_digests: Arc<RwLock<HashSet<String>>>,
+    /// Optional Sled database that every added key is written through to
+    db: Option<sled::Db>,
 }
 
 impl ApiKeyStore {
-    /// Create a new API key store
+    /// Create a new in-memory API key store
     pub fn new() -> Self {
         Self {
             keys: Arc::new(RwLock::new(HashMap::new())),
+            env_digests: Arc::new(RwLock::new(HashSet::new())),
+            db: None,
         }
     }
 
-    /// Add an API key to the store
-    pub async fn add_key(&self, key: String, key_id: ApiKeyId, auth_level: AuthLevel) {
+    /// Create an API key store persisted to a Sled database
+    ///
+    /// # Arguments
+    /// * `path` - Path to the Sled database directory
+    ///
+    /// # Returns
+    /// * `Ok(ApiKeyStore)` - Store loaded with every key previously written at `path`
+    /// * `Err(SentinelError)` - Error if the database cannot be opened or read
+    ///
+    /// # Note
+    /// Records that fail to deserialize are skipped with a warning.
+    pub fn with_persistence<P: AsRef<Path>>(path: P) -> Result<Self, SentinelError> {
+        let path = path.as_ref();
+        let db = sled::open(path).map_err(|e| SentinelError::DomainViolation {
+            rule: format!("Failed to open API key database at {:?}: {}", path, e),
+        })?;
+
+        let keys = read_persisted_keys(&db)?;
+
+        info!("Loaded {} API keys from {:?}", keys.len(), path);
+        Ok(Self {
+            keys: Arc::new(RwLock::new(keys)),
+            env_digests: Arc::new(RwLock::new(HashSet::new())),
+            db: Some(db),
+        })
+    }
+
+    /// Write a key record through to the backing database, if any
+    ///
+    /// # Returns
+    /// * `Ok(())` - Written and flushed, or the store has no backing database
+    /// * `Err(SentinelError)` - Error if the record cannot be written
+    ///
+    /// # Note
+    /// Sled I/O runs on the blocking thread pool so the flush never stalls the runtime.
+    async fn persist(&self, digest: &str, record: &KeyRecord) -> Result<(), SentinelError> {
+        let Some(db) = self.db.clone() else {
+            return Ok(());
+        };
+        let key_id = record.0.clone();
+        let bytes = bincode::serialize(record).map_err(|e| SentinelError::DomainViolation {
+            rule: format!("Failed to serialize API key {}: {}", key_id, e),
+        })?;
+        let digest = digest.to_string();
+        tokio::task::spawn_blocking(move || {
+            db.insert(digest.as_bytes(), bytes)?;
+            db.flush()
+        })
+        .await
+        .map_err(|e| SentinelError::DomainViolation {
+            rule: format!("API key persistence task failed for {}: {}", key_id, e),
+        })?
+        .map_err(|e| SentinelError::DomainViolation {
+            rule: format!("Failed to persist API key {}: {}", key_id, e),
+        })?;
+        Ok(())
+    }
+
+    /// Delete a key record from the backing database, if any
+    ///
+    /// # Note
+    /// Persistence failures are logged; the key is still revoked in memory, but would
+    /// be loaded again on restart.
+    async fn unpersist(&self, digest: &str, key_id: &ApiKeyId) {
+        let Some(db) = self.db.clone() else {
+            return;
+        };
+        let digest = digest.to_string();
+        let result = tokio::task::spawn_blocking(move || {
+            db.remove(digest.as_bytes())?;
+            db.flush()
+        })
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|result| result.map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            error!("Failed to delete persisted API key {}: {}", key_id, e);
+        }
+    }
+
+    /// Insert a key record under the key's digest, writing it through before it
+    /// becomes usable in memory
+    async fn insert(&self, key: String, record: KeyRecord) -> Result<(), SentinelError> {
+        let digest = hash_key(&key);
+        self.persist(&digest, &record).await?;
         let mut keys = self.keys.write().await;
-        keys.insert(key, (key_id, auth_level));
+        keys.insert(digest, record);
+        Ok(())
+    }
+
+    /// Add an API key to the store
+    ///
+    /// # Returns
+    /// * `Ok(())` - Key added (and persisted, if persistence is enabled)
+    /// * `Err(SentinelError)` - Error if the key cannot be persisted; it is not added
+    pub async fn add_key(
+        &self,
+        key: String,
+        key_id: ApiKeyId,
+        auth_level: AuthLevel,
+    ) -> Result<(), SentinelError> {
+        self.insert(key, (key_id, auth_level, None, None)).await
+    }
+
+    /// Add an API key that stops validating after `expires_at`
+    pub async fn add_key_with_expiry(
+        &self,
+        key: String,
+        key_id: ApiKeyId,
+        auth_level: AuthLevel,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), SentinelError> {
+        self.insert(key, (key_id, auth_level, Some(expires_at), None))
+            .await
+    }
+
+    /// Add an API key whose requests must also carry an HMAC signature
+    ///
+    /// # Arguments
+    /// * `signing_secret` - Secret the client signs `<timestamp>.<METHOD>.<path>.<body>`
+    ///   with
+    ///
+    /// # Note
+    /// Unlike the key itself, the signing secret is needed to verify signatures and is
+    /// therefore held (and persisted) as given.
+    pub async fn add_key_with_signing_secret(
+        &self,
+        key: String,
+        key_id: ApiKeyId,
+        auth_level: AuthLevel,
+        signing_secret: String,
+    ) -> Result<(), SentinelError> {
+        self.insert(key, (key_id, auth_level, None, Some(signing_secret)))
+            .await
+    }
+
+    /// Create an API key with a freshly generated secret
+    ///
+    /// # Arguments
+    /// * `key_id` - Identifier for the key; several keys may share one (e.g. during rotation)
+    /// * `auth_level` - Authorization level to grant
+    /// * `expires_at` - When the key stops validating, if it expires
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The generated secret (`sk-` followed by 48 hex characters). Only
+    ///   its digest is stored, so it cannot be retrieved again.
+    /// * `Err(SentinelError)` - Error if the key cannot be persisted
+    pub async fn create_key(
+        &self,
+        key_id: ApiKeyId,
+        auth_level: AuthLevel,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<String, SentinelError> {
+        let mut bytes = [0u8; GENERATED_KEY_BYTES];
+        rand::thread_rng().fill(&mut bytes[..]);
+        let key: String = std::iter::once(GENERATED_KEY_PREFIX.to_string())
+            .chain(bytes.iter().map(|byte| format!("{:02x}", byte)))
+            .collect();
+
+        let log_id = key_id.clone();
+        self.insert(key.clone(), (key_id, auth_level, expires_at, None))
+            .await?;
+        info!("Created API key {} with {:?} access", log_id, auth_level);
+        Ok(key)
+    }
+
+    /// List metadata of all stored keys, ordered by key ID
+    pub async fn list_keys(&self) -> Vec<ApiKeyInfo> {
+        let keys = self.keys.read().await;
+        let mut infos: Vec<ApiKeyInfo> = keys
+            .values()
+            .map(|(key_id, auth_level, expires_at, _)| ApiKeyInfo {
+                key_id: key_id.clone(),
+                auth_level: *auth_level,
+                expires_at: *expires_at,
+            })
+            .collect();
+        infos.sort_by(|a, b| a.key_id.0.cmp(&b.key_id.0));
+        infos
+    }
+
+    /// Revoke an API key
+    ///
+    /// # Returns
+    /// `true` if the key was present; it no longer validates
+    pub async fn remove_key(&self, key: &str) -> bool {
+        let digest = hash_key(key);
+        // Release the lock before touching disk so validations aren't blocked on I/O
+        let removed = self.keys.write().await.remove(&digest);
+        match removed {
+            Some((key_id, _, _, _)) => {
+                self.unpersist(&digest, &key_id).await;
+                info!("Revoked API key {}", key_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Revoke every API key registered under an ID
+    ///
+    /// # Returns
+    /// The number of keys revoked
+    pub async fn remove_key_by_id(&self, key_id: &ApiKeyId) -> usize {
+        let digests: Vec<String> = {
+            let mut keys = self.keys.write().await;
+            let digests: Vec<String> = keys
+                .iter()
+                .filter(|(_, (id, _, _, _))| id == key_id)
+                .map(|(digest, _)| digest.clone())
+                .collect();
+            for digest in &digests {
+                keys.remove(digest);
+            }
+            digests
+        };
+        for digest in &digests {
+            self.unpersist(digest, key_id).await;
+        }
+        if !digests.is_empty() {
+            info!("Revoked {} API key(s) with id {}", digests.len(), key_id);
+        }
+        digests.len()
     }
 
     /// Validate an API key and return authentication result
@@ -47,8 +405,13 @@ impl ApiKeyStore {
 
         // Check if key exists in store
         let keys = self.keys.read().await;
-        match keys.get(key) {
-            Some((key_id, _)) => AuthResult::Authenticated {
+        match keys.get(&hash_key(key)) {
+            Some((_, _, Some(expires_at), _)) if *expires_at <= Utc::now() => {
+                AuthResult::Unauthenticated {
+                    reason: "API key expired".to_string(),
+                }
+            }
+            Some((key_id, _, _, _)) => AuthResult::Authenticated {
                 key_id: key_id.clone(),
             },
             None => AuthResult::Unauthenticated {
@@ -60,55 +423,136 @@ impl ApiKeyStore {
     /// Get the authorization level for an API key
     pub async fn get_auth_level(&self, key: &str) -> Option<AuthLevel> {
         let keys = self.keys.read().await;
-        keys.get(key).map(|(_, level)| *level)
+        keys.get(&hash_key(key)).map(|(_, level, _, _)| *level)
+    }
+
+    /// Get the request signing secret of an API key, if it requires signed requests
+    pub async fn signing_secret(&self, key: &str) -> Option<String> {
+        let keys = self.keys.read().await;
+        keys.get(&hash_key(key))
+            .and_then(|(_, _, _, secret)| secret.clone())
     }
 
     /// Load API keys from environment variables
     /// Expects format: SENTINEL_API_KEY_<ID>=<KEY>:<LEVEL>
     /// Example: SENTINEL_API_KEY_VENDOR1=sk-1234567890123456:write
+    ///
+    /// # Note
+    /// Environment keys are held in memory only and never written to the backing
+    /// database, so removing a variable revokes its key on the next restart.
     pub async fn load_from_env(&self) -> Result<usize, String> {
-        let mut count = 0;
+        self.load_from_vars(std::env::vars().collect::<Vec<_>>())
+            .await
+    }
+
+    /// Load API keys from the given environment variables (see `load_from_env`)
+    async fn load_from_vars(
+        &self,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<usize, String> {
+        let entries = read_env_keys(vars);
+        let count = entries.len();
+
         let mut keys = self.keys.write().await;
+        let mut env_digests = self.env_digests.write().await;
+        for (api_key, record) in entries {
+            info!("Loaded API key: {}", record.0);
+            let digest = hash_key(&api_key);
+            env_digests.insert(digest.clone());
+            keys.insert(digest, record);
+        }
+
+        Ok(count)
+    }
 
-        for (key, value) in std::env::vars() {
-            if key.starts_with("SENTINEL_API_KEY_") {
-                let key_id_str = key.strip_prefix("SENTINEL_API_KEY_").unwrap();
-                let key_id = ApiKeyId::new(key_id_str.to_string());
+    /// Re-read API keys from the environment and, if persistence is enabled, the
+    /// backing database, merging them into the live store
+    ///
+    /// # Arguments
+    /// * `prune` - Also revoke keys previously loaded from the environment that are no
+    ///   longer set and, with persistence, keys no longer present in the database
+    ///
+    /// # Returns
+    /// * `Ok(KeyReloadSummary)` - How many keys were loaded and pruned
+    /// * `Err(SentinelError)` - Error if the backing database cannot be read
+    ///
+    /// # Note
+    /// The new key set is applied under a single write lock, so requests validating
+    /// concurrently see either the old or the new keys, never a partial set. Keys
+    /// added through the admin API on a store without persistence are never pruned.
+    /// Environment keys are never written to the database, so a key removed from the
+    /// environment is also gone after a restart.
+    pub async fn reload(&self, prune: bool) -> Result<KeyReloadSummary, SentinelError> {
+        self.reload_from_vars(std::env::vars().collect::<Vec<_>>(), prune)
+            .await
+    }
 
-                // Parse value: <key>:<level>
-                let parts: Vec<&str> = value.split(':').collect();
-                if parts.len() != 2 {
-                    warn!("Invalid API key format for {}: expected <key>:<level>", key);
-                    continue;
-                }
+    /// Reload API keys from the given environment variables (see `reload`)
+    async fn reload_from_vars(
+        &self,
+        vars: impl IntoIterator<Item = (String, String)>,
+        prune: bool,
+    ) -> Result<KeyReloadSummary, SentinelError> {
+        let persisted = match &self.db {
+            Some(db) => Some(read_persisted_keys(db)?),
+            None => None,
+        };
+        let from_env: HashMap<String, KeyRecord> = read_env_keys(vars)
+            .into_iter()
+            .map(|(api_key, record)| (hash_key(&api_key), record))
+            .collect();
 
-                let api_key = parts[0].to_string();
-                let level_str = parts[1].to_lowercase();
-
-                let auth_level = match level_str.as_str() {
-                    "read" => AuthLevel::Read,
-                    "write" => AuthLevel::Write,
-                    "admin" => AuthLevel::Admin,
-                    _ => {
-                        warn!("Invalid auth level for {}: {}", key, level_str);
-                        continue;
-                    }
-                };
-
-                // Validate API key format
-                let key_obj = ApiKey::new(api_key.clone());
-                if key_obj.validate_format().is_err() {
-                    warn!("Invalid API key format for {}", key);
-                    continue;
+        let mut keys = self.keys.write().await;
+        let mut env_digests = self.env_digests.write().await;
+
+        let mut pruned = Vec::new();
+        if prune {
+            let stale: Vec<String> = keys
+                .keys()
+                .filter(|digest| !from_env.contains_key(*digest))
+                .filter(|digest| {
+                    env_digests.contains(*digest)
+                        || persisted
+                            .as_ref()
+                            .is_some_and(|persisted| !persisted.contains_key(*digest))
+                })
+                .cloned()
+                .collect();
+            for digest in stale {
+                if let Some((key_id, _, _, _)) = keys.remove(&digest) {
+                    info!("Pruned API key {} on reload", key_id);
+                    pruned.push((digest, key_id));
                 }
+            }
+        }
 
-                keys.insert(api_key, (key_id, auth_level));
-                count += 1;
-                info!("Loaded API key: {}", key_id_str);
+        let mut loaded = 0;
+        for (digest, record) in persisted.into_iter().flatten() {
+            if !(prune && env_digests.contains(&digest) && !from_env.contains_key(&digest)) {
+                keys.insert(digest, record);
+                loaded += 1;
             }
         }
+        for (digest, record) in &from_env {
+            keys.insert(digest.clone(), record.clone());
+            loaded += 1;
+        }
+        if prune {
+            *env_digests = from_env.into_keys().collect();
+        } else {
+            env_digests.extend(from_env.into_keys());
+        }
+        drop(env_digests);
+        drop(keys);
 
-        Ok(count)
+        // Pruned keys no longer validate; delete their records without holding the locks
+        for (digest, key_id) in &pruned {
+            self.unpersist(digest, key_id).await;
+        }
+
+        let pruned = pruned.len();
+        info!("Reloaded API keys: {} loaded, {} pruned", loaded, pruned);
+        Ok(KeyReloadSummary { loaded, pruned })
     }
 }
 
@@ -118,6 +562,36 @@ impl Default for ApiKeyStore {
     }
 }
 
+/// Reload API keys whenever the process receives `SIGHUP`
+///
+/// # Arguments
+/// * `key_store` - Store to reload; shared with the authentication middleware
+/// * `prune` - Whether each reload also revokes keys that were removed at the source
+///
+/// # Returns
+/// * `Ok(JoinHandle)` - Handle of the background task listening for the signal
+/// * `Err(io::Error)` - Error if the signal handler cannot be installed
+///
+/// # Note
+/// Reload failures are logged and the previous keys stay in effect.
+#[cfg(unix)]
+pub fn spawn_key_reload_on_sighup(
+    key_store: Arc<ApiKeyStore>,
+    prune: bool,
+) -> std::io::Result<tokio::task::JoinHandle<()>> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangups = signal(SignalKind::hangup())?;
+    Ok(tokio::spawn(async move {
+        while hangups.recv().await.is_some() {
+            info!("Received SIGHUP, reloading API keys");
+            if let Err(e) = key_store.reload(prune).await {
+                error!("Failed to reload API keys: {}", e);
+            }
+        }
+    }))
+}
+
 /// Request extension containing authentication information
 #[derive(Debug, Clone)]
 pub struct AuthInfo {
@@ -127,6 +601,70 @@ pub struct AuthInfo {
     pub auth_level: AuthLevel,
 }
 
+/// Header carrying the request ID on requests and responses
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Maximum accepted length of a client-supplied request ID
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Request extension containing the request ID assigned by `request_id_middleware`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl std::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Request ID middleware
+/// Preserves a client-supplied `X-Request-Id` (or generates one), stores it in the
+/// request extensions, and echoes it on every response, including error responses.
+pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN)
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    request
+        .extensions_mut()
+        .insert(RequestId(request_id.clone()));
+
+    let mut response = next.run(request).await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Get the request ID assigned to a request, if any
+fn request_id_of(request: &Request) -> Option<RequestId> {
+    request.extensions().get::<RequestId>().cloned()
+}
+
+/// Build a nested JSON error rejection carrying the request ID
+fn error_rejection(
+    status: StatusCode,
+    code: &str,
+    message: String,
+    error_type: &str,
+    request_id: Option<&RequestId>,
+) -> AuthRejection {
+    let mut error = serde_json::json!({
+        "code": code,
+        "message": message,
+        "type": error_type
+    });
+    if let Some(request_id) = request_id {
+        error["request_id"] = serde_json::Value::String(request_id.0.clone());
+    }
+    (status, axum::Json(serde_json::json!({ "error": error })))
+}
+
 /// Extract API key from Authorization header
 /// Supports both "Bearer <key>" and "ApiKey <key>" formats
 fn extract_api_key(request: &Request) -> Option<String> {
@@ -151,6 +689,78 @@ fn extract_api_key(request: &Request) -> Option<String> {
     None
 }
 
+/// Verify the HMAC signature of a request made with a signing-enabled key
+///
+/// # Arguments
+/// * `request` - Incoming request; its body is buffered to compute the signature
+/// * `secret` - Signing secret of the authenticating key
+/// * `request_id` - Request ID echoed in rejections
+///
+/// # Returns
+/// * `Ok(Request)` - The request, rebuilt around the buffered body
+/// * `Err(AuthRejection)` - `401` with `code: "invalid_signature"` if the signature
+///   headers are missing or malformed, the timestamp is stale, or the signature does
+///   not match
+async fn verify_signed_request(
+    request: Request,
+    secret: &str,
+    request_id: Option<&RequestId>,
+) -> Result<Request, AuthRejection> {
+    let reject = |reason: String| {
+        error!(
+            "Signature verification failed: {} (request_id: {:?})",
+            reason, request_id
+        );
+        error_rejection(
+            StatusCode::UNAUTHORIZED,
+            "invalid_signature",
+            format!("Signature verification failed: {}", reason),
+            "authentication_error",
+            request_id,
+        )
+    };
+
+    let (parts, body) = request.into_parts();
+    let header = |name: &str| {
+        parts
+            .headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let signature = header(SIGNATURE_HEADER)
+        .ok_or_else(|| reject(format!("{} header is required", SIGNATURE_HEADER)))?;
+    let timestamp = header(SIGNATURE_TIMESTAMP_HEADER)
+        .ok_or_else(|| reject(format!("{} header is required", SIGNATURE_TIMESTAMP_HEADER)))?
+        .parse::<i64>()
+        .map_err(|_| {
+            reject(format!(
+                "{} must be a Unix timestamp",
+                SIGNATURE_TIMESTAMP_HEADER
+            ))
+        })?;
+
+    let bytes = axum::body::to_bytes(body, MAX_SIGNED_BODY_BYTES)
+        .await
+        .map_err(|e| reject(format!("Failed to read request body: {}", e)))?;
+    let path = parts
+        .uri
+        .path_and_query()
+        .map_or_else(|| parts.uri.path(), |path| path.as_str());
+    verify_signature(
+        secret,
+        timestamp,
+        parts.method.as_str(),
+        path,
+        &bytes,
+        &signature,
+        Utc::now(),
+    )
+    .map_err(reject)?;
+
+    Ok(Request::from_parts(parts, axum::body::Body::from(bytes)))
+}
+
 /// Authentication middleware
 /// Validates API keys from Authorization header
 pub async fn auth_middleware(
@@ -158,20 +768,22 @@ pub async fn auth_middleware(
     next: Next,
     key_store: Arc<ApiKeyStore>,
 ) -> Result<Response, (StatusCode, axum::Json<serde_json::Value>)> {
+    let request_id = request_id_of(&request);
+
     // Extract API key from header
     let api_key = match extract_api_key(&request) {
         Some(key) => key,
         None => {
-            error!("Missing Authorization header");
-            return Err((
+            error!(
+                "Missing Authorization header (request_id: {:?})",
+                request_id
+            );
+            return Err(error_rejection(
                 StatusCode::UNAUTHORIZED,
-                axum::Json(serde_json::json!({
-                    "error": {
-                        "code": "missing_authorization",
-                        "message": "Authorization header is required",
-                        "type": "authentication_error"
-                    }
-                })),
+                "missing_authorization",
+                "Authorization header is required".to_string(),
+                "authentication_error",
+                request_id.as_ref(),
             ));
         }
     };
@@ -186,6 +798,10 @@ pub async fn auth_middleware(
                 .await
                 .unwrap_or(AuthLevel::Read);
 
+            if let Some(secret) = key_store.signing_secret(&api_key).await {
+                request = verify_signed_request(request, &secret, request_id.as_ref()).await?;
+            }
+
             let key_id_for_log = key_id.clone();
 
             // Add auth info to request extensions
@@ -193,39 +809,40 @@ pub async fn auth_middleware(
                 .extensions_mut()
                 .insert(AuthInfo { key_id, auth_level });
 
-            info!("Authenticated request with key_id: {}", key_id_for_log);
+            info!(
+                "Authenticated request with key_id: {} (request_id: {:?})",
+                key_id_for_log, request_id
+            );
             Ok(next.run(request).await)
         }
         AuthResult::Unauthenticated { reason } => {
-            error!("Authentication failed: {}", reason);
-            Err((
+            error!(
+                "Authentication failed: {} (request_id: {:?})",
+                reason, request_id
+            );
+            Err(error_rejection(
                 StatusCode::UNAUTHORIZED,
-                axum::Json(serde_json::json!({
-                    "error": {
-                        "code": "invalid_api_key",
-                        "message": format!("Authentication failed: {}", reason),
-                        "type": "authentication_error"
-                    }
-                })),
+                "invalid_api_key",
+                format!("Authentication failed: {}", reason),
+                "authentication_error",
+                request_id.as_ref(),
             ))
         }
     }
 }
 
+/// Rejection returned by the authentication middleware
+pub type AuthRejection = (StatusCode, axum::Json<serde_json::Value>);
+
+/// Boxed future returned by middleware closures
+pub type MiddlewareFuture =
+    std::pin::Pin<Box<dyn std::future::Future<Output = Result<Response, AuthRejection>> + Send>>;
+
 /// Create authentication middleware with required authorization level
 pub fn create_auth_middleware(
     key_store: Arc<ApiKeyStore>,
     required_level: AuthLevel,
-) -> impl Fn(
-    Request,
-    Next,
-) -> std::pin::Pin<
-    Box<
-        dyn std::future::Future<
-                Output = Result<Response, (StatusCode, axum::Json<serde_json::Value>)>,
-            > + Send,
-    >,
-> + Clone {
+) -> impl Fn(Request, Next) -> MiddlewareFuture + Clone {
     move |request: Request, next: Next| {
         let store = key_store.clone();
         let level = required_level;
@@ -241,20 +858,22 @@ async fn auth_with_level_middleware(
     key_store: Arc<ApiKeyStore>,
     required_level: AuthLevel,
 ) -> Result<Response, (StatusCode, axum::Json<serde_json::Value>)> {
+    let request_id = request_id_of(&request);
+
     // First authenticate
     let api_key = match extract_api_key(&request) {
         Some(key) => key,
         None => {
-            error!("Missing Authorization header");
-            return Err((
+            error!(
+                "Missing Authorization header (request_id: {:?})",
+                request_id
+            );
+            return Err(error_rejection(
                 StatusCode::UNAUTHORIZED,
-                axum::Json(serde_json::json!({
-                    "error": {
-                        "code": "missing_authorization",
-                        "message": "Authorization header is required",
-                        "type": "authentication_error"
-                    }
-                })),
+                "missing_authorization",
+                "Authorization header is required".to_string(),
+                "authentication_error",
+                request_id.as_ref(),
             ));
         }
     };
@@ -270,41 +889,40 @@ async fn auth_with_level_middleware(
             (key_id, level)
         }
         AuthResult::Unauthenticated { reason } => {
-            error!("Authentication failed: {}", reason);
-            return Err((
+            error!(
+                "Authentication failed: {} (request_id: {:?})",
+                reason, request_id
+            );
+            return Err(error_rejection(
                 StatusCode::UNAUTHORIZED,
-                axum::Json(serde_json::json!({
-                    "error": {
-                        "code": "invalid_api_key",
-                        "message": format!("Authentication failed: {}", reason),
-                        "type": "authentication_error"
-                    }
-                })),
+                "invalid_api_key",
+                format!("Authentication failed: {}", reason),
+                "authentication_error",
+                request_id.as_ref(),
             ));
         }
     };
 
-    // Check authorization
-    let has_permission = match required_level {
-        AuthLevel::Read => auth_level.can_read(),
-        AuthLevel::Write => auth_level.can_write(),
-        AuthLevel::Admin => auth_level.is_admin(),
-    };
+    // Keys with a signing secret must also sign the request
+    if let Some(secret) = key_store.signing_secret(&api_key).await {
+        request = verify_signed_request(request, &secret, request_id.as_ref()).await?;
+    }
 
-    if !has_permission {
+    // Check authorization
+    if !auth_level.satisfies(required_level) {
         error!(
-            "Authorization failed: required {:?}, have {:?}",
-            required_level, auth_level
+            "Authorization failed: required {:?}, have {:?} (request_id: {:?})",
+            required_level, auth_level, request_id
         );
-        return Err((
+        return Err(error_rejection(
             StatusCode::FORBIDDEN,
-            axum::Json(serde_json::json!({
-                "error": {
-                    "code": "insufficient_permissions",
-                    "message": format!("Required {:?} access, but have {:?}", required_level, auth_level),
-                    "type": "authorization_error"
-                }
-            })),
+            "insufficient_permissions",
+            format!(
+                "Required {:?} access, but have {:?}",
+                required_level, auth_level
+            ),
+            "authorization_error",
+            request_id.as_ref(),
         ));
     }
 
@@ -315,23 +933,360 @@ async fn auth_with_level_middleware(
     });
 
     info!(
-        "Authenticated and authorized request with key_id: {}",
-        key_id
+        "Authenticated and authorized request with key_id: {} (request_id: {:?})",
+        key_id, request_id
     );
     Ok(next.run(request).await)
 }
 
+/// Default maximum duration for a request before it is aborted with 504
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Path prefix exempt from the request timeout (health probes)
+const TIMEOUT_EXEMPT_PREFIX: &str = "/health";
+
+/// Create request timeout middleware
+///
+/// # Arguments
+/// * `timeout` - Maximum time a request may take
+///
+/// # Returns
+/// Middleware that aborts requests exceeding `timeout` with `504` and
+/// `code: "request_timeout"` in the nested error format. Health endpoints are exempt.
+pub fn create_timeout_middleware(
+    timeout: Duration,
+) -> impl Fn(Request, Next) -> MiddlewareFuture + Clone {
+    move |request: Request, next: Next| {
+        Box::pin(async move { timeout_middleware(request, next, timeout).await })
+    }
+}
+
+/// Request timeout middleware
+async fn timeout_middleware(
+    request: Request,
+    next: Next,
+    timeout: Duration,
+) -> Result<Response, AuthRejection> {
+    if request.uri().path().starts_with(TIMEOUT_EXEMPT_PREFIX) {
+        return Ok(next.run(request).await);
+    }
+
+    let request_id = request_id_of(&request);
+    let path = request.uri().path().to_string();
+    match tokio::time::timeout(timeout, next.run(request)).await {
+        Ok(response) => Ok(response),
+        Err(_) => {
+            warn!(
+                "Request to {} timed out after {:?} (request_id: {:?})",
+                path, timeout, request_id
+            );
+            Err(error_rejection(
+                StatusCode::GATEWAY_TIMEOUT,
+                "request_timeout",
+                format!("Request exceeded the {:?} timeout", timeout),
+                "timeout_error",
+                request_id.as_ref(),
+            ))
+        }
+    }
+}
+
+/// Default maximum size of a request body (1 MiB)
+pub const DEFAULT_MAX_REQUEST_BYTES: usize = 1024 * 1024;
+
+/// Maximum size of a request body sent to a health endpoint
+pub const HEALTH_MAX_REQUEST_BYTES: usize = 1024;
+
+/// Create request body size limit middleware
+///
+/// # Arguments
+/// * `max_bytes` - Largest accepted request body
+///
+/// # Returns
+/// Middleware that rejects larger bodies with `413` and `code: "payload_too_large"` in
+/// the nested error format. Bodies declaring their size are checked up front; bodies
+/// of unknown size (chunked) are buffered up to the limit.
+pub fn create_body_limit_middleware(
+    max_bytes: usize,
+) -> impl Fn(Request, Next) -> MiddlewareFuture + Clone {
+    move |request: Request, next: Next| {
+        Box::pin(async move { body_limit_middleware(request, next, max_bytes).await })
+    }
+}
+
+/// Request body size limit middleware
+async fn body_limit_middleware(
+    request: Request,
+    next: Next,
+    max_bytes: usize,
+) -> Result<Response, AuthRejection> {
+    let request_id = request_id_of(&request);
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let reject = |size: Option<u64>| {
+        warn!(
+            "Rejected {} {} with a body over {} bytes (declared size: {:?}, request_id: {:?})",
+            method, path, max_bytes, size, request_id
+        );
+        error_rejection(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "payload_too_large",
+            format!("Request body exceeds the {} byte limit", max_bytes),
+            "invalid_request_error",
+            request_id.as_ref(),
+        )
+    };
+
+    let size_hint = request.body().size_hint();
+    if size_hint.lower() > max_bytes as u64 {
+        return Err(reject(Some(size_hint.lower())));
+    }
+    if size_hint
+        .upper()
+        .is_some_and(|upper| upper <= max_bytes as u64)
+    {
+        return Ok(next.run(request).await);
+    }
+
+    let (parts, body) = request.into_parts();
+    match axum::body::to_bytes(body, max_bytes).await {
+        Ok(bytes) => Ok(next
+            .run(Request::from_parts(parts, Body::from(bytes)))
+            .await),
+        Err(_) => Err(reject(None)),
+    }
+}
+
+/// Create maintenance-mode middleware for write and admin routes
+///
+/// # Arguments
+/// * `maintenance_mode` - Shared flag; while set, guarded routes are rejected
+///
+/// # Returns
+/// Middleware that rejects requests with `503` and `code: "maintenance"` in the
+/// nested error format while maintenance mode is on. Layer it inside the auth
+/// middleware so unauthenticated requests still get `401`.
+pub fn create_maintenance_middleware(
+    maintenance_mode: Arc<AtomicBool>,
+) -> impl Fn(Request, Next) -> MiddlewareFuture + Clone {
+    move |request: Request, next: Next| {
+        let maintenance_mode = maintenance_mode.clone();
+        Box::pin(async move {
+            if maintenance_mode.load(Ordering::Relaxed) {
+                let request_id = request_id_of(&request);
+                info!(
+                    "Rejected {} {} during maintenance (request_id: {:?})",
+                    request.method(),
+                    request.uri().path(),
+                    request_id
+                );
+                return Err(error_rejection(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "maintenance",
+                    "Service is in maintenance mode; only read requests are accepted".to_string(),
+                    "maintenance_error",
+                    request_id.as_ref(),
+                ));
+            }
+            Ok(next.run(request).await)
+        })
+    }
+}
+
+/// Create request metrics middleware
+///
+/// # Arguments
+/// * `metrics` - Counters incremented once per request
+///
+/// # Returns
+/// Middleware that counts each request by the caller's auth level and matched route.
+/// Layer it inside the auth middleware, which provides the `AuthInfo` extension;
+/// requests without one are not counted.
+pub fn create_request_metrics_middleware(
+    metrics: Arc<RequestMetrics>,
+) -> impl Fn(Request, Next) -> MiddlewareFuture + Clone {
+    move |request: Request, next: Next| {
+        let metrics = metrics.clone();
+        Box::pin(async move {
+            let auth_level = request
+                .extensions()
+                .get::<AuthInfo>()
+                .map(|auth| auth.auth_level);
+            if let Some(auth_level) = auth_level {
+                let route = request
+                    .extensions()
+                    .get::<MatchedPath>()
+                    .map(MatchedPath::as_str)
+                    .unwrap_or_else(|| request.uri().path());
+                metrics.record(auth_level, route);
+            }
+            Ok(next.run(request).await)
+        })
+    }
+}
+
+/// Tracing target of audit log lines (filter with e.g. `RUST_LOG=sentinel::audit=info`)
+pub const AUDIT_LOG_TARGET: &str = "sentinel::audit";
+
+/// Largest body buffered for audit logging; larger or unsized (streaming) bodies are
+/// not logged
+const MAX_AUDITED_BODY_BYTES: u64 = 1024 * 1024;
+
+/// Path prefix whose bodies are never logged (responses carry plaintext API keys)
+const AUDIT_BODY_EXEMPT_PREFIX: &str = "/v1/admin/keys";
+
+/// Render a body for the audit log, truncated to `max_bytes`
+fn truncate_body(bytes: &[u8], max_bytes: usize) -> String {
+    if bytes.len() <= max_bytes {
+        return String::from_utf8_lossy(bytes).into_owned();
+    }
+    format!(
+        "{}… ({} bytes truncated)",
+        String::from_utf8_lossy(&bytes[..max_bytes]),
+        bytes.len() - max_bytes
+    )
+}
+
+/// Buffer a body for the audit log if its size is known and small enough
+///
+/// # Returns
+/// The body to pass on (rebuilt around the buffered bytes when captured) and the
+/// rendered text, or `None` if the body was not captured
+async fn capture_body(body: Body, max_bytes: usize) -> (Body, Option<String>) {
+    let captured_size = body
+        .size_hint()
+        .upper()
+        .filter(|size| *size <= MAX_AUDITED_BODY_BYTES);
+    if captured_size.is_none() {
+        return (body, None);
+    }
+    match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => {
+            let text = truncate_body(&bytes, max_bytes);
+            (Body::from(bytes), Some(text))
+        }
+        Err(e) => {
+            warn!("Failed to buffer body for audit log: {}", e);
+            (Body::empty(), None)
+        }
+    }
+}
+
+/// Create request/response audit log middleware
+///
+/// # Arguments
+/// * `config` - Audit settings; `None` disables the middleware
+///
+/// # Returns
+/// Middleware that logs method, path, status, latency, and API key ID of each request
+/// at info level under [`AUDIT_LOG_TARGET`], plus truncated bodies if configured.
+/// Layer it inside the auth middleware, which provides the `AuthInfo` extension.
+///
+/// # Note
+/// Headers are never logged, so the `Authorization` header cannot leak. Bodies of
+/// API key management routes and streaming responses are skipped.
+pub fn create_audit_log_middleware(
+    config: Option<AuditLogConfig>,
+) -> impl Fn(Request, Next) -> MiddlewareFuture + Clone {
+    move |request: Request, next: Next| {
+        Box::pin(async move {
+            let Some(config) = config else {
+                return Ok(next.run(request).await);
+            };
+
+            let method = request.method().clone();
+            let path = request.uri().path().to_string();
+            let key_id = request
+                .extensions()
+                .get::<AuthInfo>()
+                .map(|auth| auth.key_id.to_string());
+            let request_id = request_id_of(&request);
+            let log_bodies = config.log_bodies && !path.starts_with(AUDIT_BODY_EXEMPT_PREFIX);
+
+            let (request, request_body) = if log_bodies {
+                let (parts, body) = request.into_parts();
+                let (body, text) = capture_body(body, config.max_body_bytes).await;
+                (Request::from_parts(parts, body), text)
+            } else {
+                (request, None)
+            };
+
+            let started = Instant::now();
+            let response = next.run(request).await;
+            let latency_ms = started.elapsed().as_millis() as u64;
+
+            let (response, response_body) = if log_bodies {
+                let (parts, body) = response.into_parts();
+                let (body, text) = capture_body(body, config.max_body_bytes).await;
+                (Response::from_parts(parts, body), text)
+            } else {
+                (response, None)
+            };
+
+            info!(
+                target: AUDIT_LOG_TARGET,
+                method = %method,
+                path = %path,
+                status = response.status().as_u16(),
+                latency_ms,
+                key_id = key_id.as_deref(),
+                request_id = request_id.as_ref().map(|id| id.0.as_str()),
+                request_body = request_body.as_deref(),
+                response_body = response_body.as_deref(),
+                "request completed"
+            );
+            Ok(response)
+        })
+    }
+}
+
+/// Create the CORS layer for the configured allowed origin(s)
+///
+/// # Arguments
+/// * `cors_allow_origin` - `*` for any origin, or a comma-separated list of origins
+///   (e.g. `https://app.example.com,https://admin.example.com`)
+///
+/// # Note
+/// Origins that are not valid header values are skipped with a warning.
+pub fn create_cors_layer(cors_allow_origin: &str) -> CorsLayer {
+    let allow_origin = if cors_allow_origin.trim() == "*" {
+        AllowOrigin::any()
+    } else {
+        let origins: Vec<HeaderValue> = cors_allow_origin
+            .split(',')
+            .map(str::trim)
+            .filter(|origin| !origin.is_empty())
+            .filter_map(|origin| match HeaderValue::from_str(origin) {
+                Ok(value) => Some(value),
+                Err(_) => {
+                    warn!("Ignoring invalid CORS origin: {}", origin);
+                    None
+                }
+            })
+            .collect();
+        AllowOrigin::list(origins)
+    };
+
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods(tower_http::cors::Any)
+        .allow_headers(tower_http::cors::Any)
+}
+
 /// Create middleware stack with CORS and tracing
+///
+/// # Arguments
+/// * `cors_allow_origin` - Allowed origin(s), as accepted by `create_cors_layer`
+///
+/// # Note
+/// The request timeout is installed by `create_router` (from `AppState.request_timeout`),
+/// inside the request ID middleware so a `504` still carries `x-request-id`.
 pub fn create_middleware_stack(
+    cors_allow_origin: &str,
 ) -> impl tower::Layer<axum::routing::IntoMakeService<axum::Router>> + Clone {
     ServiceBuilder::new()
         .layer(TraceLayer::new_for_http())
-        .layer(
-            CorsLayer::new()
-                .allow_origin(tower_http::cors::Any)
-                .allow_methods(tower_http::cors::Any)
-                .allow_headers(tower_http::cors::Any),
-        )
+        .layer(create_cors_layer(cors_allow_origin))
 }
 
 #[cfg(test)]
@@ -347,7 +1302,8 @@ mod tests {
 
         store
             .add_key(key.clone(), key_id.clone(), AuthLevel::Write)
-            .await;
+            .await
+            .unwrap();
 
         let result = store.validate_key(&key).await;
         match result {
@@ -358,6 +1314,401 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_api_key_store_never_holds_plaintext() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("api_keys");
+        let store = ApiKeyStore::with_persistence(&path).unwrap();
+        let key = "sk-1234567890123456".to_string();
+
+        store
+            .add_key(
+                key.clone(),
+                ApiKeyId::new("hashed".to_string()),
+                AuthLevel::Write,
+            )
+            .await
+            .unwrap();
+
+        // The key still authenticates
+        assert!(matches!(
+            store.validate_key(&key).await,
+            AuthResult::Authenticated { .. }
+        ));
+        assert_eq!(store.get_auth_level(&key).await, Some(AuthLevel::Write));
+
+        // Only the digest is held in memory
+        {
+            let keys = store.keys.read().await;
+            assert_eq!(keys.len(), 1);
+            assert!(!keys.contains_key(&key));
+            assert!(keys.contains_key(&hash_key(&key)));
+        }
+
+        // ...and on disk
+        let db = store.db.as_ref().unwrap();
+        for entry in db.iter() {
+            let (stored_key, value) = entry.unwrap();
+            assert_eq!(stored_key.as_ref(), hash_key(&key).as_bytes());
+            assert!(!value
+                .windows(key.len())
+                .any(|window| window == key.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_hash_key_is_stable_sha256_hex() {
+        assert_eq!(
+            hash_key("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_ne!(hash_key("sk-1234567890123456"), "sk-1234567890123456");
+    }
+
+    #[tokio::test]
+    async fn test_api_key_store_expired_key() {
+        let store = ApiKeyStore::new();
+        let key = "sk-1234567890123456".to_string();
+
+        store
+            .add_key_with_expiry(
+                key.clone(),
+                ApiKeyId::new("expired".to_string()),
+                AuthLevel::Read,
+                Utc::now() - chrono::Duration::seconds(1),
+            )
+            .await
+            .unwrap();
+
+        match store.validate_key(&key).await {
+            AuthResult::Unauthenticated { reason } => assert!(reason.contains("expired")),
+            _ => panic!("Expected Unauthenticated"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_api_key_store_persists_across_reopen() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("api_keys");
+        let key = "sk-1234567890123456".to_string();
+        let expiring = "sk-6543210987654321".to_string();
+        let expires_at = Utc::now() + chrono::Duration::hours(1);
+
+        {
+            let store = ApiKeyStore::with_persistence(&path).unwrap();
+            store
+                .add_key(
+                    key.clone(),
+                    ApiKeyId::new("persisted".to_string()),
+                    AuthLevel::Admin,
+                )
+                .await
+                .unwrap();
+            store
+                .add_key_with_expiry(
+                    expiring.clone(),
+                    ApiKeyId::new("expiring".to_string()),
+                    AuthLevel::Read,
+                    expires_at,
+                )
+                .await
+                .unwrap();
+        }
+
+        let reopened = ApiKeyStore::with_persistence(&path).unwrap();
+        match reopened.validate_key(&key).await {
+            AuthResult::Authenticated { key_id } => assert_eq!(key_id.0, "persisted"),
+            _ => panic!("Expected Authenticated after reopen"),
+        }
+        assert_eq!(reopened.get_auth_level(&key).await, Some(AuthLevel::Admin));
+        assert!(matches!(
+            reopened.validate_key(&expiring).await,
+            AuthResult::Authenticated { .. }
+        ));
+        assert_eq!(
+            reopened
+                .keys
+                .read()
+                .await
+                .get(&hash_key(&expiring))
+                .unwrap()
+                .2,
+            Some(expires_at)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_key_revokes_it() {
+        let store = ApiKeyStore::new();
+        let key = "sk-1234567890123456";
+        store
+            .add_key(
+                key.to_string(),
+                ApiKeyId::new("k".to_string()),
+                AuthLevel::Write,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            store.validate_key(key).await,
+            AuthResult::Authenticated { .. }
+        ));
+
+        assert!(store.remove_key(key).await);
+
+        assert!(matches!(
+            store.validate_key(key).await,
+            AuthResult::Unauthenticated { .. }
+        ));
+        assert_eq!(store.get_auth_level(key).await, None);
+        assert!(!store.remove_key(key).await);
+    }
+
+    #[tokio::test]
+    async fn test_create_key_generates_usable_secret() {
+        let store = ApiKeyStore::new();
+        let expires_at = Utc::now() + chrono::Duration::hours(1);
+        let key = store
+            .create_key(
+                ApiKeyId::new("zeta".to_string()),
+                AuthLevel::Write,
+                Some(expires_at),
+            )
+            .await
+            .unwrap();
+        store
+            .add_key(
+                "sk-alpha1234567890123".to_string(),
+                ApiKeyId::new("alpha".to_string()),
+                AuthLevel::Read,
+            )
+            .await
+            .unwrap();
+
+        assert!(key.starts_with(GENERATED_KEY_PREFIX));
+        assert_eq!(
+            key.len(),
+            GENERATED_KEY_PREFIX.len() + GENERATED_KEY_BYTES * 2
+        );
+        assert_eq!(store.get_auth_level(&key).await, Some(AuthLevel::Write));
+
+        let keys = store.list_keys().await;
+        assert_eq!(
+            keys,
+            vec![
+                ApiKeyInfo {
+                    key_id: ApiKeyId::new("alpha".to_string()),
+                    auth_level: AuthLevel::Read,
+                    expires_at: None,
+                },
+                ApiKeyInfo {
+                    key_id: ApiKeyId::new("zeta".to_string()),
+                    auth_level: AuthLevel::Write,
+                    expires_at: Some(expires_at),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_key_by_id_revokes_all_matching_keys() {
+        let store = ApiKeyStore::new();
+        let tenant = ApiKeyId::new("tenant".to_string());
+        let first = "sk-1111111111111111";
+        let second = "sk-2222222222222222";
+        let other = "sk-3333333333333333";
+        store
+            .add_key(first.to_string(), tenant.clone(), AuthLevel::Read)
+            .await
+            .unwrap();
+        store
+            .add_key(second.to_string(), tenant.clone(), AuthLevel::Admin)
+            .await
+            .unwrap();
+        store
+            .add_key(
+                other.to_string(),
+                ApiKeyId::new("other".to_string()),
+                AuthLevel::Read,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(store.remove_key_by_id(&tenant).await, 2);
+
+        for key in [first, second] {
+            assert!(matches!(
+                store.validate_key(key).await,
+                AuthResult::Unauthenticated { .. }
+            ));
+        }
+        assert!(matches!(
+            store.validate_key(other).await,
+            AuthResult::Authenticated { .. }
+        ));
+        assert_eq!(store.remove_key_by_id(&tenant).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_revocation_writes_through_to_persistence() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("api_keys");
+        let revoked = "sk-1111111111111111";
+        let revoked_by_id = "sk-2222222222222222";
+        let kept = "sk-3333333333333333";
+
+        {
+            let store = ApiKeyStore::with_persistence(&path).unwrap();
+            for (key, id) in [(revoked, "a"), (revoked_by_id, "b"), (kept, "c")] {
+                store
+                    .add_key(
+                        key.to_string(),
+                        ApiKeyId::new(id.to_string()),
+                        AuthLevel::Read,
+                    )
+                    .await
+                    .unwrap();
+            }
+            assert!(store.remove_key(revoked).await);
+            assert_eq!(
+                store
+                    .remove_key_by_id(&ApiKeyId::new("b".to_string()))
+                    .await,
+                1
+            );
+        }
+
+        let reopened = ApiKeyStore::with_persistence(&path).unwrap();
+        for key in [revoked, revoked_by_id] {
+            assert!(matches!(
+                reopened.validate_key(key).await,
+                AuthResult::Unauthenticated { .. }
+            ));
+        }
+        assert!(matches!(
+            reopened.validate_key(kept).await,
+            AuthResult::Authenticated { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reload_picks_up_new_env_keys() {
+        let store = ApiKeyStore::new();
+        let key = "sk-reload00000000001";
+        assert!(matches!(
+            store.validate_key(key).await,
+            AuthResult::Unauthenticated { .. }
+        ));
+
+        let vars = HashMap::from([(
+            "SENTINEL_API_KEY_RELOAD_ADDED".to_string(),
+            format!("{}:write", key),
+        )]);
+        let summary = store.reload_from_vars(vars, false).await.unwrap();
+
+        assert!(summary.loaded >= 1);
+        match store.validate_key(key).await {
+            AuthResult::Authenticated { key_id } => assert_eq!(key_id.0, "RELOAD_ADDED"),
+            _ => panic!("Expected Authenticated after reload"),
+        }
+        assert_eq!(store.get_auth_level(key).await, Some(AuthLevel::Write));
+    }
+
+    #[tokio::test]
+    async fn test_reload_prunes_removed_env_keys_only_when_asked() {
+        let store = ApiKeyStore::new();
+        let env_key = "sk-reload00000000002";
+        let admin_key = "sk-reload00000000003";
+        store
+            .add_key(
+                admin_key.to_string(),
+                ApiKeyId::new("admin-created".to_string()),
+                AuthLevel::Read,
+            )
+            .await
+            .unwrap();
+
+        let vars = HashMap::from([(
+            "SENTINEL_API_KEY_RELOAD_PRUNED".to_string(),
+            format!("{}:read", env_key),
+        )]);
+        store.load_from_vars(vars).await.unwrap();
+
+        store.reload_from_vars(HashMap::new(), false).await.unwrap();
+        assert!(matches!(
+            store.validate_key(env_key).await,
+            AuthResult::Authenticated { .. }
+        ));
+
+        let summary = store.reload_from_vars(HashMap::new(), true).await.unwrap();
+        assert_eq!(summary.pruned, 1);
+        assert!(matches!(
+            store.validate_key(env_key).await,
+            AuthResult::Unauthenticated { .. }
+        ));
+        assert!(matches!(
+            store.validate_key(admin_key).await,
+            AuthResult::Authenticated { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_env_keys_are_not_persisted_across_restarts() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("api_keys");
+        let env_key = "sk-reload00000000005";
+        let vars = HashMap::from([(
+            "SENTINEL_API_KEY_ROTATED".to_string(),
+            format!("{}:admin", env_key),
+        )]);
+
+        {
+            let store = ApiKeyStore::with_persistence(&path).unwrap();
+            store.reload_from_vars(vars, false).await.unwrap();
+            assert!(matches!(
+                store.validate_key(env_key).await,
+                AuthResult::Authenticated { .. }
+            ));
+        }
+
+        // The variable was removed before the restart
+        let reopened = ApiKeyStore::with_persistence(&path).unwrap();
+        reopened.load_from_vars(HashMap::new()).await.unwrap();
+        assert!(matches!(
+            reopened.validate_key(env_key).await,
+            AuthResult::Unauthenticated { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reload_merges_keys_written_to_the_database() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("api_keys");
+        let store = ApiKeyStore::with_persistence(&path).unwrap();
+        let key = "sk-reload00000000004";
+
+        // Simulate another writer adding a key directly to the shared database
+        let record: KeyRecord = (
+            ApiKeyId::new("external".to_string()),
+            AuthLevel::Admin,
+            None,
+            None,
+        );
+        let db = store.db.as_ref().unwrap();
+        db.insert(
+            hash_key(key).as_bytes(),
+            bincode::serialize(&record).unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(
+            store.validate_key(key).await,
+            AuthResult::Unauthenticated { .. }
+        ));
+
+        store.reload(false).await.unwrap();
+        assert_eq!(store.get_auth_level(key).await, Some(AuthLevel::Admin));
+    }
+
     #[tokio::test]
     async fn test_api_key_store_invalid_key() {
         let store = ApiKeyStore::new();
@@ -420,9 +1771,493 @@ mod tests {
         let key_id = ApiKeyId::new("test-key".to_string());
         let key = "sk-1234567890123456".to_string();
 
-        store.add_key(key.clone(), key_id, AuthLevel::Admin).await;
+        store
+            .add_key(key.clone(), key_id, AuthLevel::Admin)
+            .await
+            .unwrap();
 
         let level = store.get_auth_level(&key).await;
         assert_eq!(level, Some(AuthLevel::Admin));
     }
+
+    /// Router with the request ID layer wrapped around a write-protected route
+    fn request_id_router(key_store: Arc<ApiKeyStore>) -> axum::Router {
+        axum::Router::new()
+            .route(
+                "/protected",
+                axum::routing::get(|| async { "ok" }).layer(axum::middleware::from_fn(
+                    create_auth_middleware(key_store, AuthLevel::Write),
+                )),
+            )
+            .layer(axum::middleware::from_fn(request_id_middleware))
+    }
+
+    #[tokio::test]
+    async fn test_request_id_generated_and_preserved() {
+        use tower::ServiceExt;
+
+        let key_store = Arc::new(ApiKeyStore::new());
+        key_store
+            .add_key(
+                "sk-1234567890123456".to_string(),
+                ApiKeyId::new("test-key".to_string()),
+                AuthLevel::Write,
+            )
+            .await
+            .unwrap();
+        let router = request_id_router(key_store);
+
+        // A generated ID is echoed when the client sends none
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/protected")
+                    .header(AUTHORIZATION, "Bearer sk-1234567890123456")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let generated = response.headers().get(REQUEST_ID_HEADER).unwrap();
+        assert!(uuid::Uuid::parse_str(generated.to_str().unwrap()).is_ok());
+
+        // A client-supplied ID round-trips unchanged
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/protected")
+                    .header(AUTHORIZATION, "Bearer sk-1234567890123456")
+                    .header(REQUEST_ID_HEADER, "client-req-42")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers().get(REQUEST_ID_HEADER).unwrap(),
+            "client-req-42"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_id_on_auth_errors() {
+        use tower::ServiceExt;
+
+        let router = request_id_router(Arc::new(ApiKeyStore::new()));
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/protected")
+                    .header(REQUEST_ID_HEADER, "client-req-7")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            response.headers().get(REQUEST_ID_HEADER).unwrap(),
+            "client-req-7"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["code"], "missing_authorization");
+        assert_eq!(json["error"]["request_id"], "client-req-7");
+    }
+
+    /// Send a POST through a write-protected route that echoes the request body
+    async fn signed_request(
+        key_store: Arc<ApiKeyStore>,
+        body: &'static str,
+        signature: Option<(i64, String)>,
+    ) -> (StatusCode, serde_json::Value) {
+        use tower::ServiceExt;
+
+        let router = axum::Router::new().route(
+            "/protected",
+            axum::routing::post(|body: String| async move { body }).layer(
+                axum::middleware::from_fn(create_auth_middleware(key_store, AuthLevel::Write)),
+            ),
+        );
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri("/protected")
+            .header(AUTHORIZATION, "Bearer sk-1234567890123456");
+        if let Some((timestamp, signature)) = signature {
+            builder = builder
+                .header(SIGNATURE_TIMESTAMP_HEADER, timestamp.to_string())
+                .header(SIGNATURE_HEADER, signature);
+        }
+        let response = router
+            .oneshot(builder.body(axum::body::Body::from(body)).unwrap())
+            .await
+            .unwrap();
+
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json = serde_json::from_slice(&bytes)
+            .unwrap_or_else(|_| serde_json::Value::String(String::from_utf8_lossy(&bytes).into()));
+        (status, json)
+    }
+
+    /// Store with one write key that requires signed requests
+    async fn signing_key_store() -> Arc<ApiKeyStore> {
+        let key_store = Arc::new(ApiKeyStore::new());
+        key_store
+            .add_key_with_signing_secret(
+                "sk-1234567890123456".to_string(),
+                ApiKeyId::new("signer".to_string()),
+                AuthLevel::Write,
+                "signing-secret".to_string(),
+            )
+            .await
+            .unwrap();
+        key_store
+    }
+
+    #[tokio::test]
+    async fn test_signed_request_is_accepted_with_body_intact() {
+        use crate::core::auth::compute_signature;
+
+        let now = Utc::now().timestamp();
+        let signature =
+            compute_signature("signing-secret", now, "POST", "/protected", b"payload").unwrap();
+        let (status, body) =
+            signed_request(signing_key_store().await, "payload", Some((now, signature))).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "payload");
+    }
+
+    #[tokio::test]
+    async fn test_signed_request_rejects_tampered_body_and_stale_timestamp() {
+        use crate::core::auth::{compute_signature, SIGNATURE_TOLERANCE_SECS};
+
+        let now = Utc::now().timestamp();
+        let signature =
+            compute_signature("signing-secret", now, "POST", "/protected", b"payload").unwrap();
+        let (status, body) = signed_request(
+            signing_key_store().await,
+            "tampered",
+            Some((now, signature)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["code"], "invalid_signature");
+
+        let stale = now - SIGNATURE_TOLERANCE_SECS - 60;
+        let signature =
+            compute_signature("signing-secret", stale, "POST", "/protected", b"payload").unwrap();
+        let (status, body) = signed_request(
+            signing_key_store().await,
+            "payload",
+            Some((stale, signature)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["code"], "invalid_signature");
+
+        // A signature captured for another endpoint does not verify here
+        let signature =
+            compute_signature("signing-secret", now, "POST", "/other", b"payload").unwrap();
+        let (status, body) =
+            signed_request(signing_key_store().await, "payload", Some((now, signature))).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["code"], "invalid_signature");
+
+        let (status, body) = signed_request(signing_key_store().await, "payload", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["code"], "invalid_signature");
+    }
+
+    #[tokio::test]
+    async fn test_keys_without_signing_secret_need_no_signature() {
+        let key_store = Arc::new(ApiKeyStore::new());
+        key_store
+            .add_key(
+                "sk-1234567890123456".to_string(),
+                ApiKeyId::new("plain".to_string()),
+                AuthLevel::Write,
+            )
+            .await
+            .unwrap();
+
+        let (status, body) = signed_request(key_store, "payload", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "payload");
+    }
+
+    #[tokio::test]
+    async fn test_legacy_persisted_records_still_load() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("api_keys");
+        let key = "sk-1234567890123456";
+        {
+            let db = sled::open(&path).unwrap();
+            let legacy: LegacyKeyRecord =
+                (ApiKeyId::new("legacy".to_string()), AuthLevel::Read, None);
+            db.insert(
+                hash_key(key).as_bytes(),
+                bincode::serialize(&legacy).unwrap(),
+            )
+            .unwrap();
+            db.flush().unwrap();
+        }
+
+        let store = ApiKeyStore::with_persistence(&path).unwrap();
+        assert_eq!(store.get_auth_level(key).await, Some(AuthLevel::Read));
+        assert_eq!(store.signing_secret(key).await, None);
+    }
+
+    /// Send a request with an `Origin` header through a CORS-wrapped router
+    async fn cors_allow_origin_header(
+        cors_allow_origin: &str,
+        origin: &str,
+    ) -> Option<HeaderValue> {
+        use tower::ServiceExt;
+
+        let router = axum::Router::new()
+            .route("/", axum::routing::get(|| async { "ok" }))
+            .layer(create_cors_layer(cors_allow_origin));
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/")
+                    .header(axum::http::header::ORIGIN, origin)
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        response
+            .headers()
+            .get(axum::http::header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .cloned()
+    }
+
+    #[tokio::test]
+    async fn test_cors_specific_origin_is_not_wildcard() {
+        let allowed =
+            cors_allow_origin_header("https://app.example.com", "https://app.example.com").await;
+        assert_eq!(allowed.unwrap(), "https://app.example.com");
+
+        let rejected =
+            cors_allow_origin_header("https://app.example.com", "https://evil.example.com").await;
+        assert!(rejected.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cors_multiple_origins_each_registered() {
+        let config = "https://app.example.com, https://admin.example.com";
+
+        for origin in ["https://app.example.com", "https://admin.example.com"] {
+            assert_eq!(
+                cors_allow_origin_header(config, origin).await.unwrap(),
+                origin
+            );
+        }
+        assert!(
+            cors_allow_origin_header(config, "https://other.example.com")
+                .await
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cors_wildcard_allows_any_origin() {
+        let allowed = cors_allow_origin_header("*", "https://anything.example.com").await;
+        assert_eq!(allowed.unwrap(), "*");
+    }
+
+    async fn get(router: axum::Router, path: &str) -> Response {
+        use tower::ServiceExt;
+
+        router
+            .oneshot(
+                Request::builder()
+                    .uri(path)
+                    .header(REQUEST_ID_HEADER, "slow-req")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_health_endpoints_exempt_from_timeout() {
+        let router = axum::Router::new()
+            .route(
+                "/health/ready",
+                axum::routing::get(|| async {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    "ready"
+                }),
+            )
+            .layer(axum::middleware::from_fn::<_, (Request,)>(
+                create_timeout_middleware(Duration::from_millis(10)),
+            ));
+
+        assert_eq!(get(router, "/health/ready").await.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_middleware_follows_flag() {
+        let maintenance_mode = Arc::new(AtomicBool::new(true));
+        let router = axum::Router::new()
+            .route("/v1/write", axum::routing::get(|| async { "written" }))
+            .layer(axum::middleware::from_fn::<_, (Request,)>(
+                create_maintenance_middleware(maintenance_mode.clone()),
+            ))
+            .layer(axum::middleware::from_fn(request_id_middleware));
+
+        let response = get(router.clone(), "/v1/write").await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["code"], "maintenance");
+        assert_eq!(json["error"]["type"], "maintenance_error");
+
+        maintenance_mode.store(false, Ordering::Relaxed);
+        assert_eq!(get(router, "/v1/write").await.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_body_limit_applies_to_bodies_of_unknown_size() {
+        use tower::ServiceExt;
+
+        let router = axum::Router::new()
+            .route(
+                "/v1/echo",
+                axum::routing::post(|body: String| async move { body }),
+            )
+            .layer(axum::middleware::from_fn::<_, (Request,)>(
+                create_body_limit_middleware(8),
+            ));
+        let chunked = |chunks: Vec<&'static str>| {
+            let stream =
+                futures::stream::iter(chunks.into_iter().map(Ok::<_, std::convert::Infallible>));
+            Request::builder()
+                .method("POST")
+                .uri("/v1/echo")
+                .body(axum::body::Body::from_stream(stream))
+                .unwrap()
+        };
+
+        let response = router
+            .clone()
+            .oneshot(chunked(vec!["1234", "5678"]))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "12345678");
+
+        let response = router
+            .oneshot(chunked(vec!["1234", "5678", "9"]))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_records_request_and_truncates_body() {
+        use crate::telemetry::log_stream::{LogStream, LogStreamLayer};
+        use tower::ServiceExt;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let key_store = Arc::new(ApiKeyStore::new());
+        key_store
+            .add_key(
+                "sk-1234567890123456".to_string(),
+                ApiKeyId::new("auditor".to_string()),
+                AuthLevel::Write,
+            )
+            .await
+            .unwrap();
+        let config = AuditLogConfig {
+            log_bodies: true,
+            max_body_bytes: 16,
+        };
+        let router = axum::Router::new().route(
+            "/v1/echo",
+            axum::routing::post(|body: String| async move { body }).layer(
+                ServiceBuilder::new()
+                    .layer(axum::middleware::from_fn(create_auth_middleware(
+                        key_store,
+                        AuthLevel::Write,
+                    )))
+                    .layer(axum::middleware::from_fn(create_audit_log_middleware(
+                        Some(config),
+                    ))),
+            ),
+        );
+        let log_stream = Arc::new(LogStream::default());
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::registry().with(LogStreamLayer::new(log_stream.clone())),
+        );
+
+        let body = "x".repeat(100);
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/echo")
+                    .header(AUTHORIZATION, "Bearer sk-1234567890123456")
+                    .body(axum::body::Body::from(body.clone()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // The handler and client still see the full bodies
+        assert_eq!(response.status(), StatusCode::OK);
+        let echoed = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(echoed, body.as_bytes());
+
+        let (lines, _) = log_stream.subscribe();
+        let line = lines
+            .iter()
+            .find(|line| line.contains(AUDIT_LOG_TARGET))
+            .unwrap_or_else(|| panic!("no audit line in {:?}", lines));
+        for field in [
+            "method=POST",
+            "path=/v1/echo",
+            "status=200",
+            "latency_ms=",
+            "key_id=auditor",
+        ] {
+            assert!(line.contains(field), "missing {} in {}", field, line);
+        }
+        let truncated = format!("{}… (84 bytes truncated)", "x".repeat(16));
+        assert!(
+            line.contains(&format!("request_body={} response_body", truncated)),
+            "{}",
+            line
+        );
+        assert!(!line.contains(&body));
+        assert!(!lines
+            .iter()
+            .any(|line| line.contains("sk-1234567890123456")));
+    }
+
+    #[test]
+    fn test_truncate_body_keeps_short_bodies() {
+        assert_eq!(truncate_body(b"{\"ok\":true}", 16), "{\"ok\":true}");
+        assert_eq!(truncate_body(b"abcdef", 3), "abc… (3 bytes truncated)");
+    }
 }
@@ -1,2 +1,3 @@
+pub mod idempotency;
 pub mod middleware;
 pub mod routes;
@@ -3,8 +3,16 @@
 
 use anyhow::{Context, Result};
 use secrecy::{ExposeSecret, Secret};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::api::middleware::DEFAULT_MAX_REQUEST_BYTES;
+use crate::core::precision::{DEFAULT_COST_DECIMALS, MAX_COST_DECIMALS};
+use crate::core::types::{ModelParams, DEFAULT_MODEL};
+use crate::memory::conversation_budget::{ConversationBudgets, DEFAULT_CONVERSATION_TOKEN_BUDGET};
+use crate::telemetry::audit::{AuditLogConfig, DEFAULT_AUDIT_BODY_BYTES};
+use crate::telemetry::LogFormat;
+
 /// Application environment
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Environment {
@@ -53,8 +61,22 @@ impl std::fmt::Display for Environment {
     }
 }
 
+/// Placeholder printed instead of secret values
+const REDACTED: &str = "[REDACTED]";
+
+/// Debug-formats as the redaction placeholder, without quotes
+struct Redacted;
+
+impl std::fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
 /// Application configuration
-#[derive(Debug, Clone)]
+///
+/// `Debug` prints `[REDACTED]` for API keys, so the whole config can be logged safely.
+#[derive(Clone)]
 pub struct Config {
     /// Current environment
     pub environment: Environment,
@@ -80,10 +102,37 @@ pub struct Config {
     pub metrics_port: u16,
     /// CORS allowed origin
     pub cors_allow_origin: String,
+    /// Maximum request duration in seconds before a 504 is returned
+    pub request_timeout_secs: u64,
+    /// Largest accepted request body in bytes before a 413 is returned
+    pub max_request_bytes: usize,
     /// Enable debug routes
     pub enable_debug_routes: bool,
     /// Enable metrics export
     pub enable_metrics_export: bool,
+    /// Start in maintenance mode (write and admin routes return 503)
+    pub maintenance_mode: bool,
+    /// Per-model default temperature/max_tokens, keyed by model name
+    pub model_defaults: HashMap<String, ModelParams>,
+    /// Model assumed when a chat request does not name one; selects its model defaults
+    /// and is reported when the provider does not name the model it used
+    pub default_model: String,
+    /// Temperature used when neither the request nor its model's defaults set one
+    pub default_temperature: Option<f64>,
+    /// Hard cap on messages in one conversation before consolidation is forced
+    pub max_conversation_messages: Option<usize>,
+    /// Maximum tokens a single conversation may consume before completions get `402`
+    pub conversation_token_budget: u64,
+    /// Log output format (text in development, JSON in production unless overridden)
+    pub log_format: LogFormat,
+    /// Model names clients may request; empty allows any model
+    pub allowed_models: Vec<String>,
+    /// Decimal places kept when rounding cost figures (see `round_to_decimals`)
+    pub cost_decimals: u32,
+    /// Log method, path, status, latency, and key ID of each authenticated request
+    pub audit_log: bool,
+    /// Also log request/response bodies, truncated to this many bytes
+    pub audit_log_body_bytes: Option<usize>,
 }
 
 impl Config {
@@ -141,6 +190,7 @@ impl Config {
             .into();
 
         let rust_log = std::env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string());
+        crate::telemetry::parse_filter(&rust_log).context("Invalid RUST_LOG value")?;
 
         let rust_backtrace = std::env::var("RUST_BACKTRACE").unwrap_or_else(|_| "0".to_string());
 
@@ -157,6 +207,25 @@ impl Config {
         let cors_allow_origin =
             std::env::var("CORS_ALLOW_ORIGIN").unwrap_or_else(|_| "*".to_string());
 
+        let request_timeout_secs = std::env::var("REQUEST_TIMEOUT_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse::<u64>()
+            .context("Invalid REQUEST_TIMEOUT_SECS value")?;
+
+        let max_request_bytes = std::env::var("MAX_REQUEST_BYTES")
+            .ok()
+            .map(|value| value.parse::<usize>())
+            .transpose()
+            .context("Invalid MAX_REQUEST_BYTES value")?
+            .unwrap_or(DEFAULT_MAX_REQUEST_BYTES);
+
+        let conversation_token_budget = std::env::var("CONVERSATION_TOKEN_BUDGET")
+            .ok()
+            .map(|value| value.parse::<u64>())
+            .transpose()
+            .context("Invalid CONVERSATION_TOKEN_BUDGET value")?
+            .unwrap_or(DEFAULT_CONVERSATION_TOKEN_BUDGET);
+
         let enable_debug_routes = std::env::var("ENABLE_DEBUG_ROUTES")
             .unwrap_or_else(|_| {
                 if environment.is_development() {
@@ -173,6 +242,83 @@ impl Config {
             .parse::<bool>()
             .unwrap_or(true);
 
+        let maintenance_mode = std::env::var("MAINTENANCE_MODE")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse::<bool>()
+            .unwrap_or(false);
+
+        // JSON object, e.g. {"gpt-4o":{"temperature":0.2,"max_tokens":1024}}
+        let model_defaults = match std::env::var("MODEL_DEFAULTS") {
+            Ok(raw) => serde_json::from_str(&raw).context("Invalid MODEL_DEFAULTS JSON")?,
+            Err(_) => HashMap::new(),
+        };
+
+        // Defaults to the provider's model so the reported model matches what is used
+        let default_model = std::env::var("DEFAULT_MODEL")
+            .or_else(|_| std::env::var("OPENAI_MODEL"))
+            .unwrap_or_else(|_| DEFAULT_MODEL.to_string());
+
+        let default_temperature = match std::env::var("DEFAULT_TEMPERATURE") {
+            Ok(raw) => {
+                let temperature = raw
+                    .parse::<f64>()
+                    .context("Invalid DEFAULT_TEMPERATURE value")?;
+                if !(0.0..=2.0).contains(&temperature) {
+                    anyhow::bail!(
+                        "DEFAULT_TEMPERATURE must be between 0.0 and 2.0, got {}",
+                        temperature
+                    );
+                }
+                Some(temperature)
+            }
+            Err(_) => None,
+        };
+
+        let max_conversation_messages = std::env::var("MAX_CONVERSATION_MESSAGES")
+            .ok()
+            .and_then(|value| value.parse::<usize>().ok());
+
+        let log_format = match std::env::var("LOG_FORMAT") {
+            Ok(value) => value.parse::<LogFormat>().map_err(anyhow::Error::msg)?,
+            Err(_) if environment.is_production() => LogFormat::Json,
+            Err(_) => LogFormat::Text,
+        };
+
+        // Comma-separated, e.g. gpt-4o,gpt-4o-mini
+        let allowed_models = std::env::var("ALLOWED_MODELS")
+            .map(|raw| {
+                raw.split(',')
+                    .map(str::trim)
+                    .filter(|model| !model.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let cost_decimals = std::env::var("COST_DECIMALS")
+            .ok()
+            .map(|value| value.parse::<u32>())
+            .transpose()
+            .context("Invalid COST_DECIMALS value")?
+            .unwrap_or(DEFAULT_COST_DECIMALS);
+        if cost_decimals > MAX_COST_DECIMALS {
+            anyhow::bail!(
+                "Invalid COST_DECIMALS value: {} exceeds the maximum of {}",
+                cost_decimals,
+                MAX_COST_DECIMALS
+            );
+        }
+
+        let audit_log = std::env::var("AUDIT_LOG")
+            .ok()
+            .and_then(|value| value.parse::<bool>().ok())
+            .unwrap_or(false);
+
+        let audit_log_body_bytes = std::env::var("AUDIT_LOG_BODY_BYTES")
+            .ok()
+            .map(|value| parse_audit_body_bytes(&value))
+            .transpose()?;
+
         Ok(Self {
             environment,
             host,
@@ -186,15 +332,102 @@ impl Config {
             metrics_enabled,
             metrics_port,
             cors_allow_origin,
+            request_timeout_secs,
+            max_request_bytes,
             enable_debug_routes,
             enable_metrics_export,
+            maintenance_mode,
+            model_defaults,
+            default_model,
+            default_temperature,
+            max_conversation_messages,
+            conversation_token_budget,
+            log_format,
+            allowed_models,
+            cost_decimals,
+            audit_log,
+            audit_log_body_bytes,
+        })
+    }
+
+    /// Get the audit log settings, or `None` if audit logging is off
+    pub fn audit_log_config(&self) -> Option<AuditLogConfig> {
+        self.audit_log.then(|| AuditLogConfig {
+            log_bodies: self.audit_log_body_bytes.is_some(),
+            max_body_bytes: self
+                .audit_log_body_bytes
+                .unwrap_or(DEFAULT_AUDIT_BODY_BYTES),
         })
     }
 
+    /// Create the per-conversation token budget tracker for the configured limit
+    pub fn conversation_budgets(&self) -> ConversationBudgets {
+        ConversationBudgets::new(self.conversation_token_budget)
+    }
+
+    /// Install the global tracing subscriber for this configuration
+    pub fn init_tracing(&self) -> Result<()> {
+        crate::telemetry::init_tracing(self.log_format, &self.rust_log)
+            .context("Failed to initialize tracing")
+    }
+
     /// Get the server address
     pub fn server_addr(&self) -> String {
         format!("{}:{}", self.host, self.port)
     }
+
+    /// Get the request timeout
+    pub fn request_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.request_timeout_secs)
+    }
+}
+
+/// Parse `AUDIT_LOG_BODY_BYTES`; set without a value, bodies are logged at the default size
+fn parse_audit_body_bytes(value: &str) -> Result<usize> {
+    if value.trim().is_empty() {
+        return Ok(DEFAULT_AUDIT_BODY_BYTES);
+    }
+    value
+        .trim()
+        .parse::<usize>()
+        .context("Invalid AUDIT_LOG_BODY_BYTES value")
+}
+
+impl std::fmt::Debug for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Config")
+            .field("environment", &self.environment)
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("openai_api_key", &Redacted)
+            .field("qdrant_url", &self.qdrant_url)
+            .field(
+                "qdrant_api_key",
+                &self.qdrant_api_key.as_ref().map(|_| Redacted),
+            )
+            .field("sled_path", &self.sled_path)
+            .field("rust_log", &self.rust_log)
+            .field("rust_backtrace", &self.rust_backtrace)
+            .field("metrics_enabled", &self.metrics_enabled)
+            .field("metrics_port", &self.metrics_port)
+            .field("cors_allow_origin", &self.cors_allow_origin)
+            .field("request_timeout_secs", &self.request_timeout_secs)
+            .field("max_request_bytes", &self.max_request_bytes)
+            .field("enable_debug_routes", &self.enable_debug_routes)
+            .field("enable_metrics_export", &self.enable_metrics_export)
+            .field("maintenance_mode", &self.maintenance_mode)
+            .field("model_defaults", &self.model_defaults)
+            .field("default_model", &self.default_model)
+            .field("default_temperature", &self.default_temperature)
+            .field("max_conversation_messages", &self.max_conversation_messages)
+            .field("conversation_token_budget", &self.conversation_token_budget)
+            .field("log_format", &self.log_format)
+            .field("allowed_models", &self.allowed_models)
+            .field("cost_decimals", &self.cost_decimals)
+            .field("audit_log", &self.audit_log)
+            .field("audit_log_body_bytes", &self.audit_log_body_bytes)
+            .finish()
+    }
 }
 
 #[cfg(test)]
@@ -264,9 +497,8 @@ mod tests {
         env::remove_var("SLED_PATH");
     }
 
-    #[test]
-    fn test_config_server_addr() {
-        let config = Config {
+    fn test_config() -> Config {
+        Config {
             environment: Environment::Development,
             host: "127.0.0.1".to_string(),
             port: 8080,
@@ -279,10 +511,75 @@ mod tests {
             metrics_enabled: true,
             metrics_port: 9090,
             cors_allow_origin: "*".to_string(),
+            request_timeout_secs: 30,
+            max_request_bytes: DEFAULT_MAX_REQUEST_BYTES,
             enable_debug_routes: true,
             enable_metrics_export: true,
-        };
+            maintenance_mode: false,
+            model_defaults: HashMap::new(),
+            default_model: DEFAULT_MODEL.to_string(),
+            default_temperature: None,
+            max_conversation_messages: None,
+            conversation_token_budget: DEFAULT_CONVERSATION_TOKEN_BUDGET,
+            log_format: LogFormat::Text,
+            allowed_models: Vec::new(),
+            cost_decimals: DEFAULT_COST_DECIMALS,
+            audit_log: false,
+            audit_log_body_bytes: None,
+        }
+    }
+
+    #[test]
+    fn test_config_server_addr() {
+        let config = test_config();
 
         assert_eq!(config.server_addr(), "127.0.0.1:8080");
     }
+
+    #[test]
+    fn test_conversation_budgets_use_configured_limit() {
+        let config = Config {
+            conversation_token_budget: 1_000,
+            ..test_config()
+        };
+
+        assert_eq!(config.conversation_budgets().max_tokens(), 1_000);
+    }
+
+    #[test]
+    fn test_audit_body_bytes_rejects_invalid_values() {
+        assert_eq!(parse_audit_body_bytes("512").unwrap(), 512);
+        assert_eq!(
+            parse_audit_body_bytes("").unwrap(),
+            DEFAULT_AUDIT_BODY_BYTES
+        );
+
+        let err = parse_audit_body_bytes("garbage").unwrap_err();
+        assert!(err.to_string().contains("AUDIT_LOG_BODY_BYTES"));
+    }
+
+    #[test]
+    fn test_config_debug_redacts_secrets() {
+        let config = Config {
+            openai_api_key: Secret::new("sk-openai-secret-value".to_string()),
+            qdrant_api_key: Some(Secret::new("qdrant-secret-value".to_string())),
+            ..test_config()
+        };
+
+        let debug = format!("{:?}", config);
+        assert!(debug.contains("openai_api_key: [REDACTED]"), "{}", debug);
+        assert!(
+            debug.contains("qdrant_api_key: Some([REDACTED])"),
+            "{}",
+            debug
+        );
+        assert!(!debug.contains("sk-openai-secret-value"));
+        assert!(!debug.contains("qdrant-secret-value"));
+        // Everything else is still shown
+        assert!(debug.contains("host: \"127.0.0.1\""));
+        assert!(debug.contains("port: 8080"));
+
+        let debug = format!("{:?}", test_config());
+        assert!(debug.contains("qdrant_api_key: None"));
+    }
 }
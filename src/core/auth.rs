@@ -1,9 +1,15 @@
 // Authentication and authorization domain types
 // Pure domain logic with no external I/O dependencies
 
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use std::fmt;
 
+/// Maximum age, in either direction, of a signed request's timestamp in seconds
+pub const SIGNATURE_TOLERANCE_SECS: i64 = 300;
+
 /// API key identifier (NewType pattern for type safety)
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -113,7 +119,10 @@ pub enum AuthResult {
 }
 
 /// Authorization level for API keys
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Levels are ordered `Read < Write < Admin` by declaration order; a key may do
+/// anything a lower level may do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum AuthLevel {
     /// Read-only access
@@ -125,20 +134,144 @@ pub enum AuthLevel {
 }
 
 impl AuthLevel {
+    /// Check if this auth level grants everything `required` grants
+    pub fn satisfies(&self, required: AuthLevel) -> bool {
+        *self >= required
+    }
+
     /// Check if this auth level can perform a given action
     pub fn can_read(&self) -> bool {
-        matches!(self, AuthLevel::Read | AuthLevel::Write | AuthLevel::Admin)
+        self.satisfies(AuthLevel::Read)
     }
 
     /// Check if this auth level can write
     pub fn can_write(&self) -> bool {
-        matches!(self, AuthLevel::Write | AuthLevel::Admin)
+        self.satisfies(AuthLevel::Write)
     }
 
     /// Check if this auth level has admin privileges
     pub fn is_admin(&self) -> bool {
-        matches!(self, AuthLevel::Admin)
+        self.satisfies(AuthLevel::Admin)
+    }
+
+    /// Get the lowercase name of this level, as used in serialized form
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthLevel::Read => "read",
+            AuthLevel::Write => "write",
+            AuthLevel::Admin => "admin",
+        }
+    }
+}
+
+/// HMAC-SHA256 keyed with a per-key signing secret
+type HmacSha256 = Hmac<Sha256>;
+
+/// Build the MAC over a signed request: `<timestamp>.<METHOD>.<path>.<body>`
+///
+/// # Returns
+/// * `Ok(HmacSha256)` - MAC over the request, ready to finalize or verify
+/// * `Err(String)` - Reason the signing secret cannot key the MAC
+fn signature_mac(
+    secret: &str,
+    timestamp: i64,
+    method: &str,
+    path: &str,
+    body: &[u8],
+) -> Result<HmacSha256, String> {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .map_err(|e| format!("Invalid signing secret: {}", e))?;
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(method.to_ascii_uppercase().as_bytes());
+    mac.update(b".");
+    mac.update(path.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    Ok(mac)
+}
+
+/// Compute the signature a client sends for a request
+///
+/// # Arguments
+/// * `secret` - Signing secret of the API key
+/// * `timestamp` - Unix timestamp (seconds) sent alongside the signature
+/// * `method` - HTTP method, e.g. `POST` (case-insensitive)
+/// * `path` - Request path including any query string, e.g. `/v1/chat/completions`
+/// * `body` - Raw request body
+///
+/// # Returns
+/// * `Ok(String)` - Lowercase hex-encoded HMAC-SHA256 of `<timestamp>.<METHOD>.<path>.<body>`
+/// * `Err(String)` - Reason the signature cannot be computed
+pub fn compute_signature(
+    secret: &str,
+    timestamp: i64,
+    method: &str,
+    path: &str,
+    body: &[u8],
+) -> Result<String, String> {
+    Ok(signature_mac(secret, timestamp, method, path, body)?
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Verify a request signature
+///
+/// # Arguments
+/// * `secret` - Signing secret of the API key
+/// * `timestamp` - Unix timestamp (seconds) the client signed
+/// * `method` - HTTP method of the request
+/// * `path` - Request path including any query string
+/// * `body` - Raw request body
+/// * `signature` - Hex-encoded signature supplied by the client
+/// * `now` - Current time, against which the timestamp is checked
+///
+/// # Returns
+/// * `Ok(())` - Signature matches and the timestamp is within `SIGNATURE_TOLERANCE_SECS`
+/// * `Err(String)` - Reason the signature was rejected
+///
+/// # Note
+/// The comparison is constant-time. Rejecting stale timestamps prevents replaying a
+/// captured request after the tolerance window has passed, and signing the method and
+/// path prevents replaying its body against a different endpoint within the window.
+pub fn verify_signature(
+    secret: &str,
+    timestamp: i64,
+    method: &str,
+    path: &str,
+    body: &[u8],
+    signature: &str,
+    now: DateTime<Utc>,
+) -> Result<(), String> {
+    // The timestamp is client-supplied, so the difference must not overflow
+    let skew = now
+        .timestamp()
+        .checked_sub(timestamp)
+        .and_then(i64::checked_abs)
+        .ok_or_else(|| "Signature timestamp is out of range".to_string())?;
+    if skew > SIGNATURE_TOLERANCE_SECS {
+        return Err("Signature timestamp is outside the allowed window".to_string());
+    }
+
+    let signature =
+        decode_hex(signature).ok_or_else(|| "Signature is not valid hex".to_string())?;
+    signature_mac(secret, timestamp, method, path, body)?
+        .verify_slice(&signature)
+        .map_err(|_| "Signature does not match request".to_string())
+}
+
+/// Decode a hex string, returning `None` if it is malformed
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
     }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
 }
 
 #[cfg(test)]
@@ -225,9 +358,122 @@ mod tests {
         assert!(AuthLevel::Admin.is_admin());
     }
 
+    #[test]
+    fn test_auth_level_ordering() {
+        assert!(AuthLevel::Read < AuthLevel::Write);
+        assert!(AuthLevel::Write < AuthLevel::Admin);
+        assert_eq!(
+            [AuthLevel::Admin, AuthLevel::Read, AuthLevel::Write]
+                .into_iter()
+                .max(),
+            Some(AuthLevel::Admin)
+        );
+    }
+
+    #[test]
+    fn test_auth_level_satisfies() {
+        let levels = [AuthLevel::Read, AuthLevel::Write, AuthLevel::Admin];
+        for (held_rank, held) in levels.iter().enumerate() {
+            for (required_rank, required) in levels.iter().enumerate() {
+                assert_eq!(
+                    held.satisfies(*required),
+                    held_rank >= required_rank,
+                    "{:?} satisfies {:?}",
+                    held,
+                    required
+                );
+            }
+        }
+        assert!(AuthLevel::Admin.satisfies(AuthLevel::Read));
+        assert!(AuthLevel::Write.satisfies(AuthLevel::Read));
+        assert!(!AuthLevel::Read.satisfies(AuthLevel::Write));
+        assert!(!AuthLevel::Write.satisfies(AuthLevel::Admin));
+    }
+
     #[test]
     fn test_api_key_id_display() {
         let key_id = ApiKeyId::new("test-key-123".to_string());
         assert_eq!(format!("{}", key_id), "test-key-123");
     }
+
+    #[test]
+    fn test_verify_signature_accepts_valid_signature() {
+        let now = Utc::now();
+        let body = br#"{"messages":[]}"#;
+        let path = "/v1/chat/completions";
+        let signature = compute_signature("secret", now.timestamp(), "POST", path, body).unwrap();
+
+        assert_eq!(signature.len(), 64);
+        assert!(verify_signature(
+            "secret",
+            now.timestamp(),
+            "POST",
+            path,
+            body,
+            &signature,
+            now
+        )
+        .is_ok());
+        // Clock skew within the tolerance is accepted
+        let skewed = now + chrono::Duration::seconds(SIGNATURE_TOLERANCE_SECS);
+        assert!(verify_signature(
+            "secret",
+            now.timestamp(),
+            "post",
+            path,
+            body,
+            &signature,
+            skewed
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_tampering() {
+        let now = Utc::now();
+        let timestamp = now.timestamp();
+        let signature = compute_signature("secret", timestamp, "POST", "/a", b"original").unwrap();
+        let verify = |secret, timestamp, method, path, body: &[u8], signature: &str| {
+            verify_signature(secret, timestamp, method, path, body, signature, now)
+        };
+
+        assert!(verify("secret", timestamp, "POST", "/a", b"tampered", &signature).is_err());
+        assert!(verify("other", timestamp, "POST", "/a", b"original", &signature).is_err());
+        assert!(verify(
+            "secret",
+            timestamp - 1,
+            "POST",
+            "/a",
+            b"original",
+            &signature
+        )
+        .is_err());
+        assert!(verify("secret", timestamp, "POST", "/a", b"original", "not-hex").is_err());
+        // A captured signature cannot be replayed against another endpoint or method
+        assert!(verify("secret", timestamp, "POST", "/b", b"original", &signature).is_err());
+        assert!(verify("secret", timestamp, "PUT", "/a", b"original", &signature).is_err());
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_stale_timestamp() {
+        let now = Utc::now();
+        let timestamp = now.timestamp() - SIGNATURE_TOLERANCE_SECS - 1;
+        let signature = compute_signature("secret", timestamp, "POST", "/", b"body").unwrap();
+
+        let err = verify_signature("secret", timestamp, "POST", "/", b"body", &signature, now)
+            .unwrap_err();
+        assert!(err.contains("window"));
+        // Timestamps too far in the future are rejected as well
+        let future = now.timestamp() + SIGNATURE_TOLERANCE_SECS + 1;
+        let signature = compute_signature("secret", future, "POST", "/", b"body").unwrap();
+        assert!(verify_signature("secret", future, "POST", "/", b"body", &signature, now).is_err());
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_extreme_timestamps_without_overflow() {
+        let now = Utc::now();
+        for timestamp in [i64::MIN, i64::MAX] {
+            assert!(verify_signature("secret", timestamp, "POST", "/", b"", "00", now).is_err());
+        }
+    }
 }
@@ -0,0 +1,53 @@
+// Time source port
+// Lets handlers read the current time through a trait so tests can pin it
+
+use chrono::{DateTime, Utc};
+
+/// Source of the current time
+pub trait Clock: Send + Sync {
+    /// Get the current time
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Clock backed by the system wall clock
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock that always reports the same instant, for deterministic tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_clock_never_advances() {
+        let instant = DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let clock = FixedClock(instant);
+
+        assert_eq!(clock.now(), instant);
+        assert_eq!(clock.now(), instant);
+    }
+
+    #[test]
+    fn test_system_clock_tracks_wall_clock() {
+        let before = Utc::now();
+        let now = SystemClock.now();
+        assert!(before <= now && now <= Utc::now());
+    }
+}
@@ -1,7 +1,7 @@
 // Domain-specific errors using thiserror
 // This will be fully implemented in Phase 1, Item 3
 
-use crate::core::types::AgentState;
+use crate::core::types::{AgentState, ConversationId};
 use thiserror::Error;
 
 /// Sentinel domain errors.
@@ -51,6 +51,54 @@ pub enum SentinelError {
         /// Reason why the API key format is invalid
         reason: String,
     },
+
+    /// Conversation token budget exhausted
+    #[error("Conversation {conversation_id} exceeded its token budget: {used} >= {limit}")]
+    BudgetExceeded {
+        /// Conversation that exhausted its budget
+        conversation_id: ConversationId,
+        /// Tokens consumed so far
+        used: u64,
+        /// Maximum tokens allowed for the conversation
+        limit: u64,
+    },
+
+    /// Embedding provider output does not match the vector collection's dimension
+    #[error(
+        "Embedding dimension mismatch: the vector collection expects {expected} dimensions but the embedding provider produces {actual}. Reindex the collection with the current embedding model, or configure a model that produces {expected}-dimensional embeddings"
+    )]
+    EmbeddingDimensionMismatch {
+        /// Dimension the vector collection was created with
+        expected: usize,
+        /// Dimension produced by the embedding provider
+        actual: usize,
+    },
+
+    /// Persistent storage is corrupt or in an unreadable format
+    #[error("Storage at {path} is corrupt: {reason}")]
+    StorageCorrupted {
+        /// Location of the corrupt storage
+        path: String,
+        /// Underlying error reported by the storage engine
+        reason: String,
+    },
+
+    /// An upstream provider (LLM API, vector database) failed or returned an unusable
+    /// response
+    #[error("Provider {provider} failed: {detail}")]
+    ProviderError {
+        /// Name of the failing provider (e.g. "openai", "qdrant")
+        provider: String,
+        /// What went wrong, as reported by or about the provider
+        detail: String,
+    },
+
+    /// Requested resource does not exist
+    #[error("{resource} not found")]
+    NotFound {
+        /// Description of the missing resource (e.g. "Agent <id>")
+        resource: String,
+    },
 }
 
 #[cfg(test)]
@@ -164,6 +212,57 @@ mod tests {
         assert!(error.to_string().contains("Key too short"));
     }
 
+    #[test]
+    fn test_budget_exceeded_error() {
+        let error = SentinelError::BudgetExceeded {
+            conversation_id: ConversationId::new("conv-1".to_string()),
+            used: 120,
+            limit: 100,
+        };
+
+        match &error {
+            SentinelError::BudgetExceeded {
+                conversation_id,
+                used,
+                limit,
+            } => {
+                assert_eq!(conversation_id.as_str(), "conv-1");
+                assert_eq!(*used, 120);
+                assert_eq!(*limit, 100);
+            }
+            _ => panic!("Expected BudgetExceeded"),
+        }
+
+        assert!(error.to_string().contains("conv-1"));
+        assert!(error.to_string().contains("token budget"));
+    }
+
+    #[test]
+    fn test_embedding_dimension_mismatch_error() {
+        let error = SentinelError::EmbeddingDimensionMismatch {
+            expected: 1536,
+            actual: 384,
+        };
+
+        let display = error.to_string();
+        assert!(display.contains("expects 1536"));
+        assert!(display.contains("produces 384"));
+        assert!(display.contains("Reindex"));
+    }
+
+    #[test]
+    fn test_storage_corrupted_error() {
+        let error = SentinelError::StorageCorrupted {
+            path: "./data/sled".to_string(),
+            reason: "unsupported format".to_string(),
+        };
+
+        let display = error.to_string();
+        assert!(display.contains("./data/sled"));
+        assert!(display.contains("corrupt"));
+        assert!(display.contains("unsupported format"));
+    }
+
     #[test]
     fn test_error_implements_error_trait() {
         let error = SentinelError::InvalidMessage {
@@ -225,6 +324,17 @@ mod tests {
             SentinelError::InvalidApiKeyFormat {
                 reason: "Key too short".to_string(),
             },
+            SentinelError::EmbeddingDimensionMismatch {
+                expected: 1536,
+                actual: 384,
+            },
+            SentinelError::ProviderError {
+                provider: "openai".to_string(),
+                detail: "connection reset".to_string(),
+            },
+            SentinelError::NotFound {
+                resource: "Agent 42".to_string(),
+            },
         ];
 
         for error in errors {
@@ -233,4 +343,26 @@ mod tests {
             assert!(display.len() > 5); // Should have meaningful content
         }
     }
+
+    #[test]
+    fn test_not_found_error() {
+        let error = SentinelError::NotFound {
+            resource: "Agent 42".to_string(),
+        };
+
+        assert_eq!(error.to_string(), "Agent 42 not found");
+    }
+
+    #[test]
+    fn test_provider_error() {
+        let error = SentinelError::ProviderError {
+            provider: "qdrant".to_string(),
+            detail: "failed to search vectors: unavailable".to_string(),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Provider qdrant failed: failed to search vectors: unavailable"
+        );
+    }
 }
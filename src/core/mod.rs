@@ -1,5 +1,7 @@
 pub mod auth;
+pub mod clock;
 pub mod error;
+pub mod precision;
 pub mod traits;
 pub mod types;
 
@@ -7,4 +9,4 @@ pub mod types;
 pub use auth::{ApiKey, ApiKeyId, AuthLevel, AuthResult};
 pub use error::SentinelError;
 pub use traits::{LLMProvider, VectorStore};
-pub use types::{AgentId, AgentState, CanonicalMessage, MessageId, Role};
+pub use types::{AgentId, AgentState, CanonicalMessage, ConversationId, MessageId, Role};
@@ -0,0 +1,44 @@
+// Rounding for floating-point report values
+// Keeps cost figures derived from token usage at a stable number of decimal places
+
+/// Default number of decimal places for cost values
+pub const DEFAULT_COST_DECIMALS: u32 = 6;
+
+/// Largest supported precision; `f64` cannot represent more decimal places reliably
+pub const MAX_COST_DECIMALS: u32 = 15;
+
+/// Round a value half away from zero to a number of decimal places
+///
+/// # Arguments
+/// * `value` - Value to round; non-finite values are returned unchanged
+/// * `decimals` - Decimal places to keep; clamped to `MAX_COST_DECIMALS`
+pub fn round_to_decimals(value: f64, decimals: u32) -> f64 {
+    if !value.is_finite() {
+        return value;
+    }
+    let factor = 10f64.powi(decimals.min(MAX_COST_DECIMALS) as i32);
+    (value * factor).round() / factor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_to_decimals() {
+        assert_eq!(round_to_decimals(0.1 + 0.2, 6), 0.3);
+        assert_eq!(round_to_decimals(1.234_567_5, 6), 1.234_568);
+        assert_eq!(round_to_decimals(-0.000_000_4, 6), -0.0);
+        assert_eq!(round_to_decimals(2.5, 0), 3.0);
+        assert!(round_to_decimals(f64::NAN, 6).is_nan());
+    }
+
+    #[test]
+    fn test_round_to_decimals_clamps_precision() {
+        let value = 1.0 / 3.0;
+        assert_eq!(
+            round_to_decimals(value, MAX_COST_DECIMALS + 5),
+            round_to_decimals(value, MAX_COST_DECIMALS)
+        );
+    }
+}
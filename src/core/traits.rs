@@ -3,7 +3,7 @@
 // All traits use async-trait for async methods and must be mockable with mockall.
 
 use crate::core::error::SentinelError;
-use crate::core::types::{CanonicalMessage, MessageId};
+use crate::core::types::{CanonicalMessage, CompletionParams, MessageId};
 use async_trait::async_trait;
 use std::collections::HashMap;
 
@@ -24,6 +24,28 @@ pub trait LLMProvider: Send + Sync {
         messages: Vec<CanonicalMessage>,
     ) -> Result<CanonicalMessage, SentinelError>;
 
+    /// Complete a conversation with explicit generation parameters.
+    ///
+    /// # Arguments
+    /// * `messages` - Vector of canonical messages representing the conversation history
+    /// * `params` - Model, temperature and max tokens overrides for this completion
+    ///
+    /// # Returns
+    /// * `Ok(CanonicalMessage)` - The LLM's response as a canonical message
+    /// * `Err(SentinelError)` - Error if the completion fails
+    ///
+    /// # Note
+    /// The default implementation ignores `params` and calls `complete`.
+    /// Adapters that support per-request parameters should override this.
+    async fn complete_with_params(
+        &self,
+        messages: Vec<CanonicalMessage>,
+        params: CompletionParams,
+    ) -> Result<CanonicalMessage, SentinelError> {
+        let _ = params;
+        self.complete(messages).await
+    }
+
     /// Stream a conversation with the LLM, returning chunks of the response.
     ///
     /// # Arguments
@@ -43,6 +65,19 @@ pub trait LLMProvider: Send + Sync {
         Box<dyn futures::Stream<Item = Result<String, SentinelError>> + Send + Unpin>,
         SentinelError,
     >;
+
+    /// Check that the provider is reachable and able to serve requests.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Provider is reachable
+    /// * `Err(SentinelError)` - Provider cannot currently serve requests
+    ///
+    /// # Note
+    /// The default implementation assumes the provider is always reachable.
+    /// Adapters backed by a remote service should override this with a cheap probe.
+    async fn health_check(&self) -> Result<(), SentinelError> {
+        Ok(())
+    }
 }
 
 /// Trait for vector storage (embedding databases like Qdrant).
@@ -66,6 +101,68 @@ pub trait VectorStore: Send + Sync {
         metadata: HashMap<String, String>,
     ) -> Result<(), SentinelError>;
 
+    /// Upsert a batch of vector embeddings with metadata.
+    ///
+    /// # Arguments
+    /// * `items` - Tuples of (message ID, embedding, metadata) to store
+    ///
+    /// # Returns
+    /// * `Ok(())` - All items stored
+    /// * `Err(SentinelError)` - Error if storage fails
+    ///
+    /// # Note
+    /// The default implementation upserts items one at a time. Adapters that support
+    /// bulk writes should override this to send a single request.
+    async fn upsert_batch(
+        &self,
+        items: Vec<(MessageId, Vec<f32>, HashMap<String, String>)>,
+    ) -> Result<(), SentinelError> {
+        for (id, embedding, metadata) in items {
+            self.upsert(id, embedding, metadata).await?;
+        }
+        Ok(())
+    }
+
+    /// Search for similar vectors, restricted to points whose metadata matches exactly.
+    ///
+    /// # Arguments
+    /// * `query_embedding` - Vector of f32 values to search for
+    /// * `limit` - Maximum number of results to return
+    /// * `filters` - Optional metadata key/value pairs that must all match (e.g. `agent_id`)
+    ///
+    /// # Returns
+    /// * `Ok(Vec<(MessageId, f32)>)` - Message IDs with their similarity scores, highest first
+    /// * `Err(SentinelError)` - Error if search fails
+    ///
+    /// # Note
+    /// `None` or an empty map applies no filtering.
+    async fn search_filtered(
+        &self,
+        query_embedding: Vec<f32>,
+        limit: usize,
+        filters: Option<HashMap<String, String>>,
+    ) -> Result<Vec<(MessageId, f32)>, SentinelError>;
+
+    /// Search for similar vectors using a query embedding, returning similarity scores.
+    ///
+    /// # Arguments
+    /// * `query_embedding` - Vector of f32 values to search for
+    /// * `limit` - Maximum number of results to return
+    ///
+    /// # Returns
+    /// * `Ok(Vec<(MessageId, f32)>)` - Message IDs with their similarity scores, highest first
+    /// * `Err(SentinelError)` - Error if search fails
+    ///
+    /// # Note
+    /// The default implementation delegates to `search_filtered` without filters.
+    async fn search_scored(
+        &self,
+        query_embedding: Vec<f32>,
+        limit: usize,
+    ) -> Result<Vec<(MessageId, f32)>, SentinelError> {
+        self.search_filtered(query_embedding, limit, None).await
+    }
+
     /// Search for similar vectors using a query embedding.
     ///
     /// # Arguments
@@ -75,11 +172,87 @@ pub trait VectorStore: Send + Sync {
     /// # Returns
     /// * `Ok(Vec<MessageId>)` - Vector of message IDs matching the query, ordered by similarity
     /// * `Err(SentinelError)` - Error if search fails
+    ///
+    /// # Note
+    /// The default implementation delegates to `search_scored` and drops the scores.
     async fn search(
         &self,
         query_embedding: Vec<f32>,
         limit: usize,
-    ) -> Result<Vec<MessageId>, SentinelError>;
+    ) -> Result<Vec<MessageId>, SentinelError> {
+        let scored = self.search_scored(query_embedding, limit).await?;
+        Ok(scored.into_iter().map(|(id, _)| id).collect())
+    }
+
+    /// Check whether a point with this ID is stored.
+    ///
+    /// # Arguments
+    /// * `id` - Message ID to look up
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - `true` if a point with this ID exists
+    /// * `Err(SentinelError)` - Error if the lookup fails
+    ///
+    /// # Note
+    /// The default implementation returns `Ok(false)`, so callers fall back to upserting.
+    /// Adapters that can look points up by ID should override this.
+    async fn contains(&self, _id: MessageId) -> Result<bool, SentinelError> {
+        Ok(false)
+    }
+
+    /// Delete the vector embedding stored for a message.
+    ///
+    /// # Arguments
+    /// * `id` - Message ID whose embedding should be removed
+    ///
+    /// # Returns
+    /// * `Ok(())` - Embedding removed (or was not present)
+    /// * `Err(SentinelError)` - Error if deletion fails
+    async fn delete(&self, id: MessageId) -> Result<(), SentinelError>;
+
+    /// Dimension of the vectors this store was configured with.
+    ///
+    /// # Returns
+    /// `Some(dimension)` if the store enforces a fixed dimension, `None` if unknown
+    ///
+    /// # Note
+    /// Used to detect embedding model drift before querying. The default returns `None`.
+    fn dimension(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Trait for embedding providers that turn text into vectors.
+/// Implementations wrap embedding models (OpenAI, sentence-transformers, etc.)
+#[async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    /// Embed a piece of text.
+    ///
+    /// # Arguments
+    /// * `text` - Text to embed
+    ///
+    /// # Returns
+    /// * `Ok(Vec<f32>)` - Embedding with `dimension()` values
+    /// * `Err(SentinelError)` - Error if embedding fails
+    async fn embed(&self, text: &str) -> Result<Vec<f32>, SentinelError>;
+
+    /// Number of dimensions in the embeddings this provider produces.
+    fn dimension(&self) -> usize;
+}
+
+/// Trait for summarizers that condense a conversation into text.
+/// Implementations decide the summarization policy (extractive, LLM-generated, etc.)
+#[async_trait]
+pub trait Summarizer: Send + Sync {
+    /// Summarize a sequence of messages.
+    ///
+    /// # Arguments
+    /// * `messages` - Messages to summarize, oldest first
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Summary text
+    /// * `Err(SentinelError)` - Error if summarization fails
+    async fn summarize(&self, messages: &[CanonicalMessage]) -> Result<String, SentinelError>;
 }
 
 #[cfg(test)]
@@ -120,11 +293,26 @@ mod tests {
                 metadata: HashMap<String, String>,
             ) -> Result<(), SentinelError>;
 
+            async fn search_filtered(
+                &self,
+                query_embedding: Vec<f32>,
+                limit: usize,
+                filters: Option<HashMap<String, String>>,
+            ) -> Result<Vec<(MessageId, f32)>, SentinelError>;
+
+            async fn search_scored(
+                &self,
+                query_embedding: Vec<f32>,
+                limit: usize,
+            ) -> Result<Vec<(MessageId, f32)>, SentinelError>;
+
             async fn search(
                 &self,
                 query_embedding: Vec<f32>,
                 limit: usize,
             ) -> Result<Vec<MessageId>, SentinelError>;
+
+            async fn delete(&self, id: MessageId) -> Result<(), SentinelError>;
         }
     }
 
@@ -146,6 +334,30 @@ mod tests {
         assert_eq!(result.content, "Hi there!");
     }
 
+    #[tokio::test]
+    async fn test_llm_provider_complete_with_params_default_delegates_to_complete() {
+        let mut mock_llm = MockLLMProvider::new();
+        mock_llm
+            .expect_complete()
+            .times(1)
+            .returning(|_| Ok(CanonicalMessage::new(Role::Assistant, "ok".to_string())));
+
+        let params = CompletionParams {
+            model: Some("gpt-4o".to_string()),
+            temperature: Some(0.2),
+            max_tokens: Some(16),
+        };
+        let result = mock_llm
+            .complete_with_params(
+                vec![CanonicalMessage::new(Role::User, "Hello".to_string())],
+                params,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "ok");
+    }
+
     #[tokio::test]
     async fn test_vector_store_upsert() {
         let mut mock_store = MockVectorStore::new();
@@ -167,6 +379,23 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_vector_store_upsert_batch_default_delegates_to_upsert() {
+        let mut mock_store = MockVectorStore::new();
+        let items: Vec<_> = (0..3)
+            .map(|_| (MessageId::new(), vec![0.1, 0.2, 0.3], HashMap::new()))
+            .collect();
+
+        mock_store
+            .expect_upsert()
+            .times(3)
+            .returning(|_, _, _| Ok(()));
+
+        let result = mock_store.upsert_batch(items).await;
+
+        assert!(result.is_ok());
+    }
+
     #[tokio::test]
     async fn test_vector_store_search() {
         let mut mock_store = MockVectorStore::new();
@@ -184,4 +413,61 @@ mod tests {
 
         assert_eq!(result.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_vector_store_search_scored() {
+        let mut mock_store = MockVectorStore::new();
+        let first = MessageId::new();
+        let second = MessageId::new();
+        let expected = vec![(first, 0.9), (second, 0.4)];
+
+        mock_store
+            .expect_search_scored()
+            .with(eq(vec![0.1, 0.2, 0.3]), eq(5))
+            .times(1)
+            .returning(move |_, _| Ok(expected.clone()));
+
+        let result = mock_store
+            .search_scored(vec![0.1, 0.2, 0.3], 5)
+            .await
+            .unwrap();
+
+        assert_eq!(result, vec![(first, 0.9), (second, 0.4)]);
+    }
+
+    #[tokio::test]
+    async fn test_vector_store_search_filtered() {
+        let mut mock_store = MockVectorStore::new();
+        let agent_memory = MessageId::new();
+        let filters = HashMap::from([("agent_id".to_string(), "agent-1".to_string())]);
+
+        mock_store
+            .expect_search_filtered()
+            .with(eq(vec![0.1, 0.2, 0.3]), eq(5), eq(Some(filters.clone())))
+            .times(1)
+            .returning(move |_, _, _| Ok(vec![(agent_memory, 0.8)]));
+
+        let result = mock_store
+            .search_filtered(vec![0.1, 0.2, 0.3], 5, Some(filters))
+            .await
+            .unwrap();
+
+        assert_eq!(result, vec![(agent_memory, 0.8)]);
+    }
+
+    #[tokio::test]
+    async fn test_vector_store_delete() {
+        let mut mock_store = MockVectorStore::new();
+        let message_id = MessageId::new();
+
+        mock_store
+            .expect_delete()
+            .with(eq(message_id))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let result = mock_store.delete(message_id).await;
+
+        assert!(result.is_ok());
+    }
 }
@@ -2,6 +2,7 @@
 // These are immutable contracts that define the domain model.
 // Frontend must adhere to these types when interacting with the backend.
 
+use crate::core::auth::{ApiKeyId, AuthLevel};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -80,6 +81,35 @@ impl std::fmt::Display for AgentId {
     }
 }
 
+/// Identifier for a conversation (client-supplied, NewType pattern)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct ConversationId(pub String);
+
+impl ConversationId {
+    /// Create a new ConversationId
+    pub fn new(id: String) -> Self {
+        Self(id)
+    }
+
+    /// Get the identifier as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for ConversationId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl std::fmt::Display for ConversationId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Role of a message participant
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
@@ -93,6 +123,16 @@ pub enum Role {
     System,
 }
 
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Role::User => write!(f, "user"),
+            Role::Assistant => write!(f, "assistant"),
+            Role::System => write!(f, "system"),
+        }
+    }
+}
+
 /// Agent state in the state machine
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
@@ -106,9 +146,20 @@ pub enum AgentState {
     ToolCall,
     /// Agent is reflecting on results
     Reflecting,
+    /// Agent hit an unrecoverable error; it stays here until explicitly reset to Idle
+    Failed,
 }
 
 impl AgentState {
+    /// Every state, in declaration order
+    pub const ALL: [AgentState; 5] = [
+        AgentState::Idle,
+        AgentState::Thinking,
+        AgentState::ToolCall,
+        AgentState::Reflecting,
+        AgentState::Failed,
+    ];
+
     /// Validate if a state transition is allowed
     ///
     /// # Arguments
@@ -124,8 +175,14 @@ impl AgentState {
     /// - ToolCall → Reflecting (after tool execution)
     /// - Reflecting → Idle (after reflection complete)
     /// - Idle → Idle (self-loop allowed)
+    /// - Any state except Failed → Failed (on unrecoverable error)
+    /// - Failed → Idle (explicit reset)
     pub fn can_transition_to(&self, next: AgentState) -> bool {
         match (self, next) {
+            // Failure is reachable from everywhere, but only a reset leaves it
+            (AgentState::Failed, AgentState::Idle) => true,
+            (AgentState::Failed, _) => false,
+            (_, AgentState::Failed) => true,
             // Valid transitions
             (AgentState::Idle, AgentState::Thinking) => true,
             (AgentState::Idle, AgentState::Idle) => true, // Self-loop allowed
@@ -144,10 +201,15 @@ impl AgentState {
     /// Vector of all valid states that can be transitioned to from the current state
     pub fn valid_next_states(&self) -> Vec<AgentState> {
         match self {
-            AgentState::Idle => vec![AgentState::Idle, AgentState::Thinking],
-            AgentState::Thinking => vec![AgentState::ToolCall, AgentState::Reflecting],
-            AgentState::ToolCall => vec![AgentState::Reflecting],
-            AgentState::Reflecting => vec![AgentState::Idle],
+            AgentState::Idle => vec![AgentState::Idle, AgentState::Thinking, AgentState::Failed],
+            AgentState::Thinking => vec![
+                AgentState::ToolCall,
+                AgentState::Reflecting,
+                AgentState::Failed,
+            ],
+            AgentState::ToolCall => vec![AgentState::Reflecting, AgentState::Failed],
+            AgentState::Reflecting => vec![AgentState::Idle, AgentState::Failed],
+            AgentState::Failed => vec![AgentState::Idle],
         }
     }
 
@@ -174,6 +236,19 @@ impl AgentState {
     }
 }
 
+/// Default upper bound on the total byte size of a metadata map accepted for storage
+pub const DEFAULT_MAX_METADATA_BYTES: usize = 64 * 1024;
+
+/// Total byte size of a metadata map (sum of UTF-8 key and value lengths)
+///
+/// Used to reject pathologically large maps before they are written to Sled or Qdrant.
+pub fn metadata_byte_size(metadata: &HashMap<String, String>) -> usize {
+    metadata
+        .iter()
+        .map(|(key, value)| key.len() + value.len())
+        .sum()
+}
+
 /// Canonical message format - pure domain type with no external dependencies
 /// This is the immutable contract for all message communication
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
@@ -224,6 +299,47 @@ impl CanonicalMessage {
             metadata,
         }
     }
+
+    /// Validate message content for safe storage and downstream processing
+    ///
+    /// # Arguments
+    /// * `strict` - Reject code points that break Sled keys and downstream tools
+    ///
+    /// # Returns
+    /// * `Ok(())` - Content is acceptable
+    /// * `Err(SentinelError)` - InvalidMessage naming the first offending code point
+    ///
+    /// # Note
+    /// In strict mode, null bytes, control characters other than tab/newline/carriage
+    /// return, Unicode noncharacters, and U+FFFD (left behind when lone surrogates are
+    /// decoded lossily) are rejected. Non-strict mode accepts any valid UTF-8.
+    pub fn validate_content(&self, strict: bool) -> Result<(), crate::core::error::SentinelError> {
+        if !strict {
+            return Ok(());
+        }
+        match self
+            .content
+            .char_indices()
+            .find(|(_, c)| is_disallowed_strict_char(*c))
+        {
+            Some((offset, c)) => Err(crate::core::error::SentinelError::InvalidMessage {
+                reason: format!(
+                    "Content contains disallowed character U+{:04X} at byte offset {}",
+                    c as u32, offset
+                ),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Whether a character is rejected by strict content validation
+fn is_disallowed_strict_char(c: char) -> bool {
+    let code = c as u32;
+    (c.is_control() && !matches!(c, '\t' | '\n' | '\r'))
+        || c == char::REPLACEMENT_CHARACTER
+        || (0xFDD0..=0xFDEF).contains(&code)
+        || (code & 0xFFFE) == 0xFFFE
 }
 
 /// Health status response
@@ -267,6 +383,45 @@ pub struct ChatCompletionRequest {
     /// Stream responses
     #[serde(default)]
     pub stream: bool,
+    /// Conversation this request belongs to (enables per-conversation token budgets)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conversation_id: Option<ConversationId>,
+    /// Server-side session whose history is prepended and extended by this request;
+    /// send only new messages when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Default sampling parameters for a model
+///
+/// Used to fill parameters a chat request leaves unset; client-supplied values always win.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ModelParams {
+    /// Default temperature for sampling (0.0 to 2.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Default maximum tokens to generate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+}
+
+/// Chat model used when neither a request nor a provider's configuration names one
+pub const DEFAULT_MODEL: &str = "gpt-4o-mini";
+
+/// Metadata key on a provider's reply naming the model that actually generated it
+pub const MODEL_METADATA_KEY: &str = "model";
+
+/// Generation parameters passed to an LLM provider with a completion
+///
+/// Unset fields fall back to the provider's own configuration.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompletionParams {
+    /// Model to use instead of the provider's configured model
+    pub model: Option<String>,
+    /// Temperature for sampling (0.0 to 2.0)
+    pub temperature: Option<f64>,
+    /// Maximum tokens to generate
+    pub max_tokens: Option<u32>,
 }
 
 /// Chat completion response (API contract)
@@ -279,6 +434,9 @@ pub struct ChatCompletionResponse {
     /// Number of tokens used
     #[serde(skip_serializing_if = "Option::is_none")]
     pub usage: Option<TokenUsage>,
+    /// Session the exchange was recorded in, echoed from the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
 }
 
 /// Token usage information
@@ -305,6 +463,105 @@ pub struct AgentStatus {
     pub messages_processed: u64,
 }
 
+/// Aggregate health of all agents managed by the supervisor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct SupervisorHealth {
+    /// Number of managed agents
+    pub total_agents: usize,
+    /// Agents whose task is still running
+    pub alive: usize,
+    /// Agents stuck without activity past the zombie timeout
+    pub zombies: usize,
+    /// Agents in the Failed state awaiting a reset
+    pub failed: usize,
+    /// Number of agents in each state (every state is present, possibly with 0)
+    pub states: HashMap<AgentState, usize>,
+}
+
+/// Optional request body for spawning an agent
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct SpawnAgentRequest {
+    /// Unique human-readable name usable in place of the agent ID in paths
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Response returned after spawning an agent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct SpawnAgentResponse {
+    /// Identifier of the newly spawned agent
+    pub id: AgentId,
+    /// Human-readable name of the agent, if one was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Optional request body for terminating agents in bulk
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TerminateAgentsRequest {
+    /// Only terminate agents whose name starts with this prefix; all agents when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_prefix: Option<String>,
+    /// Maximum time to wait for agents to stop, in milliseconds; capped to the server
+    /// request timeout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline_ms: Option<u64>,
+}
+
+/// Result of a bulk agent termination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TerminateAgentsResponse {
+    /// Number of agents that stopped within the deadline
+    pub terminated: usize,
+    /// Number of agents that errored or had to be aborted
+    pub failed: usize,
+}
+
+/// Metadata of a stored API key (never includes the secret)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyInfo {
+    /// Identifier of the key
+    #[schema(value_type = String)]
+    pub key_id: ApiKeyId,
+    /// Authorization level granted by the key
+    #[schema(value_type = String, example = "read")]
+    pub auth_level: AuthLevel,
+    /// When the key stops validating, if it expires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Request body for creating an API key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    /// Identifier for the new key (alphanumeric, hyphens and underscores)
+    #[schema(value_type = String)]
+    pub key_id: ApiKeyId,
+    /// Authorization level to grant
+    #[schema(value_type = String, example = "write")]
+    pub auth_level: AuthLevel,
+    /// When the key stops validating; never expires when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Response returned after creating an API key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct CreateApiKeyResponse {
+    /// Generated secret; it is only returned here and cannot be retrieved later
+    pub key: String,
+    /// Metadata of the new key
+    #[serde(flatten)]
+    pub info: ApiKeyInfo,
+}
+
+/// Maintenance mode state, used both to toggle and to report it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct MaintenanceMode {
+    /// Whether write and admin routes are rejected with `503`
+    pub enabled: bool,
+}
+
 /// Error response format (API contract)
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
@@ -356,25 +613,76 @@ mod tests {
         assert!(!AgentState::Reflecting.can_transition_to(AgentState::ToolCall));
     }
 
+    #[test]
+    fn test_failed_state_transitions() {
+        // Any other state → Failed
+        for state in [
+            AgentState::Idle,
+            AgentState::Thinking,
+            AgentState::ToolCall,
+            AgentState::Reflecting,
+        ] {
+            assert!(state.can_transition_to(AgentState::Failed), "{:?}", state);
+        }
+        // Failed → Idle (explicit reset) only
+        assert!(AgentState::Failed.can_transition_to(AgentState::Idle));
+        for state in [
+            AgentState::Thinking,
+            AgentState::ToolCall,
+            AgentState::Reflecting,
+            AgentState::Failed,
+        ] {
+            assert!(!AgentState::Failed.can_transition_to(state), "{:?}", state);
+        }
+        assert!(matches!(
+            AgentState::Failed.transition_to(AgentState::Thinking),
+            Err(SentinelError::InvalidStateTransition { .. })
+        ));
+    }
+
     #[test]
     fn test_valid_next_states() {
         let idle_states = AgentState::Idle.valid_next_states();
-        assert_eq!(idle_states.len(), 2);
+        assert_eq!(idle_states.len(), 3);
         assert!(idle_states.contains(&AgentState::Idle));
         assert!(idle_states.contains(&AgentState::Thinking));
+        assert!(idle_states.contains(&AgentState::Failed));
 
         let thinking_states = AgentState::Thinking.valid_next_states();
-        assert_eq!(thinking_states.len(), 2);
+        assert_eq!(thinking_states.len(), 3);
         assert!(thinking_states.contains(&AgentState::ToolCall));
         assert!(thinking_states.contains(&AgentState::Reflecting));
+        assert!(thinking_states.contains(&AgentState::Failed));
 
         let toolcall_states = AgentState::ToolCall.valid_next_states();
-        assert_eq!(toolcall_states.len(), 1);
-        assert_eq!(toolcall_states[0], AgentState::Reflecting);
+        assert_eq!(
+            toolcall_states,
+            vec![AgentState::Reflecting, AgentState::Failed]
+        );
 
         let reflecting_states = AgentState::Reflecting.valid_next_states();
-        assert_eq!(reflecting_states.len(), 1);
-        assert_eq!(reflecting_states[0], AgentState::Idle);
+        assert_eq!(
+            reflecting_states,
+            vec![AgentState::Idle, AgentState::Failed]
+        );
+
+        let failed_states = AgentState::Failed.valid_next_states();
+        assert_eq!(failed_states, vec![AgentState::Idle]);
+    }
+
+    #[test]
+    fn test_valid_next_states_match_can_transition_to() {
+        for from in AgentState::ALL {
+            for to in AgentState::ALL {
+                assert_eq!(
+                    from.can_transition_to(to),
+                    from.valid_next_states().contains(&to),
+                    "{:?} -> {:?}",
+                    from,
+                    to
+                );
+            }
+        }
     }
 
     #[test]
@@ -447,4 +755,57 @@ mod tests {
         state = state.transition_to(AgentState::Idle).unwrap();
         assert_eq!(state, AgentState::Idle);
     }
+
+    #[test]
+    fn test_strict_content_rejects_null_bytes() {
+        let message = CanonicalMessage::new(Role::User, "hello\0world".to_string());
+
+        match message.validate_content(true).unwrap_err() {
+            SentinelError::InvalidMessage { reason } => {
+                assert!(reason.contains("U+0000"));
+                assert!(reason.contains("byte offset 5"));
+            }
+            other => panic!("Expected InvalidMessage, got {:?}", other),
+        }
+
+        // Non-strict mode accepts any valid UTF-8
+        assert!(message.validate_content(false).is_ok());
+    }
+
+    #[test]
+    fn test_strict_content_rejects_problematic_code_points() {
+        for content in [
+            "bell\u{7}",
+            "c1\u{85}control",
+            "lossy\u{FFFD}surrogate",
+            "non\u{FFFF}character",
+            "non\u{FDD0}character",
+            "plane\u{1FFFE}nonchar",
+        ] {
+            let message = CanonicalMessage::new(Role::User, content.to_string());
+            assert!(
+                message.validate_content(true).is_err(),
+                "expected {:?} to be rejected",
+                content
+            );
+        }
+    }
+
+    #[test]
+    fn test_strict_content_accepts_valid_unicode() {
+        for content in [
+            "plain ascii",
+            "tabs\tand\nnewlines\r\n",
+            "emoji 🦀🚀👩‍💻",
+            "日本語とالعربية",
+            "combining e\u{301}",
+        ] {
+            let message = CanonicalMessage::new(Role::User, content.to_string());
+            assert!(
+                message.validate_content(true).is_ok(),
+                "expected {:?} to be accepted",
+                content
+            );
+        }
+    }
 }
@@ -1,12 +1,38 @@
 // Actor event loop implementation for The Sentinel (orchestrator)
 // Manages state transitions, message processing, and coordination
 
-use crate::core::types::{AgentId, AgentState, CanonicalMessage, Role};
+use crate::core::types::{AgentId, AgentState};
 use crate::engine::channels::{create_actor_channel, ActorMessage, DEFAULT_CHANNEL_SIZE};
 use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::sync::watch;
-use tracing::{debug, error, info};
+use tokio::task::{Id as TaskId, JoinSet};
+use tracing::{debug, error, info, warn};
+
+/// Default number of messages an actor processes at once (strictly sequential)
+pub const DEFAULT_MAX_CONCURRENCY: usize = 1;
+
+/// Default time a processor may spend on one message before it is abandoned (2 minutes)
+pub const DEFAULT_PROCESSING_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Work performed by an actor for each received message
+#[async_trait]
+pub trait MessageProcessor: Send + Sync {
+    /// Process a single message on behalf of an agent
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent processing the message
+    /// * `msg` - The message to process
+    ///
+    /// # Returns
+    /// * `Ok(())` - Message processed
+    /// * `Err(anyhow::Error)` - Processing failed (logged by the actor)
+    async fn process(&self, agent_id: AgentId, msg: ActorMessage) -> Result<()>;
+}
 
 /// Actor structure for The Sentinel orchestrator
 pub struct Actor {
@@ -14,10 +40,127 @@ pub struct Actor {
     pub id: AgentId,
     /// Current state of the actor
     pub state: AgentState,
+    /// Publishes every state change to observers such as the supervisor
+    state_tx: watch::Sender<AgentState>,
+    /// Publishes the number of dequeued messages whose processing has not finished
+    in_flight_tx: watch::Sender<usize>,
     /// Receiver channel for incoming messages
     rx: mpsc::Receiver<ActorMessage>,
     /// Shutdown signal receiver
     shutdown_rx: watch::Receiver<()>,
+    /// Optional signal asking a failed actor to reset to Idle
+    reset_rx: Option<watch::Receiver<()>>,
+    /// Optional processor invoked for every message
+    processor: Option<Arc<dyn MessageProcessor>>,
+    /// Maximum number of messages processed at once
+    max_concurrency: usize,
+    /// Deadline for the processor to finish a single message
+    processing_timeout: Duration,
+}
+
+/// In-flight and queued work for an actor running in concurrent mode
+///
+/// Messages sharing a conversation ID run one at a time in arrival order;
+/// messages without one share a single lane.
+#[derive(Default)]
+struct ConversationLanes {
+    /// Spawned processing tasks
+    in_flight: JoinSet<()>,
+    /// Conversation lane of each spawned task
+    task_lanes: HashMap<TaskId, Option<String>>,
+    /// Lanes with a message currently being processed
+    busy: HashSet<Option<String>>,
+    /// Messages waiting for their lane to become free
+    pending: HashMap<Option<String>, VecDeque<ActorMessage>>,
+    /// Total number of messages in `pending`
+    queued: usize,
+}
+
+impl ConversationLanes {
+    /// Number of accepted messages not yet finished (in flight or queued)
+    fn outstanding(&self) -> usize {
+        self.in_flight.len() + self.queued
+    }
+
+    /// Start a message, or queue it behind the in-flight message of its conversation
+    fn submit(
+        &mut self,
+        agent_id: AgentId,
+        processor: &Option<Arc<dyn MessageProcessor>>,
+        processing_timeout: Duration,
+        msg: ActorMessage,
+    ) {
+        let lane = msg.conversation_id().map(str::to_string);
+        if self.busy.contains(&lane) {
+            self.pending.entry(lane).or_default().push_back(msg);
+            self.queued += 1;
+        } else {
+            self.busy.insert(lane.clone());
+            self.spawn(agent_id, processor, processing_timeout, lane, msg);
+        }
+    }
+
+    /// Mark a task's lane as free and start the next queued message for it, if any
+    fn complete(
+        &mut self,
+        task_id: TaskId,
+        agent_id: AgentId,
+        processor: &Option<Arc<dyn MessageProcessor>>,
+        processing_timeout: Duration,
+    ) {
+        let Some(lane) = self.task_lanes.remove(&task_id) else {
+            return;
+        };
+        match self.pending.get_mut(&lane).and_then(VecDeque::pop_front) {
+            Some(next) => {
+                self.queued -= 1;
+                self.spawn(agent_id, processor, processing_timeout, lane, next);
+            }
+            None => {
+                self.pending.remove(&lane);
+                self.busy.remove(&lane);
+            }
+        }
+    }
+
+    /// Spawn a processing task for a message in the given lane
+    fn spawn(
+        &mut self,
+        agent_id: AgentId,
+        processor: &Option<Arc<dyn MessageProcessor>>,
+        processing_timeout: Duration,
+        lane: Option<String>,
+        msg: ActorMessage,
+    ) {
+        let processor = processor.clone();
+        let handle = self.in_flight.spawn(async move {
+            if let Some(processor) = processor {
+                if let Err(e) =
+                    process_with_timeout(processor.as_ref(), agent_id, msg, processing_timeout)
+                        .await
+                {
+                    error!("Actor {} error processing message: {:#}", agent_id, e);
+                }
+            }
+        });
+        self.task_lanes.insert(handle.id(), lane);
+    }
+}
+
+/// Run a processor on one message, failing if it exceeds `processing_timeout`
+///
+/// On timeout the processing future is dropped, so the actor's event loop is never
+/// blocked by a hung processor.
+async fn process_with_timeout(
+    processor: &dyn MessageProcessor,
+    agent_id: AgentId,
+    msg: ActorMessage,
+    processing_timeout: Duration,
+) -> Result<()> {
+    match tokio::time::timeout(processing_timeout, processor.process(agent_id, msg)).await {
+        Ok(result) => result,
+        Err(_) => anyhow::bail!("Processing did not finish within {:?}", processing_timeout),
+    }
 }
 
 impl Actor {
@@ -35,11 +178,76 @@ impl Actor {
         Self {
             id,
             state: AgentState::Idle,
+            state_tx: watch::Sender::new(AgentState::Idle),
+            in_flight_tx: watch::Sender::new(0),
             rx,
             shutdown_rx,
+            reset_rx: None,
+            processor: None,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            processing_timeout: DEFAULT_PROCESSING_TIMEOUT,
         }
     }
 
+    /// Invoke `processor` for every message this actor receives
+    pub fn with_processor(mut self, processor: Arc<dyn MessageProcessor>) -> Self {
+        self.processor = Some(processor);
+        self
+    }
+
+    /// Allow up to `max_concurrency` messages to be processed at once
+    ///
+    /// # Note
+    /// Values above 1 enable concurrent mode, where ordering is only preserved within
+    /// a conversation and the state machine is relaxed to Thinking (work in flight)
+    /// and Idle (drained); processing errors are logged per message and never move the
+    /// actor to Failed. A value of 0 is treated as 1.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Abandon a message whose processor runs longer than `processing_timeout`
+    ///
+    /// # Note
+    /// A timeout counts as a processing error: in sequential mode the actor moves to
+    /// Failed, in concurrent mode it is logged for that message only.
+    pub fn with_processing_timeout(mut self, processing_timeout: Duration) -> Self {
+        self.processing_timeout = processing_timeout;
+        self
+    }
+
+    /// Reset the actor from Failed to Idle whenever `reset_rx` is signalled
+    ///
+    /// # Note
+    /// Only sequential actors can fail, so concurrent actors ignore the signal. A signal
+    /// received while the actor is not Failed is logged and has no effect.
+    pub fn with_reset_signal(mut self, reset_rx: watch::Receiver<()>) -> Self {
+        self.reset_rx = Some(reset_rx);
+        self
+    }
+
+    /// Subscribe to the actor's state; the receiver always holds the latest state
+    pub fn subscribe_state(&self) -> watch::Receiver<AgentState> {
+        self.state_tx.subscribe()
+    }
+
+    /// Subscribe to the number of messages the actor has dequeued but not finished
+    ///
+    /// # Note
+    /// Unlike the state, which stays Thinking or Reflecting between messages in
+    /// sequential mode, this is 0 whenever the actor is not working on a message.
+    /// In concurrent mode it also counts messages queued behind their conversation.
+    pub fn subscribe_in_flight(&self) -> watch::Receiver<usize> {
+        self.in_flight_tx.subscribe()
+    }
+
+    /// Change the state and publish it to subscribers
+    fn set_state(&mut self, state: AgentState) {
+        self.state = state;
+        self.state_tx.send_replace(state);
+    }
+
     /// Run the actor event loop
     ///
     /// This is the main event loop that processes messages and manages state transitions.
@@ -49,6 +257,10 @@ impl Actor {
     /// * `Ok(())` - Graceful shutdown
     /// * `Err(anyhow::Error)` - Error during processing
     pub async fn run(&mut self) -> Result<()> {
+        if self.max_concurrency > 1 {
+            return self.run_concurrent().await;
+        }
+
         info!("Actor {} started in state {:?}", self.id, self.state);
 
         loop {
@@ -58,14 +270,18 @@ impl Actor {
                     match msg {
                         Some(actor_msg) => {
                             debug!("Actor {} received message", self.id);
-                            match self.process_message(actor_msg).await {
+                            self.in_flight_tx.send_replace(1);
+                            let result = self.process_message(actor_msg).await;
+                            // Cleared before the new state is published, so observers of the
+                            // state already see the message as finished
+                            self.in_flight_tx.send_replace(0);
+                            match result {
                                 Ok(new_state) => {
-                                    self.state = new_state;
+                                    self.set_state(new_state);
                                     debug!("Actor {} transitioned to state {:?}", self.id, self.state);
                                 }
                                 Err(e) => {
-                                    error!("Actor {} error processing message: {}", self.id, e);
-                                    // Continue processing despite errors
+                                    warn!("Actor {} rejected message: {:#}", self.id, e);
                                 }
                             }
                         }
@@ -75,9 +291,23 @@ impl Actor {
                         }
                     }
                 }
+                // Handle reset signal
+                signal = reset_signalled(&mut self.reset_rx) => {
+                    match signal {
+                        Ok(()) => {
+                            if let Err(e) = self.reset() {
+                                warn!("Actor {} ignored reset signal: {:#}", self.id, e);
+                            }
+                        }
+                        // The sender is gone; no reset can arrive any more
+                        Err(_) => self.reset_rx = None,
+                    }
+                }
                 // Handle shutdown signal
                 _ = self.shutdown_rx.changed() => {
                     info!("Actor {} received shutdown signal", self.id);
+                    // Reject further sends immediately instead of queuing them unprocessed
+                    self.rx.close();
                     break;
                 }
             }
@@ -87,15 +317,85 @@ impl Actor {
         Ok(())
     }
 
+    /// Run the event loop with up to `max_concurrency` messages in flight
+    ///
+    /// New messages are only received while fewer than `max_concurrency` are
+    /// outstanding, so channel backpressure still applies. Closing the channel drains
+    /// all accepted messages; a shutdown signal waits for in-flight work and drops
+    /// queued messages.
+    async fn run_concurrent(&mut self) -> Result<()> {
+        info!(
+            "Actor {} started in state {:?} with max concurrency {}",
+            self.id, self.state, self.max_concurrency
+        );
+
+        let mut lanes = ConversationLanes::default();
+        let mut channel_open = true;
+
+        while channel_open || !lanes.in_flight.is_empty() {
+            tokio::select! {
+                msg = self.rx.recv(), if channel_open && lanes.outstanding() < self.max_concurrency => {
+                    match msg {
+                        Some(actor_msg) => {
+                            debug!("Actor {} received message", self.id);
+                            lanes.submit(self.id, &self.processor, self.processing_timeout, actor_msg);
+                        }
+                        None => {
+                            info!("Actor {} channel closed, draining in-flight messages", self.id);
+                            channel_open = false;
+                        }
+                    }
+                }
+                Some(joined) = lanes.in_flight.join_next_with_id() => {
+                    let task_id = match joined {
+                        Ok((task_id, ())) => task_id,
+                        Err(e) => {
+                            error!("Actor {} processing task failed: {}", self.id, e);
+                            e.id()
+                        }
+                    };
+                    lanes.complete(task_id, self.id, &self.processor, self.processing_timeout);
+                }
+                _ = self.shutdown_rx.changed() => {
+                    info!("Actor {} received shutdown signal", self.id);
+                    self.rx.close();
+                    if lanes.queued > 0 {
+                        warn!("Actor {} dropping {} queued messages on shutdown", self.id, lanes.queued);
+                    }
+                    while lanes.in_flight.join_next().await.is_some() {}
+                    break;
+                }
+            }
+
+            // Relaxed state machine: Thinking while work is in flight, Idle once drained
+            let state = if lanes.in_flight.is_empty() {
+                AgentState::Idle
+            } else {
+                AgentState::Thinking
+            };
+            if state != self.state {
+                self.set_state(state);
+            }
+            self.in_flight_tx.send_replace(lanes.outstanding());
+        }
+
+        self.set_state(AgentState::Idle);
+        self.in_flight_tx.send_replace(0);
+        info!("Actor {} stopped", self.id);
+        Ok(())
+    }
+
     /// Process a single message and determine the next state
     ///
     /// # Arguments
-    /// * `_msg` - The actor message to process
+    /// * `msg` - The actor message to process
     ///
     /// # Returns
-    /// * `Ok(AgentState)` - The new state after processing
-    /// * `Err(anyhow::Error)` - Error during processing
-    async fn process_message(&self, _msg: ActorMessage) -> Result<AgentState> {
+    /// * `Ok(AgentState)` - The new state after processing; `Failed` if the processor
+    ///   returned an error or did not finish within the processing timeout
+    /// * `Err(anyhow::Error)` - The actor is `Failed` (the message is not processed
+    ///   until it is reset) or the state transition was invalid
+    async fn process_message(&self, msg: ActorMessage) -> Result<AgentState> {
         let current_state = self.state;
         let next_state = match current_state {
             AgentState::Idle => {
@@ -128,8 +428,27 @@ impl Actor {
                 );
                 AgentState::Idle
             }
+            AgentState::Failed => {
+                anyhow::bail!("Actor {} is in Failed state and must be reset", self.id);
+            }
         };
 
+        if let Some(processor) = &self.processor {
+            if let Err(e) =
+                process_with_timeout(processor.as_ref(), self.id, msg, self.processing_timeout)
+                    .await
+            {
+                error!(
+                    "Actor {} error processing message, transitioning to Failed: {:#}",
+                    self.id, e
+                );
+                return current_state
+                    .transition_to(AgentState::Failed)
+                    .map_err(|e| anyhow::anyhow!("State transition error: {}", e))
+                    .context("Failed to transition state");
+            }
+        }
+
         // Validate the state transition
         current_state
             .transition_to(next_state)
@@ -139,6 +458,28 @@ impl Actor {
         Ok(next_state)
     }
 
+    /// Reset a failed actor back to Idle so it processes messages again
+    ///
+    /// # Returns
+    /// * `Ok(())` - The actor is Idle
+    /// * `Err(anyhow::Error)` - The actor is not in the Failed state
+    pub fn reset(&mut self) -> Result<()> {
+        if self.state != AgentState::Failed {
+            anyhow::bail!(
+                "Actor {} is in {:?} state; only a failed actor can be reset",
+                self.id,
+                self.state
+            );
+        }
+        let state = self
+            .state
+            .transition_to(AgentState::Idle)
+            .map_err(|e| anyhow::anyhow!("State transition error: {}", e))?;
+        self.set_state(state);
+        info!("Actor {} reset to Idle", self.id);
+        Ok(())
+    }
+
     /// Get the current state of the actor
     pub fn current_state(&self) -> AgentState {
         self.state
@@ -150,10 +491,22 @@ impl Actor {
     }
 }
 
+/// Wait for a reset signal; never completes when the actor has no reset signal
+async fn reset_signalled(
+    reset_rx: &mut Option<watch::Receiver<()>>,
+) -> Result<(), watch::error::RecvError> {
+    match reset_rx {
+        Some(reset_rx) => reset_rx.changed().await,
+        None => std::future::pending().await,
+    }
+}
+
 /// Spawn a new actor with a bounded channel
 ///
 /// # Arguments
+/// * `agent_id` - ID the actor passes to its processor; the supervisor's ID for the agent
 /// * `buffer_size` - Size of the message channel buffer
+/// * `processing_timeout` - Deadline for processing a single message
 ///
 /// # Returns
 /// Tuple of (sender, shutdown_tx, join_handle)
@@ -161,42 +514,142 @@ impl Actor {
 /// * `shutdown_tx` - Shutdown signal sender
 /// * `join_handle` - Task join handle for awaiting completion
 pub fn spawn_actor(
+    agent_id: AgentId,
     buffer_size: usize,
+    processing_timeout: Duration,
 ) -> (
     mpsc::Sender<ActorMessage>,
     watch::Sender<()>,
     tokio::task::JoinHandle<Result<()>>,
 ) {
-    let agent_id = AgentId::new();
     let (tx, rx) = create_actor_channel(buffer_size);
     let (shutdown_tx, shutdown_rx) = watch::channel(());
 
-    let mut actor = Actor::new(agent_id, rx, shutdown_rx);
+    let mut actor =
+        Actor::new(agent_id, rx, shutdown_rx).with_processing_timeout(processing_timeout);
 
     let handle = tokio::spawn(async move { actor.run().await });
 
     (tx, shutdown_tx, handle)
 }
 
+/// Spawn a new actor that processes up to `max_concurrency` messages at once
+///
+/// # Arguments
+/// * `agent_id` - ID the actor passes to its processor
+/// * `buffer_size` - Size of the message channel buffer
+/// * `max_concurrency` - Maximum number of messages in flight (1 = sequential)
+/// * `processing_timeout` - Deadline for processing a single message
+/// * `processor` - Optional processor invoked for every message
+///
+/// # Returns
+/// Tuple of (sender, shutdown_tx, join_handle), as for `spawn_actor`
+pub fn spawn_actor_with_concurrency(
+    agent_id: AgentId,
+    buffer_size: usize,
+    max_concurrency: usize,
+    processing_timeout: Duration,
+    processor: Option<Arc<dyn MessageProcessor>>,
+) -> (
+    mpsc::Sender<ActorMessage>,
+    watch::Sender<()>,
+    tokio::task::JoinHandle<Result<()>>,
+) {
+    let actor = spawn_actor_with_state(
+        agent_id,
+        buffer_size,
+        max_concurrency,
+        processing_timeout,
+        processor,
+    );
+    (actor.tx, actor.shutdown_tx, actor.handle)
+}
+
+/// Control handles of an actor spawned by [`spawn_actor_with_state`]
+pub struct SpawnedActor {
+    /// Channel sender for sending messages to the actor
+    pub tx: mpsc::Sender<ActorMessage>,
+    /// Shutdown signal sender
+    pub shutdown_tx: watch::Sender<()>,
+    /// Reset signal sender; signalling it returns a failed actor to Idle
+    pub reset_tx: watch::Sender<()>,
+    /// Receiver holding the actor's current state, updated after every transition;
+    /// it keeps the last state once the actor has stopped
+    pub state_rx: watch::Receiver<AgentState>,
+    /// Receiver holding the number of messages being processed (see
+    /// [`Actor::subscribe_in_flight`])
+    pub in_flight_rx: watch::Receiver<usize>,
+    /// Task join handle for awaiting completion
+    pub handle: tokio::task::JoinHandle<Result<()>>,
+}
+
+/// Spawn a new actor that can be observed and reset
+///
+/// # Arguments
+/// * `agent_id` - ID the actor passes to its processor
+/// * `buffer_size` - Size of the message channel buffer
+/// * `max_concurrency` - Maximum number of messages in flight (1 = sequential)
+/// * `processing_timeout` - Deadline for processing a single message
+/// * `processor` - Optional processor invoked for every message
+///
+/// # Returns
+/// The actor's message, shutdown, reset and state handles
+pub fn spawn_actor_with_state(
+    agent_id: AgentId,
+    buffer_size: usize,
+    max_concurrency: usize,
+    processing_timeout: Duration,
+    processor: Option<Arc<dyn MessageProcessor>>,
+) -> SpawnedActor {
+    let (tx, rx) = create_actor_channel(buffer_size);
+    let (shutdown_tx, shutdown_rx) = watch::channel(());
+    let (reset_tx, reset_rx) = watch::channel(());
+
+    let mut actor = Actor::new(agent_id, rx, shutdown_rx)
+        .with_max_concurrency(max_concurrency)
+        .with_processing_timeout(processing_timeout)
+        .with_reset_signal(reset_rx);
+    if let Some(processor) = processor {
+        actor = actor.with_processor(processor);
+    }
+    let state_rx = actor.subscribe_state();
+    let in_flight_rx = actor.subscribe_in_flight();
+
+    let handle = tokio::spawn(async move { actor.run().await });
+
+    SpawnedActor {
+        tx,
+        shutdown_tx,
+        reset_tx,
+        state_rx,
+        in_flight_rx,
+        handle,
+    }
+}
+
 /// Spawn a new actor with default channel size
-pub fn spawn_default_actor() -> (
+pub fn spawn_default_actor(
+    agent_id: AgentId,
+) -> (
     mpsc::Sender<ActorMessage>,
     watch::Sender<()>,
     tokio::task::JoinHandle<Result<()>>,
 ) {
-    spawn_actor(DEFAULT_CHANNEL_SIZE)
+    spawn_actor(agent_id, DEFAULT_CHANNEL_SIZE, DEFAULT_PROCESSING_TIMEOUT)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::engine::channels::ActorMessage;
+    use crate::core::types::{CanonicalMessage, Role};
+    use crate::engine::channels::{try_send_with_timeout, ActorMessage, AgentSendError};
     use std::time::Duration;
     use tokio::time::timeout;
 
     #[tokio::test]
     async fn test_actor_spawns_and_receives_messages() {
-        let (tx, _shutdown_tx, handle) = spawn_actor(10);
+        let (tx, _shutdown_tx, handle) =
+            spawn_actor(AgentId::new(), 10, DEFAULT_PROCESSING_TIMEOUT);
 
         let msg = ActorMessage::new(CanonicalMessage::new(Role::User, "test".to_string()));
         tx.send(msg).await.unwrap();
@@ -215,7 +668,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_actor_state_transitions() {
-        let (tx, _shutdown_tx, handle) = spawn_actor(10);
+        let (tx, _shutdown_tx, handle) =
+            spawn_actor(AgentId::new(), 10, DEFAULT_PROCESSING_TIMEOUT);
 
         // Send a message to trigger state transition from Idle to Thinking
         let msg1 = ActorMessage::new(CanonicalMessage::new(Role::User, "msg1".to_string()));
@@ -242,7 +696,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_actor_channel_closure_graceful_shutdown() {
-        let (tx, _shutdown_tx, handle) = spawn_actor(10);
+        let (tx, _shutdown_tx, handle) =
+            spawn_actor(AgentId::new(), 10, DEFAULT_PROCESSING_TIMEOUT);
 
         // Send a message
         let msg = ActorMessage::new(CanonicalMessage::new(Role::User, "test".to_string()));
@@ -261,7 +716,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_actor_shutdown_signal() {
-        let (tx, shutdown_tx, handle) = spawn_actor(10);
+        let (tx, shutdown_tx, handle) = spawn_actor(AgentId::new(), 10, DEFAULT_PROCESSING_TIMEOUT);
 
         // Send shutdown signal
         shutdown_tx.send(()).unwrap();
@@ -270,15 +725,16 @@ mod tests {
         let result = timeout(Duration::from_secs(1), handle).await;
         assert!(result.is_ok());
 
-        // Try to send a message (should fail or be ignored)
+        // Sends after shutdown fail fast with a typed error
         let msg = ActorMessage::new(CanonicalMessage::new(Role::User, "test".to_string()));
-        // Channel might still accept, but actor is shutting down
-        let _ = tx.send(msg).await;
+        let result = try_send_with_timeout(&tx, msg, Duration::from_secs(1)).await;
+        assert_eq!(result, Err(AgentSendError::Closed));
     }
 
     #[tokio::test]
     async fn test_actor_multiple_messages_processed() {
-        let (tx, _shutdown_tx, handle) = spawn_actor(10);
+        let (tx, _shutdown_tx, handle) =
+            spawn_actor(AgentId::new(), 10, DEFAULT_PROCESSING_TIMEOUT);
 
         // Send multiple messages
         for i in 0..5 {
@@ -300,7 +756,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_actor_backpressure_handling() {
-        let (tx, _shutdown_tx, handle) = spawn_actor(2);
+        let (tx, _shutdown_tx, handle) = spawn_actor(AgentId::new(), 2, DEFAULT_PROCESSING_TIMEOUT);
 
         // Fill channel to capacity
         let msg1 = ActorMessage::new(CanonicalMessage::new(Role::User, "msg1".to_string()));
@@ -329,7 +785,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_actor_with_sender_info() {
-        let (tx, _shutdown_tx, handle) = spawn_actor(10);
+        let (tx, _shutdown_tx, handle) =
+            spawn_actor(AgentId::new(), 10, DEFAULT_PROCESSING_TIMEOUT);
 
         let sender_id = AgentId::new();
         let msg = ActorMessage::with_sender(
@@ -348,7 +805,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_spawn_default_actor() {
-        let (tx, _shutdown_tx, handle) = spawn_default_actor();
+        let (tx, _shutdown_tx, handle) = spawn_default_actor(AgentId::new());
 
         let msg = ActorMessage::new(CanonicalMessage::new(Role::User, "test".to_string()));
         tx.send(msg).await.unwrap();
@@ -359,4 +816,423 @@ mod tests {
         let result = timeout(Duration::from_secs(1), handle).await;
         assert!(result.is_ok());
     }
+
+    /// Processor that fails every message whose content is "fail"
+    #[derive(Default)]
+    struct FailingProcessor {
+        processed: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl MessageProcessor for FailingProcessor {
+        async fn process(&self, _agent_id: AgentId, msg: ActorMessage) -> Result<()> {
+            self.processed
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if msg.message.content == "fail" {
+                anyhow::bail!("unrecoverable");
+            }
+            Ok(())
+        }
+    }
+
+    fn user_message(content: &str) -> ActorMessage {
+        ActorMessage::new(CanonicalMessage::new(Role::User, content.to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_processing_error_transitions_to_failed_until_reset() {
+        let processor = Arc::new(FailingProcessor::default());
+        let (_tx, rx) = mpsc::channel(1);
+        let (_shutdown_tx, shutdown_rx) = watch::channel(());
+        let mut actor =
+            Actor::new(AgentId::new(), rx, shutdown_rx).with_processor(processor.clone());
+
+        actor.state = actor.process_message(user_message("ok")).await.unwrap();
+        assert_eq!(actor.current_state(), AgentState::Thinking);
+
+        actor.state = actor.process_message(user_message("fail")).await.unwrap();
+        assert_eq!(actor.current_state(), AgentState::Failed);
+
+        // Failed actors reject messages without processing them
+        assert!(actor.process_message(user_message("ok")).await.is_err());
+        assert_eq!(
+            processor
+                .processed
+                .load(std::sync::atomic::Ordering::SeqCst),
+            2
+        );
+
+        actor.reset().unwrap();
+        assert_eq!(actor.current_state(), AgentState::Idle);
+        assert!(actor.reset().is_err());
+
+        actor.state = actor.process_message(user_message("ok")).await.unwrap();
+        assert_eq!(actor.current_state(), AgentState::Thinking);
+        assert_eq!(
+            processor
+                .processed
+                .load(std::sync::atomic::Ordering::SeqCst),
+            3
+        );
+    }
+
+    /// Processor that takes far longer than any test processing timeout
+    struct SlowProcessor;
+
+    #[async_trait]
+    impl MessageProcessor for SlowProcessor {
+        async fn process(&self, _agent_id: AgentId, _msg: ActorMessage) -> Result<()> {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_processing_timeout_transitions_to_failed() {
+        let SpawnedActor {
+            tx,
+            shutdown_tx,
+            mut state_rx,
+            handle,
+            ..
+        } = spawn_actor_with_state(
+            AgentId::new(),
+            10,
+            DEFAULT_MAX_CONCURRENCY,
+            Duration::from_millis(50),
+            Some(Arc::new(SlowProcessor)),
+        );
+
+        tx.send(user_message("hang")).await.unwrap();
+        timeout(
+            Duration::from_secs(1),
+            state_rx.wait_for(|state| *state == AgentState::Failed),
+        )
+        .await
+        .expect("processing timeout should fire well before the processor finishes")
+        .unwrap();
+
+        // The event loop is free again, so the shutdown signal is handled promptly
+        shutdown_tx.send(()).unwrap();
+        timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("actor should stop promptly")
+            .unwrap()
+            .unwrap();
+    }
+
+    /// Processor that records concurrency and per-conversation processing order
+    ///
+    /// Each message reports on `started` once it is in flight and then waits for a
+    /// permit on `gate`, so tests control overlap with channels rather than sleeps.
+    struct RecordingProcessor {
+        current: std::sync::atomic::AtomicUsize,
+        max_seen: std::sync::atomic::AtomicUsize,
+        processed: std::sync::Mutex<Vec<(String, String)>>,
+        started: mpsc::UnboundedSender<()>,
+        gate: tokio::sync::Semaphore,
+    }
+
+    impl RecordingProcessor {
+        /// Create a processor whose messages wait until permits are added to `gate`
+        fn gated() -> (Arc<Self>, mpsc::UnboundedReceiver<()>) {
+            let (started, started_rx) = mpsc::unbounded_channel();
+            let recorder = Arc::new(Self {
+                current: Default::default(),
+                max_seen: Default::default(),
+                processed: Default::default(),
+                started,
+                gate: tokio::sync::Semaphore::new(0),
+            });
+            (recorder, started_rx)
+        }
+
+        /// Let `count` more messages finish processing
+        fn release(&self, count: usize) {
+            self.gate.add_permits(count);
+        }
+    }
+
+    #[async_trait]
+    impl MessageProcessor for RecordingProcessor {
+        async fn process(&self, _agent_id: AgentId, msg: ActorMessage) -> Result<()> {
+            use std::sync::atomic::Ordering;
+
+            let now = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_seen.fetch_max(now, Ordering::SeqCst);
+            self.processed.lock().unwrap().push((
+                msg.conversation_id().unwrap_or_default().to_string(),
+                msg.message.content.clone(),
+            ));
+            let _ = self.started.send(());
+            self.gate.acquire().await?.forget();
+            self.current.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    /// Wait until `count` messages have started processing
+    async fn wait_started(started: &mut mpsc::UnboundedReceiver<()>, count: usize) {
+        for _ in 0..count {
+            timeout(Duration::from_secs(5), started.recv())
+                .await
+                .expect("message should start processing")
+                .expect("processor should be alive");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_count_tracks_processing_not_state() {
+        let (recorder, mut started) = RecordingProcessor::gated();
+        let SpawnedActor {
+            tx,
+            shutdown_tx: _shutdown_tx,
+            mut state_rx,
+            in_flight_rx,
+            ..
+        } = spawn_actor_with_state(
+            AgentId::new(),
+            16,
+            DEFAULT_MAX_CONCURRENCY,
+            DEFAULT_PROCESSING_TIMEOUT,
+            Some(recorder.clone()),
+        );
+        assert_eq!(*in_flight_rx.borrow(), 0);
+
+        tx.send(user_message("first")).await.unwrap();
+        wait_started(&mut started, 1).await;
+        assert_eq!(*in_flight_rx.borrow(), 1);
+
+        // Once the message finishes the actor stays Thinking but has nothing in flight
+        recorder.release(1);
+        timeout(
+            Duration::from_secs(1),
+            state_rx.wait_for(|state| *state == AgentState::Thinking),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(*in_flight_rx.borrow(), 0);
+
+        // Concurrent actors count every accepted message until it finishes
+        let (recorder, mut started) = RecordingProcessor::gated();
+        let SpawnedActor {
+            tx,
+            shutdown_tx: _shutdown_tx,
+            mut in_flight_rx,
+            ..
+        } = spawn_actor_with_state(
+            AgentId::new(),
+            16,
+            4,
+            DEFAULT_PROCESSING_TIMEOUT,
+            Some(recorder.clone()),
+        );
+        // Without conversation IDs the second message queues behind the first
+        for content in ["a", "b"] {
+            tx.send(user_message(content)).await.unwrap();
+        }
+        wait_started(&mut started, 1).await;
+        timeout(
+            Duration::from_secs(1),
+            in_flight_rx.wait_for(|count| *count == 2),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        recorder.release(2);
+        timeout(
+            Duration::from_secs(1),
+            in_flight_rx.wait_for(|count| *count == 0),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    }
+
+    fn conversation_message(conversation_id: &str, content: String) -> ActorMessage {
+        ActorMessage::new(CanonicalMessage::with_metadata(
+            Role::User,
+            content,
+            std::collections::HashMap::from([(
+                crate::engine::channels::CONVERSATION_ID_METADATA_KEY.to_string(),
+                conversation_id.to_string(),
+            )]),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_actor_bounds_in_flight_and_preserves_conversation_order() {
+        let (recorder, mut started) = RecordingProcessor::gated();
+        let (tx, _shutdown_tx, handle) = spawn_actor_with_concurrency(
+            AgentId::new(),
+            16,
+            3,
+            DEFAULT_PROCESSING_TIMEOUT,
+            Some(recorder.clone()),
+        );
+
+        let conversations = ["a", "b", "c", "d"];
+        for i in 0..3 {
+            for conversation in conversations {
+                tx.send(conversation_message(
+                    conversation,
+                    format!("{}-{}", conversation, i),
+                ))
+                .await
+                .unwrap();
+            }
+        }
+        drop(tx);
+
+        // Three messages are held in flight at once before any is allowed to finish
+        wait_started(&mut started, 3).await;
+        assert_eq!(
+            recorder.current.load(std::sync::atomic::Ordering::SeqCst),
+            3
+        );
+        recorder.release(12);
+
+        // Closing the channel drains every accepted message
+        timeout(Duration::from_secs(5), handle)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            recorder.max_seen.load(std::sync::atomic::Ordering::SeqCst),
+            3
+        );
+        let processed = recorder.processed.lock().unwrap();
+        assert_eq!(processed.len(), 12);
+        for conversation in conversations {
+            let order: Vec<&str> = processed
+                .iter()
+                .filter(|(id, _)| id == conversation)
+                .map(|(_, content)| content.as_str())
+                .collect();
+            let expected: Vec<String> = (0..3).map(|i| format!("{}-{}", conversation, i)).collect();
+            assert_eq!(order, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_during_shutdown_drain_fails_fast() {
+        let (recorder, mut started) = RecordingProcessor::gated();
+        let (tx, shutdown_tx, handle) = spawn_actor_with_concurrency(
+            AgentId::new(),
+            16,
+            2,
+            DEFAULT_PROCESSING_TIMEOUT,
+            Some(recorder.clone()),
+        );
+
+        tx.send(conversation_message("a", "in-flight".to_string()))
+            .await
+            .unwrap();
+        wait_started(&mut started, 1).await;
+        shutdown_tx.send(()).unwrap();
+        timeout(Duration::from_secs(1), tx.closed())
+            .await
+            .expect("shutdown should close the channel");
+
+        // The actor is still draining in-flight work but already refuses new messages
+        assert!(!handle.is_finished());
+        let result = try_send_with_timeout(
+            &tx,
+            conversation_message("a", "late".to_string()),
+            Duration::from_secs(1),
+        )
+        .await;
+        assert_eq!(result, Err(AgentSendError::Closed));
+
+        recorder.release(1);
+        timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_same_conversation_never_runs_concurrently() {
+        let (recorder, mut started) = RecordingProcessor::gated();
+        let (tx, _shutdown_tx, handle) = spawn_actor_with_concurrency(
+            AgentId::new(),
+            16,
+            4,
+            DEFAULT_PROCESSING_TIMEOUT,
+            Some(recorder.clone()),
+        );
+
+        for i in 0..4 {
+            tx.send(conversation_message("only", format!("only-{}", i)))
+                .await
+                .unwrap();
+        }
+        drop(tx);
+
+        // Each message only starts once the previous one was allowed to finish
+        for _ in 0..4 {
+            wait_started(&mut started, 1).await;
+            assert_eq!(
+                recorder.current.load(std::sync::atomic::Ordering::SeqCst),
+                1
+            );
+            recorder.release(1);
+        }
+
+        timeout(Duration::from_secs(5), handle)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            recorder.max_seen.load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+        assert_eq!(recorder.processed.lock().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_sequential_actor_invokes_processor_one_at_a_time() {
+        let (recorder, mut started) = RecordingProcessor::gated();
+        let (tx, _shutdown_tx, handle) = spawn_actor_with_concurrency(
+            AgentId::new(),
+            16,
+            1,
+            DEFAULT_PROCESSING_TIMEOUT,
+            Some(recorder.clone()),
+        );
+
+        for conversation in ["a", "b", "c"] {
+            tx.send(conversation_message(conversation, conversation.to_string()))
+                .await
+                .unwrap();
+        }
+        drop(tx);
+
+        for _ in 0..3 {
+            wait_started(&mut started, 1).await;
+            assert_eq!(
+                recorder.current.load(std::sync::atomic::Ordering::SeqCst),
+                1
+            );
+            recorder.release(1);
+        }
+
+        timeout(Duration::from_secs(5), handle)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            recorder.max_seen.load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+        assert_eq!(recorder.processed.lock().unwrap().len(), 3);
+    }
 }
@@ -2,11 +2,11 @@
 // All channels are bounded to prevent unbounded memory growth
 
 use crate::core::types::{AgentId, CanonicalMessage};
-use anyhow::Result;
+use std::fmt;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::time::timeout;
-use tracing::warn;
+use tracing::{error, warn};
 
 /// Message wrapper for actor communication
 /// Includes the canonical message and optional sender metadata
@@ -18,6 +18,9 @@ pub struct ActorMessage {
     pub sender: Option<AgentId>,
 }
 
+/// Metadata key identifying the conversation a message belongs to
+pub const CONVERSATION_ID_METADATA_KEY: &str = "conversation_id";
+
 impl ActorMessage {
     /// Create a new actor message
     pub fn new(message: CanonicalMessage) -> Self {
@@ -34,6 +37,14 @@ impl ActorMessage {
             sender: Some(sender),
         }
     }
+
+    /// Conversation this message belongs to, taken from the `conversation_id` metadata
+    pub fn conversation_id(&self) -> Option<&str> {
+        self.message
+            .metadata
+            .get(CONVERSATION_ID_METADATA_KEY)
+            .map(String::as_str)
+    }
 }
 
 impl From<CanonicalMessage> for ActorMessage {
@@ -70,6 +81,26 @@ pub fn create_default_actor_channel() -> (mpsc::Sender<ActorMessage>, mpsc::Rece
     create_actor_channel(DEFAULT_CHANNEL_SIZE)
 }
 
+/// Reason a message could not be delivered to an actor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentSendError {
+    /// The actor has shut down (or is shutting down) and no longer accepts messages
+    Closed,
+    /// The channel stayed full for the whole send timeout
+    Timeout,
+}
+
+impl fmt::Display for AgentSendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Closed => write!(f, "Agent has shut down"),
+            Self::Timeout => write!(f, "Timeout sending message"),
+        }
+    }
+}
+
+impl std::error::Error for AgentSendError {}
+
 /// Send a message with timeout handling
 ///
 /// # Arguments
@@ -78,21 +109,79 @@ pub fn create_default_actor_channel() -> (mpsc::Sender<ActorMessage>, mpsc::Rece
 /// * `timeout_duration` - Maximum time to wait for send
 ///
 /// # Returns
-/// Ok(()) if sent successfully, Err if timeout or channel closed
+/// * `Ok(())` - Message queued
+/// * `Err(AgentSendError::Closed)` - The actor has shut down; fails without waiting
+/// * `Err(AgentSendError::Timeout)` - The channel stayed full for `timeout_duration`
 pub async fn try_send_with_timeout(
     tx: &mpsc::Sender<ActorMessage>,
     msg: ActorMessage,
     timeout_duration: Duration,
-) -> Result<()> {
-    match timeout(timeout_duration, tx.send(msg)).await {
-        Ok(Ok(())) => Ok(()),
+) -> Result<(), AgentSendError> {
+    send_or_return(tx, msg, timeout_duration)
+        .await
+        .map_err(|(e, _)| e)
+}
+
+/// Send a message with timeout handling, forwarding undeliverable messages to a
+/// dead-letter queue
+///
+/// # Arguments
+/// * `tx` - Channel sender
+/// * `dlq` - Dead-letter sink receiving messages that could not be delivered; its
+///   consumer decides whether to log, persist or retry them
+/// * `msg` - Message to send
+/// * `timeout_duration` - Maximum time to wait for send
+///
+/// # Returns
+/// The same result as `try_send_with_timeout`; on `Err` the message has been handed to
+/// `dlq` if one is given and it has room (it is never waited on)
+pub async fn try_send_with_dlq(
+    tx: &mpsc::Sender<ActorMessage>,
+    dlq: Option<&mpsc::Sender<ActorMessage>>,
+    msg: ActorMessage,
+    timeout_duration: Duration,
+) -> Result<(), AgentSendError> {
+    let (e, msg) = match send_or_return(tx, msg, timeout_duration).await {
+        Ok(()) => return Ok(()),
+        Err(failed) => failed,
+    };
+
+    match dlq {
+        Some(dlq) => {
+            if let Err(dlq_error) = dlq.try_send(msg) {
+                error!(
+                    "Dropping undeliverable message ({}): dead-letter queue unavailable: {}",
+                    e, dlq_error
+                );
+            }
+        }
+        None => warn!(
+            "Dropping undeliverable message ({}): no dead-letter queue",
+            e
+        ),
+    }
+    Err(e)
+}
+
+/// Send a message, giving it back to the caller if it could not be delivered
+async fn send_or_return(
+    tx: &mpsc::Sender<ActorMessage>,
+    msg: ActorMessage,
+    timeout_duration: Duration,
+) -> Result<(), (AgentSendError, ActorMessage)> {
+    // Reserve first so the message is still ours if the wait times out
+    match timeout(timeout_duration, tx.reserve()).await {
+        Ok(Ok(permit)) => {
+            permit.send(msg);
+            Ok(())
+        }
         Ok(Err(_)) => {
-            warn!("Channel receiver dropped, cannot send message");
-            anyhow::bail!("Channel receiver dropped");
+            warn!("Channel receiver closed, cannot send message");
+            Err((AgentSendError::Closed, msg))
         }
         Err(_) => {
             warn!("Timeout sending message to channel");
-            anyhow::bail!("Timeout sending message");
+            Err((AgentSendError::Timeout, msg))
         }
     }
 }
@@ -249,7 +338,7 @@ mod tests {
         // Try to send another with short timeout (should timeout due to backpressure)
         let msg2 = ActorMessage::new(CanonicalMessage::new(Role::User, "msg2".to_string()));
         let result = try_send_with_timeout(&tx, msg2, Duration::from_millis(10)).await;
-        assert!(result.is_err());
+        assert_eq!(result, Err(AgentSendError::Timeout));
 
         // Make space and verify it works
         let _ = rx.recv().await;
@@ -264,7 +353,59 @@ mod tests {
 
         let msg = ActorMessage::new(CanonicalMessage::new(Role::User, "test".to_string()));
         let result = try_send_with_timeout(&tx, msg, Duration::from_millis(100)).await;
-        assert!(result.is_err());
+        assert_eq!(result, Err(AgentSendError::Closed));
+    }
+
+    #[tokio::test]
+    async fn test_try_send_with_dlq_forwards_when_channel_full() {
+        let (tx, mut rx) = create_actor_channel(1);
+        let (dlq_tx, mut dlq_rx) = create_actor_channel(10);
+
+        let msg1 = ActorMessage::new(CanonicalMessage::new(Role::User, "msg1".to_string()));
+        tx.send(msg1).await.unwrap();
+
+        let msg2 = ActorMessage::new(CanonicalMessage::new(Role::User, "msg2".to_string()));
+        let result =
+            try_send_with_dlq(&tx, Some(&dlq_tx), msg2.clone(), Duration::from_millis(10)).await;
+        assert_eq!(result, Err(AgentSendError::Timeout));
+
+        // The message landed in the dead-letter queue instead of vanishing
+        let dead = dlq_rx.try_recv().unwrap();
+        assert_eq!(dead.message.id, msg2.message.id);
+        assert_eq!(rx.recv().await.unwrap().message.content, "msg1");
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_try_send_with_dlq_forwards_when_channel_closed() {
+        let (tx, rx) = create_actor_channel(10);
+        let (dlq_tx, mut dlq_rx) = create_actor_channel(10);
+        drop(rx);
+
+        let msg = ActorMessage::new(CanonicalMessage::new(Role::User, "test".to_string()));
+        let result =
+            try_send_with_dlq(&tx, Some(&dlq_tx), msg.clone(), Duration::from_millis(100)).await;
+        assert_eq!(result, Err(AgentSendError::Closed));
+        assert_eq!(dlq_rx.try_recv().unwrap().message.id, msg.message.id);
+    }
+
+    #[tokio::test]
+    async fn test_try_send_with_dlq_delivers_normally() {
+        let (tx, mut rx) = create_actor_channel(10);
+        let (dlq_tx, mut dlq_rx) = create_actor_channel(10);
+
+        let msg = ActorMessage::new(CanonicalMessage::new(Role::User, "test".to_string()));
+        try_send_with_dlq(&tx, Some(&dlq_tx), msg, Duration::from_millis(100))
+            .await
+            .unwrap();
+        assert_eq!(rx.recv().await.unwrap().message.content, "test");
+        assert!(dlq_rx.try_recv().is_err());
+
+        // Without a dead-letter queue the send error is still reported
+        drop(rx);
+        let msg = ActorMessage::new(CanonicalMessage::new(Role::User, "lost".to_string()));
+        let result = try_send_with_dlq(&tx, None, msg, Duration::from_millis(100)).await;
+        assert_eq!(result, Err(AgentSendError::Closed));
     }
 
     #[tokio::test]
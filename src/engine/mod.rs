@@ -1,3 +1,5 @@
 pub mod actor;
 pub mod channels;
+pub mod shutdown;
 pub mod supervisor;
+pub mod warmup;
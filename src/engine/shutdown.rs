@@ -0,0 +1,207 @@
+// Graceful shutdown coordination
+// Signals long-running tasks one stage at a time, each after the previous stage has stopped
+
+use anyhow::{bail, Result};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tracing::{error, info, warn};
+
+/// Default time allowed for each registered task to finish after its signal
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A registered task and the sender that signals it to stop
+#[derive(Debug)]
+struct ShutdownStage {
+    /// Name used in shutdown logs and errors
+    name: String,
+    /// Sender whose change signals this task to stop
+    shutdown_tx: watch::Sender<()>,
+    /// Join handle of the task
+    handle: JoinHandle<Result<()>>,
+}
+
+/// Coordinates graceful shutdown of the server, supervisor, and dreamer loop
+///
+/// Each task is registered as its own stage and spawned with a receiver for that stage
+/// (the shape `Supervisor::run` and `MemoryManager::run_dreamer_loop` expect).
+/// `shutdown()` signals the stages in registration order, each only after the previous
+/// one has stopped or timed out, so registering the server, supervisor, and dreamer in
+/// that order tears down as stop accepting requests → drain agents → flush memory.
+#[derive(Debug, Default)]
+pub struct ShutdownCoordinator {
+    /// Registered stages, in the order they are signalled on shutdown
+    stages: Vec<ShutdownStage>,
+}
+
+impl ShutdownCoordinator {
+    /// Create a coordinator with no registered tasks
+    pub fn new() -> Self {
+        Self { stages: Vec::new() }
+    }
+
+    /// Register a task as the next shutdown stage
+    ///
+    /// # Arguments
+    /// * `name` - Name used in shutdown logs and errors
+    /// * `spawn` - Starts the task with the receiver of its stage's shutdown signal and
+    ///   returns its join handle; the task should stop once the receiver changes
+    pub fn register<F>(&mut self, name: impl Into<String>, spawn: F)
+    where
+        F: FnOnce(watch::Receiver<()>) -> JoinHandle<Result<()>>,
+    {
+        let (shutdown_tx, shutdown_rx) = watch::channel(());
+        let handle = spawn(shutdown_rx);
+        self.stages.push(ShutdownStage {
+            name: name.into(),
+            shutdown_tx,
+            handle,
+        });
+    }
+
+    /// Get the number of registered tasks
+    pub fn task_count(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Signal and await each registered task in turn
+    ///
+    /// # Arguments
+    /// * `timeout_per_stage` - Time allowed for each task to finish after its signal
+    ///
+    /// # Returns
+    /// * `Ok(())` - Every task finished within the timeout
+    /// * `Err(anyhow::Error)` - Names of the tasks that did not stop in time
+    ///
+    /// # Note
+    /// A task that misses its timeout is aborted before the next stage is signalled.
+    /// A task that finishes with an error or panics is logged but counts as finished.
+    pub async fn shutdown(self, timeout_per_stage: Duration) -> Result<()> {
+        info!(
+            "Shutting down {} task(s) (timeout per task: {:?})",
+            self.stages.len(),
+            timeout_per_stage
+        );
+
+        let mut timed_out = Vec::new();
+        for stage in self.stages {
+            let ShutdownStage {
+                name,
+                shutdown_tx,
+                mut handle,
+            } = stage;
+            // Send fails only when the receiver is gone, i.e. the task has already exited
+            let _ = shutdown_tx.send(());
+
+            match timeout(timeout_per_stage, &mut handle).await {
+                Ok(Ok(Ok(()))) => info!("Task {} stopped", name),
+                Ok(Ok(Err(e))) => error!("Task {} stopped with error: {}", name, e),
+                Ok(Err(e)) => error!("Task {} panicked or was cancelled: {}", name, e),
+                Err(_) => {
+                    warn!("Task {} did not stop before the shutdown timeout", name);
+                    handle.abort();
+                    timed_out.push(name);
+                }
+            }
+        }
+
+        if !timed_out.is_empty() {
+            bail!("Shutdown timed out waiting for: {}", timed_out.join(", "));
+        }
+        info!("Shutdown complete");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Spawn a task that waits for the shutdown signal, cleans up, then counts itself stopped
+    fn fake_task(
+        mut shutdown_rx: watch::Receiver<()>,
+        cleanup: Duration,
+        stopped: Arc<AtomicUsize>,
+    ) -> JoinHandle<Result<()>> {
+        tokio::spawn(async move {
+            let _ = shutdown_rx.changed().await;
+            tokio::time::sleep(cleanup).await;
+            stopped.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        })
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_completes_registered_tasks() {
+        let mut coordinator = ShutdownCoordinator::new();
+        let stopped = Arc::new(AtomicUsize::new(0));
+        coordinator.register("server", |shutdown_rx| {
+            fake_task(shutdown_rx, Duration::from_millis(20), stopped.clone())
+        });
+        coordinator.register("dreamer", |shutdown_rx| {
+            fake_task(shutdown_rx, Duration::from_millis(10), stopped.clone())
+        });
+        assert_eq!(coordinator.task_count(), 2);
+
+        coordinator.shutdown(Duration::from_secs(1)).await.unwrap();
+        assert_eq!(stopped.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_signals_stage_after_previous_stopped() {
+        let mut coordinator = ShutdownCoordinator::new();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        for name in ["server", "supervisor", "dreamer"] {
+            let events = events.clone();
+            coordinator.register(name, move |mut shutdown_rx| {
+                tokio::spawn(async move {
+                    let _ = shutdown_rx.changed().await;
+                    events.lock().unwrap().push(format!("{} signalled", name));
+                    tokio::task::yield_now().await;
+                    events.lock().unwrap().push(format!("{} stopped", name));
+                    Ok(())
+                })
+            });
+        }
+
+        coordinator.shutdown(Duration::from_secs(1)).await.unwrap();
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                "server signalled",
+                "server stopped",
+                "supervisor signalled",
+                "supervisor stopped",
+                "dreamer signalled",
+                "dreamer stopped",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_times_out_and_names_stuck_tasks() {
+        let mut coordinator = ShutdownCoordinator::new();
+        let stopped = Arc::new(AtomicUsize::new(0));
+        coordinator.register("stuck", |_shutdown_rx| {
+            tokio::spawn(async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok(())
+            })
+        });
+        coordinator.register("supervisor", |shutdown_rx| {
+            fake_task(shutdown_rx, Duration::ZERO, stopped.clone())
+        });
+
+        let err = coordinator
+            .shutdown(Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("stuck"));
+        assert!(!err.to_string().contains("supervisor"));
+        // A stuck stage still lets later stages shut down
+        assert_eq!(stopped.load(Ordering::SeqCst), 1);
+    }
+}
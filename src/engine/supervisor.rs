@@ -1,16 +1,22 @@
 // Supervisor for agent lifecycle management
 // Monitors agent health, detects zombies, and manages agent lifecycle
 
-use crate::core::types::{AgentId, AgentState};
-use crate::engine::actor::spawn_actor;
-use crate::engine::channels::ActorMessage;
-use anyhow::Result;
+use crate::core::error::SentinelError;
+use crate::core::types::{AgentId, AgentState, CanonicalMessage, SupervisorHealth};
+use crate::engine::actor::{
+    spawn_actor_with_state, MessageProcessor, SpawnedActor, DEFAULT_MAX_CONCURRENCY,
+    DEFAULT_PROCESSING_TIMEOUT,
+};
+use crate::engine::channels::{try_send_with_timeout, ActorMessage, DEFAULT_CHANNEL_SIZE};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
-use tokio::sync::watch;
-use tokio::time::interval;
+use tokio::sync::{watch, RwLock};
+use tokio::time::{interval, timeout};
 use tracing::{error, info, warn};
 
 /// Default health check interval (10 seconds)
@@ -19,36 +25,108 @@ pub const DEFAULT_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
 /// Default zombie timeout (60 seconds)
 pub const DEFAULT_ZOMBIE_TIMEOUT: Duration = Duration::from_secs(60);
 
+/// Maximum time a broadcast waits for room in each agent's channel
+pub const BROADCAST_SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum time a failed agent may take to acknowledge a reset
+pub const AGENT_RESET_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default time the spawn hook may take to initialize an agent
+pub const DEFAULT_SPAWN_HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum length of a human-readable agent name
+pub const MAX_AGENT_NAME_LEN: usize = 64;
+
+/// Error for an agent the supervisor does not manage
+fn agent_not_found(id: AgentId) -> SentinelError {
+    SentinelError::NotFound {
+        resource: format!("Agent {}", id),
+    }
+}
+
+/// Validate a human-readable agent name
+///
+/// Names are 1-64 characters of ASCII letters, digits, `-` and `_`, and must not
+/// parse as a UUID so they can never be confused with an agent ID in a URL path.
+///
+/// # Returns
+/// * `Ok(())` - The name is acceptable
+/// * `Err(anyhow::Error)` - Description of why the name is invalid
+pub fn validate_agent_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > MAX_AGENT_NAME_LEN {
+        anyhow::bail!(
+            "Agent name must be between 1 and {} characters",
+            MAX_AGENT_NAME_LEN
+        );
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!("Agent name may only contain ASCII letters, digits, '-' and '_'");
+    }
+    if uuid::Uuid::parse_str(name).is_ok() {
+        anyhow::bail!("Agent name must not be a UUID");
+    }
+    Ok(())
+}
+
 /// Handle for a managed agent
 pub struct AgentHandle {
     /// Channel sender for communicating with the agent
     pub tx: mpsc::Sender<ActorMessage>,
     /// Shutdown signal sender
     pub shutdown_tx: watch::Sender<()>,
+    /// Reset signal sender; returns a failed agent to Idle
+    reset_tx: watch::Sender<()>,
     /// Task join handle
     pub handle: tokio::task::JoinHandle<Result<()>>,
     /// Last activity timestamp
     pub last_activity: DateTime<Utc>,
-    /// Current agent state (best effort tracking)
-    pub state: AgentState,
+    /// State published by the agent's actor after every transition
+    state_rx: watch::Receiver<AgentState>,
+    /// Number of messages the agent's actor is currently processing
+    in_flight_rx: watch::Receiver<usize>,
+    /// Optional unique human-readable name
+    pub name: Option<String>,
 }
 
 impl AgentHandle {
     /// Create a new agent handle
-    pub fn new(
-        tx: mpsc::Sender<ActorMessage>,
-        shutdown_tx: watch::Sender<()>,
-        handle: tokio::task::JoinHandle<Result<()>>,
-    ) -> Self {
+    ///
+    /// # Arguments
+    /// * `actor` - Handles of the agent's freshly spawned actor
+    pub fn new(actor: SpawnedActor) -> Self {
+        let SpawnedActor {
+            tx,
+            shutdown_tx,
+            reset_tx,
+            state_rx,
+            in_flight_rx,
+            handle,
+        } = actor;
         Self {
             tx,
             shutdown_tx,
+            reset_tx,
             handle,
             last_activity: Utc::now(),
-            state: AgentState::Idle,
+            state_rx,
+            in_flight_rx,
+            name: None,
         }
     }
 
+    /// Get the agent's current state as last published by its actor
+    pub fn state(&self) -> AgentState {
+        *self.state_rx.borrow()
+    }
+
+    /// Get the number of messages the agent is processing right now
+    pub fn in_flight(&self) -> usize {
+        *self.in_flight_rx.borrow()
+    }
+
     /// Update the last activity timestamp
     pub fn update_activity(&mut self) {
         self.last_activity = Utc::now();
@@ -60,14 +138,44 @@ impl AgentHandle {
     }
 }
 
+/// Initialization run for each newly spawned agent before it is handed out
+#[async_trait]
+pub trait SpawnHook: Send + Sync {
+    /// Initialize a freshly spawned agent (e.g. load a persona or seed a system message)
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent that was just spawned; its actor is already running
+    ///
+    /// # Returns
+    /// * `Ok(())` - Agent initialized
+    /// * `Err(anyhow::Error)` - Initialization failed; the agent is terminated
+    async fn on_spawn(&self, agent_id: AgentId) -> Result<()>;
+}
+
 /// Supervisor for managing agent lifecycle
 pub struct Supervisor {
     /// Map of agent IDs to their handles
     agents: HashMap<AgentId, AgentHandle>,
+    /// Index of agent names to their IDs
+    names: HashMap<String, AgentId>,
     /// Interval between health checks
     health_check_interval: Duration,
     /// Timeout for zombie detection
     zombie_timeout: Duration,
+    /// Maximum number of concurrently managed agents (unlimited if `None`)
+    max_agents: Option<usize>,
+    /// Initialization run by [`Supervisor::spawn_initialized_agent`]
+    spawn_hook: Option<Arc<dyn SpawnHook>>,
+    /// Deadline for the spawn hook to initialize one agent
+    spawn_hook_timeout: Duration,
+    /// Message buffer size of each spawned agent's channel (always > 0)
+    channel_buffer: usize,
+    /// Processor invoked by spawned agents for every message
+    processor: Option<Arc<dyn MessageProcessor>>,
+    /// Deadline for a spawned agent's processor to finish one message
+    processing_timeout: Duration,
+    /// Messages a spawned agent processes at once unless spawned with its own limit
+    max_concurrency: usize,
 }
 
 impl Supervisor {
@@ -75,8 +183,16 @@ impl Supervisor {
     pub fn new() -> Self {
         Self {
             agents: HashMap::new(),
+            names: HashMap::new(),
             health_check_interval: DEFAULT_HEALTH_CHECK_INTERVAL,
             zombie_timeout: DEFAULT_ZOMBIE_TIMEOUT,
+            max_agents: None,
+            spawn_hook: None,
+            spawn_hook_timeout: DEFAULT_SPAWN_HOOK_TIMEOUT,
+            channel_buffer: DEFAULT_CHANNEL_SIZE,
+            processor: None,
+            processing_timeout: DEFAULT_PROCESSING_TIMEOUT,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
         }
     }
 
@@ -84,27 +200,283 @@ impl Supervisor {
     pub fn with_settings(health_check_interval: Duration, zombie_timeout: Duration) -> Self {
         Self {
             agents: HashMap::new(),
+            names: HashMap::new(),
             health_check_interval,
             zombie_timeout,
+            max_agents: None,
+            spawn_hook: None,
+            spawn_hook_timeout: DEFAULT_SPAWN_HOOK_TIMEOUT,
+            channel_buffer: DEFAULT_CHANNEL_SIZE,
+            processor: None,
+            processing_timeout: DEFAULT_PROCESSING_TIMEOUT,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+        }
+    }
+
+    /// Cap the number of agents this supervisor will manage at once
+    pub fn with_max_agents(mut self, max_agents: usize) -> Self {
+        self.max_agents = Some(max_agents);
+        self
+    }
+
+    /// Run `hook` for every agent spawned with [`Supervisor::spawn_initialized_agent`]
+    pub fn with_spawn_hook(mut self, hook: Arc<dyn SpawnHook>) -> Self {
+        self.spawn_hook = Some(hook);
+        self
+    }
+
+    /// Set how long the spawn hook may take before the agent is terminated
+    pub fn with_spawn_hook_timeout(mut self, spawn_hook_timeout: Duration) -> Self {
+        self.spawn_hook_timeout = spawn_hook_timeout;
+        self
+    }
+
+    /// Have agents spawned from now on invoke `processor` for every message
+    ///
+    /// A processor error moves the agent to `AgentState::Failed`.
+    pub fn with_message_processor(mut self, processor: Arc<dyn MessageProcessor>) -> Self {
+        self.processor = Some(processor);
+        self
+    }
+
+    /// Set how long agents spawned from now on may spend processing one message
+    ///
+    /// A message that exceeds the deadline is abandoned and, like a processor error,
+    /// moves a sequential agent to `AgentState::Failed`.
+    pub fn with_processing_timeout(mut self, processing_timeout: Duration) -> Self {
+        self.processing_timeout = processing_timeout;
+        self
+    }
+
+    /// Set how many messages agents spawned from now on process at once
+    ///
+    /// Messages of one conversation are still processed in order. Use
+    /// [`Supervisor::spawn_agent_with_concurrency`] to override it for one agent.
+    ///
+    /// # Arguments
+    /// * `max_concurrency` - Maximum in-flight messages per agent (1 = sequential)
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    /// Set the message buffer size of channels for agents spawned from now on
+    ///
+    /// # Arguments
+    /// * `channel_buffer` - Messages an agent can queue before senders wait; must be > 0
+    ///
+    /// # Returns
+    /// * `Ok(Supervisor)` - Supervisor using the new buffer size
+    /// * `Err(anyhow::Error)` - `channel_buffer` is 0 (`create_actor_channel` would panic)
+    pub fn with_channel_buffer(mut self, channel_buffer: usize) -> Result<Self> {
+        if channel_buffer == 0 {
+            anyhow::bail!("Channel buffer size must be greater than 0");
         }
+        self.channel_buffer = channel_buffer;
+        Ok(self)
+    }
+
+    /// Get the message buffer size of spawned agents' channels
+    pub fn channel_buffer(&self) -> usize {
+        self.channel_buffer
+    }
+
+    /// Get the maximum number of agents, if capped
+    pub fn max_agents(&self) -> Option<usize> {
+        self.max_agents
+    }
+
+    /// Check whether the supervisor has reached its agent cap
+    pub fn at_capacity(&self) -> bool {
+        self.max_agents
+            .is_some_and(|max_agents| self.agents.len() >= max_agents)
     }
 
     /// Spawn a new agent and register it with the supervisor
     ///
     /// # Returns
     /// * `Ok(AgentId)` - The ID of the newly spawned agent
-    /// * `Err(anyhow::Error)` - Error if spawning fails
+    /// * `Err(anyhow::Error)` - Error if spawning fails or the agent cap is reached
     pub fn spawn_agent(&mut self) -> Result<AgentId> {
-        let (tx, shutdown_tx, handle) = spawn_actor(32);
-        let agent_id = AgentId::new();
+        self.spawn_named_agent(None)
+    }
 
-        let agent_handle = AgentHandle::new(tx, shutdown_tx, handle);
-        self.agents.insert(agent_id, agent_handle);
+    /// Spawn a new agent with an optional unique human-readable name
+    ///
+    /// # Arguments
+    /// * `name` - Name to register for the agent (see [`validate_agent_name`])
+    ///
+    /// # Returns
+    /// * `Ok(AgentId)` - The ID of the newly spawned agent
+    /// * `Err(anyhow::Error)` - Error if the name is invalid or taken, or the agent cap is reached
+    pub fn spawn_named_agent(&mut self, name: Option<String>) -> Result<AgentId> {
+        self.check_can_spawn(name.as_deref())?;
+
+        let agent_id = AgentId::new();
+        let actor = spawn_actor_with_state(
+            agent_id,
+            self.channel_buffer,
+            self.max_concurrency,
+            self.processing_timeout,
+            self.processor.clone(),
+        );
+        self.register(agent_id, AgentHandle::new(actor), name);
 
         info!("Supervisor spawned agent {}", agent_id);
         Ok(agent_id)
     }
 
+    /// Spawn a new agent and run the spawn hook before returning its ID
+    ///
+    /// The agent ID is not handed out until the hook completes, so no traffic reaches
+    /// the agent before it is initialized. The hook runs without holding the supervisor
+    /// lock (see [`Supervisor::initialize_agent`]). Without a hook this is
+    /// `spawn_named_agent`.
+    ///
+    /// # Arguments
+    /// * `supervisor` - Supervisor to spawn the agent on
+    /// * `name` - Name to register for the agent (see [`validate_agent_name`])
+    ///
+    /// # Returns
+    /// * `Ok(AgentId)` - The ID of the newly spawned, initialized agent
+    /// * `Err(anyhow::Error)` - Error if spawning fails or the hook fails or times out;
+    ///   an agent whose hook failed is terminated
+    pub async fn spawn_initialized_agent(
+        supervisor: &RwLock<Supervisor>,
+        name: Option<String>,
+    ) -> Result<AgentId> {
+        let agent_id = supervisor.write().await.spawn_named_agent(name)?;
+        Self::initialize_agent(supervisor, agent_id).await?;
+        Ok(agent_id)
+    }
+
+    /// Run the spawn hook for an agent that was just spawned
+    ///
+    /// The supervisor lock is only held to read the hook and, if it fails, to terminate
+    /// the agent, so a slow hook never stalls other supervisor operations.
+    ///
+    /// # Arguments
+    /// * `supervisor` - Supervisor managing the agent
+    /// * `agent_id` - Agent to initialize
+    ///
+    /// # Returns
+    /// * `Ok(())` - The hook succeeded, or no hook is set
+    /// * `Err(anyhow::Error)` - The hook failed or exceeded the spawn hook timeout; the
+    ///   agent is terminated
+    pub async fn initialize_agent(
+        supervisor: &RwLock<Supervisor>,
+        agent_id: AgentId,
+    ) -> Result<()> {
+        let (hook, hook_timeout) = {
+            let supervisor = supervisor.read().await;
+            match supervisor.spawn_hook.clone() {
+                Some(hook) => (hook, supervisor.spawn_hook_timeout),
+                None => return Ok(()),
+            }
+        };
+
+        let result = match timeout(hook_timeout, hook.on_spawn(agent_id)).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow::anyhow!("timed out after {:?}", hook_timeout)),
+        };
+        if let Err(e) = result {
+            warn!("Spawn hook failed for agent {}: {:#}", agent_id, e);
+            let detached = supervisor.write().await.detach_agent(agent_id);
+            match detached {
+                Ok(agent_handle) => Self::stop_detached_agent(agent_id, agent_handle).await,
+                Err(terminate_error) => error!(
+                    "Failed to terminate agent {} after spawn hook failure: {}",
+                    agent_id, terminate_error
+                ),
+            }
+            return Err(e.context(format!("Spawn hook failed for agent {}", agent_id)));
+        }
+        Ok(())
+    }
+
+    /// Spawn a new agent that processes up to `max_concurrency` messages at once
+    ///
+    /// # Arguments
+    /// * `max_concurrency` - Maximum in-flight messages for this agent (1 = sequential)
+    ///
+    /// # Returns
+    /// * `Ok(AgentId)` - The ID of the newly spawned agent
+    /// * `Err(anyhow::Error)` - Error if spawning fails or the agent cap is reached
+    pub fn spawn_agent_with_concurrency(&mut self, max_concurrency: usize) -> Result<AgentId> {
+        self.check_can_spawn(None)?;
+
+        let agent_id = AgentId::new();
+        let actor = spawn_actor_with_state(
+            agent_id,
+            self.channel_buffer,
+            max_concurrency,
+            self.processing_timeout,
+            self.processor.clone(),
+        );
+        self.register(agent_id, AgentHandle::new(actor), None);
+
+        info!(
+            "Supervisor spawned agent {} with max concurrency {}",
+            agent_id, max_concurrency
+        );
+        Ok(agent_id)
+    }
+
+    /// Check the agent cap and, if given, the validity and uniqueness of a name
+    fn check_can_spawn(&self, name: Option<&str>) -> Result<()> {
+        if self.at_capacity() {
+            anyhow::bail!(
+                "Agent limit reached ({} agents)",
+                self.max_agents.unwrap_or_default()
+            );
+        }
+        if let Some(name) = name {
+            validate_agent_name(name)?;
+            if self.names.contains_key(name) {
+                anyhow::bail!("Agent name '{}' is already in use", name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Track a freshly spawned agent under the ID its actor was spawned with
+    fn register(&mut self, agent_id: AgentId, mut agent_handle: AgentHandle, name: Option<String>) {
+        if let Some(name) = &name {
+            self.names.insert(name.clone(), agent_id);
+        }
+        agent_handle.name = name;
+        self.agents.insert(agent_id, agent_handle);
+    }
+
+    /// Look up an agent by its human-readable name
+    ///
+    /// # Returns
+    /// `Some(AgentId)` if an agent with this name is managed, `None` otherwise
+    pub fn agent_id_by_name(&self, name: &str) -> Option<AgentId> {
+        self.names.get(name).copied()
+    }
+
+    /// Get the human-readable name of an agent, if it has one
+    pub fn agent_name(&self, id: AgentId) -> Option<&str> {
+        self.agents
+            .get(&id)
+            .and_then(|handle| handle.name.as_deref())
+    }
+
+    /// Resolve an agent reference that is either a UUID or a registered name
+    ///
+    /// # Returns
+    /// `Some(AgentId)` if the reference identifies a managed agent, `None` otherwise
+    pub fn resolve_agent(&self, id_or_name: &str) -> Option<AgentId> {
+        match uuid::Uuid::parse_str(id_or_name) {
+            Ok(uuid) => {
+                let id = AgentId::from(uuid);
+                self.agents.contains_key(&id).then_some(id)
+            }
+            Err(_) => self.agent_id_by_name(id_or_name),
+        }
+    }
+
     /// Terminate an agent and remove it from tracking
     ///
     /// # Arguments
@@ -112,37 +484,177 @@ impl Supervisor {
     ///
     /// # Returns
     /// * `Ok(())` - Agent terminated successfully
-    /// * `Err(anyhow::Error)` - Error if termination fails
-    pub async fn terminate_agent(&mut self, id: AgentId) -> Result<()> {
-        let agent_handle = self
-            .agents
-            .remove(&id)
-            .ok_or_else(|| anyhow::anyhow!("Agent {} not found", id))?;
+    /// * `Err(SentinelError::NotFound)` - Error if the agent is not managed
+    ///
+    /// # Note
+    /// This waits for the agent task while borrowing the supervisor. Callers sharing the
+    /// supervisor behind a lock should `detach_agent` under the lock and
+    /// `stop_detached_agent` after releasing it.
+    pub async fn terminate_agent(&mut self, id: AgentId) -> Result<(), SentinelError> {
+        let agent_handle = self.detach_agent(id)?;
+        Self::stop_detached_agent(id, agent_handle).await;
+        Ok(())
+    }
 
+    /// Remove an agent from tracking without stopping it
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the agent to detach
+    ///
+    /// # Returns
+    /// * `Ok(AgentHandle)` - Handle to pass to `stop_detached_agent`
+    /// * `Err(SentinelError::NotFound)` - Error if the agent is not managed
+    pub fn detach_agent(&mut self, id: AgentId) -> Result<AgentHandle, SentinelError> {
+        let agent_handle = self.agents.remove(&id).ok_or_else(|| agent_not_found(id))?;
+        if let Some(name) = &agent_handle.name {
+            self.names.remove(name);
+        }
+        Ok(agent_handle)
+    }
+
+    /// Signal a detached agent to shut down and wait for its task to finish
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the detached agent, for logging
+    /// * `agent_handle` - Handle returned by `detach_agent`
+    ///
+    /// # Note
+    /// The task is aborted if it does not stop within 5 seconds.
+    pub async fn stop_detached_agent(id: AgentId, agent_handle: AgentHandle) {
         info!("Supervisor terminating agent {}", id);
 
         // Send shutdown signal
         let _ = agent_handle.shutdown_tx.send(());
 
         // Wait for task to complete (with timeout)
-        match tokio::time::timeout(Duration::from_secs(5), agent_handle.handle).await {
+        let mut task = agent_handle.handle;
+        match tokio::time::timeout(Duration::from_secs(5), &mut task).await {
             Ok(join_result) => {
                 if let Err(e) = join_result {
                     warn!("Agent {} task error: {}", id, e);
                 }
             }
             Err(_) => {
-                warn!("Agent {} did not terminate within timeout", id);
-                // Task will be dropped, which will abort it
+                warn!("Agent {} did not terminate within timeout, aborting", id);
+                task.abort();
             }
         }
 
         info!("Supervisor terminated agent {}", id);
-        Ok(())
+    }
+
+    /// Terminate several agents concurrently, waiting at most `deadline` for each
+    ///
+    /// Agents are removed from tracking immediately. Tasks still running when the
+    /// deadline passes are aborted and counted as failed.
+    ///
+    /// # Arguments
+    /// * `ids` - The agents to terminate; unknown IDs are counted as failed
+    /// * `deadline` - Maximum time to wait for the agents to stop
+    ///
+    /// # Returns
+    /// Counts of agents that stopped cleanly and agents that failed to
+    ///
+    /// # Note
+    /// As with `terminate_agent`, callers sharing the supervisor behind a lock should
+    /// `detach_agents` under the lock and `stop_detached_agents` after releasing it.
+    pub async fn terminate_agents(
+        &mut self,
+        ids: &[AgentId],
+        deadline: Duration,
+    ) -> TerminationReport {
+        let (detached, unknown) = self.detach_agents(ids);
+        let mut report = Self::stop_detached_agents(detached, deadline).await;
+        report.failed += unknown;
+        report
+    }
+
+    /// Remove several agents from tracking without stopping them
+    ///
+    /// # Arguments
+    /// * `ids` - The agents to detach
+    ///
+    /// # Returns
+    /// The detached agents with their handles, for `stop_detached_agents`, and the number
+    /// of IDs that were not managed
+    pub fn detach_agents(&mut self, ids: &[AgentId]) -> (Vec<(AgentId, AgentHandle)>, usize) {
+        let mut detached = Vec::with_capacity(ids.len());
+        let mut unknown = 0;
+        for id in ids {
+            match self.detach_agent(*id) {
+                Ok(agent_handle) => detached.push((*id, agent_handle)),
+                Err(_) => {
+                    warn!("Agent {} not found for bulk termination", id);
+                    unknown += 1;
+                }
+            }
+        }
+        (detached, unknown)
+    }
+
+    /// Stop detached agents concurrently, waiting at most `deadline` for each
+    ///
+    /// # Arguments
+    /// * `agents` - Agents returned by `detach_agents`
+    /// * `deadline` - Maximum time to wait for the agents to stop
+    ///
+    /// # Returns
+    /// Counts of agents that stopped cleanly and agents that errored or were aborted
+    pub async fn stop_detached_agents(
+        agents: Vec<(AgentId, AgentHandle)>,
+        deadline: Duration,
+    ) -> TerminationReport {
+        info!("Supervisor terminating {} agents", agents.len());
+        let results =
+            futures::future::join_all(agents.into_iter().map(|(id, agent_handle)| async move {
+                let _ = agent_handle.shutdown_tx.send(());
+                let mut task = agent_handle.handle;
+                match tokio::time::timeout(deadline, &mut task).await {
+                    Ok(Ok(_)) => true,
+                    Ok(Err(e)) => {
+                        warn!("Agent {} task error: {}", id, e);
+                        false
+                    }
+                    Err(_) => {
+                        warn!(
+                            "Agent {} did not terminate within {:?}, aborting",
+                            id, deadline
+                        );
+                        task.abort();
+                        false
+                    }
+                }
+            }))
+            .await;
+
+        let mut report = TerminationReport::default();
+        for stopped in results {
+            if stopped {
+                report.terminated += 1;
+            } else {
+                report.failed += 1;
+            }
+        }
+        info!(
+            "Supervisor bulk termination complete: {} terminated, {} failed",
+            report.terminated, report.failed
+        );
+        report
+    }
+
+    /// Get the IDs of agents whose name starts with `prefix`
+    pub fn agent_ids_with_name_prefix(&self, prefix: &str) -> Vec<AgentId> {
+        self.names
+            .iter()
+            .filter(|(name, _)| name.starts_with(prefix))
+            .map(|(_, id)| *id)
+            .collect()
     }
 
     /// Restart an agent (terminate and spawn new one)
     ///
+    /// The replacement agent keeps the original agent's name, if any.
+    ///
     /// # Arguments
     /// * `id` - The ID of the agent to restart
     ///
@@ -151,8 +663,47 @@ impl Supervisor {
     /// * `Err(anyhow::Error)` - Error if restart fails
     pub async fn restart_agent(&mut self, id: AgentId) -> Result<AgentId> {
         info!("Supervisor restarting agent {}", id);
+        let name = self.agent_name(id).map(str::to_string);
         self.terminate_agent(id).await?;
-        self.spawn_agent()
+        self.spawn_named_agent(name)
+    }
+
+    /// Reset a failed agent back to Idle so it processes messages again
+    ///
+    /// Unlike `restart_agent`, the agent keeps its ID, name and channel.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the failed agent
+    ///
+    /// # Returns
+    /// * `Ok(())` - The agent's actor published the Idle state
+    /// * `Err(anyhow::Error)` - Error if the agent is not managed, is not Failed, has
+    ///   stopped, or does not reset within `AGENT_RESET_TIMEOUT`
+    pub async fn reset_agent(&self, id: AgentId) -> Result<()> {
+        let handle = self.agents.get(&id).ok_or_else(|| agent_not_found(id))?;
+        if handle.state() != AgentState::Failed {
+            anyhow::bail!(
+                "Agent {} is in {:?} state; only a failed agent can be reset",
+                id,
+                handle.state()
+            );
+        }
+
+        let mut state_rx = handle.state_rx.clone();
+        handle
+            .reset_tx
+            .send(())
+            .map_err(|_| anyhow::anyhow!("Agent {} has stopped", id))?;
+        timeout(
+            AGENT_RESET_TIMEOUT,
+            state_rx.wait_for(|state| *state != AgentState::Failed),
+        )
+        .await
+        .with_context(|| format!("Agent {} did not reset in time", id))?
+        .with_context(|| format!("Agent {} stopped before resetting", id))?;
+
+        info!("Supervisor reset agent {}", id);
+        Ok(())
     }
 
     /// Check the health of a specific agent
@@ -162,28 +713,31 @@ impl Supervisor {
     ///
     /// # Returns
     /// * `Ok(AgentHealth)` - Health status of the agent
-    /// * `Err(anyhow::Error)` - Error if agent not found
-    pub fn check_agent_health(&self, id: AgentId) -> Result<AgentHealth> {
-        let handle = self
-            .agents
-            .get(&id)
-            .ok_or_else(|| anyhow::anyhow!("Agent {} not found", id))?;
+    /// * `Err(SentinelError::NotFound)` - Error if the agent is not managed
+    pub fn check_agent_health(&self, id: AgentId) -> Result<AgentHealth, SentinelError> {
+        let handle = self.agents.get(&id).ok_or_else(|| agent_not_found(id))?;
 
         let time_since_activity = Utc::now() - handle.last_activity;
+        let state = handle.state();
+        let is_failed = state == AgentState::Failed;
         let is_zombie = time_since_activity.num_seconds() > self.zombie_timeout.as_secs() as i64
-            && handle.is_alive();
+            && handle.is_alive()
+            && !is_failed;
 
         Ok(AgentHealth {
             id,
-            state: handle.state,
+            state,
             last_activity: handle.last_activity,
             is_alive: handle.is_alive(),
             is_zombie,
+            is_failed,
         })
     }
 
     /// Detect all zombie agents (stuck >60s)
     ///
+    /// Failed agents are idle by design and are reported by `detect_failed` instead.
+    ///
     /// # Returns
     /// Vector of agent IDs that are zombies
     pub fn detect_zombies(&self) -> Vec<AgentId> {
@@ -193,7 +747,8 @@ impl Supervisor {
             let time_since_activity = Utc::now() - handle.last_activity;
             let is_zombie = time_since_activity.num_seconds()
                 > self.zombie_timeout.as_secs() as i64
-                && handle.is_alive();
+                && handle.is_alive()
+                && handle.state() != AgentState::Failed;
 
             if is_zombie {
                 warn!(
@@ -208,6 +763,50 @@ impl Supervisor {
         zombies
     }
 
+    /// Detect all agents in the Failed state
+    ///
+    /// Failed agents reject messages until they are reset or restarted.
+    ///
+    /// # Returns
+    /// Vector of agent IDs that are failed
+    pub fn detect_failed(&self) -> Vec<AgentId> {
+        self.agents
+            .iter()
+            .filter(|(_, handle)| handle.state() == AgentState::Failed)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Summarize the health of all managed agents
+    ///
+    /// # Returns
+    /// Total, alive, zombie and failed counts plus the number of agents in each state
+    pub fn health_summary(&self) -> SupervisorHealth {
+        let mut states: HashMap<AgentState, usize> =
+            AgentState::ALL.iter().map(|state| (*state, 0)).collect();
+        let mut alive = 0;
+        let mut failed = 0;
+
+        for id in self.agents.keys() {
+            match self.check_agent_health(*id) {
+                Ok(health) => {
+                    *states.entry(health.state).or_default() += 1;
+                    alive += usize::from(health.is_alive);
+                    failed += usize::from(health.is_failed);
+                }
+                Err(e) => warn!("Failed to get health for agent {}: {}", id, e),
+            }
+        }
+
+        SupervisorHealth {
+            total_agents: self.agent_count(),
+            alive,
+            zombies: self.detect_zombies().len(),
+            failed,
+            states,
+        }
+    }
+
     /// Update activity for an agent (called when agent processes a message)
     ///
     /// # Arguments
@@ -218,6 +817,100 @@ impl Supervisor {
         }
     }
 
+    /// Get the current state of an agent
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the agent
+    ///
+    /// # Returns
+    /// `Some(AgentState)` if the agent is managed, `None` otherwise
+    pub fn agent_state(&self, id: AgentId) -> Option<AgentState> {
+        self.agents.get(&id).map(AgentHandle::state)
+    }
+
+    /// Get the number of messages an agent is processing right now
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the agent
+    ///
+    /// # Returns
+    /// `Some(count)` if the agent is managed (0 when it is not working on a message),
+    /// `None` otherwise
+    pub fn agent_in_flight(&self, id: AgentId) -> Option<usize> {
+        self.agents.get(&id).map(AgentHandle::in_flight)
+    }
+
+    /// Subscribe to the state changes of an agent
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the agent
+    ///
+    /// # Returns
+    /// `Some(Receiver)` if the agent is managed, `None` otherwise
+    pub fn subscribe_agent_state(&self, id: AgentId) -> Option<watch::Receiver<AgentState>> {
+        self.agents.get(&id).map(|handle| handle.state_rx.clone())
+    }
+
+    /// Get a sender for delivering messages to an agent
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the agent
+    ///
+    /// # Returns
+    /// `Some(Sender)` if the agent is managed, `None` otherwise
+    pub fn agent_sender(&self, id: AgentId) -> Option<mpsc::Sender<ActorMessage>> {
+        self.agents.get(&id).map(|handle| handle.tx.clone())
+    }
+
+    /// Send a message to every managed agent
+    ///
+    /// Sends run concurrently, each waiting up to `BROADCAST_SEND_TIMEOUT` for room in
+    /// the agent's channel, so one busy agent does not delay the others.
+    ///
+    /// # Arguments
+    /// * `message` - Message delivered to each agent (e.g. a system prompt or notice)
+    ///
+    /// # Returns
+    /// One result per agent; agents that accepted the message have their activity
+    /// timestamp updated
+    pub async fn broadcast(
+        &mut self,
+        message: CanonicalMessage,
+    ) -> Vec<(AgentId, Result<(), SentinelError>)> {
+        let sends = self.agents.iter().map(|(id, handle)| {
+            let id = *id;
+            let tx = handle.tx.clone();
+            let msg = ActorMessage::new(message.clone());
+            async move {
+                let result = try_send_with_timeout(&tx, msg, BROADCAST_SEND_TIMEOUT)
+                    .await
+                    .map_err(|e| SentinelError::DomainViolation {
+                        rule: format!("Failed to deliver broadcast to agent {}: {}", id, e),
+                    });
+                (id, result)
+            }
+        });
+        let results = futures::future::join_all(sends).await;
+
+        let mut failed = 0;
+        for (id, result) in &results {
+            match result {
+                Ok(()) => self.update_agent_activity(*id),
+                Err(e) => {
+                    warn!("{}", e);
+                    failed += 1;
+                }
+            }
+        }
+        info!(
+            "Broadcast message {} to {} agents ({} failed)",
+            message.id,
+            results.len(),
+            failed
+        );
+        results
+    }
+
     /// Get all agent IDs currently managed
     pub fn agent_ids(&self) -> Vec<AgentId> {
         self.agents.keys().copied().collect()
@@ -230,7 +923,8 @@ impl Supervisor {
 
     /// Run the supervisor event loop
     ///
-    /// This loop periodically checks for zombies and handles shutdown signals.
+    /// This loop periodically terminates zombies, resets failed agents and handles
+    /// shutdown signals.
     ///
     /// # Arguments
     /// * `shutdown_rx` - Shutdown signal receiver
@@ -253,6 +947,11 @@ impl Supervisor {
                             error!("Failed to terminate zombie agent {}: {}", zombie_id, e);
                         }
                     }
+                    for failed_id in self.detect_failed() {
+                        if let Err(e) = self.reset_agent(failed_id).await {
+                            error!("Failed to reset failed agent {}: {:#}", failed_id, e);
+                        }
+                    }
                 }
                 // Shutdown signal
                 _ = shutdown_rx.changed() => {
@@ -285,6 +984,15 @@ impl Default for Supervisor {
     }
 }
 
+/// Outcome of a bulk termination
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TerminationReport {
+    /// Agents that stopped within the deadline
+    pub terminated: usize,
+    /// Agents that were unknown, errored, or had to be aborted
+    pub failed: usize,
+}
+
 /// Health status of an agent
 #[derive(Debug, Clone)]
 pub struct AgentHealth {
@@ -298,11 +1006,15 @@ pub struct AgentHealth {
     pub is_alive: bool,
     /// Whether the agent is a zombie (stuck >60s)
     pub is_zombie: bool,
+    /// Whether the agent hit an unrecoverable error and awaits a reset
+    pub is_failed: bool,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::types::{CanonicalMessage, Role};
+    use crate::engine::channels::{try_send_with_timeout, AgentSendError};
     use std::time::Duration;
     use tokio::time::timeout;
 
@@ -368,6 +1080,51 @@ mod tests {
         assert!(supervisor.check_agent_health(agent_id).is_err());
     }
 
+    #[tokio::test]
+    async fn test_detached_agent_is_untracked_before_it_stops() {
+        let supervisor = RwLock::new(Supervisor::new());
+        let agent_id = supervisor.write().await.spawn_agent().unwrap();
+
+        let agent_handle = supervisor.write().await.detach_agent(agent_id).unwrap();
+        {
+            // The supervisor is usable while the detached agent is still running
+            let guard = supervisor.read().await;
+            assert_eq!(guard.agent_count(), 0);
+            assert!(guard.check_agent_health(agent_id).is_err());
+        }
+        assert!(!agent_handle.handle.is_finished());
+
+        Supervisor::stop_detached_agent(agent_id, agent_handle).await;
+        assert!(matches!(
+            supervisor.write().await.detach_agent(agent_id),
+            Err(SentinelError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_agent_is_typed_not_found() {
+        let mut supervisor = Supervisor::new();
+        let unknown = AgentId::new();
+        let expected = SentinelError::NotFound {
+            resource: format!("Agent {}", unknown),
+        };
+
+        assert_eq!(
+            supervisor.terminate_agent(unknown).await.unwrap_err(),
+            expected
+        );
+        assert_eq!(
+            supervisor.check_agent_health(unknown).unwrap_err(),
+            expected
+        );
+        // Through anyhow, the typed error can still be recovered
+        let restart_error = supervisor.restart_agent(unknown).await.unwrap_err();
+        assert_eq!(
+            restart_error.downcast_ref::<SentinelError>(),
+            Some(&expected)
+        );
+    }
+
     #[tokio::test]
     async fn test_restart_agent() {
         let mut supervisor = Supervisor::new();
@@ -399,6 +1156,277 @@ mod tests {
         assert!(health2.last_activity > last_activity1);
     }
 
+    #[tokio::test]
+    async fn test_spawn_respects_max_agents() {
+        let mut supervisor = Supervisor::new().with_max_agents(2);
+        supervisor.spawn_agent().unwrap();
+        let agent_id = supervisor.spawn_agent().unwrap();
+
+        assert!(supervisor.at_capacity());
+        assert!(supervisor.spawn_agent().is_err());
+        assert_eq!(supervisor.agent_count(), 2);
+
+        // Terminating an agent frees a slot
+        supervisor.terminate_agent(agent_id).await.unwrap();
+        assert!(!supervisor.at_capacity());
+        assert!(supervisor.spawn_agent().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_spawn_uses_configured_channel_buffer() {
+        assert!(Supervisor::new().with_channel_buffer(0).is_err());
+        assert_eq!(Supervisor::new().channel_buffer(), DEFAULT_CHANNEL_SIZE);
+
+        let buffer = DEFAULT_CHANNEL_SIZE * 4;
+        let mut supervisor = Supervisor::new().with_channel_buffer(buffer).unwrap();
+        let agent_id = supervisor.spawn_agent().unwrap();
+        let tx = supervisor.agent_sender(agent_id).unwrap();
+        assert_eq!(tx.max_capacity(), buffer);
+
+        // A full buffer's worth of messages is accepted without waiting for the actor
+        for i in 0..buffer {
+            let msg = ActorMessage::new(CanonicalMessage::new(Role::User, format!("msg-{}", i)));
+            timeout(Duration::from_millis(100), tx.send(msg))
+                .await
+                .expect("send should not block")
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_reports_result_per_agent() {
+        let mut supervisor = Supervisor::new();
+        let agent_ids: Vec<AgentId> = (0..3).map(|_| supervisor.spawn_agent().unwrap()).collect();
+        let before: Vec<_> = agent_ids
+            .iter()
+            .map(|id| supervisor.check_agent_health(*id).unwrap().last_activity)
+            .collect();
+        // Stop one agent so its send fails while it is still tracked
+        let stopped = agent_ids[2];
+        supervisor.agents[&stopped].shutdown_tx.send(()).unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let results = supervisor
+            .broadcast(CanonicalMessage::new(
+                Role::System,
+                "Shutting down soon".to_string(),
+            ))
+            .await;
+
+        assert_eq!(results.len(), 3);
+        for (index, id) in agent_ids.iter().enumerate() {
+            let (_, result) = results.iter().find(|(agent_id, _)| agent_id == id).unwrap();
+            let last_activity = supervisor.check_agent_health(*id).unwrap().last_activity;
+            if *id == stopped {
+                assert!(result.is_err());
+                assert_eq!(last_activity, before[index]);
+            } else {
+                assert!(result.is_ok());
+                assert!(last_activity > before[index]);
+            }
+        }
+    }
+
+    /// Processor failing every message whose content is "fail"
+    struct FailOnRequest;
+
+    #[async_trait]
+    impl MessageProcessor for FailOnRequest {
+        async fn process(&self, _agent_id: AgentId, msg: ActorMessage) -> Result<()> {
+            if msg.message.content == "fail" {
+                anyhow::bail!("requested failure");
+            }
+            Ok(())
+        }
+    }
+
+    /// Processor forwarding the agent ID it is called with
+    struct AgentIdRecorder(mpsc::UnboundedSender<AgentId>);
+
+    #[async_trait]
+    impl MessageProcessor for AgentIdRecorder {
+        async fn process(&self, agent_id: AgentId, _msg: ActorMessage) -> Result<()> {
+            let _ = self.0.send(agent_id);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_processor_sees_supervisor_agent_id() {
+        let (seen_tx, mut seen_rx) = mpsc::unbounded_channel();
+        let mut supervisor =
+            Supervisor::new().with_message_processor(Arc::new(AgentIdRecorder(seen_tx)));
+        let agent_id = supervisor.spawn_agent().unwrap();
+        let named_id = supervisor
+            .spawn_named_agent(Some("named".to_string()))
+            .unwrap();
+
+        for id in [agent_id, named_id] {
+            let msg = ActorMessage::new(CanonicalMessage::new(Role::User, "hi".to_string()));
+            supervisor
+                .agent_sender(id)
+                .unwrap()
+                .send(msg)
+                .await
+                .unwrap();
+            let seen = timeout(Duration::from_secs(1), seen_rx.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(seen, id);
+        }
+    }
+
+    /// Send a message to an agent and wait until its actor publishes `state`
+    async fn send_and_wait_for(
+        supervisor: &Supervisor,
+        id: AgentId,
+        content: &str,
+        state: AgentState,
+    ) {
+        let mut state_rx = supervisor.subscribe_agent_state(id).unwrap();
+        let msg = ActorMessage::new(CanonicalMessage::new(Role::User, content.to_string()));
+        supervisor
+            .agent_sender(id)
+            .unwrap()
+            .send(msg)
+            .await
+            .unwrap();
+        timeout(
+            Duration::from_secs(1),
+            state_rx.wait_for(|current| *current == state),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("agent {} never reached {:?}", id, state))
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_agent_state_tracking() {
+        let mut supervisor = Supervisor::new();
+        let agent_id = supervisor.spawn_agent().unwrap();
+
+        assert_eq!(supervisor.agent_state(agent_id), Some(AgentState::Idle));
+        send_and_wait_for(&supervisor, agent_id, "msg", AgentState::Thinking).await;
+        assert_eq!(supervisor.agent_state(agent_id), Some(AgentState::Thinking));
+
+        assert!(supervisor.agent_sender(agent_id).is_some());
+        assert_eq!(supervisor.agent_state(AgentId::new()), None);
+        assert!(supervisor.agent_sender(AgentId::new()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handle_observes_state_cycle() {
+        let mut supervisor = Supervisor::new();
+        let agent_id = supervisor.spawn_agent().unwrap();
+
+        for state in [
+            AgentState::Thinking,
+            AgentState::Reflecting,
+            AgentState::Idle,
+            AgentState::Thinking,
+        ] {
+            send_and_wait_for(&supervisor, agent_id, "msg", state).await;
+            assert_eq!(supervisor.agent_state(agent_id), Some(state));
+            assert_eq!(
+                supervisor.check_agent_health(agent_id).unwrap().state,
+                state
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_agent_reported_distinctly_from_zombie() {
+        let mut supervisor = Supervisor::with_settings(Duration::from_secs(10), Duration::ZERO)
+            .with_message_processor(Arc::new(FailOnRequest));
+        let failed_id = supervisor.spawn_agent().unwrap();
+        let stuck_id = supervisor.spawn_agent().unwrap();
+        send_and_wait_for(&supervisor, failed_id, "fail", AgentState::Failed).await;
+        send_and_wait_for(&supervisor, stuck_id, "ok", AgentState::Thinking).await;
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        let health = supervisor.check_agent_health(failed_id).unwrap();
+        assert!(health.is_failed);
+        assert!(!health.is_zombie);
+        assert_eq!(health.state, AgentState::Failed);
+        assert!(!supervisor.check_agent_health(stuck_id).unwrap().is_failed);
+
+        assert_eq!(supervisor.detect_failed(), vec![failed_id]);
+        assert_eq!(supervisor.detect_zombies(), vec![stuck_id]);
+    }
+
+    #[tokio::test]
+    async fn test_reset_agent_recovers_failed_agent() {
+        let mut supervisor = Supervisor::new().with_message_processor(Arc::new(FailOnRequest));
+        let agent_id = supervisor
+            .spawn_named_agent(Some("worker".to_string()))
+            .unwrap();
+
+        // Only failed agents can be reset
+        assert!(supervisor.reset_agent(agent_id).await.is_err());
+        assert!(supervisor.reset_agent(AgentId::new()).await.is_err());
+
+        send_and_wait_for(&supervisor, agent_id, "fail", AgentState::Failed).await;
+        supervisor.reset_agent(agent_id).await.unwrap();
+
+        assert_eq!(supervisor.agent_state(agent_id), Some(AgentState::Idle));
+        assert_eq!(supervisor.agent_id_by_name("worker"), Some(agent_id));
+        assert!(supervisor.detect_failed().is_empty());
+        // The same agent processes messages again
+        send_and_wait_for(&supervisor, agent_id, "ok", AgentState::Thinking).await;
+    }
+
+    #[tokio::test]
+    async fn test_run_resets_failed_agents() {
+        let mut supervisor =
+            Supervisor::with_settings(Duration::from_millis(50), DEFAULT_ZOMBIE_TIMEOUT)
+                .with_message_processor(Arc::new(FailOnRequest));
+        let agent_id = supervisor.spawn_agent().unwrap();
+        send_and_wait_for(&supervisor, agent_id, "fail", AgentState::Failed).await;
+        let mut state_rx = supervisor.subscribe_agent_state(agent_id).unwrap();
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(());
+        let supervisor_handle = tokio::spawn(async move { supervisor.run(shutdown_rx).await });
+
+        timeout(
+            Duration::from_secs(1),
+            state_rx.wait_for(|state| *state == AgentState::Idle),
+        )
+        .await
+        .expect("failed agent was never reset")
+        .unwrap();
+
+        shutdown_tx.send(()).unwrap();
+        assert!(supervisor_handle.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_health_summary_counts_agents() {
+        let mut supervisor = Supervisor::with_settings(Duration::from_secs(10), Duration::ZERO)
+            .with_message_processor(Arc::new(FailOnRequest));
+        let thinking_id = supervisor.spawn_agent().unwrap();
+        let failed_id = supervisor.spawn_agent().unwrap();
+        supervisor.spawn_agent().unwrap();
+        send_and_wait_for(&supervisor, thinking_id, "ok", AgentState::Thinking).await;
+        send_and_wait_for(&supervisor, failed_id, "fail", AgentState::Failed).await;
+
+        let summary = supervisor.health_summary();
+        assert_eq!(summary.total_agents, 3);
+        assert_eq!(summary.alive, 3);
+        assert_eq!(summary.zombies, 0);
+        assert_eq!(summary.failed, 1);
+
+        // With a zero timeout, every live agent that is not failed becomes a zombie
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        let summary = supervisor.health_summary();
+        assert_eq!(summary.zombies, 2);
+        assert_eq!(summary.states.len(), AgentState::ALL.len());
+        assert_eq!(summary.states[&AgentState::Idle], 1);
+        assert_eq!(summary.states[&AgentState::Thinking], 1);
+        assert_eq!(summary.states[&AgentState::Failed], 1);
+        assert_eq!(summary.states[&AgentState::Reflecting], 0);
+    }
+
     #[tokio::test]
     async fn test_graceful_shutdown_terminates_all_agents() {
         let mut supervisor = Supervisor::new();
@@ -446,4 +1474,246 @@ mod tests {
 
         let _ = timeout(Duration::from_secs(1), supervisor_handle).await;
     }
+
+    #[tokio::test]
+    async fn test_spawn_named_agent_lookup_by_name() {
+        let mut supervisor = Supervisor::new();
+        let agent_id = supervisor
+            .spawn_named_agent(Some("researcher".to_string()))
+            .unwrap();
+
+        assert_eq!(supervisor.agent_id_by_name("researcher"), Some(agent_id));
+        assert_eq!(supervisor.agent_name(agent_id), Some("researcher"));
+        assert_eq!(supervisor.resolve_agent("researcher"), Some(agent_id));
+        assert_eq!(
+            supervisor.resolve_agent(&agent_id.0.to_string()),
+            Some(agent_id)
+        );
+        assert_eq!(supervisor.resolve_agent("unknown"), None);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_named_agent_rejects_duplicate_name() {
+        let mut supervisor = Supervisor::new();
+        supervisor
+            .spawn_named_agent(Some("writer".to_string()))
+            .unwrap();
+
+        let result = supervisor.spawn_named_agent(Some("writer".to_string()));
+        assert!(result.unwrap_err().to_string().contains("already in use"));
+        assert_eq!(supervisor.agent_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_agent_name_released_on_terminate() {
+        let mut supervisor = Supervisor::new();
+        let agent_id = supervisor
+            .spawn_named_agent(Some("worker".to_string()))
+            .unwrap();
+        supervisor.terminate_agent(agent_id).await.unwrap();
+
+        assert_eq!(supervisor.agent_id_by_name("worker"), None);
+        assert!(supervisor
+            .spawn_named_agent(Some("worker".to_string()))
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_restart_agent_keeps_name() {
+        let mut supervisor = Supervisor::new();
+        let agent_id = supervisor
+            .spawn_named_agent(Some("planner".to_string()))
+            .unwrap();
+
+        let new_id = supervisor.restart_agent(agent_id).await.unwrap();
+
+        assert_eq!(supervisor.agent_id_by_name("planner"), Some(new_id));
+    }
+
+    #[test]
+    fn test_validate_agent_name() {
+        assert!(validate_agent_name("agent-1_a").is_ok());
+        assert!(validate_agent_name("").is_err());
+        assert!(validate_agent_name("has space").is_err());
+        assert!(validate_agent_name(&"a".repeat(MAX_AGENT_NAME_LEN + 1)).is_err());
+        assert!(validate_agent_name(&uuid::Uuid::new_v4().to_string()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_send_after_terminate_fails_with_closed() {
+        let mut supervisor = Supervisor::new();
+        let agent_id = supervisor.spawn_agent().unwrap();
+        let tx = supervisor.agent_sender(agent_id).unwrap();
+
+        supervisor.terminate_agent(agent_id).await.unwrap();
+
+        let msg = ActorMessage::new(CanonicalMessage::new(Role::User, "late".to_string()));
+        let result = try_send_with_timeout(&tx, msg, Duration::from_secs(1)).await;
+        assert_eq!(result, Err(AgentSendError::Closed));
+    }
+
+    #[tokio::test]
+    async fn test_terminate_agents_concurrently() {
+        let mut supervisor = Supervisor::new();
+        let ids: Vec<AgentId> = (0..3).map(|_| supervisor.spawn_agent().unwrap()).collect();
+
+        let report = supervisor
+            .terminate_agents(&ids, Duration::from_secs(5))
+            .await;
+
+        assert_eq!(
+            report,
+            TerminationReport {
+                terminated: 3,
+                failed: 0
+            }
+        );
+        assert_eq!(supervisor.agent_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_terminate_agents_counts_unknown_as_failed() {
+        let mut supervisor = Supervisor::new();
+        let known = supervisor.spawn_agent().unwrap();
+
+        let report = supervisor
+            .terminate_agents(&[known, AgentId::new()], Duration::from_secs(5))
+            .await;
+
+        assert_eq!(report.terminated, 1);
+        assert_eq!(report.failed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_detached_agents_stop_without_holding_supervisor() {
+        let supervisor = RwLock::new(Supervisor::new());
+        let (first, second, kept) = {
+            let mut guard = supervisor.write().await;
+            (
+                guard.spawn_agent().unwrap(),
+                guard.spawn_agent().unwrap(),
+                guard.spawn_agent().unwrap(),
+            )
+        };
+
+        let (detached, unknown) =
+            supervisor
+                .write()
+                .await
+                .detach_agents(&[first, second, AgentId::new()]);
+        assert_eq!(unknown, 1);
+        // The supervisor is usable while the detached agents are still running
+        assert_eq!(supervisor.read().await.agent_ids(), vec![kept]);
+        assert!(detached
+            .iter()
+            .all(|(_, agent_handle)| !agent_handle.handle.is_finished()));
+
+        let report = Supervisor::stop_detached_agents(detached, Duration::from_secs(5)).await;
+        assert_eq!(report.terminated, 2);
+        assert_eq!(report.failed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_agent_ids_with_name_prefix() {
+        let mut supervisor = Supervisor::new();
+        let batch_a = supervisor
+            .spawn_named_agent(Some("batch-a".to_string()))
+            .unwrap();
+        supervisor
+            .spawn_named_agent(Some("other".to_string()))
+            .unwrap();
+        supervisor.spawn_agent().unwrap();
+
+        assert_eq!(
+            supervisor.agent_ids_with_name_prefix("batch-"),
+            vec![batch_a]
+        );
+    }
+
+    /// Spawn hook that always fails
+    struct FailingHook;
+
+    #[async_trait]
+    impl SpawnHook for FailingHook {
+        async fn on_spawn(&self, _agent_id: AgentId) -> Result<()> {
+            anyhow::bail!("persona not found")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_spawn_hook_terminates_agent() {
+        let supervisor = RwLock::new(Supervisor::new().with_spawn_hook(Arc::new(FailingHook)));
+
+        let error = Supervisor::spawn_initialized_agent(&supervisor, Some("planner".to_string()))
+            .await
+            .unwrap_err();
+
+        assert!(format!("{:#}", error).contains("persona not found"));
+        let supervisor = supervisor.read().await;
+        assert_eq!(supervisor.agent_count(), 0);
+        assert!(supervisor.agent_id_by_name("planner").is_none());
+    }
+
+    /// Spawn hook that waits until released, signalling when it has started
+    struct BlockingHook {
+        started: mpsc::UnboundedSender<()>,
+        release: tokio::sync::Notify,
+    }
+
+    #[async_trait]
+    impl SpawnHook for BlockingHook {
+        async fn on_spawn(&self, _agent_id: AgentId) -> Result<()> {
+            let _ = self.started.send(());
+            self.release.notified().await;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spawn_hook_runs_without_holding_supervisor_lock() {
+        let (started_tx, mut started_rx) = mpsc::unbounded_channel();
+        let hook = Arc::new(BlockingHook {
+            started: started_tx,
+            release: tokio::sync::Notify::new(),
+        });
+        let supervisor = Arc::new(RwLock::new(Supervisor::new().with_spawn_hook(hook.clone())));
+
+        let spawn = tokio::spawn({
+            let supervisor = supervisor.clone();
+            async move { Supervisor::spawn_initialized_agent(&supervisor, None).await }
+        });
+        started_rx.recv().await.unwrap();
+
+        // Other supervisor operations proceed while the hook is still running
+        timeout(Duration::from_secs(1), supervisor.write())
+            .await
+            .expect("supervisor lock should be free while the hook runs")
+            .spawn_agent()
+            .unwrap();
+
+        hook.release.notify_one();
+        spawn.await.unwrap().unwrap();
+        assert_eq!(supervisor.read().await.agent_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_slow_spawn_hook_times_out_and_terminates_agent() {
+        let (started_tx, _started_rx) = mpsc::unbounded_channel();
+        let hook = Arc::new(BlockingHook {
+            started: started_tx,
+            release: tokio::sync::Notify::new(),
+        });
+        let supervisor = RwLock::new(
+            Supervisor::new()
+                .with_spawn_hook(hook)
+                .with_spawn_hook_timeout(Duration::from_millis(10)),
+        );
+
+        let error = Supervisor::spawn_initialized_agent(&supervisor, None)
+            .await
+            .unwrap_err();
+
+        assert!(format!("{:#}", error).contains("timed out"));
+        assert_eq!(supervisor.read().await.agent_count(), 0);
+    }
 }
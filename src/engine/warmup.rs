@@ -0,0 +1,209 @@
+// Startup warm-up for the LLM provider
+// Pre-initializes the provider client so the first real request avoids cold-start latency
+
+use crate::core::traits::LLMProvider;
+use crate::core::types::{CanonicalMessage, Role};
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Default timeout for each warm-up step
+pub const DEFAULT_WARMUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Prompt used for the optional warm-up completion
+const WARMUP_PROMPT: &str = "ping";
+
+/// Warm-up settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WarmupOptions {
+    /// Also send a minimal completion after the health check succeeds
+    pub completion: bool,
+    /// Maximum time allowed for each warm-up step
+    pub timeout: Duration,
+}
+
+impl Default for WarmupOptions {
+    fn default() -> Self {
+        Self {
+            completion: false,
+            timeout: DEFAULT_WARMUP_TIMEOUT,
+        }
+    }
+}
+
+/// Result of a single warm-up step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WarmupStep {
+    /// Step was not run
+    Skipped,
+    /// Step succeeded
+    Succeeded,
+    /// Step failed or timed out
+    Failed(String),
+}
+
+/// Outcome of a warm-up run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarmupReport {
+    /// Provider health check
+    pub health_check: WarmupStep,
+    /// Minimal completion
+    pub completion: WarmupStep,
+    /// Total time spent warming up
+    pub elapsed: Duration,
+}
+
+/// Warm up the LLM provider
+///
+/// Calls `LLMProvider::health_check` and, if enabled and the health check passed,
+/// a minimal completion. Failures are logged and reported but never returned as
+/// errors, so warm-up cannot fail server startup.
+///
+/// # Arguments
+/// * `provider` - The LLM provider to warm up
+/// * `options` - Warm-up settings
+///
+/// # Returns
+/// A report of each step
+pub async fn run_warmup(provider: &dyn LLMProvider, options: &WarmupOptions) -> WarmupReport {
+    let started = Instant::now();
+    info!("Warming up LLM provider");
+    let health_check = run_step("health check", options.timeout, provider.health_check()).await;
+
+    let completion = if options.completion && health_check == WarmupStep::Succeeded {
+        let messages = vec![CanonicalMessage::new(Role::User, WARMUP_PROMPT.to_string())];
+        run_step("completion", options.timeout, async {
+            provider.complete(messages).await.map(|_| ())
+        })
+        .await
+    } else {
+        WarmupStep::Skipped
+    };
+
+    let report = WarmupReport {
+        health_check,
+        completion,
+        elapsed: started.elapsed(),
+    };
+    info!("LLM provider warm-up finished in {:?}", report.elapsed);
+    report
+}
+
+/// Run one warm-up step with a timeout, logging its outcome
+async fn run_step<E: std::fmt::Display>(
+    name: &str,
+    timeout: Duration,
+    step: impl Future<Output = Result<(), E>>,
+) -> WarmupStep {
+    match tokio::time::timeout(timeout, step).await {
+        Ok(Ok(())) => {
+            info!("Warm-up {} succeeded", name);
+            WarmupStep::Succeeded
+        }
+        Ok(Err(e)) => {
+            warn!("Warm-up {} failed: {}", name, e);
+            WarmupStep::Failed(e.to_string())
+        }
+        Err(_) => {
+            warn!("Warm-up {} timed out after {:?}", name, timeout);
+            WarmupStep::Failed(format!("timed out after {:?}", timeout))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::error::SentinelError;
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    mock! {
+        pub WarmupLLMProvider {}
+
+        #[async_trait]
+        impl LLMProvider for WarmupLLMProvider {
+            async fn complete(
+                &self,
+                messages: Vec<CanonicalMessage>,
+            ) -> Result<CanonicalMessage, SentinelError>;
+
+            async fn stream(
+                &self,
+                messages: Vec<CanonicalMessage>,
+            ) -> Result<Box<dyn futures::Stream<Item = Result<String, SentinelError>> + Send + Unpin>, SentinelError>;
+
+            async fn health_check(&self) -> Result<(), SentinelError>;
+        }
+    }
+
+    fn options(completion: bool) -> WarmupOptions {
+        WarmupOptions {
+            completion,
+            timeout: Duration::from_secs(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warmup_runs_health_check_and_completion() {
+        let mut provider = MockWarmupLLMProvider::new();
+        provider.expect_health_check().times(1).returning(|| Ok(()));
+        provider
+            .expect_complete()
+            .withf(|messages| messages.len() == 1 && messages[0].content == WARMUP_PROMPT)
+            .times(1)
+            .returning(|_| Ok(CanonicalMessage::new(Role::Assistant, "pong".to_string())));
+
+        let report = run_warmup(&provider, &options(true)).await;
+
+        assert_eq!(report.health_check, WarmupStep::Succeeded);
+        assert_eq!(report.completion, WarmupStep::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_warmup_health_check_only_by_default() {
+        let mut provider = MockWarmupLLMProvider::new();
+        provider.expect_health_check().times(1).returning(|| Ok(()));
+        provider.expect_complete().never();
+
+        let report = run_warmup(&provider, &options(false)).await;
+
+        assert_eq!(report.health_check, WarmupStep::Succeeded);
+        assert_eq!(report.completion, WarmupStep::Skipped);
+    }
+
+    #[tokio::test]
+    async fn test_warmup_failure_is_reported_not_returned() {
+        let mut provider = MockWarmupLLMProvider::new();
+        provider.expect_health_check().times(1).returning(|| {
+            Err(SentinelError::DomainViolation {
+                rule: "provider unreachable".to_string(),
+            })
+        });
+        provider.expect_complete().never();
+
+        let report = run_warmup(&provider, &options(true)).await;
+
+        match report.health_check {
+            WarmupStep::Failed(reason) => assert!(reason.contains("provider unreachable")),
+            other => panic!("Expected Failed, got {:?}", other),
+        }
+        assert_eq!(report.completion, WarmupStep::Skipped);
+    }
+
+    #[tokio::test]
+    async fn test_warmup_completion_failure_is_reported() {
+        let mut provider = MockWarmupLLMProvider::new();
+        provider.expect_health_check().times(1).returning(|| Ok(()));
+        provider.expect_complete().times(1).returning(|_| {
+            Err(SentinelError::DomainViolation {
+                rule: "model overloaded".to_string(),
+            })
+        });
+
+        let report = run_warmup(&provider, &options(true)).await;
+
+        assert_eq!(report.health_check, WarmupStep::Succeeded);
+        assert!(matches!(report.completion, WarmupStep::Failed(_)));
+    }
+}
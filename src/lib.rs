@@ -1,5 +1,6 @@
 pub mod adapters;
 pub mod api;
+pub mod config;
 pub mod core;
 pub mod engine;
 pub mod memory;
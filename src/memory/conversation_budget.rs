@@ -0,0 +1,396 @@
+// Per-conversation token budgets
+// Caps cumulative token usage of a single conversation so a runaway loop can't exhaust the global budget
+
+use crate::core::error::SentinelError;
+use crate::core::types::ConversationId;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use tracing::{info, warn};
+
+/// Default maximum tokens a single conversation may consume
+pub const DEFAULT_CONVERSATION_TOKEN_BUDGET: u64 = 200_000;
+
+/// Default number of conversations whose usage is tracked at once
+pub const DEFAULT_MAX_TRACKED_CONVERSATIONS: usize = 10_000;
+
+/// Tokens consumed by a conversation and its bookkeeping
+#[derive(Debug)]
+struct TrackedUsage {
+    used: u64,
+    /// Logical clock value of the last update, for LRU eviction
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct BudgetState {
+    entries: HashMap<ConversationId, TrackedUsage>,
+    clock: u64,
+}
+
+impl BudgetState {
+    /// Get the usage entry of a conversation, evicting the least recently used
+    /// conversation when a new one would exceed `capacity`
+    fn entry(&mut self, conversation_id: &ConversationId, capacity: usize) -> &mut TrackedUsage {
+        self.clock += 1;
+        let clock = self.clock;
+
+        if !self.entries.contains_key(conversation_id) && self.entries.len() >= capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(id, _)| id.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        let entry = self
+            .entries
+            .entry(conversation_id.clone())
+            .or_insert(TrackedUsage {
+                used: 0,
+                last_used: clock,
+            });
+        entry.last_used = clock;
+        entry
+    }
+}
+
+/// Tracks cumulative token usage per conversation against a fixed limit
+///
+/// The number of tracked conversations is bounded; when it is reached the least
+/// recently used conversation is forgotten and starts over with a fresh budget.
+#[derive(Debug)]
+pub struct ConversationBudgets {
+    /// Maximum tokens allowed per conversation
+    max_tokens: u64,
+    /// Maximum number of conversations tracked at once
+    max_conversations: usize,
+    /// Map of conversation ID to tokens consumed so far
+    state: Mutex<BudgetState>,
+}
+
+/// Tokens reserved for an in-flight completion
+///
+/// Settle it with the tokens actually consumed; a reservation dropped without being
+/// settled (failed or cancelled completion) is released as if settled with `0`.
+#[derive(Debug)]
+#[must_use = "dropping a reservation releases it immediately"]
+pub struct BudgetReservation<'a> {
+    budgets: &'a ConversationBudgets,
+    conversation_id: ConversationId,
+    tokens: u64,
+    settled: bool,
+}
+
+impl BudgetReservation<'_> {
+    /// Get the conversation the reservation was made for
+    pub fn conversation_id(&self) -> &ConversationId {
+        &self.conversation_id
+    }
+
+    /// Get the number of reserved tokens
+    pub fn tokens(&self) -> u64 {
+        self.tokens
+    }
+
+    /// Replace the reservation with the tokens actually consumed
+    ///
+    /// # Arguments
+    /// * `actual` - Tokens the completion consumed
+    ///
+    /// # Returns
+    /// Cumulative tokens consumed by the conversation
+    pub fn settle(mut self, actual: u64) -> u64 {
+        self.settled = true;
+        self.budgets
+            .settle(&self.conversation_id, self.tokens, actual)
+    }
+}
+
+impl Drop for BudgetReservation<'_> {
+    fn drop(&mut self) {
+        if !self.settled {
+            self.budgets.settle(&self.conversation_id, self.tokens, 0);
+        }
+    }
+}
+
+impl ConversationBudgets {
+    /// Create a new budget tracker with the given per-conversation limit
+    pub fn new(max_tokens: u64) -> Self {
+        Self {
+            max_tokens,
+            max_conversations: DEFAULT_MAX_TRACKED_CONVERSATIONS,
+            state: Mutex::new(BudgetState::default()),
+        }
+    }
+
+    /// Set the maximum number of conversations tracked at once (at least 1)
+    pub fn with_max_conversations(mut self, max_conversations: usize) -> Self {
+        self.max_conversations = max_conversations.max(1);
+        self
+    }
+
+    /// Get the per-conversation token limit
+    pub fn max_tokens(&self) -> u64 {
+        self.max_tokens
+    }
+
+    /// Get the maximum number of conversations tracked at once
+    pub fn max_conversations(&self) -> usize {
+        self.max_conversations
+    }
+
+    /// Get the number of conversations currently tracked
+    pub fn tracked_conversations(&self) -> usize {
+        self.state().entries.len()
+    }
+
+    /// Reserve tokens for a completion if the conversation is within its budget
+    ///
+    /// The check and the reservation happen under one lock, so concurrent completions
+    /// see each other's reserved tokens and cannot all pass the check at once.
+    ///
+    /// # Arguments
+    /// * `conversation_id` - The conversation running the completion
+    /// * `tokens` - Tokens expected to be consumed (e.g. the prompt)
+    ///
+    /// # Returns
+    /// * `Ok(BudgetReservation)` - The reservation, to be settled with the actual usage
+    /// * `Err(SentinelError)` - BudgetExceeded if the budget is exhausted
+    ///
+    /// # Note
+    /// Dropping the reservation without settling it releases the reserved tokens.
+    pub async fn reserve(
+        &self,
+        conversation_id: &ConversationId,
+        tokens: u64,
+    ) -> Result<BudgetReservation<'_>, SentinelError> {
+        let mut state = self.state();
+        let entry = state.entry(conversation_id, self.max_conversations);
+        if entry.used >= self.max_tokens {
+            warn!(
+                "Conversation {} rejected: budget exhausted ({} >= {})",
+                conversation_id, entry.used, self.max_tokens
+            );
+            return Err(SentinelError::BudgetExceeded {
+                conversation_id: conversation_id.clone(),
+                used: entry.used,
+                limit: self.max_tokens,
+            });
+        }
+        entry.used = entry.used.saturating_add(tokens);
+        Ok(BudgetReservation {
+            budgets: self,
+            conversation_id: conversation_id.clone(),
+            tokens,
+            settled: false,
+        })
+    }
+
+    /// Replace a reservation with the tokens actually consumed
+    ///
+    /// Conversations left with no usage stop being tracked.
+    fn settle(&self, conversation_id: &ConversationId, reserved: u64, actual: u64) -> u64 {
+        let mut state = self.state();
+        let entry = state.entry(conversation_id, self.max_conversations);
+        entry.used = entry.used.saturating_sub(reserved).saturating_add(actual);
+        let used = entry.used;
+        if used == 0 {
+            state.entries.remove(conversation_id);
+        }
+        used
+    }
+
+    /// Record tokens consumed by a conversation
+    ///
+    /// # Arguments
+    /// * `conversation_id` - The conversation that consumed the tokens
+    /// * `tokens` - Number of tokens consumed
+    ///
+    /// # Returns
+    /// Cumulative tokens consumed by the conversation
+    pub async fn record(&self, conversation_id: &ConversationId, tokens: u64) -> u64 {
+        let mut state = self.state();
+        let entry = state.entry(conversation_id, self.max_conversations);
+        entry.used = entry.used.saturating_add(tokens);
+        entry.used
+    }
+
+    /// Get cumulative tokens consumed by a conversation (0 if untracked)
+    pub async fn usage(&self, conversation_id: &ConversationId) -> u64 {
+        self.state()
+            .entries
+            .get(conversation_id)
+            .map_or(0, |entry| entry.used)
+    }
+
+    /// Reset a conversation's usage back to zero
+    ///
+    /// # Returns
+    /// `true` if the conversation was being tracked
+    pub async fn reset(&self, conversation_id: &ConversationId) -> bool {
+        let existed = self.state().entries.remove(conversation_id).is_some();
+        info!("Reset token budget for conversation {}", conversation_id);
+        existed
+    }
+
+    /// Lock the usage map, recovering it if a holder panicked
+    ///
+    /// Every update leaves the entries consistent, so a poisoned map is still valid.
+    fn state(&self) -> MutexGuard<'_, BudgetState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for ConversationBudgets {
+    fn default() -> Self {
+        Self::new(DEFAULT_CONVERSATION_TOKEN_BUDGET)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conversation(id: &str) -> ConversationId {
+        ConversationId::new(id.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_record_accumulates_usage() {
+        let budgets = ConversationBudgets::new(100);
+        let id = conversation("conv-1");
+
+        assert_eq!(budgets.record(&id, 30).await, 30);
+        assert_eq!(budgets.record(&id, 20).await, 50);
+        assert_eq!(budgets.usage(&id).await, 50);
+    }
+
+    #[tokio::test]
+    async fn test_reserve_rejects_exhausted_conversation() {
+        let budgets = ConversationBudgets::new(100);
+        let id = conversation("conv-1");
+
+        assert!(budgets.reserve(&id, 0).await.is_ok());
+        budgets.record(&id, 100).await;
+
+        match budgets.reserve(&id, 0).await.unwrap_err() {
+            SentinelError::BudgetExceeded {
+                conversation_id,
+                used,
+                limit,
+            } => {
+                assert_eq!(conversation_id, id);
+                assert_eq!(used, 100);
+                assert_eq!(limit, 100);
+            }
+            other => panic!("Expected BudgetExceeded, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conversations_are_isolated() {
+        let budgets = ConversationBudgets::new(100);
+        let exhausted = conversation("conv-1");
+        let fresh = conversation("conv-2");
+
+        budgets.record(&exhausted, 150).await;
+
+        assert!(budgets.reserve(&exhausted, 10).await.is_err());
+        assert!(budgets.reserve(&fresh, 10).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reservations_count_before_they_are_settled() {
+        let budgets = ConversationBudgets::new(100);
+        let id = conversation("conv-1");
+
+        // The first reservation exhausts the budget for a concurrent second one
+        let reservation = budgets.reserve(&id, 100).await.unwrap();
+        assert_eq!(reservation.tokens(), 100);
+        assert!(budgets.reserve(&id, 10).await.is_err());
+
+        // Settling replaces the reservation with the actual usage
+        assert_eq!(reservation.settle(40), 40);
+        assert_eq!(budgets.usage(&id).await, 40);
+        assert!(budgets.reserve(&id, 10).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_settle_with_zero_releases_reservation() {
+        let budgets = ConversationBudgets::new(100);
+        let id = conversation("conv-1");
+
+        budgets.record(&id, 20).await;
+        let reservation = budgets.reserve(&id, 50).await.unwrap();
+        assert_eq!(budgets.usage(&id).await, 70);
+
+        assert_eq!(reservation.settle(0), 20);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_reservation_is_released() {
+        let budgets = ConversationBudgets::new(100);
+        let id = conversation("conv-1");
+
+        budgets.record(&id, 20).await;
+        {
+            let _reservation = budgets.reserve(&id, 80).await.unwrap();
+            assert_eq!(budgets.usage(&id).await, 100);
+            assert!(budgets.reserve(&id, 10).await.is_err());
+        }
+
+        // A cancelled completion gives its reservation back
+        assert_eq!(budgets.usage(&id).await, 20);
+        assert!(budgets.reserve(&id, 10).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_released_conversations_stop_being_tracked() {
+        let budgets = ConversationBudgets::new(100);
+
+        for i in 0..10 {
+            let reservation = budgets
+                .reserve(&conversation(&format!("conv-{}", i)), 50)
+                .await
+                .unwrap();
+            drop(reservation);
+        }
+
+        assert_eq!(budgets.tracked_conversations(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_tracked_conversations_are_bounded() {
+        let budgets = ConversationBudgets::new(100).with_max_conversations(2);
+        let oldest = conversation("conv-1");
+        let recent = conversation("conv-2");
+
+        budgets.record(&oldest, 100).await;
+        budgets.record(&recent, 100).await;
+        budgets.record(&conversation("conv-3"), 10).await;
+
+        // The least recently used conversation is evicted to make room
+        assert_eq!(budgets.tracked_conversations(), 2);
+        assert_eq!(budgets.usage(&oldest).await, 0);
+        assert_eq!(budgets.usage(&recent).await, 100);
+        assert!(budgets.reserve(&recent, 10).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reset_clears_usage() {
+        let budgets = ConversationBudgets::new(100);
+        let id = conversation("conv-1");
+
+        budgets.record(&id, 150).await;
+        assert!(budgets.reset(&id).await);
+        assert_eq!(budgets.usage(&id).await, 0);
+        assert!(budgets.reserve(&id, 10).await.is_ok());
+
+        // Resetting an untracked conversation is a no-op
+        assert!(!budgets.reset(&conversation("unknown")).await);
+    }
+}
@@ -0,0 +1,198 @@
+// Per-conversation locks
+// Serializes completions within one conversation while different conversations run in parallel
+
+use crate::core::types::ConversationId;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+/// Hands out one async mutex per conversation
+///
+/// Entries are created on first use and dropped once no task holds or waits on them.
+/// The key type defaults to `ConversationId`; other resources needing the same
+/// per-key serialization (sessions, idempotency keys) use their own table, so a
+/// client-chosen ID can never name another resource's lock.
+#[derive(Debug)]
+pub struct ConversationLocks<K = ConversationId> {
+    /// Map of key to its lock
+    locks: Mutex<HashMap<K, Arc<AsyncMutex<()>>>>,
+}
+
+impl<K> Default for ConversationLocks<K> {
+    fn default() -> Self {
+        Self {
+            locks: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone> ConversationLocks<K> {
+    /// Create an empty lock table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait for exclusive access to a conversation
+    ///
+    /// # Arguments
+    /// * `conversation_id` - The conversation to lock
+    ///
+    /// # Returns
+    /// A guard that releases the conversation when dropped
+    ///
+    /// # Note
+    /// Cancelling the returned future while it waits still removes the table entry
+    /// if nobody else holds or awaits it.
+    pub async fn lock(&self, conversation_id: &K) -> ConversationGuard<'_, K> {
+        // Declared before the pending lock future, so on cancellation that future (and
+        // its reference to the lock) is dropped first and the entry can be removed
+        let entry = LockEntry {
+            locks: self,
+            conversation_id: conversation_id.clone(),
+        };
+        let lock = entry.acquire_handle();
+        ConversationGuard {
+            _guard: lock.lock_owned().await,
+            _entry: entry,
+        }
+    }
+
+    /// Get the number of conversations currently locked or awaited
+    pub fn active_count(&self) -> usize {
+        self.table().len()
+    }
+
+    /// Lock the table, recovering it if a holder panicked
+    ///
+    /// Every update leaves the map consistent, so a poisoned table is still valid.
+    fn table(&self) -> MutexGuard<'_, HashMap<K, Arc<AsyncMutex<()>>>> {
+        self.locks.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Interest of one task in a table entry; removes the entry once it is unused
+#[derive(Debug)]
+struct LockEntry<'a, K: Eq + Hash + Clone> {
+    locks: &'a ConversationLocks<K>,
+    conversation_id: K,
+}
+
+impl<K: Eq + Hash + Clone> LockEntry<'_, K> {
+    /// Get the entry's lock, creating it on first use
+    fn acquire_handle(&self) -> Arc<AsyncMutex<()>> {
+        self.locks
+            .table()
+            .entry(self.conversation_id.clone())
+            .or_default()
+            .clone()
+    }
+}
+
+impl<K: Eq + Hash + Clone> Drop for LockEntry<'_, K> {
+    fn drop(&mut self) {
+        let mut locks = self.locks.table();
+        // Only the table's own reference is left: nobody holds or awaits the lock
+        if locks
+            .get(&self.conversation_id)
+            .is_some_and(|lock| Arc::strong_count(lock) == 1)
+        {
+            locks.remove(&self.conversation_id);
+        }
+    }
+}
+
+/// Exclusive access to one conversation, released on drop
+#[derive(Debug)]
+pub struct ConversationGuard<'a, K: Eq + Hash + Clone = ConversationId> {
+    // Field order matters: the lock is released before the entry checks whether it is unused
+    _guard: OwnedMutexGuard<()>,
+    _entry: LockEntry<'a, K>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn conversation(id: &str) -> ConversationId {
+        ConversationId::new(id.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_same_conversation_serializes() {
+        let locks = Arc::new(ConversationLocks::new());
+        let id = conversation("conv-1");
+        let guard = locks.lock(&id).await;
+
+        let waiter = {
+            let locks = locks.clone();
+            let id = id.clone();
+            tokio::spawn(async move {
+                let _guard = locks.lock(&id).await;
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        drop(guard);
+        waiter.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_different_conversations_do_not_block() {
+        let locks = ConversationLocks::new();
+        let _first = locks.lock(&conversation("conv-1")).await;
+
+        let second = tokio::time::timeout(
+            Duration::from_millis(100),
+            locks.lock(&conversation("conv-2")),
+        )
+        .await;
+        assert!(second.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_released_locks_are_removed() {
+        let locks = ConversationLocks::new();
+        {
+            let _guard = locks.lock(&conversation("conv-1")).await;
+            assert_eq!(locks.active_count(), 1);
+        }
+        assert_eq!(locks.active_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_waiter_removes_its_entry() {
+        let locks = Arc::new(ConversationLocks::new());
+        let id = conversation("conv-1");
+        let guard = locks.lock(&id).await;
+
+        let waiter = {
+            let locks = locks.clone();
+            let id = id.clone();
+            tokio::spawn(async move {
+                let _guard = locks.lock(&id).await;
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // The waiter is woken but cancelled before it runs again
+        drop(guard);
+        waiter.abort();
+        assert!(waiter.await.unwrap_err().is_cancelled());
+        assert_eq!(locks.active_count(), 0);
+    }
+
+    #[test]
+    fn test_poisoned_table_is_recovered() {
+        let locks = ConversationLocks::<String>::new();
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _table = locks.locks.lock().unwrap();
+            panic!("poison the table");
+        }));
+        assert!(locks.locks.is_poisoned());
+
+        assert_eq!(locks.active_count(), 0);
+    }
+}
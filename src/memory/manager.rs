@@ -2,20 +2,33 @@
 // The Dreamer - coordinates the three-tier memory system
 
 use crate::core::error::SentinelError;
-use crate::core::traits::VectorStore;
+use crate::core::traits::{EmbeddingProvider, Summarizer, VectorStore};
 use crate::core::types::{AgentId, CanonicalMessage, MessageId};
+use crate::memory::conversation_lock::ConversationLocks;
 use crate::memory::medium_term::{ConversationSummary, MediumTermMemory};
+use crate::memory::recall::{
+    merge_tiered, term_overlap_score, MemoryTier, RecallContext, RecallSource, RecalledMemory,
+    TieredMemory, DEFAULT_MAX_RECALL_CONTEXT_TOKENS,
+};
 use crate::memory::short_term::{SharedShortTermMemory, ShortTermMemory};
+use crate::memory::summarizer::ConcatSummarizer;
+use crate::memory::token_counter::{SimpleTokenCounter, TokenCounter};
+use crate::memory::triggers::{
+    ConsolidationConfig, ConsolidationPriority, ConsolidationTrigger, TokenBudget,
+};
 use anyhow::{Context, Result};
-use chrono::Utc;
-use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
 use tokio::sync::watch;
+use tokio::sync::RwLock;
 use tokio::time::interval;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, instrument, warn, Span};
 
 /// Default check interval for the dreamer loop (30 seconds)
 pub const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(30);
@@ -23,6 +36,128 @@ pub const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(30);
 /// Default medium-term consolidation threshold (10 summaries)
 pub const DEFAULT_MEDIUM_TERM_THRESHOLD: usize = 10;
 
+/// Long-term metadata key holding the agent a memory belongs to
+pub const AGENT_ID_KEY: &str = "agent_id";
+
+/// Long-term metadata key holding the SHA-256 hex digest of a memory's content
+pub const CONTENT_HASH_KEY: &str = "content_hash";
+
+/// Hex-encoded SHA-256 digest of memory content, used to detect repeated facts
+fn content_hash(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Long-term metadata key holding the conversation a consolidated summary came from
+pub const CONVERSATION_ID_KEY: &str = "conversation_id";
+
+/// Point ID for an agent's long-term memory, derived from the agent and content hash so
+/// storing the same content again overwrites the existing point instead of adding another
+fn long_term_point_id(agent_id: AgentId, hash: &str) -> MessageId {
+    let digest = Sha256::new()
+        .chain_update(agent_id.to_string())
+        .chain_update(hash)
+        .finalize();
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    MessageId(uuid::Builder::from_custom_bytes(bytes).into_uuid())
+}
+
+/// What to do when a conversation reaches its maximum message count
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConversationLengthPolicy {
+    /// Consolidate the short-term buffer into medium-term memory, then continue
+    #[default]
+    Consolidate,
+    /// Reject further messages with `DomainViolation`
+    Reject,
+}
+
+/// Per-agent memory settings that override the manager's defaults
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgentMemoryProfile {
+    /// Whether the dreamer loop consolidates this agent's memory automatically
+    pub auto_consolidation: bool,
+}
+
+impl Default for AgentMemoryProfile {
+    fn default() -> Self {
+        Self {
+            auto_consolidation: true,
+        }
+    }
+}
+
+/// Consolidation step the dreamer loop runs for an agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsolidationKind {
+    /// Summarize the short-term buffer into medium-term memory
+    ShortToMedium,
+    /// Archive medium-term summaries into long-term memory
+    MediumToLong,
+}
+
+/// Consolidation found due by the dreamer loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingConsolidation {
+    /// Agent whose memory is consolidated
+    pub agent_id: AgentId,
+    /// Which tiers are consolidated
+    pub kind: ConsolidationKind,
+    /// How urgently the consolidation is needed
+    pub priority: ConsolidationPriority,
+}
+
+/// Snapshot of consolidation activity since the manager was created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ConsolidationStats {
+    /// Number of short-term to medium-term consolidations that moved messages
+    pub short_to_medium_count: u64,
+    /// Number of medium-term to long-term consolidations that processed summaries
+    pub medium_to_long_count: u64,
+    /// When short-term to medium-term consolidation last ran
+    pub last_short_to_medium: Option<DateTime<Utc>>,
+    /// When medium-term to long-term consolidation last ran
+    pub last_medium_to_long: Option<DateTime<Utc>>,
+}
+
+/// Lock-free consolidation counters; timestamps are Unix milliseconds (0 = never)
+#[derive(Debug, Default)]
+struct ConsolidationCounters {
+    short_to_medium_count: AtomicU64,
+    medium_to_long_count: AtomicU64,
+    last_short_to_medium_ms: AtomicI64,
+    last_medium_to_long_ms: AtomicI64,
+}
+
+impl ConsolidationCounters {
+    /// Count a consolidation run and stamp it with the current time
+    fn record(count: &AtomicU64, last_run_ms: &AtomicI64) {
+        count.fetch_add(1, Ordering::Relaxed);
+        last_run_ms.store(Utc::now().timestamp_millis(), Ordering::Relaxed);
+    }
+
+    /// Convert a stored timestamp back to a date, if the run ever happened
+    fn last_run(last_run_ms: &AtomicI64) -> Option<DateTime<Utc>> {
+        match last_run_ms.load(Ordering::Relaxed) {
+            0 => None,
+            ms => DateTime::from_timestamp_millis(ms),
+        }
+    }
+
+    /// Read all counters into a stats snapshot
+    fn snapshot(&self) -> ConsolidationStats {
+        ConsolidationStats {
+            short_to_medium_count: self.short_to_medium_count.load(Ordering::Relaxed),
+            medium_to_long_count: self.medium_to_long_count.load(Ordering::Relaxed),
+            last_short_to_medium: Self::last_run(&self.last_short_to_medium_ms),
+            last_medium_to_long: Self::last_run(&self.last_medium_to_long_ms),
+        }
+    }
+}
+
 /// Memory manager coordinating all three tiers of memory
 pub struct MemoryManager {
     /// Short-term memory instances per agent (thread-safe)
@@ -35,11 +170,37 @@ pub struct MemoryManager {
     check_interval: Duration,
     /// Medium-term consolidation threshold
     medium_term_threshold: usize,
+    /// Embedding provider used to turn recall queries into vectors
+    embedder: Option<Arc<dyn EmbeddingProvider>>,
+    /// Summarizer condensing short-term buffers into medium-term summaries
+    summarizer: Arc<dyn Summarizer>,
+    /// Interval at which the dreamer loop persists short-term buffers (disabled if `None`)
+    short_term_flush_interval: Option<Duration>,
+    /// Consolidation activity counters
+    counters: ConsolidationCounters,
+    /// Hard cap on messages in one agent's conversation, independent of token thresholds
+    max_conversation_messages: Option<usize>,
+    /// Behaviour once `max_conversation_messages` is reached
+    conversation_length_policy: ConversationLengthPolicy,
+    /// Per-agent overrides; agents without a profile use the defaults
+    agent_profiles: RwLock<HashMap<AgentId, AgentMemoryProfile>>,
+    /// Thresholds that decide which consolidations are due and at what priority
+    trigger: ConsolidationTrigger,
+    /// Tokens held in short-term memory; exceeding its limit forces consolidation
+    token_budget: RwLock<TokenBudget>,
+    /// Counter measuring short-term buffers against their limits and the token budget
+    token_counter: Arc<dyn TokenCounter>,
+    /// Per-agent locks so concurrent consolidations never summarize the same messages
+    consolidation_locks: ConversationLocks<AgentId>,
+    /// Maximum tokens of recalled content injected by `recall_context`
+    max_recall_context_tokens: u64,
 }
 
 impl MemoryManager {
     /// Create a new memory manager
     ///
+    /// Short-term buffers persisted by a previous run are rehydrated from the database.
+    ///
     /// # Arguments
     /// * `medium_term_path` - Path to the Sled database for medium-term memory
     /// * `long_term` - Vector store for long-term memory
@@ -47,16 +208,35 @@ impl MemoryManager {
     /// # Returns
     /// * `Ok(MemoryManager)` - Successfully created
     /// * `Err(anyhow::Error)` - Error if creation fails
-    pub fn new<P: AsRef<Path>>(medium_term_path: P, long_term: Arc<dyn VectorStore>) -> Result<Self> {
+    pub fn new<P: AsRef<Path>>(
+        medium_term_path: P,
+        long_term: Arc<dyn VectorStore>,
+    ) -> Result<Self> {
         let medium_term = MediumTermMemory::new(medium_term_path)
             .context("Failed to create medium-term memory")?;
 
+        let short_term_stores = rehydrate_short_term(&medium_term);
+        let mut token_budget = TokenBudget::new();
+        token_budget.update_short_term(short_term_total(&short_term_stores));
+
         Ok(Self {
-            short_term_stores: Arc::new(RwLock::new(HashMap::new())),
+            short_term_stores: Arc::new(RwLock::new(short_term_stores)),
             medium_term,
             long_term,
             check_interval: DEFAULT_CHECK_INTERVAL,
             medium_term_threshold: DEFAULT_MEDIUM_TERM_THRESHOLD,
+            embedder: None,
+            summarizer: Arc::new(ConcatSummarizer),
+            short_term_flush_interval: None,
+            counters: ConsolidationCounters::default(),
+            max_conversation_messages: None,
+            conversation_length_policy: ConversationLengthPolicy::default(),
+            agent_profiles: RwLock::new(HashMap::new()),
+            trigger: ConsolidationTrigger::new(),
+            token_budget: RwLock::new(token_budget),
+            token_counter: Arc::new(SimpleTokenCounter),
+            consolidation_locks: ConversationLocks::new(),
+            max_recall_context_tokens: DEFAULT_MAX_RECALL_CONTEXT_TOKENS,
         })
     }
 
@@ -80,250 +260,1460 @@ impl MemoryManager {
         let medium_term = MediumTermMemory::new(medium_term_path)
             .context("Failed to create medium-term memory")?;
 
+        let short_term_stores = rehydrate_short_term(&medium_term);
+        let mut token_budget = TokenBudget::new();
+        token_budget.update_short_term(short_term_total(&short_term_stores));
+
         Ok(Self {
-            short_term_stores: Arc::new(RwLock::new(HashMap::new())),
+            short_term_stores: Arc::new(RwLock::new(short_term_stores)),
             medium_term,
             long_term,
             check_interval,
             medium_term_threshold,
+            embedder: None,
+            summarizer: Arc::new(ConcatSummarizer),
+            short_term_flush_interval: None,
+            counters: ConsolidationCounters::default(),
+            max_conversation_messages: None,
+            conversation_length_policy: ConversationLengthPolicy::default(),
+            agent_profiles: RwLock::new(HashMap::new()),
+            trigger: ConsolidationTrigger::with_config(ConsolidationConfig {
+                medium_term_summary_threshold: medium_term_threshold,
+                ..ConsolidationConfig::default()
+            }),
+            token_budget: RwLock::new(token_budget),
+            token_counter: Arc::new(SimpleTokenCounter),
+            consolidation_locks: ConversationLocks::new(),
+            max_recall_context_tokens: DEFAULT_MAX_RECALL_CONTEXT_TOKENS,
         })
     }
 
-    /// Get or create short-term memory for an agent
+    /// Periodically persist every agent's short-term buffer from the dreamer loop
     ///
     /// # Arguments
-    /// * `agent_id` - The agent ID
+    /// * `flush_interval` - Interval between flushes; buffers are also flushed on shutdown
+    pub fn with_short_term_flush(mut self, flush_interval: Duration) -> Self {
+        self.short_term_flush_interval = Some(flush_interval);
+        self
+    }
+
+    /// Cap the number of messages in a conversation
     ///
-    /// # Returns
-    /// Shared short-term memory instance
-    pub async fn get_short_term(&self, agent_id: AgentId) -> SharedShortTermMemory {
-        let stores = self.short_term_stores.read().await;
-        if let Some(memory) = stores.get(&agent_id) {
-            return memory.clone();
-        }
-        drop(stores);
+    /// # Arguments
+    /// * `max_messages` - Maximum messages held in an agent's short-term buffer
+    /// * `policy` - Whether reaching the cap forces consolidation or rejects the message
+    pub fn with_max_conversation_messages(
+        mut self,
+        max_messages: usize,
+        policy: ConversationLengthPolicy,
+    ) -> Self {
+        self.max_conversation_messages = Some(max_messages);
+        self.conversation_length_policy = policy;
+        self
+    }
 
-        // Create new short-term memory for this agent
-        let mut stores = self.short_term_stores.write().await;
-        let memory = Arc::new(RwLock::new(ShortTermMemory::new()));
-        stores.insert(agent_id, memory.clone());
-        memory
+    /// Apply consolidation settings to the dreamer loop
+    ///
+    /// # Arguments
+    /// * `config` - Its summary threshold replaces the medium-term threshold; summaries
+    ///   older than its age threshold are consolidated regardless of count, and
+    ///   disabling auto-consolidation stops the dreamer from consolidating any agent
+    pub fn with_consolidation_config(mut self, config: ConsolidationConfig) -> Self {
+        self.medium_term_threshold = config.medium_term_summary_threshold;
+        self.trigger = ConsolidationTrigger::with_config(config);
+        self
     }
 
-    /// Check if short-term memory should be consolidated
+    /// Limit the tokens held in short-term memory across agents
     ///
     /// # Arguments
-    /// * `agent_id` - The agent ID
+    /// * `max_total_tokens` - Budget across every agent's short-term buffer; while it is
+    ///   exceeded the dreamer loop force-consolidates the agents holding the most
+    ///   short-term tokens
     ///
-    /// # Returns
-    /// `true` if consolidation is needed
-    pub async fn should_consolidate_short(&self, agent_id: AgentId) -> bool {
-        let memory = self.get_short_term(agent_id).await;
-        let guard = memory.read().await;
-        guard.should_consolidate()
+    /// # Note
+    /// Summaries are not counted: forced consolidation moves tokens into medium-term
+    /// memory, so counting them would keep the budget exceeded after every run.
+    pub fn with_token_budget(mut self, max_total_tokens: u64) -> Self {
+        self.token_budget.get_mut().max_total_tokens = Some(max_total_tokens);
+        self
     }
 
-    /// Check if medium-term memory should be consolidated
+    /// Count short-term tokens with a different strategy than `SimpleTokenCounter`
     ///
     /// # Arguments
-    /// * `agent_id` - The agent ID
+    /// * `token_counter` - Counter applied to every agent's short-term limits,
+    ///   consolidation threshold, and the token budget
     ///
-    /// # Returns
-    /// `true` if consolidation is needed
-    pub async fn should_consolidate_medium(&self, agent_id: AgentId) -> bool {
-        match self.medium_term.list_summaries(agent_id) {
-            Ok(summaries) => summaries.len() >= self.medium_term_threshold,
-            Err(e) => {
-                warn!("Failed to list summaries for agent {}: {}", agent_id, e);
-                false
+    /// # Note
+    /// Buffers rehydrated from the database are recounted with the new counter.
+    pub fn with_token_counter(mut self, token_counter: Arc<dyn TokenCounter>) -> Self {
+        // Only rehydrated buffers exist while the manager is being built
+        match self.short_term_stores.try_read() {
+            Ok(stores) => {
+                for (agent_id, memory) in stores.iter() {
+                    match memory.write() {
+                        Ok(mut guard) => guard.set_token_counter(Box::new(token_counter.clone())),
+                        Err(e) => warn!(
+                            "Short-term memory lock poisoned for agent {}: {}",
+                            agent_id, e
+                        ),
+                    }
+                }
+                let total = short_term_total(&stores);
+                self.token_budget.get_mut().update_short_term(total);
             }
+            Err(e) => warn!("Failed to recount rehydrated short-term buffers: {}", e),
         }
+        self.token_counter = token_counter;
+        self
     }
 
-    /// Consolidate short-term memory to medium-term memory
+    /// Attach the embedding provider used for recall
     ///
     /// # Arguments
-    /// * `agent_id` - The agent ID
+    /// * `embedder` - Provider whose output must match the long-term store's dimension
     ///
     /// # Returns
-    /// * `Ok(())` - Successfully consolidated
-    /// * `Err(anyhow::Error)` - Error during consolidation
-    pub async fn consolidate_short_to_medium(&self, agent_id: AgentId) -> Result<()> {
-        let memory = self.get_short_term(agent_id).await;
-        let messages = {
-            let mut guard = memory.write().await;
-            let msgs = guard.get_messages();
-            guard.clear().context("Failed to clear short-term memory")?;
-            msgs
-        };
+    /// * `Ok(MemoryManager)` - Provider attached
+    /// * `Err(anyhow::Error)` - `EmbeddingDimensionMismatch` if the provider's output
+    ///   dimension differs from the dimension the long-term collection was built with
+    pub fn with_embedding_provider(mut self, embedder: Arc<dyn EmbeddingProvider>) -> Result<Self> {
+        self.embedder = Some(embedder);
+        self.verify_embedding_dimension()?;
+        Ok(self)
+    }
 
-        if messages.is_empty() {
-            return Ok(());
-        }
+    /// Cap the recalled content injected by `recall_context`
+    ///
+    /// # Arguments
+    /// * `max_tokens` - Maximum tokens of recalled content, measured with the manager's
+    ///   token counter; the most relevant memories are kept
+    pub fn with_max_recall_context_tokens(mut self, max_tokens: u64) -> Self {
+        self.max_recall_context_tokens = max_tokens;
+        self
+    }
 
-        // Generate simple summary (concatenation for now)
-        let summary_text = generate_summary(&messages);
-        let conversation_id = uuid::Uuid::new_v4().to_string();
-        let message_count = messages.len() as u64;
+    /// Use a custom summarizer for short-to-medium consolidation (default: `ConcatSummarizer`)
+    pub fn with_summarizer(mut self, summarizer: Arc<dyn Summarizer>) -> Self {
+        self.summarizer = summarizer;
+        self
+    }
 
-        let summary = ConversationSummary::new(
-            agent_id,
-            conversation_id,
-            summary_text,
-            message_count,
-        );
+    /// Check that the embedding provider matches the long-term store's dimension
+    ///
+    /// # Returns
+    /// * `Ok(())` - Dimensions match, or either side does not report one
+    /// * `Err(SentinelError)` - `EmbeddingDimensionMismatch` with remediation guidance
+    pub fn verify_embedding_dimension(&self) -> Result<(), SentinelError> {
+        let (Some(embedder), Some(expected)) = (&self.embedder, self.long_term.dimension()) else {
+            return Ok(());
+        };
+        let actual = embedder.dimension();
+        if actual != expected {
+            error!(
+                "Embedding provider produces {} dimensions but long-term collection expects {}",
+                actual, expected
+            );
+            return Err(SentinelError::EmbeddingDimensionMismatch { expected, actual });
+        }
+        Ok(())
+    }
 
-        self.medium_term
-            .store_summary(summary)
-            .context("Failed to store summary in medium-term memory")?;
+    /// Recall long-term memories similar to a query
+    ///
+    /// # Arguments
+    /// * `query` - Text to search for
+    /// * `limit` - Maximum number of memories to return
+    ///
+    /// # Returns
+    /// * `Ok(Vec<(MessageId, f32)>)` - Matching memory IDs with similarity scores, highest first
+    /// * `Err(anyhow::Error)` - No embedding provider is configured, the provider's
+    ///   dimension does not match the collection (`EmbeddingDimensionMismatch`), or the
+    ///   embedding or search fails
+    #[instrument(
+        skip(self, query),
+        fields(query_len = query.len(), limit, result_count = tracing::field::Empty)
+    )]
+    pub async fn recall(&self, query: &str, limit: usize) -> Result<Vec<(MessageId, f32)>> {
+        self.recall_filtered(query, limit, None).await
+    }
 
-        info!(
-            "Consolidated {} messages from short-term to medium-term for agent {}",
-            message_count, agent_id
-        );
+    /// Embed a query and search long-term memory, optionally filtered on metadata
+    async fn recall_filtered(
+        &self,
+        query: &str,
+        limit: usize,
+        filters: Option<HashMap<String, String>>,
+    ) -> Result<Vec<(MessageId, f32)>> {
+        let embedder = self
+            .embedder
+            .as_ref()
+            .context("No embedding provider configured for recall")?;
+        // Fail before embedding rather than with a generic error from the store
+        self.verify_embedding_dimension()?;
 
-        Ok(())
+        let embedding = embedder
+            .embed(query)
+            .await
+            .context("Failed to embed recall query")?;
+        let results = self
+            .long_term
+            .search_filtered(embedding, limit, filters)
+            .await
+            .context("Failed to search long-term memory")?;
+        Span::current().record("result_count", results.len());
+        Ok(results)
     }
 
-    /// Consolidate medium-term memory to long-term memory
+    /// Recall memories relevant to a query from all three tiers
+    ///
+    /// Short-term messages and medium-term summaries are scored by term overlap with
+    /// the query; long-term results use vector similarity, are restricted to points
+    /// stored for the agent (`AGENT_ID_KEY`), and are skipped when no embedding provider
+    /// is configured. Scores are normalized per tier before merging.
     ///
     /// # Arguments
-    /// * `agent_id` - The agent ID
+    /// * `agent_id` - Agent whose memories are searched
+    /// * `query` - Text to search for
+    /// * `limit` - Maximum number of results to return
     ///
     /// # Returns
-    /// * `Ok(())` - Successfully consolidated
-    /// * `Err(anyhow::Error)` - Error during consolidation
-    pub async fn consolidate_medium_to_long(&self, agent_id: AgentId) -> Result<()> {
+    /// * `Ok(Vec<TieredMemory>)` - De-duplicated results tagged with their tier, highest score first
+    /// * `Err(anyhow::Error)` - Listing summaries or the long-term search fails
+    #[instrument(
+        skip(self, query),
+        fields(agent_id = %agent_id, query_len = query.len(), limit, result_count = tracing::field::Empty)
+    )]
+    pub async fn recall_all(
+        &self,
+        agent_id: AgentId,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<TieredMemory>> {
+        let mut results = Vec::new();
+
+        for msg in self.snapshot_short_term(agent_id).await {
+            let score = term_overlap_score(query, &msg.content);
+            if score > 0.0 {
+                results.push(TieredMemory {
+                    tier: MemoryTier::ShortTerm,
+                    source: RecallSource::Message(msg.id),
+                    content: Some(msg.content),
+                    score,
+                });
+            }
+        }
+
         let summaries = self
             .medium_term
             .list_summaries(agent_id)
             .context("Failed to list summaries")?;
+        for summary in summaries {
+            let score = term_overlap_score(query, &summary.summary);
+            if score > 0.0 {
+                results.push(TieredMemory {
+                    tier: MemoryTier::MediumTerm,
+                    source: RecallSource::Summary {
+                        conversation_id: summary.conversation_id,
+                    },
+                    content: Some(summary.summary),
+                    score,
+                });
+            }
+        }
 
-        if summaries.is_empty() {
-            return Ok(());
+        if self.embedder.is_some() {
+            let filters = HashMap::from([(AGENT_ID_KEY.to_string(), agent_id.to_string())]);
+            for (id, score) in self.recall_filtered(query, limit, Some(filters)).await? {
+                results.push(TieredMemory {
+                    tier: MemoryTier::LongTerm,
+                    source: RecallSource::Message(id),
+                    content: None,
+                    score,
+                });
+            }
+        } else {
+            debug!("No embedding provider configured; skipping long-term recall");
         }
 
-        // For now: Simple placeholder - would need embedding generation
-        // TODO: Generate embeddings for summaries and store in long-term
-        warn!(
-            "Medium-to-long consolidation not fully implemented (needs embedding generation) for agent {}",
-            agent_id
+        let merged = merge_tiered(results, limit);
+        Span::current().record("result_count", merged.len());
+        Ok(merged)
+    }
+
+    /// Recall memories relevant to a query and build the context message injecting them
+    ///
+    /// Results come from `recall_all`; only results with text can be injected, and they
+    /// are capped at `max_recall_context_tokens`, most relevant first.
+    ///
+    /// # Arguments
+    /// * `agent_id` - Agent whose memories are searched
+    /// * `query` - Text to search for
+    /// * `limit` - Maximum number of results to consider
+    ///
+    /// # Returns
+    /// * `Ok(Some(CanonicalMessage))` - System message holding the selected memories
+    /// * `Ok(None)` - Nothing relevant was recalled, or no memory fits within the cap
+    /// * `Err(anyhow::Error)` - Recall fails
+    pub async fn recall_context(
+        &self,
+        agent_id: AgentId,
+        query: &str,
+        limit: usize,
+    ) -> Result<Option<CanonicalMessage>> {
+        let memories: Vec<RecalledMemory> = self
+            .recall_all(agent_id, query, limit)
+            .await?
+            .into_iter()
+            .filter_map(RecalledMemory::from_tiered)
+            .collect();
+        let recall = RecallContext::with_token_counter(
+            self.max_recall_context_tokens,
+            Box::new(self.token_counter.clone()),
         );
+        Ok(recall.build_message(memories))
+    }
+
+    /// Embed content and store it in long-term memory unless the agent already has it
+    ///
+    /// # Arguments
+    /// * `agent_id` - Agent the memory belongs to
+    /// * `content` - Text to embed and store (e.g. a summary or message)
+    /// * `metadata` - Extra metadata stored with the point
+    ///
+    /// # Returns
+    /// * `Ok(MessageId)` - ID of the stored point, or of the existing point with identical
+    ///   content
+    /// * `Err(anyhow::Error)` - No embedding provider is configured, or the duplicate
+    ///   check, embedding or the upsert fails
+    ///
+    /// # Note
+    /// The content hash is stored under `CONTENT_HASH_KEY` (and the agent under
+    /// `AGENT_ID_KEY`), overriding those keys in `metadata`. The point ID is derived from
+    /// both, so content already stored for the agent is found by ID and neither embedded
+    /// nor upserted again; if two stores race, the second upsert overwrites the first.
+    #[instrument(
+        skip(self, content, metadata),
+        fields(agent_id = %agent_id, content_len = content.len())
+    )]
+    pub async fn store_long_term(
+        &self,
+        agent_id: AgentId,
+        content: &str,
+        mut metadata: HashMap<String, String>,
+    ) -> Result<MessageId> {
+        let embedder = self
+            .embedder
+            .as_ref()
+            .context("No embedding provider configured for long-term storage")?;
+        self.verify_embedding_dimension()?;
+
+        let hash = content_hash(content);
+        let id = long_term_point_id(agent_id, &hash);
+        if self
+            .long_term
+            .contains(id)
+            .await
+            .context("Failed to check long-term memory for duplicates")?
+        {
+            debug!(
+                "Skipping long-term upsert for agent {}: identical content stored as {}",
+                agent_id, id
+            );
+            return Ok(id);
+        }
 
-        // Note: In a full implementation, we would:
-        // 1. Generate embeddings for each summary
-        // 2. Store embeddings in long-term memory (Qdrant)
-        // 3. Optionally delete from medium-term after successful storage
+        let embedding = embedder
+            .embed(content)
+            .await
+            .context("Failed to embed long-term memory")?;
 
-        Ok(())
+        metadata.insert(AGENT_ID_KEY.to_string(), agent_id.to_string());
+        metadata.insert(CONTENT_HASH_KEY.to_string(), hash);
+        self.long_term
+            .upsert(id, embedding, metadata)
+            .await
+            .context("Failed to store long-term memory")?;
+        Ok(id)
     }
 
-    /// Run the dreamer loop (background consolidation task)
+    /// Get or create short-term memory for an agent
     ///
     /// # Arguments
-    /// * `shutdown_rx` - Shutdown signal receiver
+    /// * `agent_id` - The agent ID
     ///
     /// # Returns
-    /// * `Ok(())` - Graceful shutdown
-    /// * `Err(anyhow::Error)` - Error during operation
-    pub async fn run_dreamer_loop(&self, mut shutdown_rx: watch::Receiver<()>) -> Result<()> {
-        let mut check_interval = interval(self.check_interval);
+    /// Shared short-term memory instance
+    pub async fn get_short_term(&self, agent_id: AgentId) -> SharedShortTermMemory {
+        let stores = self.short_term_stores.read().await;
+        if let Some(memory) = stores.get(&agent_id) {
+            return memory.clone();
+        }
+        drop(stores);
+
+        // Create new short-term memory for this agent
+        let mut stores = self.short_term_stores.write().await;
+        stores
+            .entry(agent_id)
+            .or_insert_with(|| Arc::new(std::sync::RwLock::new(self.new_short_term())))
+            .clone()
+    }
 
-        info!("Dreamer loop started (check interval: {:?})", self.check_interval);
+    /// Create an empty short-term buffer counting tokens with the manager's counter
+    fn new_short_term(&self) -> ShortTermMemory {
+        ShortTermMemory::new().with_token_counter(Box::new(self.token_counter.clone()))
+    }
 
-        loop {
-            tokio::select! {
-                _ = check_interval.tick() => {
-                    // Get all agent IDs with short-term memory
-                    let agent_ids: Vec<AgentId> = {
-                        let stores = self.short_term_stores.read().await;
-                        stores.keys().copied().collect()
-                    };
-
-                    // Check each agent's memory
-                    for agent_id in agent_ids {
-                        // Check short-term consolidation
-                        if self.should_consolidate_short(agent_id).await {
-                            if let Err(e) = self.consolidate_short_to_medium(agent_id).await {
-                                error!("Failed to consolidate short-to-medium for agent {}: {}", agent_id, e);
-                            }
-                        }
+    /// Append a message to an agent's conversation, enforcing the length guard
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent ID
+    /// * `msg` - The message continuing the conversation
+    ///
+    /// # Returns
+    /// * `Ok(())` - Message appended (after a forced consolidation, if one was needed)
+    /// * `Err(anyhow::Error)` - `DomainViolation` if the conversation is at its cap under
+    ///   the reject policy or short-term limits are exceeded, or consolidation fails
+    #[instrument(skip(self, msg), fields(agent_id = %agent_id))]
+    pub async fn append_message(&self, agent_id: AgentId, msg: CanonicalMessage) -> Result<()> {
+        self.append_messages(agent_id, vec![msg]).await
+    }
 
-                        // Check medium-term consolidation
-                        if self.should_consolidate_medium(agent_id).await {
-                            if let Err(e) = self.consolidate_medium_to_long(agent_id).await {
-                                error!("Failed to consolidate medium-to-long for agent {}: {}", agent_id, e);
-                            }
+    /// Append messages to an agent's conversation as one unit, enforcing the length guard
+    ///
+    /// Either every message is appended or, on error, none is.
+    async fn append_messages(
+        &self,
+        agent_id: AgentId,
+        messages: Vec<CanonicalMessage>,
+    ) -> Result<()> {
+        let memory = self.get_short_term(agent_id).await;
+
+        if let Some(max_messages) = self.max_conversation_messages {
+            let count = memory
+                .read()
+                .map_err(|e| anyhow::anyhow!("Short-term memory lock poisoned: {}", e))?
+                .message_count();
+            if messages.len() > max_messages {
+                return Err(SentinelError::DomainViolation {
+                    rule: format!(
+                        "Conversation length limit exceeded by new messages alone: {} > {}",
+                        messages.len(),
+                        max_messages
+                    ),
+                }
+                .into());
+            }
+            if count + messages.len() > max_messages {
+                match self.conversation_length_policy {
+                    ConversationLengthPolicy::Reject => {
+                        warn!(
+                            "Rejected message for agent {}: conversation at {} messages",
+                            agent_id, count
+                        );
+                        return Err(SentinelError::DomainViolation {
+                            rule: format!(
+                                "Conversation length limit reached: {} >= {}",
+                                count, max_messages
+                            ),
                         }
+                        .into());
+                    }
+                    ConversationLengthPolicy::Consolidate => {
+                        info!(
+                            "Forcing consolidation for agent {}: conversation at {} messages",
+                            agent_id, count
+                        );
+                        self.consolidate_short_to_medium(agent_id).await?;
                     }
-                }
-                _ = shutdown_rx.changed() => {
-                    info!("Dreamer loop received shutdown signal");
-                    break;
                 }
             }
         }
 
-        info!("Dreamer loop stopped");
+        let (before, after) = {
+            let mut guard = memory
+                .write()
+                .map_err(|e| anyhow::anyhow!("Short-term memory lock poisoned: {}", e))?;
+            let before = guard.token_count();
+            guard.import_messages(messages)?;
+            (before, guard.token_count())
+        };
+        // Adjust the running total rather than recounting every agent on each append
+        let mut budget = self.token_budget.write().await;
+        let tokens = (budget.short_term_tokens + after).saturating_sub(before);
+        budget.update_short_term(tokens);
         Ok(())
     }
-}
 
-/// Generate a simple summary from messages (concatenation for now)
-///
-/// # Arguments
-/// * `messages` - Messages to summarize
-///
-/// # Returns
-/// Summary string
-fn generate_summary(messages: &[CanonicalMessage]) -> String {
-    // Simple implementation: concatenate all message content
-    // Future: Use LLM for intelligent summarization
-    messages
-        .iter()
-        .map(|msg| format!("{}: {}", msg.role, msg.content))
-        .collect::<Vec<_>>()
-        .join("\n")
-}
+    /// Get the token count of every agent's short-term buffer
+    async fn short_term_token_counts(&self) -> Vec<(AgentId, u64)> {
+        let stores: Vec<(AgentId, SharedShortTermMemory)> = {
+            let stores = self.short_term_stores.read().await;
+            stores
+                .iter()
+                .map(|(agent_id, memory)| (*agent_id, memory.clone()))
+                .collect()
+        };
+        stores
+            .into_iter()
+            .filter_map(|(agent_id, memory)| match memory.read() {
+                Ok(guard) => Some((agent_id, guard.token_count())),
+                Err(e) => {
+                    warn!(
+                        "Short-term memory lock poisoned for agent {}: {}",
+                        agent_id, e
+                    );
+                    None
+                }
+            })
+            .collect()
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::core::types::Role;
-    use std::sync::Arc;
-    use tempfile::TempDir;
+    /// Recompute the token budget from every agent's short-term buffer
+    ///
+    /// Appends adjust the budget incrementally; this recount corrects any drift after
+    /// consolidations and on each dreamer tick.
+    async fn refresh_token_budget(&self) {
+        let tokens = self
+            .short_term_token_counts()
+            .await
+            .iter()
+            .map(|(_, tokens)| tokens)
+            .sum();
+        self.token_budget.write().await.update_short_term(tokens);
+    }
 
-    // Mock vector store for testing
-    struct MockVectorStore;
+    /// Get the tokens currently held in short-term memory across agents
+    ///
+    /// # Returns
+    /// `(total, usage)` where `usage` is the percentage (0-100) of the configured budget
+    /// in use, or `None` if the budget is unlimited
+    pub async fn budget_status(&self) -> (u64, Option<f64>) {
+        let budget = self.token_budget.read().await;
+        (budget.total(), budget.usage_percentage())
+    }
 
-    #[async_trait::async_trait]
-    impl VectorStore for MockVectorStore {
-        async fn upsert(
-            &self,
-            _id: MessageId,
-            _embedding: Vec<f32>,
-            _metadata: HashMap<String, String>,
-        ) -> Result<(), SentinelError> {
-            Ok(())
-        }
+    /// Map a client session ID to the agent ID that owns its short-term buffer
+    ///
+    /// The mapping is deterministic, so a session's persisted buffer is found again
+    /// after a restart.
+    pub fn session_agent_id(session_id: &str) -> AgentId {
+        let digest = Sha256::digest(format!("session:{}", session_id).as_bytes());
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&digest[..16]);
+        AgentId(uuid::Builder::from_custom_bytes(bytes).into_uuid())
+    }
 
-        async fn search(
-            &self,
-            _query_embedding: Vec<f32>,
-            _limit: usize,
-        ) -> Result<Vec<MessageId>, SentinelError> {
-            Ok(Vec::new())
-        }
+    /// Get the conversation history of a client session
+    ///
+    /// # Arguments
+    /// * `session_id` - Client-supplied session ID
+    ///
+    /// # Returns
+    /// The session's messages in chronological order (empty for a new session)
+    pub async fn session_history(&self, session_id: &str) -> Vec<CanonicalMessage> {
+        self.snapshot_short_term(Self::session_agent_id(session_id))
+            .await
     }
 
-    #[tokio::test]
-    async fn test_memory_manager_creation() {
-        let temp_dir = TempDir::new().unwrap();
-        let path = temp_dir.path().join("sled_test");
-        let long_term: Arc<dyn VectorStore> = Arc::new(MockVectorStore);
+    /// Append messages to a client session, creating its buffer if needed
+    ///
+    /// # Arguments
+    /// * `session_id` - Client-supplied session ID
+    /// * `messages` - Messages in chronological order
+    ///
+    /// # Returns
+    /// * `Ok(())` - All messages appended
+    /// * `Err(anyhow::Error)` - The messages were rejected by the conversation length
+    ///   guard or short-term limits; none of them is appended
+    pub async fn append_session_messages(
+        &self,
+        session_id: &str,
+        messages: Vec<CanonicalMessage>,
+    ) -> Result<()> {
+        self.append_messages(Self::session_agent_id(session_id), messages)
+            .await
+    }
 
-        let manager = MemoryManager::new(path, long_term);
-        assert!(manager.is_ok());
+    /// Copy an agent's short-term buffer
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent ID
+    ///
+    /// # Returns
+    /// The buffered messages in chronological order (empty if the agent has none)
+    pub async fn snapshot_short_term(&self, agent_id: AgentId) -> Vec<CanonicalMessage> {
+        match self.read_short_term(agent_id).await {
+            Ok(messages) => messages,
+            Err(e) => {
+                warn!("{:#}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Read an agent's short-term messages, telling an empty buffer from an unreadable one
+    ///
+    /// # Returns
+    /// * `Ok(Vec<CanonicalMessage>)` - The messages (empty if the agent has no buffer)
+    /// * `Err(anyhow::Error)` - The agent's short-term memory lock is poisoned
+    async fn read_short_term(&self, agent_id: AgentId) -> Result<Vec<CanonicalMessage>> {
+        let memory = match self.short_term_stores.read().await.get(&agent_id) {
+            Some(memory) => memory.clone(),
+            None => return Ok(Vec::new()),
+        };
+        let messages = memory
+            .read()
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "Short-term memory lock poisoned for agent {}: {}",
+                    agent_id,
+                    e
+                )
+            })?
+            .get_messages();
+        Ok(messages)
+    }
+
+    /// Export an agent's short-term conversation in the OpenAI chat format
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent ID
+    ///
+    /// # Returns
+    /// `{"messages": [...]}` as produced by `ShortTermMemory::export_openai` (no
+    /// messages if the agent has no buffer)
+    pub async fn export_openai(&self, agent_id: AgentId) -> serde_json::Value {
+        let memory = match self.short_term_stores.read().await.get(&agent_id) {
+            Some(memory) => memory.clone(),
+            None => return ShortTermMemory::new().export_openai(),
+        };
+        let exported = match memory.read() {
+            Ok(guard) => guard.export_openai(),
+            Err(e) => {
+                warn!(
+                    "Short-term memory lock poisoned for agent {}: {}",
+                    agent_id, e
+                );
+                ShortTermMemory::new().export_openai()
+            }
+        };
+        exported
+    }
+
+    /// Replace an agent's short-term buffer with previously snapshotted messages
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent ID
+    /// * `messages` - Messages in chronological order
+    ///
+    /// # Returns
+    /// * `Ok(())` - Buffer restored
+    /// * `Err(anyhow::Error)` - Error if the messages exceed short-term limits; the
+    ///   previous buffer is left in place
+    pub async fn restore_short_term(
+        &self,
+        agent_id: AgentId,
+        messages: Vec<CanonicalMessage>,
+    ) -> Result<()> {
+        let mut restored = self.new_short_term();
+        restored
+            .import_messages(messages)
+            .context("Failed to restore short-term memory")?;
+
+        let memory = self.get_short_term(agent_id).await;
+        *memory
+            .write()
+            .map_err(|e| anyhow::anyhow!("Short-term memory lock poisoned: {}", e))? = restored;
+        self.refresh_token_budget().await;
+        Ok(())
+    }
+
+    /// Persist every agent's short-term buffer to the database
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of agents whose buffers were written
+    /// * `Err(anyhow::Error)` - Error if storing or flushing fails
+    ///
+    /// # Note
+    /// A buffer that cannot be read (poisoned lock) is skipped, leaving its last
+    /// persisted copy in place rather than deleting it as if it were empty.
+    #[instrument(skip(self), fields(agent_count = tracing::field::Empty))]
+    pub async fn flush_short_term(&self) -> Result<usize> {
+        let agent_ids: Vec<AgentId> = {
+            let stores = self.short_term_stores.read().await;
+            stores.keys().copied().collect()
+        };
+
+        Span::current().record("agent_count", agent_ids.len());
+        let mut written = 0;
+        for agent_id in agent_ids {
+            let messages = match self.read_short_term(agent_id).await {
+                Ok(messages) => messages,
+                Err(e) => {
+                    warn!("Skipping short-term flush: {:#}", e);
+                    continue;
+                }
+            };
+            if messages.is_empty() {
+                self.medium_term
+                    .delete_short_term_buffer(agent_id)
+                    .context("Failed to delete short-term buffer")?;
+            } else {
+                self.medium_term
+                    .store_short_term_buffer(agent_id, &messages)
+                    .context("Failed to persist short-term buffer")?;
+                written += 1;
+            }
+        }
+        self.medium_term
+            .flush()
+            .context("Failed to flush medium-term memory")?;
+
+        Ok(written)
+    }
+
+    /// Get consolidation counters and last-run timestamps
+    pub fn stats(&self) -> ConsolidationStats {
+        self.counters.snapshot()
+    }
+
+    /// Get the long-term vector store shared across all agents
+    pub fn long_term(&self) -> &Arc<dyn VectorStore> {
+        &self.long_term
+    }
+
+    /// Check if short-term memory should be consolidated
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent ID
+    ///
+    /// # Returns
+    /// `true` if consolidation is needed
+    pub async fn should_consolidate_short(&self, agent_id: AgentId) -> bool {
+        let memory = self.get_short_term(agent_id).await;
+        let should_consolidate = match memory.read() {
+            Ok(guard) => guard.should_consolidate(),
+            Err(e) => {
+                warn!(
+                    "Short-term memory lock poisoned for agent {}: {}",
+                    agent_id, e
+                );
+                false
+            }
+        };
+        should_consolidate
+    }
+
+    /// Check if medium-term memory should be consolidated
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent ID
+    ///
+    /// # Returns
+    /// `true` if the agent has at least the threshold number of summaries, or any
+    /// summary older than the configured age threshold
+    pub async fn should_consolidate_medium(&self, agent_id: AgentId) -> bool {
+        match self.medium_term.list_summaries(agent_id) {
+            Ok(summaries) => {
+                summaries.len() >= self.medium_term_threshold
+                    || !self.stale_summaries(agent_id).is_empty()
+            }
+            Err(e) => {
+                warn!("Failed to list summaries for agent {}: {}", agent_id, e);
+                false
+            }
+        }
+    }
+
+    /// Get an agent's summaries older than the configured age threshold
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent ID
+    ///
+    /// # Returns
+    /// Stale summaries, oldest first (empty if listing fails or the threshold is too
+    /// large to represent)
+    pub fn stale_summaries(&self, agent_id: AgentId) -> Vec<ConversationSummary> {
+        let Ok(max_age) =
+            chrono::Duration::from_std(self.trigger.config().medium_term_age_threshold)
+        else {
+            return Vec::new();
+        };
+        match self
+            .medium_term
+            .list_summaries_older_than(agent_id, max_age, Utc::now())
+        {
+            Ok(summaries) => summaries,
+            Err(e) => {
+                warn!(
+                    "Failed to list stale summaries for agent {}: {}",
+                    agent_id, e
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    /// Consolidate short-term memory to medium-term memory
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent ID
+    ///
+    /// # Returns
+    /// * `Ok(())` - Successfully consolidated
+    /// * `Err(anyhow::Error)` - Error during consolidation
+    ///
+    /// # Note
+    /// A snapshot of the buffer is summarized while the messages stay in short-term
+    /// memory, so they remain recallable and persisted during a slow summarizer call.
+    /// Only the summarized messages are removed, once the summary is stored; messages
+    /// appended in the meantime are kept for the next consolidation.
+    #[instrument(
+        skip(self),
+        fields(agent_id = %agent_id, message_count = tracing::field::Empty)
+    )]
+    pub async fn consolidate_short_to_medium(&self, agent_id: AgentId) -> Result<()> {
+        let _consolidating = self.consolidation_locks.lock(&agent_id).await;
+        let memory = self.get_short_term(agent_id).await;
+        let messages = memory
+            .read()
+            .map_err(|e| anyhow::anyhow!("Short-term memory lock poisoned: {}", e))?
+            .get_messages();
+        Span::current().record("message_count", messages.len());
+
+        if messages.is_empty() {
+            return Ok(());
+        }
+
+        let summary_text = match self.summarizer.summarize(&messages).await {
+            Ok(summary) => summary,
+            Err(e) => {
+                warn!(
+                    "Summarizer failed for agent {}, falling back to concatenation: {}",
+                    agent_id, e
+                );
+                ConcatSummarizer.summarize(&messages).await?
+            }
+        };
+        let conversation_id = uuid::Uuid::new_v4().to_string();
+        let message_count = messages.len() as u64;
+
+        let summary =
+            ConversationSummary::new(agent_id, conversation_id, summary_text, message_count);
+
+        self.medium_term
+            .store_summary(summary)
+            .context("Failed to store summary in medium-term memory")?;
+
+        let summarized: HashSet<MessageId> = messages.iter().map(|msg| msg.id).collect();
+        let remaining = {
+            let mut guard = memory
+                .write()
+                .map_err(|e| anyhow::anyhow!("Short-term memory lock poisoned: {}", e))?;
+            guard.remove_messages(&summarized);
+            guard.get_messages()
+        };
+        // The summarized messages are now covered by the summary; don't rehydrate them
+        // on restart
+        if remaining.is_empty() {
+            self.medium_term
+                .delete_short_term_buffer(agent_id)
+                .context("Failed to delete persisted short-term buffer")?;
+        } else {
+            self.medium_term
+                .store_short_term_buffer(agent_id, &remaining)
+                .context("Failed to persist remaining short-term buffer")?;
+        }
+
+        ConsolidationCounters::record(
+            &self.counters.short_to_medium_count,
+            &self.counters.last_short_to_medium_ms,
+        );
+        info!(
+            "Consolidated {} messages from short-term to medium-term for agent {}",
+            message_count, agent_id
+        );
+        self.refresh_token_budget().await;
+
+        Ok(())
+    }
+
+    /// Consolidate medium-term memory to long-term memory
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent ID
+    ///
+    /// # Returns
+    /// * `Ok(())` - Successfully consolidated
+    /// * `Err(anyhow::Error)` - Error during consolidation
+    ///
+    /// # Note
+    /// Each summary is stored through `store_long_term` with its conversation under
+    /// `CONVERSATION_ID_KEY`, then deleted from medium-term memory, so it is embedded
+    /// only once. Summaries stored before a failure are already removed; the rest are
+    /// retried on the next run. Consolidation is skipped when no embedding provider is
+    /// configured.
+    #[instrument(
+        skip(self),
+        fields(agent_id = %agent_id, summary_count = tracing::field::Empty)
+    )]
+    pub async fn consolidate_medium_to_long(&self, agent_id: AgentId) -> Result<()> {
+        let summaries = self
+            .medium_term
+            .list_summaries(agent_id)
+            .context("Failed to list summaries")?;
+        Span::current().record("summary_count", summaries.len());
+
+        if summaries.is_empty() {
+            return Ok(());
+        }
+
+        if self.embedder.is_none() {
+            warn!(
+                "Skipping medium-to-long consolidation for agent {}: no embedding provider configured",
+                agent_id
+            );
+            return Ok(());
+        }
+
+        for summary in &summaries {
+            let mut metadata = summary.metadata.clone();
+            metadata.insert(
+                CONVERSATION_ID_KEY.to_string(),
+                summary.conversation_id.clone(),
+            );
+            self.store_long_term(agent_id, &summary.summary, metadata)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to store summary of conversation {} in long-term memory",
+                        summary.conversation_id
+                    )
+                })?;
+            self.medium_term
+                .delete_summary(agent_id, &summary.conversation_id)
+                .with_context(|| {
+                    format!(
+                        "Failed to delete consolidated summary of conversation {}",
+                        summary.conversation_id
+                    )
+                })?;
+        }
+
+        ConsolidationCounters::record(
+            &self.counters.medium_to_long_count,
+            &self.counters.last_medium_to_long_ms,
+        );
+        info!(
+            "Consolidated {} summaries from medium-term to long-term for agent {}",
+            summaries.len(),
+            agent_id
+        );
+        self.refresh_token_budget().await;
+
+        Ok(())
+    }
+
+    /// Set the memory profile for an agent
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent the profile applies to
+    /// * `profile` - Settings overriding the manager's defaults for this agent
+    pub async fn set_agent_profile(&self, agent_id: AgentId, profile: AgentMemoryProfile) {
+        self.agent_profiles.write().await.insert(agent_id, profile);
+    }
+
+    /// Get the memory profile for an agent (defaults if none was set)
+    pub async fn agent_profile(&self, agent_id: AgentId) -> AgentMemoryProfile {
+        self.agent_profiles
+            .read()
+            .await
+            .get(&agent_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Priority of consolidating an agent's short-term buffer, if it is due
+    ///
+    /// The trigger's thresholds set the priority; a buffer past its own consolidation
+    /// threshold but below the trigger's is still due at `High`.
+    async fn short_term_priority(&self, agent_id: AgentId) -> Option<ConsolidationPriority> {
+        let memory = self.get_short_term(agent_id).await;
+        let priority = match memory.read() {
+            Ok(guard) => self
+                .trigger
+                .should_consolidate_short(guard.token_count(), guard.message_count())
+                .or(guard
+                    .should_consolidate()
+                    .then_some(ConsolidationPriority::High)),
+            Err(e) => {
+                warn!(
+                    "Short-term memory lock poisoned for agent {}: {}",
+                    agent_id, e
+                );
+                None
+            }
+        };
+        priority
+    }
+
+    /// Priority of consolidating an agent's medium-term summaries, if it is due
+    ///
+    /// Reaching the summary threshold is `Medium`; only having summaries past the age
+    /// threshold is `Low` maintenance.
+    fn medium_term_priority(&self, agent_id: AgentId) -> Option<ConsolidationPriority> {
+        let summary_count = match self.medium_term.list_summaries(agent_id) {
+            Ok(summaries) => summaries.len(),
+            Err(e) => {
+                warn!("Failed to list summaries for agent {}: {}", agent_id, e);
+                return None;
+            }
+        };
+        self.trigger
+            .should_consolidate_medium(summary_count)
+            .or((!self.stale_summaries(agent_id).is_empty()).then_some(ConsolidationPriority::Low))
+    }
+
+    /// Gather the consolidations currently due across all agents
+    ///
+    /// Agents whose profile disables auto-consolidation are skipped.
+    ///
+    /// # Returns
+    /// Pending consolidations, most urgent first; for equal priority an agent's
+    /// short-to-medium step precedes its medium-to-long step
+    pub async fn pending_consolidations(&self) -> Vec<PendingConsolidation> {
+        // Get all agent IDs with short-term memory
+        let agent_ids: Vec<AgentId> = {
+            let stores = self.short_term_stores.read().await;
+            stores.keys().copied().collect()
+        };
+
+        let mut pending = Vec::new();
+        for agent_id in agent_ids {
+            if !self.agent_profile(agent_id).await.auto_consolidation {
+                continue;
+            }
+
+            if let Some(priority) = self.short_term_priority(agent_id).await {
+                pending.push(PendingConsolidation {
+                    agent_id,
+                    kind: ConsolidationKind::ShortToMedium,
+                    priority,
+                });
+            }
+            if let Some(priority) = self.medium_term_priority(agent_id) {
+                pending.push(PendingConsolidation {
+                    agent_id,
+                    kind: ConsolidationKind::MediumToLong,
+                    priority,
+                });
+            }
+        }
+
+        // Stable sort keeps the short-before-medium order within a priority
+        pending.sort_by_key(|consolidation| std::cmp::Reverse(consolidation.priority));
+        pending
+    }
+
+    /// Run one round of consolidation checks across all agents
+    ///
+    /// Nothing is consolidated if auto-consolidation is disabled in the consolidation
+    /// config. Due consolidations run in priority order, so memory-pressured agents are
+    /// handled before maintenance; a summary created this round is considered next round.
+    ///
+    /// If the token budget is still exceeded afterwards, the agents holding the most
+    /// short-term tokens are force-consolidated at `Critical` priority until the tokens
+    /// moved out of short-term memory cover the overage.
+    ///
+    /// # Returns
+    /// The consolidations attempted, in the order they ran
+    async fn run_consolidation_checks(&self) -> Vec<PendingConsolidation> {
+        if !self.trigger.config().enable_auto_consolidation {
+            return Vec::new();
+        }
+
+        let mut pending = self.pending_consolidations().await;
+        self.run_consolidations(&pending).await;
+
+        self.refresh_token_budget().await;
+        let forced = self.budget_pressure_consolidations().await;
+        self.run_consolidations(&forced).await;
+        pending.extend(forced);
+        pending
+    }
+
+    /// Pick the short-term buffers to force-consolidate while over the token budget
+    ///
+    /// # Returns
+    /// `Critical` short-to-medium consolidations for the agents holding the most
+    /// short-term tokens, until their tokens cover the overage (empty if within budget)
+    async fn budget_pressure_consolidations(&self) -> Vec<PendingConsolidation> {
+        let overage = {
+            let budget = self.token_budget.read().await;
+            if !budget.exceeds_budget() {
+                return Vec::new();
+            }
+            budget.total() - budget.max_total_tokens.unwrap_or_default()
+        };
+
+        let mut candidates = Vec::new();
+        for (agent_id, tokens) in self.short_term_token_counts().await {
+            if tokens > 0 && self.agent_profile(agent_id).await.auto_consolidation {
+                candidates.push((agent_id, tokens));
+            }
+        }
+        candidates.sort_by_key(|(_, tokens)| std::cmp::Reverse(*tokens));
+
+        let mut relieved = 0;
+        let mut forced = Vec::new();
+        for (agent_id, tokens) in candidates {
+            if relieved >= overage {
+                break;
+            }
+            relieved += tokens;
+            forced.push(PendingConsolidation {
+                agent_id,
+                kind: ConsolidationKind::ShortToMedium,
+                priority: ConsolidationPriority::Critical,
+            });
+        }
+        if !forced.is_empty() {
+            warn!(
+                "Token budget exceeded by {} tokens, forcing consolidation of {} agent(s)",
+                overage,
+                forced.len()
+            );
+        }
+        forced
+    }
+
+    /// Run consolidations in the given order, logging failures
+    async fn run_consolidations(&self, consolidations: &[PendingConsolidation]) {
+        for consolidation in consolidations {
+            let agent_id = consolidation.agent_id;
+            debug!(
+                "Running {:?} consolidation for agent {} ({} priority)",
+                consolidation.kind,
+                agent_id,
+                consolidation.priority.name()
+            );
+            match consolidation.kind {
+                ConsolidationKind::ShortToMedium => {
+                    if let Err(e) = self.consolidate_short_to_medium(agent_id).await {
+                        error!(
+                            "Failed to consolidate short-to-medium for agent {}: {}",
+                            agent_id, e
+                        );
+                    }
+                }
+                ConsolidationKind::MediumToLong => {
+                    if let Err(e) = self.consolidate_medium_to_long(agent_id).await {
+                        error!(
+                            "Failed to consolidate medium-to-long for agent {}: {}",
+                            agent_id, e
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Run the dreamer loop (background consolidation task)
+    ///
+    /// # Arguments
+    /// * `shutdown_rx` - Shutdown signal receiver
+    ///
+    /// # Returns
+    /// * `Ok(())` - Graceful shutdown
+    /// * `Err(anyhow::Error)` - Error during operation
+    pub async fn run_dreamer_loop(&self, mut shutdown_rx: watch::Receiver<()>) -> Result<()> {
+        let mut check_interval = interval(self.check_interval);
+        let mut flush_interval = interval(
+            self.short_term_flush_interval
+                .unwrap_or(self.check_interval),
+        );
+
+        info!(
+            "Dreamer loop started (check interval: {:?}, short-term flush: {:?})",
+            self.check_interval, self.short_term_flush_interval
+        );
+
+        loop {
+            tokio::select! {
+                _ = check_interval.tick() => {
+                    self.run_consolidation_checks().await;
+                }
+                _ = flush_interval.tick(), if self.short_term_flush_interval.is_some() => {
+                    if let Err(e) = self.flush_short_term().await {
+                        error!("Failed to flush short-term memory: {}", e);
+                    }
+                }
+                _ = shutdown_rx.changed() => {
+                    info!("Dreamer loop received shutdown signal");
+                    break;
+                }
+            }
+        }
+
+        if self.short_term_flush_interval.is_some() {
+            if let Err(e) = self.flush_short_term().await {
+                error!("Failed to flush short-term memory on shutdown: {}", e);
+            }
+        }
+
+        info!("Dreamer loop stopped");
+        Ok(())
+    }
+}
+
+/// Sum the token counts of short-term buffers, skipping any with a poisoned lock
+fn short_term_total(stores: &HashMap<AgentId, SharedShortTermMemory>) -> u64 {
+    stores
+        .values()
+        .filter_map(|memory| memory.read().ok().map(|guard| guard.token_count()))
+        .sum()
+}
+
+/// Rebuild short-term buffers persisted by a previous run
+///
+/// # Arguments
+/// * `medium_term` - Medium-term memory holding the persisted buffers
+///
+/// # Returns
+/// Short-term memory per agent; unreadable or oversized buffers are skipped with a warning
+fn rehydrate_short_term(medium_term: &MediumTermMemory) -> HashMap<AgentId, SharedShortTermMemory> {
+    let buffers = match medium_term.load_short_term_buffers() {
+        Ok(buffers) => buffers,
+        Err(e) => {
+            warn!("Failed to load persisted short-term buffers: {}", e);
+            return HashMap::new();
+        }
+    };
+
+    let mut stores = HashMap::new();
+    for (agent_id, messages) in buffers {
+        let mut memory = ShortTermMemory::new();
+        let count = messages.len();
+        if let Err(e) = messages
+            .into_iter()
+            .try_for_each(|msg| memory.append_message(msg))
+        {
+            warn!(
+                "Skipping persisted short-term buffer for agent {}: {}",
+                agent_id, e
+            );
+            continue;
+        }
+        info!(
+            "Rehydrated {} short-term messages for agent {}",
+            count, agent_id
+        );
+        stores.insert(agent_id, Arc::new(std::sync::RwLock::new(memory)));
+    }
+    stores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Role;
+    use crate::memory::short_term::DEFAULT_MAX_MESSAGES;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    // Mock vector store for testing
+    struct MockVectorStore;
+
+    #[async_trait::async_trait]
+    impl VectorStore for MockVectorStore {
+        async fn upsert(
+            &self,
+            _id: MessageId,
+            _embedding: Vec<f32>,
+            _metadata: HashMap<String, String>,
+        ) -> Result<(), SentinelError> {
+            Ok(())
+        }
+
+        async fn search_filtered(
+            &self,
+            _query_embedding: Vec<f32>,
+            _limit: usize,
+            _filters: Option<HashMap<String, String>>,
+        ) -> Result<Vec<(MessageId, f32)>, SentinelError> {
+            Ok(Vec::new())
+        }
+
+        async fn delete(&self, _id: MessageId) -> Result<(), SentinelError> {
+            Ok(())
+        }
+    }
+
+    // Vector store reporting a fixed collection dimension
+    struct DimensionedVectorStore(usize);
+
+    #[async_trait::async_trait]
+    impl VectorStore for DimensionedVectorStore {
+        async fn upsert(
+            &self,
+            _id: MessageId,
+            _embedding: Vec<f32>,
+            _metadata: HashMap<String, String>,
+        ) -> Result<(), SentinelError> {
+            Ok(())
+        }
+
+        async fn search_filtered(
+            &self,
+            query_embedding: Vec<f32>,
+            _limit: usize,
+            _filters: Option<HashMap<String, String>>,
+        ) -> Result<Vec<(MessageId, f32)>, SentinelError> {
+            if query_embedding.len() != self.0 {
+                return Err(SentinelError::InvalidMessage {
+                    reason: "wrong dimension".to_string(),
+                });
+            }
+            Ok(vec![(MessageId::new(), 0.9)])
+        }
+
+        async fn delete(&self, _id: MessageId) -> Result<(), SentinelError> {
+            Ok(())
+        }
+
+        fn dimension(&self) -> Option<usize> {
+            Some(self.0)
+        }
+    }
+
+    // Embedding provider producing zero vectors of a fixed dimension
+    struct FixedEmbedder(usize);
+
+    #[async_trait::async_trait]
+    impl EmbeddingProvider for FixedEmbedder {
+        async fn embed(&self, _text: &str) -> Result<Vec<f32>, SentinelError> {
+            Ok(vec![0.0; self.0])
+        }
+
+        fn dimension(&self) -> usize {
+            self.0
+        }
+    }
+
+    // Embedder returning zero vectors and counting how often it is called
+    #[derive(Default)]
+    struct CountingEmbedder {
+        calls: AtomicU64,
+    }
+
+    #[async_trait::async_trait]
+    impl EmbeddingProvider for CountingEmbedder {
+        async fn embed(&self, _text: &str) -> Result<Vec<f32>, SentinelError> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            Ok(vec![0.0; 4])
+        }
+
+        fn dimension(&self) -> usize {
+            4
+        }
+    }
+
+    // Vector store returning fixed search results
+    struct SeededVectorStore(Vec<(MessageId, f32)>);
+
+    #[async_trait::async_trait]
+    impl VectorStore for SeededVectorStore {
+        async fn upsert(
+            &self,
+            _id: MessageId,
+            _embedding: Vec<f32>,
+            _metadata: HashMap<String, String>,
+        ) -> Result<(), SentinelError> {
+            Ok(())
+        }
+
+        async fn search_filtered(
+            &self,
+            _query_embedding: Vec<f32>,
+            limit: usize,
+            _filters: Option<HashMap<String, String>>,
+        ) -> Result<Vec<(MessageId, f32)>, SentinelError> {
+            Ok(self.0.iter().copied().take(limit).collect())
+        }
+
+        async fn delete(&self, _id: MessageId) -> Result<(), SentinelError> {
+            Ok(())
+        }
+    }
+
+    // Vector store keeping upserted metadata in memory and filtering on it exactly
+    #[derive(Default)]
+    struct RecordingVectorStore {
+        points: std::sync::Mutex<Vec<(MessageId, HashMap<String, String>)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl VectorStore for RecordingVectorStore {
+        async fn upsert(
+            &self,
+            id: MessageId,
+            _embedding: Vec<f32>,
+            metadata: HashMap<String, String>,
+        ) -> Result<(), SentinelError> {
+            let mut points = self.points.lock().unwrap();
+            match points.iter_mut().find(|(existing, _)| *existing == id) {
+                Some(point) => point.1 = metadata,
+                None => points.push((id, metadata)),
+            }
+            Ok(())
+        }
+
+        async fn search_filtered(
+            &self,
+            _query_embedding: Vec<f32>,
+            limit: usize,
+            filters: Option<HashMap<String, String>>,
+        ) -> Result<Vec<(MessageId, f32)>, SentinelError> {
+            let filters = filters.unwrap_or_default();
+            Ok(self
+                .points
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, metadata)| {
+                    filters
+                        .iter()
+                        .all(|(key, value)| metadata.get(key) == Some(value))
+                })
+                .map(|(id, _)| (*id, 1.0))
+                .take(limit)
+                .collect())
+        }
+
+        async fn contains(&self, id: MessageId) -> Result<bool, SentinelError> {
+            Ok(self
+                .points
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|(existing, _)| *existing == id))
+        }
+
+        async fn delete(&self, _id: MessageId) -> Result<(), SentinelError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_manager_creation() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("sled_test");
+        let long_term: Arc<dyn VectorStore> = Arc::new(MockVectorStore);
+
+        let manager = MemoryManager::new(path, long_term);
+        assert!(manager.is_ok());
     }
 
     #[tokio::test]
@@ -336,7 +1726,7 @@ mod tests {
         let agent_id = AgentId::new();
 
         let memory = manager.get_short_term(agent_id).await;
-        let guard = memory.read().await;
+        let guard = memory.read().unwrap();
         assert_eq!(guard.message_count(), 0);
     }
 
@@ -355,7 +1745,7 @@ mod tests {
         // Add many messages to exceed threshold
         let memory = manager.get_short_term(agent_id).await;
         {
-            let mut guard = memory.write().await;
+            let mut guard = memory.write().unwrap();
             for _ in 0..200 {
                 // Create a large message to exceed token threshold
                 let large_content = "x".repeat(1000); // ~250 tokens each
@@ -364,8 +1754,253 @@ mod tests {
             }
         }
 
-        // Should now need consolidation
-        assert!(manager.should_consolidate_short(agent_id).await);
+        // Should now need consolidation
+        assert!(manager.should_consolidate_short(agent_id).await);
+    }
+
+    #[tokio::test]
+    async fn test_auto_consolidation_skips_disabled_agents() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"));
+        let enabled = AgentId::new();
+        let disabled = AgentId::new();
+        manager
+            .set_agent_profile(
+                disabled,
+                AgentMemoryProfile {
+                    auto_consolidation: false,
+                },
+            )
+            .await;
+
+        for agent_id in [enabled, disabled] {
+            let memory = manager.get_short_term(agent_id).await;
+            let mut guard = memory.write().unwrap();
+            for _ in 0..200 {
+                let msg = CanonicalMessage::new(Role::User, "x".repeat(1000));
+                let _ = guard.append_message(msg);
+            }
+        }
+
+        manager.run_consolidation_checks().await;
+
+        assert!(!manager.should_consolidate_short(enabled).await);
+        assert!(manager.should_consolidate_short(disabled).await);
+        assert_eq!(manager.stats().short_to_medium_count, 1);
+        assert!(manager.agent_profile(enabled).await.auto_consolidation);
+    }
+
+    /// Store a summary for `agent_id` created `age` ago
+    fn store_aged_summary(manager: &MemoryManager, agent_id: AgentId, age: chrono::Duration) {
+        let summary = ConversationSummary::new(
+            agent_id,
+            uuid::Uuid::new_v4().to_string(),
+            "Old conversation".to_string(),
+            4,
+        )
+        .with_created_at(Utc::now() - age);
+        manager.medium_term.store_summary(summary).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stale_summary_selected_for_consolidation_regardless_of_count() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"))
+            .with_embedding_provider(Arc::new(FixedEmbedder(4)))
+            .unwrap();
+        let stale = AgentId::new();
+        let fresh = AgentId::new();
+        store_aged_summary(&manager, stale, chrono::Duration::hours(25));
+        store_aged_summary(&manager, stale, chrono::Duration::minutes(5));
+        store_aged_summary(&manager, fresh, chrono::Duration::minutes(5));
+
+        let selected = manager.stale_summaries(stale);
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].summary, "Old conversation");
+        assert!(selected[0].created_at < Utc::now() - chrono::Duration::hours(24));
+        assert!(manager.should_consolidate_medium(stale).await);
+        assert!(manager.stale_summaries(fresh).is_empty());
+        assert!(!manager.should_consolidate_medium(fresh).await);
+
+        // The dreamer checks agents with a short-term buffer
+        manager.get_short_term(stale).await;
+        manager.get_short_term(fresh).await;
+        manager.run_consolidation_checks().await;
+        assert_eq!(manager.stats().medium_to_long_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_consolidations_run_in_priority_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"))
+            .with_consolidation_config(ConsolidationConfig {
+                short_term_token_threshold: 100,
+                medium_term_summary_threshold: 2,
+                ..ConsolidationConfig::default()
+            })
+            .with_embedding_provider(Arc::new(FixedEmbedder(4)))
+            .unwrap();
+        let (low, medium, high, critical) = (
+            AgentId::new(),
+            AgentId::new(),
+            AgentId::new(),
+            AgentId::new(),
+        );
+
+        // Low: one summary past the 24h age threshold
+        store_aged_summary(&manager, low, chrono::Duration::days(2));
+        manager.get_short_term(low).await;
+        // Medium: summary count at the threshold
+        store_aged_summary(&manager, medium, chrono::Duration::minutes(1));
+        store_aged_summary(&manager, medium, chrono::Duration::minutes(2));
+        manager.get_short_term(medium).await;
+        // High: token threshold reached (400 chars = 100 tokens)
+        manager
+            .append_message(high, CanonicalMessage::new(Role::User, "x".repeat(400)))
+            .await
+            .unwrap();
+        // Critical: twice the token threshold
+        manager
+            .append_message(critical, CanonicalMessage::new(Role::User, "x".repeat(800)))
+            .await
+            .unwrap();
+
+        let expected = vec![
+            (
+                critical,
+                ConsolidationKind::ShortToMedium,
+                ConsolidationPriority::Critical,
+            ),
+            (
+                high,
+                ConsolidationKind::ShortToMedium,
+                ConsolidationPriority::High,
+            ),
+            (
+                medium,
+                ConsolidationKind::MediumToLong,
+                ConsolidationPriority::Medium,
+            ),
+            (
+                low,
+                ConsolidationKind::MediumToLong,
+                ConsolidationPriority::Low,
+            ),
+        ];
+        let as_tuples = |pending: Vec<PendingConsolidation>| -> Vec<_> {
+            pending
+                .into_iter()
+                .map(|p| (p.agent_id, p.kind, p.priority))
+                .collect()
+        };
+        assert_eq!(as_tuples(manager.pending_consolidations().await), expected);
+
+        let processed = manager.run_consolidation_checks().await;
+        assert_eq!(as_tuples(processed), expected);
+        let stats = manager.stats();
+        assert_eq!(stats.short_to_medium_count, 2);
+        assert_eq!(stats.medium_to_long_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_exceeded_token_budget_forces_consolidation_of_largest_agents() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test")).with_token_budget(100);
+        let (large, medium, small) = (AgentId::new(), AgentId::new(), AgentId::new());
+        for (agent_id, chars) in [(large, 400), (medium, 200), (small, 40)] {
+            manager
+                .append_message(
+                    agent_id,
+                    CanonicalMessage::new(Role::User, "x".repeat(chars)),
+                )
+                .await
+                .unwrap();
+        }
+        // 100 + 50 + 10 tokens, all far below the per-agent consolidation thresholds
+        assert_eq!(manager.budget_status().await, (160, Some(100.0)));
+        assert!(manager.pending_consolidations().await.is_empty());
+
+        let processed = manager.run_consolidation_checks().await;
+
+        // Consolidating the largest buffer alone covers the 60-token overage
+        assert_eq!(
+            processed,
+            vec![PendingConsolidation {
+                agent_id: large,
+                kind: ConsolidationKind::ShortToMedium,
+                priority: ConsolidationPriority::Critical,
+            }]
+        );
+        assert!(manager.snapshot_short_term(large).await.is_empty());
+        assert_eq!(manager.snapshot_short_term(medium).await.len(), 1);
+        assert_eq!(manager.snapshot_short_term(small).await.len(), 1);
+        assert_eq!(manager.stats().short_to_medium_count, 1);
+
+        // The consolidated tokens no longer count, so backpressure lifts
+        assert_eq!(manager.budget_status().await, (60, Some(60.0)));
+        assert!(manager.run_consolidation_checks().await.is_empty());
+    }
+
+    // Counter treating every whitespace-separated word as one token
+    struct WordCounter;
+
+    impl TokenCounter for WordCounter {
+        fn count_tokens(&self, text: &str) -> u64 {
+            text.split_whitespace().count() as u64
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_budget_uses_injected_counter() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"))
+            .with_token_counter(Arc::new(WordCounter))
+            .with_token_budget(10);
+        let agent_id = AgentId::new();
+        manager
+            .append_message(
+                agent_id,
+                CanonicalMessage::new(Role::User, "three short words".to_string()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(manager.budget_status().await, (3, Some(30.0)));
+        let memory = manager.get_short_term(agent_id).await;
+        assert_eq!(memory.read().unwrap().token_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_budget_status_without_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"));
+        manager
+            .append_message(
+                AgentId::new(),
+                CanonicalMessage::new(Role::User, "x".repeat(40)),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(manager.budget_status().await, (10, None));
+        assert!(manager.run_consolidation_checks().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_auto_consolidation_skips_stale_summaries() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test")).with_consolidation_config(
+            ConsolidationConfig {
+                enable_auto_consolidation: false,
+                ..ConsolidationConfig::default()
+            },
+        );
+        let agent_id = AgentId::new();
+        store_aged_summary(&manager, agent_id, chrono::Duration::days(3));
+        manager.get_short_term(agent_id).await;
+
+        manager.run_consolidation_checks().await;
+        assert_eq!(manager.stats().medium_to_long_count, 0);
     }
 
     #[tokio::test]
@@ -380,7 +2015,7 @@ mod tests {
         // Add messages
         let memory = manager.get_short_term(agent_id).await;
         {
-            let mut guard = memory.write().await;
+            let mut guard = memory.write().unwrap();
             for i in 0..5 {
                 let msg = CanonicalMessage::new(Role::User, format!("Message {}", i));
                 let _ = guard.append_message(msg);
@@ -393,11 +2028,842 @@ mod tests {
 
         // Short-term memory should be cleared
         let memory = manager.get_short_term(agent_id).await;
-        let guard = memory.read().await;
+        let guard = memory.read().unwrap();
         assert_eq!(guard.message_count(), 0);
 
         // Summary should be stored in medium-term
         let summaries = manager.medium_term.list_summaries(agent_id).unwrap();
         assert!(!summaries.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_identical_long_term_content_is_stored_once_per_agent() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = Arc::new(RecordingVectorStore::default());
+        let embedder = Arc::new(CountingEmbedder::default());
+        let manager = MemoryManager::new(temp_dir.path().join("sled_test"), store.clone())
+            .unwrap()
+            .with_embedding_provider(embedder.clone())
+            .unwrap();
+        let agent_id = AgentId::new();
+        let fact = "The capital of France is Paris.";
+
+        let first = manager
+            .store_long_term(agent_id, fact, HashMap::new())
+            .await
+            .unwrap();
+        let repeated = manager
+            .store_long_term(agent_id, fact, HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(repeated, first);
+        assert_eq!(store.points.lock().unwrap().len(), 1);
+        // The repeat is found by its derived ID before paying for an embedding
+        assert_eq!(embedder.calls.load(Ordering::Relaxed), 1);
+
+        // Different content, or the same content for another agent, is still stored
+        let other_fact = manager
+            .store_long_term(agent_id, "Berlin is in Germany.", HashMap::new())
+            .await
+            .unwrap();
+        let other_agent = manager
+            .store_long_term(AgentId::new(), fact, HashMap::new())
+            .await
+            .unwrap();
+        assert_ne!(other_fact, first);
+        assert_ne!(other_agent, first);
+
+        let points = store.points.lock().unwrap();
+        assert_eq!(points.len(), 3);
+        let (id, metadata) = &points[0];
+        assert_eq!(*id, first);
+        assert_eq!(metadata[AGENT_ID_KEY], agent_id.to_string());
+        assert_eq!(metadata[CONTENT_HASH_KEY], content_hash(fact));
+        assert_eq!(metadata[CONTENT_HASH_KEY].len(), 64);
+    }
+
+    #[tokio::test]
+    async fn test_medium_to_long_consolidation_embeds_each_summary_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = Arc::new(RecordingVectorStore::default());
+        let embedder = Arc::new(CountingEmbedder::default());
+        let manager = MemoryManager::new(temp_dir.path().join("sled_test"), store.clone())
+            .unwrap()
+            .with_embedding_provider(embedder.clone())
+            .unwrap();
+        let agent_id = AgentId::new();
+        manager
+            .restore_short_term(agent_id, sample_conversation())
+            .await
+            .unwrap();
+        manager.consolidate_short_to_medium(agent_id).await.unwrap();
+        let summary = manager
+            .medium_term
+            .list_summaries(agent_id)
+            .unwrap()
+            .remove(0);
+
+        manager.consolidate_medium_to_long(agent_id).await.unwrap();
+        manager.consolidate_medium_to_long(agent_id).await.unwrap();
+
+        assert_eq!(embedder.calls.load(Ordering::Relaxed), 1);
+        assert!(manager
+            .medium_term
+            .list_summaries(agent_id)
+            .unwrap()
+            .is_empty());
+        assert!(!manager.should_consolidate_medium(agent_id).await);
+        let points = store.points.lock().unwrap();
+        assert_eq!(points.len(), 1);
+        let (_, metadata) = &points[0];
+        assert_eq!(metadata[AGENT_ID_KEY], agent_id.to_string());
+        assert_eq!(metadata[CONVERSATION_ID_KEY], summary.conversation_id);
+        assert_eq!(metadata[CONTENT_HASH_KEY], content_hash(&summary.summary));
+        assert_eq!(manager.stats().medium_to_long_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_medium_to_long_consolidation_skipped_without_embedder() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"));
+        let agent_id = AgentId::new();
+        manager
+            .restore_short_term(agent_id, sample_conversation())
+            .await
+            .unwrap();
+        manager.consolidate_short_to_medium(agent_id).await.unwrap();
+
+        manager.consolidate_medium_to_long(agent_id).await.unwrap();
+        assert_eq!(manager.stats().medium_to_long_count, 0);
+    }
+
+    // Summarizer replying with a fixed summary, or failing if none is set
+    struct FixedSummarizer(Option<&'static str>);
+
+    #[async_trait::async_trait]
+    impl Summarizer for FixedSummarizer {
+        async fn summarize(&self, _messages: &[CanonicalMessage]) -> Result<String, SentinelError> {
+            self.0
+                .map(str::to_string)
+                .ok_or_else(|| SentinelError::ProviderError {
+                    provider: "test".to_string(),
+                    detail: "unavailable".to_string(),
+                })
+        }
+    }
+
+    /// Consolidate one message for a new agent and return the stored summary text
+    async fn consolidated_summary(manager: &MemoryManager) -> String {
+        let agent_id = AgentId::new();
+        manager
+            .append_message(
+                agent_id,
+                CanonicalMessage::new(Role::User, "remember the milk".to_string()),
+            )
+            .await
+            .unwrap();
+        manager.consolidate_short_to_medium(agent_id).await.unwrap();
+        let summaries = manager.medium_term.list_summaries(agent_id).unwrap();
+        summaries[0].summary.clone()
+    }
+
+    #[tokio::test]
+    async fn test_consolidation_uses_configured_summarizer() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager =
+            MemoryManager::new(temp_dir.path().join("sled_test"), Arc::new(MockVectorStore))
+                .unwrap()
+                .with_summarizer(Arc::new(FixedSummarizer(Some("buy milk"))));
+        assert_eq!(consolidated_summary(&manager).await, "buy milk");
+
+        // A failing summarizer falls back to concatenation instead of losing messages
+        let manager = manager.with_summarizer(Arc::new(FixedSummarizer(None)));
+        assert_eq!(
+            consolidated_summary(&manager).await,
+            "user: remember the milk"
+        );
+    }
+
+    // Summarizer that signals when it starts and blocks until released
+    struct GatedSummarizer {
+        started: Arc<tokio::sync::Notify>,
+        release: Arc<tokio::sync::Notify>,
+    }
+
+    #[async_trait::async_trait]
+    impl Summarizer for GatedSummarizer {
+        async fn summarize(&self, _messages: &[CanonicalMessage]) -> Result<String, SentinelError> {
+            self.started.notify_one();
+            self.release.notified().await;
+            Ok("gated summary".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_messages_stay_in_short_term_while_summarizing() {
+        let temp_dir = TempDir::new().unwrap();
+        let started = Arc::new(tokio::sync::Notify::new());
+        let release = Arc::new(tokio::sync::Notify::new());
+        let manager = Arc::new(
+            MemoryManager::new(temp_dir.path().join("sled_test"), Arc::new(MockVectorStore))
+                .unwrap()
+                .with_summarizer(Arc::new(GatedSummarizer {
+                    started: started.clone(),
+                    release: release.clone(),
+                })),
+        );
+        let agent_id = AgentId::new();
+        let first = CanonicalMessage::new(Role::User, "summarize me".to_string());
+        manager
+            .append_message(agent_id, first.clone())
+            .await
+            .unwrap();
+
+        let consolidation = {
+            let manager = manager.clone();
+            tokio::spawn(async move { manager.consolidate_short_to_medium(agent_id).await })
+        };
+        started.notified().await;
+
+        // Mid-summarization the message is still recallable and survives a flush
+        assert_eq!(
+            manager.snapshot_short_term(agent_id).await,
+            vec![first.clone()]
+        );
+        manager.flush_short_term().await.unwrap();
+        let persisted = manager.medium_term.load_short_term_buffers().unwrap();
+        assert_eq!(persisted, vec![(agent_id, vec![first])]);
+
+        let late = CanonicalMessage::new(Role::User, "arrived meanwhile".to_string());
+        manager
+            .append_message(agent_id, late.clone())
+            .await
+            .unwrap();
+        release.notify_one();
+        consolidation.await.unwrap().unwrap();
+
+        // Only the summarized message left short-term memory
+        assert_eq!(
+            manager.snapshot_short_term(agent_id).await,
+            vec![late.clone()]
+        );
+        let summaries = manager.medium_term.list_summaries(agent_id).unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].message_count, 1);
+        let persisted = manager.medium_term.load_short_term_buffers().unwrap();
+        assert_eq!(persisted, vec![(agent_id, vec![late])]);
+    }
+
+    #[tokio::test]
+    async fn test_embedding_dimension_mismatch_rejected_at_startup() {
+        let temp_dir = TempDir::new().unwrap();
+        let long_term: Arc<dyn VectorStore> = Arc::new(DimensionedVectorStore(1536));
+
+        let err = MemoryManager::new(temp_dir.path().join("sled_test"), long_term)
+            .unwrap()
+            .with_embedding_provider(Arc::new(FixedEmbedder(384)))
+            .err()
+            .expect("mismatched dimensions should be rejected");
+
+        assert_eq!(
+            err.downcast_ref::<SentinelError>(),
+            Some(&SentinelError::EmbeddingDimensionMismatch {
+                expected: 1536,
+                actual: 384,
+            })
+        );
+        assert!(err.to_string().contains("Reindex"));
+    }
+
+    #[tokio::test]
+    async fn test_recall_detects_dimension_drift_before_search() {
+        let temp_dir = TempDir::new().unwrap();
+        let long_term: Arc<dyn VectorStore> = Arc::new(DimensionedVectorStore(1536));
+        let mut manager = MemoryManager::new(temp_dir.path().join("sled_test"), long_term).unwrap();
+        // Bypass the startup check to simulate a provider swapped after construction
+        manager.embedder = Some(Arc::new(FixedEmbedder(768)));
+
+        let err = manager.recall("what did we discuss?", 5).await.unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<SentinelError>(),
+            Some(SentinelError::EmbeddingDimensionMismatch {
+                expected: 1536,
+                actual: 768,
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_recall_with_matching_dimensions() {
+        let temp_dir = TempDir::new().unwrap();
+        let long_term: Arc<dyn VectorStore> = Arc::new(DimensionedVectorStore(384));
+        let manager = MemoryManager::new(temp_dir.path().join("sled_test"), long_term)
+            .unwrap()
+            .with_embedding_provider(Arc::new(FixedEmbedder(384)))
+            .unwrap();
+
+        let results = manager.recall("hello", 5).await.unwrap();
+
+        assert_eq!(results.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recall_all_merges_tiers() {
+        let temp_dir = TempDir::new().unwrap();
+        let agent_id = AgentId::new();
+        let recent = CanonicalMessage::new(Role::User, "deploy the service".to_string());
+        let archived = MessageId::new();
+        let long_term = Arc::new(SeededVectorStore(vec![(recent.id, 0.95), (archived, 0.4)]));
+        let manager = MemoryManager::new(temp_dir.path().join("sled_test"), long_term)
+            .unwrap()
+            .with_embedding_provider(Arc::new(FixedEmbedder(4)))
+            .unwrap();
+
+        manager
+            .append_message(agent_id, recent.clone())
+            .await
+            .unwrap();
+        manager
+            .append_message(
+                agent_id,
+                CanonicalMessage::new(Role::User, "unrelated chatter".to_string()),
+            )
+            .await
+            .unwrap();
+        manager
+            .medium_term
+            .store_summary(ConversationSummary::new(
+                agent_id,
+                "conv-old".to_string(),
+                "Discussed how to deploy".to_string(),
+                4,
+            ))
+            .unwrap();
+
+        let results = manager
+            .recall_all(agent_id, "deploy rust", 10)
+            .await
+            .unwrap();
+
+        // The recent message appears once with its content; each tier's best match
+        // normalizes to 1.0, so it ties with the summary and ranks first by tier
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].source, RecallSource::Message(recent.id));
+        assert_eq!(results[0].tier, MemoryTier::ShortTerm);
+        assert_eq!(results[0].content.as_deref(), Some("deploy the service"));
+        assert_eq!(results[1].tier, MemoryTier::MediumTerm);
+        assert_eq!(
+            results[1].source,
+            RecallSource::Summary {
+                conversation_id: "conv-old".to_string()
+            }
+        );
+        assert_eq!(results[2].source, RecallSource::Message(archived));
+        assert!(results[2].content.is_none());
+        assert!(results[2].score < 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_recall_all_only_returns_the_agents_long_term_memories() {
+        let temp_dir = TempDir::new().unwrap();
+        let long_term = Arc::new(RecordingVectorStore::default());
+        let manager = MemoryManager::new(temp_dir.path().join("sled_test"), long_term)
+            .unwrap()
+            .with_embedding_provider(Arc::new(FixedEmbedder(4)))
+            .unwrap();
+        let agent_id = AgentId::new();
+        let other_agent = AgentId::new();
+
+        let own = manager
+            .store_long_term(agent_id, "own fact", HashMap::new())
+            .await
+            .unwrap();
+        manager
+            .store_long_term(other_agent, "someone else's fact", HashMap::new())
+            .await
+            .unwrap();
+
+        let results = manager.recall_all(agent_id, "fact", 10).await.unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].source, RecallSource::Message(own));
+    }
+
+    #[tokio::test]
+    async fn test_recall_all_without_embedder_skips_long_term() {
+        let temp_dir = TempDir::new().unwrap();
+        let agent_id = AgentId::new();
+        let long_term = Arc::new(SeededVectorStore(vec![(MessageId::new(), 0.99)]));
+        let manager = MemoryManager::new(temp_dir.path().join("sled_test"), long_term).unwrap();
+        manager
+            .append_message(
+                agent_id,
+                CanonicalMessage::new(Role::User, "deploy notes".to_string()),
+            )
+            .await
+            .unwrap();
+
+        let results = manager.recall_all(agent_id, "deploy", 10).await.unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].tier, MemoryTier::ShortTerm);
+        assert_eq!(results[0].score, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_recall_context_injects_top_matches_within_cap() {
+        let temp_dir = TempDir::new().unwrap();
+        let agent_id = AgentId::new();
+        // Each message is 20 characters, i.e. 5 tokens under SimpleTokenCounter
+        let manager =
+            manager_at(&temp_dir.path().join("sled_test")).with_max_recall_context_tokens(8);
+        for content in ["deploy notes for ops", "deploy rust services"] {
+            manager
+                .append_message(
+                    agent_id,
+                    CanonicalMessage::new(Role::User, content.to_string()),
+                )
+                .await
+                .unwrap();
+        }
+
+        let message = manager
+            .recall_context(agent_id, "deploy rust", 10)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(message.role, Role::System);
+        assert!(message.content.contains("deploy rust services"));
+        assert!(!message.content.contains("deploy notes for ops"));
+    }
+
+    #[tokio::test]
+    async fn test_recall_context_empty_when_nothing_matches() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"));
+
+        let message = manager
+            .recall_context(AgentId::new(), "deploy", 10)
+            .await
+            .unwrap();
+
+        assert!(message.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_session_messages_accumulate() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"));
+
+        manager
+            .append_session_messages("session-a", sample_conversation())
+            .await
+            .unwrap();
+        manager
+            .append_session_messages(
+                "session-a",
+                vec![CanonicalMessage::new(Role::User, "Again".to_string())],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(manager.session_history("session-a").await.len(), 4);
+        assert!(manager.session_history("session-b").await.is_empty());
+        assert_eq!(
+            MemoryManager::session_agent_id("session-a"),
+            MemoryManager::session_agent_id("session-a")
+        );
+        assert_ne!(
+            MemoryManager::session_agent_id("session-a"),
+            MemoryManager::session_agent_id("session-b")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejected_session_exchange_appends_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"))
+            .with_max_conversation_messages(3, ConversationLengthPolicy::Reject);
+        let history = vec![
+            CanonicalMessage::new(Role::User, "Hello".to_string()),
+            CanonicalMessage::new(Role::Assistant, "Hi there".to_string()),
+        ];
+        manager
+            .append_session_messages("session-a", history.clone())
+            .await
+            .unwrap();
+
+        // The user turn would fit, but not together with the reply
+        let exchange = vec![
+            CanonicalMessage::new(Role::User, "Again".to_string()),
+            CanonicalMessage::new(Role::Assistant, "Sure".to_string()),
+        ];
+        assert!(manager
+            .append_session_messages("session-a", exchange)
+            .await
+            .is_err());
+
+        assert_eq!(manager.session_history("session-a").await, history);
+    }
+
+    fn manager_at(path: &Path) -> MemoryManager {
+        MemoryManager::new(path, Arc::new(MockVectorStore)).unwrap()
+    }
+
+    fn sample_conversation() -> Vec<CanonicalMessage> {
+        vec![
+            CanonicalMessage::new(Role::System, "Be concise".to_string()),
+            CanonicalMessage::new(Role::User, "Hello".to_string()),
+            CanonicalMessage::new(Role::Assistant, "Hi there".to_string()),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_clear_restore_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"));
+        let agent_id = AgentId::new();
+        let messages = sample_conversation();
+
+        manager
+            .restore_short_term(agent_id, messages.clone())
+            .await
+            .unwrap();
+        let snapshot = manager.snapshot_short_term(agent_id).await;
+        assert_eq!(snapshot, messages);
+
+        manager
+            .get_short_term(agent_id)
+            .await
+            .write()
+            .unwrap()
+            .clear()
+            .unwrap();
+        assert!(manager.snapshot_short_term(agent_id).await.is_empty());
+
+        manager
+            .restore_short_term(agent_id, snapshot)
+            .await
+            .unwrap();
+        assert_eq!(manager.snapshot_short_term(agent_id).await, messages);
+    }
+
+    #[tokio::test]
+    async fn test_failed_restore_keeps_previous_buffer() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"));
+        let agent_id = AgentId::new();
+        let messages = sample_conversation();
+
+        manager
+            .restore_short_term(agent_id, messages.clone())
+            .await
+            .unwrap();
+        let (tokens, _) = manager.budget_status().await;
+        assert!(tokens > 0);
+
+        // Too many messages for the buffer: nothing of the snapshot is applied
+        let oversized: Vec<CanonicalMessage> = (0..=DEFAULT_MAX_MESSAGES)
+            .map(|i| CanonicalMessage::new(Role::User, format!("message {}", i)))
+            .collect();
+        assert!(manager
+            .restore_short_term(agent_id, oversized)
+            .await
+            .is_err());
+
+        assert_eq!(manager.snapshot_short_term(agent_id).await, messages);
+        assert_eq!(manager.budget_status().await.0, tokens);
+    }
+
+    #[tokio::test]
+    async fn test_export_openai_for_agent() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"));
+        let agent_id = AgentId::new();
+        manager
+            .restore_short_term(agent_id, sample_conversation())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            manager.export_openai(agent_id).await,
+            serde_json::json!({ "messages": [
+                { "role": "system", "content": "Be concise" },
+                { "role": "user", "content": "Hello" },
+                { "role": "assistant", "content": "Hi there" },
+            ] })
+        );
+        assert_eq!(
+            manager.export_openai(AgentId::new()).await,
+            serde_json::json!({ "messages": [] })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_unknown_agent_is_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"));
+
+        assert!(manager.snapshot_short_term(AgentId::new()).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_flushed_short_term_rehydrates_after_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("sled_test");
+        let agent_id = AgentId::new();
+        let messages = sample_conversation();
+
+        {
+            let manager = manager_at(&path);
+            manager
+                .restore_short_term(agent_id, messages.clone())
+                .await
+                .unwrap();
+            assert_eq!(manager.flush_short_term().await.unwrap(), 1);
+        }
+
+        let manager = manager_at(&path);
+        assert_eq!(manager.snapshot_short_term(agent_id).await, messages);
+    }
+
+    #[tokio::test]
+    async fn test_flush_keeps_persisted_buffer_when_unreadable() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"));
+        let agent_id = AgentId::new();
+        let messages = sample_conversation();
+        manager
+            .restore_short_term(agent_id, messages.clone())
+            .await
+            .unwrap();
+        assert_eq!(manager.flush_short_term().await.unwrap(), 1);
+
+        let memory = manager.get_short_term(agent_id).await;
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = memory.write().unwrap();
+            panic!("poison the short-term lock");
+        }));
+        assert!(memory.is_poisoned());
+
+        assert_eq!(manager.flush_short_term().await.unwrap(), 0);
+        let persisted = manager.medium_term.load_short_term_buffers().unwrap();
+        assert_eq!(persisted, vec![(agent_id, messages)]);
+    }
+
+    #[tokio::test]
+    async fn test_consolidation_drops_persisted_buffer() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("sled_test");
+        let agent_id = AgentId::new();
+
+        {
+            let manager = manager_at(&path);
+            manager
+                .restore_short_term(agent_id, sample_conversation())
+                .await
+                .unwrap();
+            manager.flush_short_term().await.unwrap();
+            manager.consolidate_short_to_medium(agent_id).await.unwrap();
+            manager.medium_term.flush().unwrap();
+        }
+
+        let manager = manager_at(&path);
+        assert!(manager.snapshot_short_term(agent_id).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_consolidation_stats_count_runs() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"))
+            .with_embedding_provider(Arc::new(FixedEmbedder(4)))
+            .unwrap();
+        let agent_id = AgentId::new();
+        assert_eq!(manager.stats(), ConsolidationStats::default());
+
+        // Consolidating an empty buffer is not counted
+        manager.consolidate_short_to_medium(agent_id).await.unwrap();
+        assert_eq!(manager.stats().short_to_medium_count, 0);
+
+        let before = Utc::now();
+        manager
+            .restore_short_term(agent_id, sample_conversation())
+            .await
+            .unwrap();
+        manager.consolidate_short_to_medium(agent_id).await.unwrap();
+        manager.consolidate_medium_to_long(agent_id).await.unwrap();
+
+        let stats = manager.stats();
+        assert_eq!(stats.short_to_medium_count, 1);
+        assert_eq!(stats.medium_to_long_count, 1);
+        assert!(stats.last_short_to_medium.unwrap() >= before - chrono::Duration::milliseconds(1));
+        assert!(stats.last_medium_to_long.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_conversation_length_guard_forces_consolidation() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"))
+            .with_max_conversation_messages(3, ConversationLengthPolicy::Consolidate);
+        let agent_id = AgentId::new();
+
+        for i in 0..4 {
+            manager
+                .append_message(
+                    agent_id,
+                    CanonicalMessage::new(Role::User, format!("turn {}", i)),
+                )
+                .await
+                .unwrap();
+        }
+
+        // The first three turns were consolidated; only the fourth remains
+        let remaining = manager.snapshot_short_term(agent_id).await;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].content, "turn 3");
+        assert_eq!(manager.stats().short_to_medium_count, 1);
+        let summaries = manager.medium_term.list_summaries(agent_id).unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].message_count, 3);
+    }
+
+    #[tokio::test]
+    async fn test_conversation_length_guard_rejects() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"))
+            .with_max_conversation_messages(2, ConversationLengthPolicy::Reject);
+        let agent_id = AgentId::new();
+
+        for i in 0..2 {
+            manager
+                .append_message(
+                    agent_id,
+                    CanonicalMessage::new(Role::User, format!("turn {}", i)),
+                )
+                .await
+                .unwrap();
+        }
+        let err = manager
+            .append_message(
+                agent_id,
+                CanonicalMessage::new(Role::User, "one too many".to_string()),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<SentinelError>(),
+            Some(SentinelError::DomainViolation { .. })
+        ));
+        assert_eq!(manager.snapshot_short_term(agent_id).await.len(), 2);
+        assert_eq!(manager.stats().short_to_medium_count, 0);
+    }
+
+    /// Span captured by [`SpanCapture`]: name, parent name and recorded fields
+    #[derive(Debug, Clone, Default)]
+    struct CapturedSpan {
+        name: String,
+        parent: Option<String>,
+        fields: HashMap<String, String>,
+    }
+
+    /// Test layer recording every span and the fields recorded on it
+    #[derive(Clone, Default)]
+    struct SpanCapture {
+        spans: Arc<std::sync::Mutex<HashMap<u64, CapturedSpan>>>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    impl<S> tracing_subscriber::Layer<S> for SpanCapture
+    where
+        S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            id: &tracing::span::Id,
+            ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let mut span = CapturedSpan {
+                name: attrs.metadata().name().to_string(),
+                parent: ctx
+                    .span(id)
+                    .and_then(|span| span.parent())
+                    .map(|parent| parent.name().to_string()),
+                ..Default::default()
+            };
+            attrs.record(&mut FieldVisitor(&mut span.fields));
+            self.spans.lock().unwrap().insert(id.into_u64(), span);
+        }
+
+        fn on_record(
+            &self,
+            id: &tracing::span::Id,
+            values: &tracing::span::Record<'_>,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            if let Some(span) = self.spans.lock().unwrap().get_mut(&id.into_u64()) {
+                values.record(&mut FieldVisitor(&mut span.fields));
+            }
+        }
+    }
+
+    impl SpanCapture {
+        fn find(&self, name: &str) -> Option<CapturedSpan> {
+            self.spans
+                .lock()
+                .unwrap()
+                .values()
+                .find(|span| span.name == name)
+                .cloned()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consolidation_emits_spans_with_fields() {
+        use tracing::Instrument;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let capture = SpanCapture::default();
+        let _default =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager_at(&temp_dir.path().join("sled_test"));
+        let agent_id = AgentId::new();
+        manager
+            .restore_short_term(agent_id, sample_conversation())
+            .await
+            .unwrap();
+
+        async {
+            manager.consolidate_short_to_medium(agent_id).await.unwrap();
+            manager.consolidate_medium_to_long(agent_id).await.unwrap();
+        }
+        .instrument(tracing::info_span!("request"))
+        .await;
+
+        let short = capture
+            .find("consolidate_short_to_medium")
+            .expect("short-to-medium span");
+        assert_eq!(short.parent.as_deref(), Some("request"));
+        assert_eq!(short.fields["agent_id"], agent_id.to_string());
+        assert_eq!(short.fields["message_count"], "3");
+
+        let medium = capture
+            .find("consolidate_medium_to_long")
+            .expect("medium-to-long span");
+        assert_eq!(medium.parent.as_deref(), Some("request"));
+        assert_eq!(medium.fields["summary_count"], "1");
+    }
 }
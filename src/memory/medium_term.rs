@@ -2,12 +2,109 @@
 // Stores conversation summaries that survive process restarts
 
 use crate::core::error::SentinelError;
-use crate::core::types::AgentId;
+use crate::core::types::{
+    metadata_byte_size, AgentId, CanonicalMessage, DEFAULT_MAX_METADATA_BYTES,
+};
+use crate::memory::token_counter::TokenCounter;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tracing::{debug, error, warn};
 
+/// Name of the Sled tree holding persisted short-term buffers, keyed by agent ID
+const SHORT_TERM_TREE: &str = "short_term_buffers";
+
+/// Environment variable selecting how a corrupt database is handled
+pub const SLED_RECOVERY_ENV: &str = "SLED_RECOVERY";
+
+/// Policy for a Sled database that fails to open because it is corrupt
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SledRecovery {
+    /// Return `StorageCorrupted` and leave the database untouched (default)
+    #[default]
+    Fail,
+    /// Move the corrupt directory aside as a backup and create a fresh database
+    Recreate,
+}
+
+impl SledRecovery {
+    /// Read the policy from `SLED_RECOVERY` (`recreate` enables recovery; anything
+    /// else, or unset, fails on corruption)
+    pub fn from_env() -> Self {
+        match std::env::var(SLED_RECOVERY_ENV) {
+            Ok(value) if value.eq_ignore_ascii_case("recreate") => Self::Recreate,
+            _ => Self::Fail,
+        }
+    }
+}
+
+/// Check whether a Sled open error indicates corrupt or unreadable data
+///
+/// Lock contention and permission errors are deliberately excluded so a healthy
+/// database held by another process is never recreated.
+fn is_corruption(error: &sled::Error) -> bool {
+    match error {
+        sled::Error::Corruption { .. } | sled::Error::Unsupported(_) => true,
+        sled::Error::Io(e) => matches!(
+            e.kind(),
+            std::io::ErrorKind::InvalidData | std::io::ErrorKind::UnexpectedEof
+        ),
+        _ => false,
+    }
+}
+
+/// Move a corrupt database directory aside and open a fresh one in its place
+///
+/// # Returns
+/// * `Ok(sled::Db)` - The new, empty database
+/// * `Err(SentinelError)` - Error if the backup or the new database cannot be created
+fn recreate_database(path: &Path) -> Result<sled::Db, SentinelError> {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".corrupt-{}", Utc::now().format("%Y%m%d%H%M%S%3f")));
+    let backup = PathBuf::from(backup);
+
+    std::fs::rename(path, &backup).map_err(|e| SentinelError::DomainViolation {
+        rule: format!(
+            "Failed to back up corrupt Sled database {:?} to {:?}: {}",
+            path, backup, e
+        ),
+    })?;
+    warn!(
+        "Backed up corrupt Sled database to {:?}; recreating an empty database at {:?}",
+        backup, path
+    );
+
+    sled::open(path).map_err(|e| SentinelError::DomainViolation {
+        rule: format!("Failed to recreate Sled database at {:?}: {}", path, e),
+    })
+}
+
+/// Summary layout written before summaries carried metadata
+#[derive(Deserialize)]
+struct LegacyConversationSummary {
+    agent_id: AgentId,
+    conversation_id: String,
+    summary: String,
+    message_count: u64,
+    created_at: DateTime<Utc>,
+    last_updated: DateTime<Utc>,
+}
+
+impl From<LegacyConversationSummary> for ConversationSummary {
+    fn from(legacy: LegacyConversationSummary) -> Self {
+        Self {
+            agent_id: legacy.agent_id,
+            conversation_id: legacy.conversation_id,
+            summary: legacy.summary,
+            message_count: legacy.message_count,
+            created_at: legacy.created_at,
+            last_updated: legacy.last_updated,
+            metadata: HashMap::new(),
+        }
+    }
+}
+
 /// Conversation summary stored in medium-term memory
 /// This represents a condensed version of a conversation for persistent storage
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -24,6 +121,9 @@ pub struct ConversationSummary {
     pub created_at: DateTime<Utc>,
     /// When this summary was last updated
     pub last_updated: DateTime<Utc>,
+    /// Optional metadata (key-value pairs)
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
 }
 
 impl ConversationSummary {
@@ -42,9 +142,22 @@ impl ConversationSummary {
             message_count,
             created_at: now,
             last_updated: now,
+            metadata: HashMap::new(),
         }
     }
 
+    /// Attach metadata to the summary
+    pub fn with_metadata(mut self, metadata: HashMap<String, String>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Set when the summary was created, e.g. when importing an older conversation
+    pub fn with_created_at(mut self, created_at: DateTime<Utc>) -> Self {
+        self.created_at = created_at;
+        self
+    }
+
     /// Update the summary content and timestamp
     pub fn update_summary(&mut self, summary: String, message_count: u64) {
         self.summary = summary;
@@ -59,11 +172,17 @@ impl ConversationSummary {
         })
     }
 
-    /// Deserialize summary from bytes using bincode
+    /// Deserialize summary from bytes using bincode, accepting the layout without metadata
     fn from_bytes(data: &[u8]) -> Result<Self, SentinelError> {
-        bincode::deserialize(data).map_err(|e| SentinelError::InvalidMessage {
-            reason: format!("Deserialization error: {}", e),
-        })
+        bincode::deserialize::<Self>(data)
+            .or_else(|e| {
+                bincode::deserialize::<LegacyConversationSummary>(data)
+                    .map(Self::from)
+                    .map_err(|_| e)
+            })
+            .map_err(|e| SentinelError::InvalidMessage {
+                reason: format!("Deserialization error: {}", e),
+            })
     }
 
     /// Generate the storage key for this summary
@@ -82,26 +201,74 @@ impl ConversationSummary {
 pub struct MediumTermMemory {
     db: sled::Db,
     path: PathBuf,
+    max_metadata_bytes: usize,
 }
 
 impl MediumTermMemory {
     /// Create a new medium-term memory instance
     ///
+    /// Corruption is handled according to the `SLED_RECOVERY` environment variable
+    /// (see [`SledRecovery::from_env`]).
+    ///
     /// # Arguments
     /// * `path` - Path to the Sled database directory
     ///
     /// # Returns
     /// * `Ok(MediumTermMemory)` - Successfully created
-    /// * `Err(SentinelError)` - Error if database creation fails
+    /// * `Err(SentinelError)` - `StorageCorrupted` if the database is corrupt and recovery
+    ///   is disabled, otherwise `DomainViolation` if the database cannot be opened
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, SentinelError> {
+        Self::open(path, SledRecovery::from_env())
+    }
+
+    /// Create a new medium-term memory instance with an explicit corruption policy
+    ///
+    /// # Arguments
+    /// * `path` - Path to the Sled database directory
+    /// * `recovery` - What to do if the database is corrupt
+    ///
+    /// # Returns
+    /// * `Ok(MediumTermMemory)` - Successfully created (possibly from a fresh database)
+    /// * `Err(SentinelError)` - Error if the database cannot be opened or recreated
+    pub fn open<P: AsRef<Path>>(path: P, recovery: SledRecovery) -> Result<Self, SentinelError> {
         let path_buf = path.as_ref().to_path_buf();
-        let db = sled::open(&path_buf).map_err(|e| SentinelError::DomainViolation {
-            rule: format!("Failed to open Sled database at {:?}: {}", path_buf, e),
-        })?;
+        let db = match sled::open(&path_buf) {
+            Ok(db) => db,
+            Err(e) if is_corruption(&e) => {
+                error!(
+                    "Sled database at {:?} is corrupt: {}. Restore it from a backup, or set {}=recreate to move it aside and start with an empty database",
+                    path_buf, e, SLED_RECOVERY_ENV
+                );
+                match recovery {
+                    SledRecovery::Fail => {
+                        return Err(SentinelError::StorageCorrupted {
+                            path: path_buf.display().to_string(),
+                            reason: e.to_string(),
+                        })
+                    }
+                    SledRecovery::Recreate => recreate_database(&path_buf)?,
+                }
+            }
+            Err(e) => {
+                return Err(SentinelError::DomainViolation {
+                    rule: format!("Failed to open Sled database at {:?}: {}", path_buf, e),
+                })
+            }
+        };
 
         debug!("Opened medium-term memory database at {:?}", path_buf);
 
-        Ok(Self { db, path: path_buf })
+        Ok(Self {
+            db,
+            path: path_buf,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+        })
+    }
+
+    /// Set the maximum total metadata byte size accepted by `store_summary`
+    pub fn with_max_metadata_bytes(mut self, max_metadata_bytes: usize) -> Self {
+        self.max_metadata_bytes = max_metadata_bytes;
+        self
     }
 
     /// Store a conversation summary
@@ -111,9 +278,19 @@ impl MediumTermMemory {
     ///
     /// # Returns
     /// * `Ok(())` - Successfully stored
-    /// * `Err(SentinelError)` - Error if storage fails
+    /// * `Err(SentinelError)` - InvalidMessage if the metadata exceeds the configured
+    ///   byte limit, or an error if storage fails
     pub fn store_summary(&self, summary: ConversationSummary) -> Result<(), SentinelError> {
         let key = summary.storage_key();
+        let metadata_bytes = metadata_byte_size(&summary.metadata);
+        if metadata_bytes > self.max_metadata_bytes {
+            return Err(SentinelError::InvalidMessage {
+                reason: format!(
+                    "Summary {} metadata is {} bytes, exceeding the limit of {} bytes",
+                    key, metadata_bytes, self.max_metadata_bytes
+                ),
+            });
+        }
         let bytes = summary.to_bytes()?;
 
         self.db
@@ -161,7 +338,7 @@ impl MediumTermMemory {
     /// * `agent_id` - The agent ID to list summaries for
     ///
     /// # Returns
-    /// * `Ok(Vec<ConversationSummary>)` - List of summaries
+    /// * `Ok(Vec<ConversationSummary>)` - List of summaries, oldest `created_at` first
     /// * `Err(SentinelError)` - Error if listing fails
     pub fn list_summaries(
         &self,
@@ -190,6 +367,7 @@ impl MediumTermMemory {
             }
         }
 
+        summaries.sort_by_key(|summary| summary.created_at);
         debug!(
             "Listed {} summaries for agent {}",
             summaries.len(),
@@ -198,6 +376,50 @@ impl MediumTermMemory {
         Ok(summaries)
     }
 
+    /// List the summaries of an agent created more than `max_age` ago
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent ID to list summaries for
+    /// * `max_age` - Age beyond which a summary is stale
+    /// * `now` - Time the age is measured from
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ConversationSummary>)` - Stale summaries, oldest first
+    /// * `Err(SentinelError)` - Error if listing fails
+    pub fn list_summaries_older_than(
+        &self,
+        agent_id: AgentId,
+        max_age: chrono::Duration,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<ConversationSummary>, SentinelError> {
+        let cutoff = now - max_age;
+        let mut summaries = self.list_summaries(agent_id)?;
+        summaries.retain(|summary| summary.created_at < cutoff);
+        Ok(summaries)
+    }
+
+    /// Count the tokens of every stored summary, across all agents
+    ///
+    /// # Arguments
+    /// * `counter` - Strategy used to count each summary's text
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Total tokens; unreadable summaries are skipped with a warning
+    /// * `Err(SentinelError)` - Error if the database cannot be scanned
+    pub fn total_summary_tokens(&self, counter: &dyn TokenCounter) -> Result<u64, SentinelError> {
+        let mut total = 0;
+        for result in self.db.iter() {
+            let (_key, bytes) = result.map_err(|e| SentinelError::DomainViolation {
+                rule: format!("Failed to scan summaries: {}", e),
+            })?;
+            match ConversationSummary::from_bytes(&bytes) {
+                Ok(summary) => total += counter.count_tokens(&summary.summary),
+                Err(e) => warn!("Failed to deserialize summary: {}", e),
+            }
+        }
+        Ok(total)
+    }
+
     /// Delete a conversation summary
     ///
     /// # Arguments
@@ -224,6 +446,165 @@ impl MediumTermMemory {
         Ok(())
     }
 
+    /// Open the tree holding persisted short-term buffers
+    fn short_term_tree(&self) -> Result<sled::Tree, SentinelError> {
+        self.db
+            .open_tree(SHORT_TERM_TREE)
+            .map_err(|e| SentinelError::DomainViolation {
+                rule: format!("Failed to open short-term buffer tree: {}", e),
+            })
+    }
+
+    /// Persist an agent's short-term buffer, replacing any previous snapshot
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent the buffer belongs to
+    /// * `messages` - The buffer contents in chronological order
+    ///
+    /// # Returns
+    /// * `Ok(())` - Buffer stored
+    /// * `Err(SentinelError)` - Error if serialization or storage fails
+    ///
+    /// # Note
+    /// Buffers are stored as JSON because message metadata is skipped when empty,
+    /// which bincode cannot round-trip.
+    pub fn store_short_term_buffer(
+        &self,
+        agent_id: AgentId,
+        messages: &[CanonicalMessage],
+    ) -> Result<(), SentinelError> {
+        let bytes = serde_json::to_vec(messages).map_err(|e| SentinelError::InvalidMessage {
+            reason: format!("Serialization error: {}", e),
+        })?;
+
+        self.short_term_tree()?
+            .insert(agent_id.to_string().as_bytes(), bytes)
+            .map_err(|e| SentinelError::DomainViolation {
+                rule: format!("Failed to store short-term buffer for {}: {}", agent_id, e),
+            })?;
+
+        debug!(
+            "Stored short-term buffer of {} messages for agent {}",
+            messages.len(),
+            agent_id
+        );
+        Ok(())
+    }
+
+    /// Remove an agent's persisted short-term buffer
+    ///
+    /// # Arguments
+    /// * `agent_id` - The agent whose buffer should be removed
+    ///
+    /// # Returns
+    /// * `Ok(())` - Buffer removed (or none was stored)
+    /// * `Err(SentinelError)` - Error if deletion fails
+    pub fn delete_short_term_buffer(&self, agent_id: AgentId) -> Result<(), SentinelError> {
+        self.short_term_tree()?
+            .remove(agent_id.to_string().as_bytes())
+            .map_err(|e| SentinelError::DomainViolation {
+                rule: format!("Failed to delete short-term buffer for {}: {}", agent_id, e),
+            })?;
+        Ok(())
+    }
+
+    /// Load every persisted short-term buffer
+    ///
+    /// # Returns
+    /// * `Ok(Vec<(AgentId, Vec<CanonicalMessage>)>)` - Buffers per agent; unreadable
+    ///   entries are skipped with a warning
+    /// * `Err(SentinelError)` - Error if the tree cannot be scanned
+    pub fn load_short_term_buffers(
+        &self,
+    ) -> Result<Vec<(AgentId, Vec<CanonicalMessage>)>, SentinelError> {
+        let mut buffers = Vec::new();
+
+        for result in self.short_term_tree()?.iter() {
+            let (key, bytes) = result.map_err(|e| SentinelError::DomainViolation {
+                rule: format!("Failed to scan short-term buffers: {}", e),
+            })?;
+            let agent_id = match std::str::from_utf8(&key)
+                .ok()
+                .and_then(|key| uuid::Uuid::parse_str(key).ok())
+            {
+                Some(uuid) => AgentId::from(uuid),
+                None => {
+                    warn!("Skipping short-term buffer with invalid agent key");
+                    continue;
+                }
+            };
+            match serde_json::from_slice(&bytes) {
+                Ok(messages) => buffers.push((agent_id, messages)),
+                Err(e) => warn!(
+                    "Failed to deserialize short-term buffer for agent {}: {}",
+                    agent_id, e
+                ),
+            }
+        }
+
+        Ok(buffers)
+    }
+
+    /// Count the stored summaries across all agents
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of summaries (persisted short-term buffers are not counted)
+    /// * `Err(SentinelError)` - Error if the database cannot be scanned
+    pub fn summary_count(&self) -> Result<usize, SentinelError> {
+        let mut count = 0;
+        for result in self.db.iter().keys() {
+            result.map_err(|e| SentinelError::DomainViolation {
+                rule: format!("Failed to scan summaries: {}", e),
+            })?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Get the space the database currently occupies on disk
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Size in bytes
+    /// * `Err(SentinelError)` - `DomainViolation` if the size cannot be determined
+    pub fn size_on_disk(&self) -> Result<u64, SentinelError> {
+        self.db
+            .size_on_disk()
+            .map_err(|e| SentinelError::DomainViolation {
+                rule: format!("Failed to read database size: {}", e),
+            })
+    }
+
+    /// Flush every tree to disk and report the resulting database size
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Size on disk in bytes after flushing
+    /// * `Err(SentinelError)` - `DomainViolation` if flushing or sizing fails
+    ///
+    /// # Note
+    /// This does not compact the database: sled 0.34 has no compaction call and
+    /// reclaims freed segments on its own schedule, so the size may not shrink after
+    /// deletes.
+    pub fn flush_all(&self) -> Result<u64, SentinelError> {
+        for name in self.db.tree_names() {
+            let tree = self
+                .db
+                .open_tree(&name)
+                .map_err(|e| SentinelError::DomainViolation {
+                    rule: format!("Failed to open tree for flushing: {}", e),
+                })?;
+            tree.flush().map_err(|e| SentinelError::DomainViolation {
+                rule: format!("Failed to flush tree: {}", e),
+            })?;
+        }
+        self.flush()?;
+        let size = self.size_on_disk()?;
+        debug!(
+            "Flushed medium-term memory at {:?} ({} bytes on disk)",
+            self.path, size
+        );
+        Ok(size)
+    }
+
     /// Get the database path
     pub fn path(&self) -> &Path {
         &self.path
@@ -249,6 +630,64 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    /// Create a database directory whose config file is garbage
+    fn corrupt_database(temp_dir: &TempDir) -> PathBuf {
+        let path = temp_dir.path().join("db");
+        {
+            let db = sled::open(&path).unwrap();
+            db.insert("key", "value").unwrap();
+            db.flush().unwrap();
+        }
+        std::fs::write(path.join("conf"), b"\x00\xffnot a sled config").unwrap();
+        path
+    }
+
+    #[test]
+    fn test_corrupt_database_reported_distinctly() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = corrupt_database(&temp_dir);
+
+        let result = MediumTermMemory::open(&path, SledRecovery::Fail);
+
+        assert!(matches!(
+            result,
+            Err(SentinelError::StorageCorrupted { .. })
+        ));
+        // The corrupt data is left in place
+        assert!(path.join("conf").exists());
+    }
+
+    #[test]
+    fn test_corrupt_database_recreated_when_enabled() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = corrupt_database(&temp_dir);
+
+        let memory = MediumTermMemory::open(&path, SledRecovery::Recreate).unwrap();
+
+        let agent_id = AgentId::new();
+        memory
+            .store_summary(ConversationSummary::new(
+                agent_id,
+                "conv-1".to_string(),
+                "After recovery".to_string(),
+                1,
+            ))
+            .unwrap();
+        assert!(memory.get_summary(agent_id, "conv-1").unwrap().is_some());
+
+        let backups: Vec<_> = std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with("db.corrupt-")
+            })
+            .collect();
+        assert_eq!(backups.len(), 1);
+    }
+
     fn create_test_memory() -> (TempDir, MediumTermMemory) {
         let temp_dir = tempfile::tempdir().unwrap();
         let memory = MediumTermMemory::new(temp_dir.path()).unwrap();
@@ -305,6 +744,66 @@ mod tests {
         assert_eq!(summaries.len(), 2);
     }
 
+    #[test]
+    fn test_list_summaries_older_than_filters_and_sorts_by_age() {
+        let (_temp_dir, memory) = create_test_memory();
+        let agent_id = AgentId::new();
+        let now = Utc::now();
+
+        for (conversation_id, age_hours) in [("conv-a", 30), ("conv-b", 1), ("conv-c", 48)] {
+            memory
+                .store_summary(
+                    ConversationSummary::new(
+                        agent_id,
+                        conversation_id.to_string(),
+                        "Summary".to_string(),
+                        1,
+                    )
+                    .with_created_at(now - chrono::Duration::hours(age_hours)),
+                )
+                .unwrap();
+        }
+
+        let all: Vec<String> = memory
+            .list_summaries(agent_id)
+            .unwrap()
+            .into_iter()
+            .map(|summary| summary.conversation_id)
+            .collect();
+        assert_eq!(all, vec!["conv-c", "conv-a", "conv-b"]);
+
+        let stale: Vec<String> = memory
+            .list_summaries_older_than(agent_id, chrono::Duration::hours(24), now)
+            .unwrap()
+            .into_iter()
+            .map(|summary| summary.conversation_id)
+            .collect();
+        assert_eq!(stale, vec!["conv-c", "conv-a"]);
+    }
+
+    #[test]
+    fn test_total_summary_tokens_spans_agents() {
+        use crate::memory::token_counter::SimpleTokenCounter;
+
+        let (_temp_dir, memory) = create_test_memory();
+        assert_eq!(memory.total_summary_tokens(&SimpleTokenCounter).unwrap(), 0);
+
+        for agent_id in [AgentId::new(), AgentId::new()] {
+            memory
+                .store_summary(ConversationSummary::new(
+                    agent_id,
+                    "conv-1".to_string(),
+                    "x".repeat(40),
+                    2,
+                ))
+                .unwrap();
+        }
+        assert_eq!(
+            memory.total_summary_tokens(&SimpleTokenCounter).unwrap(),
+            20
+        );
+    }
+
     #[test]
     fn test_list_summaries_multiple_agents() {
         let (_temp_dir, memory) = create_test_memory();
@@ -376,6 +875,37 @@ mod tests {
         assert_eq!(original.last_updated, deserialized.last_updated);
     }
 
+    #[test]
+    fn test_summary_written_without_metadata_still_loads() {
+        let (_temp_dir, memory) = create_test_memory();
+        let agent_id = AgentId::new();
+        let created_at = Utc::now();
+
+        // Field layout of summaries persisted before metadata was added
+        let legacy = (
+            agent_id,
+            "conv-legacy".to_string(),
+            "Summary from an older release".to_string(),
+            7u64,
+            created_at,
+            created_at,
+        );
+        let key = ConversationSummary::key_from_parts(agent_id, "conv-legacy");
+        memory
+            .db
+            .insert(key.as_bytes(), bincode::serialize(&legacy).unwrap())
+            .unwrap();
+
+        let summary = memory
+            .get_summary(agent_id, "conv-legacy")
+            .unwrap()
+            .unwrap();
+        assert_eq!(summary.summary, "Summary from an older release");
+        assert_eq!(summary.message_count, 7);
+        assert_eq!(summary.created_at, created_at);
+        assert!(summary.metadata.is_empty());
+    }
+
     #[test]
     fn test_update_summary() {
         let mut summary = ConversationSummary::new(
@@ -422,4 +952,102 @@ mod tests {
         memory.store_summary(summary).unwrap();
         memory.flush().unwrap(); // Should not panic
     }
+
+    #[test]
+    fn test_store_rejects_oversized_metadata() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let memory = MediumTermMemory::new(temp_dir.path())
+            .unwrap()
+            .with_max_metadata_bytes(64);
+        let agent_id = AgentId::new();
+        let metadata = HashMap::from([("blob".to_string(), "x".repeat(128))]);
+        let summary = ConversationSummary::new(agent_id, "conv-1".to_string(), "s".to_string(), 1)
+            .with_metadata(metadata);
+
+        match memory.store_summary(summary).unwrap_err() {
+            SentinelError::InvalidMessage { reason } => {
+                assert!(reason.contains("exceeding the limit of 64 bytes"));
+            }
+            other => panic!("Expected InvalidMessage, got {:?}", other),
+        }
+
+        // Nothing was written
+        assert!(memory.get_summary(agent_id, "conv-1").unwrap().is_none());
+        assert!(memory.list_summaries(agent_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_summary_count_tracks_inserts_and_deletes() {
+        let (_temp_dir, memory) = create_test_memory();
+        assert_eq!(memory.summary_count().unwrap(), 0);
+
+        let agent_a = AgentId::new();
+        let agent_b = AgentId::new();
+        for (agent_id, conversation_id) in [
+            (agent_a, "conv-1"),
+            (agent_a, "conv-2"),
+            (agent_b, "conv-1"),
+        ] {
+            memory
+                .store_summary(ConversationSummary::new(
+                    agent_id,
+                    conversation_id.to_string(),
+                    "summary".to_string(),
+                    1,
+                ))
+                .unwrap();
+        }
+        // Short-term buffers live in their own tree and are not summaries
+        memory.store_short_term_buffer(agent_a, &[]).unwrap();
+        assert_eq!(memory.summary_count().unwrap(), 3);
+
+        memory.delete_summary(agent_a, "conv-1").unwrap();
+        memory.delete_summary(agent_a, "missing").unwrap();
+        assert_eq!(memory.summary_count().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_flush_all_after_bulk_delete() {
+        let (_temp_dir, memory) = create_test_memory();
+        let agent_id = AgentId::new();
+        for i in 0..100 {
+            memory
+                .store_summary(ConversationSummary::new(
+                    agent_id,
+                    format!("conv-{}", i),
+                    "x".repeat(1024),
+                    1,
+                ))
+                .unwrap();
+        }
+        memory.flush().unwrap();
+        assert!(memory.size_on_disk().unwrap() > 0);
+
+        for i in 0..100 {
+            memory
+                .delete_summary(agent_id, &format!("conv-{}", i))
+                .unwrap();
+        }
+        let size = memory.flush_all().unwrap();
+
+        assert_eq!(memory.summary_count().unwrap(), 0);
+        assert_eq!(size, memory.size_on_disk().unwrap());
+    }
+
+    #[test]
+    fn test_store_accepts_metadata_within_limit() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let memory = MediumTermMemory::new(temp_dir.path())
+            .unwrap()
+            .with_max_metadata_bytes(64);
+        let agent_id = AgentId::new();
+        let metadata = HashMap::from([("topic".to_string(), "billing".to_string())]);
+        let summary = ConversationSummary::new(agent_id, "conv-1".to_string(), "s".to_string(), 1)
+            .with_metadata(metadata.clone());
+
+        memory.store_summary(summary).unwrap();
+
+        let stored = memory.get_summary(agent_id, "conv-1").unwrap().unwrap();
+        assert_eq!(stored.metadata, metadata);
+    }
 }
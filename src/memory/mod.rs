@@ -1,5 +1,9 @@
+pub mod conversation_budget;
+pub mod conversation_lock;
 pub mod manager;
 pub mod medium_term;
+pub mod recall;
 pub mod short_term;
+pub mod summarizer;
 pub mod token_counter;
 pub mod triggers;
@@ -0,0 +1,411 @@
+// Recall context injection
+// Selects recalled long-term memories to inject into a conversation under a token cap
+
+use crate::core::types::{CanonicalMessage, MessageId, Role};
+use crate::memory::token_counter::{SimpleTokenCounter, TokenCounter};
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Default maximum tokens of recalled content injected into a conversation
+pub const DEFAULT_MAX_RECALL_CONTEXT_TOKENS: u64 = 2_000;
+
+/// Header prepended to the injected recall context
+const RECALL_CONTEXT_HEADER: &str = "Relevant memories from earlier conversations:";
+
+/// A recalled memory with text to inject and its relevance score
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecalledMemory {
+    /// Message or summary the memory was recalled from
+    pub source: RecallSource,
+    /// Recalled text content
+    pub content: String,
+    /// Relevance score (higher is more relevant)
+    pub score: f32,
+}
+
+impl RecalledMemory {
+    /// Create a new recalled memory
+    pub fn new(source: RecallSource, content: String, score: f32) -> Self {
+        Self {
+            source,
+            content,
+            score,
+        }
+    }
+
+    /// Take the text of a recall result, if its tier stored any
+    ///
+    /// # Returns
+    /// `None` for results without content (long-term results only carry IDs)
+    pub fn from_tiered(memory: TieredMemory) -> Option<Self> {
+        let content = memory.content?;
+        Some(Self::new(memory.source, content, memory.score))
+    }
+}
+
+/// Builds the recall context block injected ahead of a conversation
+pub struct RecallContext {
+    /// Maximum tokens of recalled content to inject
+    max_recall_context_tokens: u64,
+    /// Token counter used to measure recalled content
+    token_counter: Box<dyn TokenCounter>,
+}
+
+impl RecallContext {
+    /// Create a recall context builder with the given token cap
+    pub fn new(max_recall_context_tokens: u64) -> Self {
+        Self::with_token_counter(max_recall_context_tokens, Box::new(SimpleTokenCounter))
+    }
+
+    /// Create a recall context builder with a custom token counter
+    pub fn with_token_counter(
+        max_recall_context_tokens: u64,
+        token_counter: Box<dyn TokenCounter>,
+    ) -> Self {
+        Self {
+            max_recall_context_tokens,
+            token_counter,
+        }
+    }
+
+    /// Get the maximum tokens of recalled content that will be injected
+    pub fn max_recall_context_tokens(&self) -> u64 {
+        self.max_recall_context_tokens
+    }
+
+    /// Select the recalled memories that fit within the token cap
+    ///
+    /// Memories are taken in order of descending similarity until the next one
+    /// would exceed the cap, so lower-ranked matches never displace better ones.
+    ///
+    /// # Arguments
+    /// * `memories` - Recalled memories in any order
+    ///
+    /// # Returns
+    /// The highest-similarity memories whose combined tokens fit within the cap
+    pub fn select(&self, mut memories: Vec<RecalledMemory>) -> Vec<RecalledMemory> {
+        memories.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+        let mut used = 0u64;
+        let mut selected = Vec::new();
+        for memory in memories {
+            let tokens = self.token_counter.count_tokens(&memory.content);
+            if used + tokens > self.max_recall_context_tokens {
+                break;
+            }
+            used += tokens;
+            selected.push(memory);
+        }
+
+        debug!(
+            "Selected {} recalled memories ({} / {} tokens)",
+            selected.len(),
+            used,
+            self.max_recall_context_tokens
+        );
+        selected
+    }
+
+    /// Build the system message injecting recalled memories into a conversation
+    ///
+    /// # Arguments
+    /// * `memories` - Recalled memories in any order
+    ///
+    /// # Returns
+    /// * `Some(CanonicalMessage)` - System message containing the selected memories
+    /// * `None` - If no memory fits within the cap
+    pub fn build_message(&self, memories: Vec<RecalledMemory>) -> Option<CanonicalMessage> {
+        let selected = self.select(memories);
+        if selected.is_empty() {
+            return None;
+        }
+
+        let mut content = RECALL_CONTEXT_HEADER.to_string();
+        for memory in &selected {
+            content.push_str("\n- ");
+            content.push_str(&memory.content);
+        }
+        Some(CanonicalMessage::new(Role::System, content))
+    }
+}
+
+impl Default for RecallContext {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_RECALL_CONTEXT_TOKENS)
+    }
+}
+
+/// Memory tier a recall result came from
+///
+/// Ordered from most to least recent; ties in merged rankings favour earlier tiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MemoryTier {
+    /// Recent messages in an agent's short-term buffer
+    ShortTerm,
+    /// Conversation summaries in medium-term storage
+    MediumTerm,
+    /// Embeddings in the long-term vector store
+    LongTerm,
+}
+
+/// What a recall result refers to, used to de-duplicate results across tiers
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RecallSource {
+    /// A single message (short-term buffer or long-term embedding)
+    Message(MessageId),
+    /// A medium-term conversation summary
+    Summary {
+        /// Conversation the summary covers
+        conversation_id: String,
+    },
+}
+
+/// A recall result tagged with the tier it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct TieredMemory {
+    /// Tier that produced the best match for this source
+    pub tier: MemoryTier,
+    /// Message or summary the result refers to
+    pub source: RecallSource,
+    /// Text content, if the tier stores it (long-term results only carry IDs)
+    pub content: Option<String>,
+    /// Relevance score in `0.0..=1.0` (higher is more relevant)
+    pub score: f32,
+}
+
+/// Score how well `text` matches `query` by term overlap
+///
+/// # Returns
+/// The fraction of distinct, case-insensitive query terms that appear in `text`,
+/// or `0.0` when the query has no terms
+pub fn term_overlap_score(query: &str, text: &str) -> f32 {
+    let text = text.to_lowercase();
+    let mut terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    terms.sort();
+    terms.dedup();
+    if terms.is_empty() {
+        return 0.0;
+    }
+    let matched = terms
+        .iter()
+        .filter(|term| text.contains(term.as_str()))
+        .count();
+    matched as f32 / terms.len() as f32
+}
+
+/// Rescale scores so each tier's best result scores `1.0`
+///
+/// Tiers score on different scales (term overlap vs. vector similarity), so raw
+/// scores are only comparable within a tier. Non-positive scores become `0.0`.
+fn normalize_per_tier(results: &mut [TieredMemory]) {
+    let mut best: HashMap<MemoryTier, f32> = HashMap::new();
+    for result in results.iter() {
+        let entry = best.entry(result.tier).or_insert(result.score);
+        *entry = entry.max(result.score);
+    }
+    for result in results.iter_mut() {
+        let best = best.get(&result.tier).copied().unwrap_or_default();
+        result.score = if best > 0.0 {
+            (result.score / best).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+    }
+}
+
+/// Merge recall results from several tiers into one ranked list
+///
+/// Scores are first normalized per tier (see `normalize_per_tier`). Results referring
+/// to the same message or summary are then collapsed into the highest-scoring one,
+/// keeping content from any tier that had it.
+///
+/// # Arguments
+/// * `results` - Results from all tiers in any order
+/// * `limit` - Maximum number of results to return
+///
+/// # Returns
+/// At most `limit` results, highest normalized score first (equal scores in tier order)
+pub fn merge_tiered(mut results: Vec<TieredMemory>, limit: usize) -> Vec<TieredMemory> {
+    normalize_per_tier(&mut results);
+
+    let mut by_source: HashMap<RecallSource, TieredMemory> = HashMap::new();
+    for result in results {
+        match by_source.get_mut(&result.source) {
+            Some(existing) => {
+                let content = existing.content.take().or(result.content.clone());
+                if result.score > existing.score {
+                    *existing = result;
+                }
+                existing.content = existing.content.take().or(content);
+            }
+            None => {
+                by_source.insert(result.source.clone(), result);
+            }
+        }
+    }
+
+    let mut merged: Vec<TieredMemory> = by_source.into_values().collect();
+    merged.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.tier.cmp(&b.tier)));
+    merged.truncate(limit);
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Memory whose content is exactly `tokens` tokens under SimpleTokenCounter
+    fn memory(label: &str, tokens: usize, score: f32) -> RecalledMemory {
+        let content = format!("{:<width$}", label, width = tokens * 4);
+        RecalledMemory::new(RecallSource::Message(MessageId::new()), content, score)
+    }
+
+    #[test]
+    fn test_select_keeps_top_matches_within_cap() {
+        let recall = RecallContext::new(100);
+        let memories = vec![
+            memory("low", 40, 0.2),
+            memory("best", 50, 0.9),
+            memory("good", 40, 0.7),
+            memory("tiny", 5, 0.1),
+        ];
+
+        let selected = recall.select(memories);
+        let labels: Vec<&str> = selected.iter().map(|m| m.content.trim()).collect();
+
+        // best (50) + good (40) = 90; low (40) would exceed the cap, and the
+        // smaller but less relevant "tiny" must not be pulled in after it
+        assert_eq!(labels, vec!["best", "good"]);
+    }
+
+    #[test]
+    fn test_select_returns_nothing_when_top_match_exceeds_cap() {
+        let recall = RecallContext::new(10);
+        let selected = recall.select(vec![memory("huge", 50, 0.9), memory("small", 5, 0.1)]);
+        assert!(selected.is_empty());
+    }
+
+    #[test]
+    fn test_build_message_injects_selected_memories() {
+        let recall = RecallContext::new(60);
+        let message = recall
+            .build_message(vec![
+                memory("second", 20, 0.5),
+                memory("first", 30, 0.8),
+                memory("dropped", 20, 0.3),
+            ])
+            .unwrap();
+
+        assert_eq!(message.role, Role::System);
+        assert!(message.content.starts_with(RECALL_CONTEXT_HEADER));
+        let first = message.content.find("first").unwrap();
+        let second = message.content.find("second").unwrap();
+        assert!(first < second);
+        assert!(!message.content.contains("dropped"));
+    }
+
+    #[test]
+    fn test_build_message_empty_when_nothing_recalled() {
+        assert!(RecallContext::default().build_message(Vec::new()).is_none());
+    }
+
+    fn tiered(
+        tier: MemoryTier,
+        source: RecallSource,
+        content: Option<&str>,
+        score: f32,
+    ) -> TieredMemory {
+        TieredMemory {
+            tier,
+            source,
+            content: content.map(str::to_string),
+            score,
+        }
+    }
+
+    #[test]
+    fn test_term_overlap_score() {
+        assert_eq!(term_overlap_score("rust actors", "Actors in Rust"), 1.0);
+        assert_eq!(term_overlap_score("rust python", "rust only"), 0.5);
+        assert_eq!(term_overlap_score("", "anything"), 0.0);
+        assert_eq!(term_overlap_score("rust", "go"), 0.0);
+    }
+
+    #[test]
+    fn test_merge_tiered_dedupes_and_ranks() {
+        let shared = MessageId::new();
+        let results = vec![
+            tiered(
+                MemoryTier::ShortTerm,
+                RecallSource::Message(shared),
+                Some("shared message"),
+                0.5,
+            ),
+            tiered(
+                MemoryTier::LongTerm,
+                RecallSource::Message(shared),
+                None,
+                0.9,
+            ),
+            tiered(
+                MemoryTier::MediumTerm,
+                RecallSource::Summary {
+                    conversation_id: "conv-1".to_string(),
+                },
+                Some("summary"),
+                0.7,
+            ),
+            tiered(
+                MemoryTier::LongTerm,
+                RecallSource::Message(MessageId::new()),
+                None,
+                0.1,
+            ),
+        ];
+
+        let merged = merge_tiered(results, 2);
+
+        assert_eq!(merged.len(), 2);
+        // Each tier's best result normalizes to 1.0; ties rank in tier order
+        assert_eq!(merged[0].tier, MemoryTier::ShortTerm);
+        assert_eq!(merged[0].source, RecallSource::Message(shared));
+        assert_eq!(merged[0].content.as_deref(), Some("shared message"));
+        assert_eq!(merged[0].score, 1.0);
+        assert_eq!(merged[1].tier, MemoryTier::MediumTerm);
+    }
+
+    #[test]
+    fn test_merge_tiered_normalizes_scores_per_tier() {
+        let best_vector = MessageId::new();
+        let weak_overlap = MessageId::new();
+        let results = vec![
+            // A cosine score of 0.3 is the long-term tier's best match
+            tiered(
+                MemoryTier::LongTerm,
+                RecallSource::Message(best_vector),
+                None,
+                0.3,
+            ),
+            tiered(
+                MemoryTier::ShortTerm,
+                RecallSource::Message(MessageId::new()),
+                Some("full overlap"),
+                0.8,
+            ),
+            tiered(
+                MemoryTier::ShortTerm,
+                RecallSource::Message(weak_overlap),
+                Some("partial overlap"),
+                0.4,
+            ),
+        ];
+
+        let merged = merge_tiered(results, 3);
+
+        // Raw 0.3 outranks raw 0.4 because each is compared within its own tier
+        assert_eq!(merged[1].source, RecallSource::Message(best_vector));
+        assert_eq!(merged[1].score, 1.0);
+        assert_eq!(merged[2].source, RecallSource::Message(weak_overlap));
+        assert_eq!(merged[2].score, 0.5);
+    }
+}
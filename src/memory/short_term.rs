@@ -2,7 +2,9 @@
 // In-memory conversation history with token counting and consolidation triggers
 
 use crate::core::error::SentinelError;
-use crate::core::types::CanonicalMessage;
+use crate::core::types::{CanonicalMessage, MessageId, Role};
+use crate::memory::token_counter::{SimpleTokenCounter, TokenCounter};
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 
 /// Default maximum number of messages in short-term memory
@@ -14,10 +16,14 @@ pub const DEFAULT_MAX_TOKENS: u64 = 100_000;
 /// Default consolidation threshold (50k tokens)
 pub const DEFAULT_CONSOLIDATION_THRESHOLD: u64 = 50_000;
 
-/// Simple token counter using character approximation
-/// Tokens ≈ characters / 4 (rough approximation)
-fn approximate_tokens(text: &str) -> u64 {
-    text.chars().count() as u64 / 4
+/// Default maximum number of named checkpoints kept at once
+pub const DEFAULT_MAX_CHECKPOINTS: usize = 8;
+
+/// Snapshot of the conversation taken by `ShortTermMemory::checkpoint`
+#[derive(Debug, Clone)]
+struct Checkpoint {
+    messages: Vec<CanonicalMessage>,
+    token_count: u64,
 }
 
 /// Short-term memory for in-memory conversation history
@@ -28,6 +34,14 @@ pub struct ShortTermMemory {
     max_messages: usize,
     max_tokens: u64,
     consolidation_threshold: u64,
+    /// When true, appending past the limits evicts the oldest messages instead of erroring
+    evict_oldest: bool,
+    /// Strategy used to count message tokens against the limits and threshold
+    token_counter: Box<dyn TokenCounter>,
+    /// Named snapshots the conversation can be rewound to
+    checkpoints: HashMap<String, Checkpoint>,
+    /// Maximum number of checkpoints; each holds a full copy of the buffer
+    max_checkpoints: usize,
 }
 
 impl ShortTermMemory {
@@ -39,6 +53,10 @@ impl ShortTermMemory {
             max_messages: DEFAULT_MAX_MESSAGES,
             max_tokens: DEFAULT_MAX_TOKENS,
             consolidation_threshold: DEFAULT_CONSOLIDATION_THRESHOLD,
+            evict_oldest: false,
+            token_counter: Box::new(SimpleTokenCounter),
+            checkpoints: HashMap::new(),
+            max_checkpoints: DEFAULT_MAX_CHECKPOINTS,
         }
     }
 
@@ -50,9 +68,78 @@ impl ShortTermMemory {
             max_messages,
             max_tokens,
             consolidation_threshold,
+            evict_oldest: false,
+            token_counter: Box::new(SimpleTokenCounter),
+            checkpoints: HashMap::new(),
+            max_checkpoints: DEFAULT_MAX_CHECKPOINTS,
+        }
+    }
+
+    /// Create a new short-term memory in ring-buffer mode
+    ///
+    /// Appending past either limit drops the oldest messages until the new
+    /// message fits, instead of returning `DomainViolation`.
+    ///
+    /// # Arguments
+    /// * `max_messages` - Maximum number of messages retained
+    /// * `max_tokens` - Maximum token count retained
+    ///
+    /// # Note
+    /// A single message larger than `max_tokens` is still rejected.
+    pub fn with_eviction(max_messages: usize, max_tokens: u64) -> Self {
+        Self {
+            messages: Vec::new(),
+            token_count: 0,
+            max_messages,
+            max_tokens,
+            consolidation_threshold: DEFAULT_CONSOLIDATION_THRESHOLD,
+            evict_oldest: true,
+            token_counter: Box::new(SimpleTokenCounter),
+            checkpoints: HashMap::new(),
+            max_checkpoints: DEFAULT_MAX_CHECKPOINTS,
         }
     }
 
+    /// Limit how many checkpoints can be held at once
+    ///
+    /// # Arguments
+    /// * `max_checkpoints` - Maximum number of named checkpoints (0 disables checkpoints)
+    ///
+    /// # Note
+    /// Every checkpoint copies the whole buffer, so worst-case memory use is
+    /// `max_checkpoints + 1` times the message and token limits.
+    pub fn with_max_checkpoints(mut self, max_checkpoints: usize) -> Self {
+        self.max_checkpoints = max_checkpoints;
+        self
+    }
+
+    /// Count tokens with a different strategy than the default `SimpleTokenCounter`
+    ///
+    /// # Arguments
+    /// * `token_counter` - Counter applied to limits and the consolidation threshold
+    ///
+    /// # Note
+    /// The token count of any messages already held (including checkpoints) is
+    /// recomputed with the new counter.
+    pub fn with_token_counter(mut self, token_counter: Box<dyn TokenCounter>) -> Self {
+        self.set_token_counter(token_counter);
+        self
+    }
+
+    /// Replace the token counter of an existing memory (see `with_token_counter`)
+    pub fn set_token_counter(&mut self, token_counter: Box<dyn TokenCounter>) {
+        self.token_count = token_counter.count_messages(&self.messages);
+        for checkpoint in self.checkpoints.values_mut() {
+            checkpoint.token_count = token_counter.count_messages(&checkpoint.messages);
+        }
+        self.token_counter = token_counter;
+    }
+
+    /// Check whether this memory evicts the oldest messages when full
+    pub fn is_evicting(&self) -> bool {
+        self.evict_oldest
+    }
+
     /// Append a message to the conversation history
     ///
     /// # Arguments
@@ -63,9 +150,22 @@ impl ShortTermMemory {
     /// * `Err(SentinelError)` - Error if memory limits exceeded
     ///
     /// # Errors
-    /// Returns `DomainViolation` if memory limits would be exceeded
+    /// Returns `DomainViolation` if memory limits would be exceeded. In eviction
+    /// mode this only happens when the message alone exceeds the token limit.
     pub fn append_message(&mut self, msg: CanonicalMessage) -> Result<(), SentinelError> {
-        let msg_tokens = approximate_tokens(&msg.content);
+        let msg_tokens = self.token_counter.count_message(&msg);
+
+        if self.evict_oldest {
+            if msg_tokens > self.max_tokens || self.max_messages == 0 {
+                return Err(SentinelError::DomainViolation {
+                    rule: format!(
+                        "Message exceeds memory budget: {} tokens > {}",
+                        msg_tokens, self.max_tokens
+                    ),
+                });
+            }
+            self.evict_until_fits(msg_tokens);
+        }
 
         // Check if adding this message would exceed limits
         if self.messages.len() >= self.max_messages {
@@ -94,6 +194,91 @@ impl ShortTermMemory {
         Ok(())
     }
 
+    /// Append a batch of messages atomically
+    ///
+    /// # Arguments
+    /// * `messages` - Messages to append, in chronological order
+    ///
+    /// # Returns
+    /// * `Ok(())` - Every message appended, in order
+    /// * `Err(SentinelError)` - Nothing appended; memory is left unchanged
+    ///
+    /// # Errors
+    /// Returns `DomainViolation` if the batch would push the memory past its message
+    /// or token limit. In eviction mode older messages (including earlier messages of
+    /// the batch) are evicted instead, and only a message that alone exceeds the token
+    /// limit fails the import.
+    pub fn import_messages(
+        &mut self,
+        messages: Vec<CanonicalMessage>,
+    ) -> Result<(), SentinelError> {
+        if self.evict_oldest {
+            if let Some(msg_tokens) = messages
+                .iter()
+                .map(|msg| self.token_counter.count_message(msg))
+                .find(|tokens| *tokens > self.max_tokens)
+            {
+                return Err(SentinelError::DomainViolation {
+                    rule: format!(
+                        "Imported message exceeds memory budget: {} tokens > {}",
+                        msg_tokens, self.max_tokens
+                    ),
+                });
+            }
+            if self.max_messages == 0 && !messages.is_empty() {
+                return Err(SentinelError::DomainViolation {
+                    rule: "Message limit exceeded: memory holds no messages".to_string(),
+                });
+            }
+        } else {
+            let batch_tokens: u64 = messages
+                .iter()
+                .map(|msg| self.token_counter.count_message(msg))
+                .sum();
+            if self.messages.len() + messages.len() > self.max_messages {
+                return Err(SentinelError::DomainViolation {
+                    rule: format!(
+                        "Message limit would be exceeded by import: {} + {} > {}",
+                        self.messages.len(),
+                        messages.len(),
+                        self.max_messages
+                    ),
+                });
+            }
+            if self.token_count + batch_tokens > self.max_tokens {
+                return Err(SentinelError::DomainViolation {
+                    rule: format!(
+                        "Token limit would be exceeded by import: {} + {} > {}",
+                        self.token_count, batch_tokens, self.max_tokens
+                    ),
+                });
+            }
+        }
+
+        // Every message is known to fit, so these appends cannot fail part-way
+        for msg in messages {
+            self.append_message(msg)?;
+        }
+        Ok(())
+    }
+
+    /// Drop the oldest messages until one more message of `msg_tokens` fits
+    fn evict_until_fits(&mut self, msg_tokens: u64) {
+        let mut evict = 0;
+        let mut remaining_tokens = self.token_count;
+        while evict < self.messages.len()
+            && (self.messages.len() - evict >= self.max_messages
+                || remaining_tokens + msg_tokens > self.max_tokens)
+        {
+            remaining_tokens -= self.token_counter.count_message(&self.messages[evict]);
+            evict += 1;
+        }
+        if evict > 0 {
+            self.messages.drain(..evict);
+            self.token_count = remaining_tokens;
+        }
+    }
+
     /// Get all messages in the conversation history
     ///
     /// # Returns
@@ -114,17 +299,178 @@ impl ShortTermMemory {
         self.messages[start..].to_vec()
     }
 
-    /// Clear all messages and reset token count
+    /// Get the messages whose role is in the given set
+    ///
+    /// # Arguments
+    /// * `roles` - Roles to keep; an empty slice matches no messages
+    ///
+    /// # Returns
+    /// Vector of matching messages in chronological order
+    pub fn get_messages_by_role(&self, roles: &[Role]) -> Vec<CanonicalMessage> {
+        self.messages
+            .iter()
+            .filter(|msg| roles.contains(&msg.role))
+            .cloned()
+            .collect()
+    }
+
+    /// Search message content for a substring
+    ///
+    /// # Arguments
+    /// * `substring` - Text to look for; an empty substring matches no messages
+    /// * `case_insensitive` - Compare the `to_lowercase` forms of both strings
+    ///
+    /// # Returns
+    /// Vector of matching messages in chronological order
+    pub fn search_content(&self, substring: &str, case_insensitive: bool) -> Vec<CanonicalMessage> {
+        if substring.is_empty() {
+            return Vec::new();
+        }
+
+        let needle = if case_insensitive {
+            substring.to_lowercase()
+        } else {
+            substring.to_string()
+        };
+
+        self.messages
+            .iter()
+            .filter(|msg| {
+                if case_insensitive {
+                    msg.content.to_lowercase().contains(&needle)
+                } else {
+                    msg.content.contains(&needle)
+                }
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Export the conversation in the OpenAI chat format
+    ///
+    /// # Returns
+    /// `{"messages": [{"role": ..., "content": ...}, ...]}` in chronological order, with
+    /// lowercase roles. Internal fields (`id`, `timestamp`, `metadata`) are omitted so the
+    /// result can be pasted into any OpenAI-compatible client.
+    pub fn export_openai(&self) -> serde_json::Value {
+        let messages: Vec<serde_json::Value> = self
+            .messages
+            .iter()
+            .map(|msg| {
+                serde_json::json!({
+                    "role": msg.role.to_string(),
+                    "content": msg.content,
+                })
+            })
+            .collect();
+        serde_json::json!({ "messages": messages })
+    }
+
+    /// Clear all messages and checkpoints and reset token count
     ///
     /// # Returns
     /// * `Ok(())` - Successfully cleared
     /// * `Err(SentinelError)` - Error if operation fails
+    ///
+    /// # Note
+    /// Checkpoints are dropped too, since clearing usually means the messages were
+    /// consolidated and restoring them would duplicate them.
     pub fn clear(&mut self) -> Result<(), SentinelError> {
         self.messages.clear();
         self.token_count = 0;
+        self.checkpoints.clear();
         Ok(())
     }
 
+    /// Remove specific messages, e.g. once they have been consolidated elsewhere
+    ///
+    /// # Arguments
+    /// * `ids` - IDs of the messages to remove; IDs not in the buffer are ignored
+    ///
+    /// # Returns
+    /// The number of messages removed
+    ///
+    /// # Note
+    /// Messages appended after the removed ones are kept. As with `clear`, checkpoints
+    /// are dropped whenever something is removed, since restoring them would bring the
+    /// removed messages back.
+    pub fn remove_messages(&mut self, ids: &HashSet<MessageId>) -> usize {
+        let before = self.messages.len();
+        self.messages.retain(|msg| !ids.contains(&msg.id));
+        let removed = before - self.messages.len();
+        if removed > 0 {
+            self.token_count = self.token_counter.count_messages(&self.messages);
+            self.checkpoints.clear();
+        }
+        removed
+    }
+
+    /// Snapshot the current conversation under a name
+    ///
+    /// # Arguments
+    /// * `name` - Checkpoint name; an existing checkpoint with this name is replaced
+    ///
+    /// # Returns
+    /// * `Ok(())` - Checkpoint stored
+    /// * `Err(SentinelError)` - `DomainViolation` if `name` is new and the checkpoint
+    ///   limit is already reached (existing checkpoints are left unchanged)
+    pub fn checkpoint(&mut self, name: impl Into<String>) -> Result<(), SentinelError> {
+        let name = name.into();
+        if !self.checkpoints.contains_key(&name) && self.checkpoints.len() >= self.max_checkpoints {
+            return Err(SentinelError::DomainViolation {
+                rule: format!(
+                    "Checkpoint limit exceeded: {} >= {}",
+                    self.checkpoints.len(),
+                    self.max_checkpoints
+                ),
+            });
+        }
+        self.checkpoints.insert(
+            name,
+            Checkpoint {
+                messages: self.messages.clone(),
+                token_count: self.token_count,
+            },
+        );
+        Ok(())
+    }
+
+    /// Rewind the conversation to a checkpoint, discarding messages added since
+    ///
+    /// # Arguments
+    /// * `name` - Checkpoint to restore
+    ///
+    /// # Returns
+    /// * `Ok(())` - Conversation restored; the checkpoint is kept for further restores
+    /// * `Err(SentinelError)` - `NotFound` if no checkpoint has this name (memory is
+    ///   left unchanged)
+    pub fn restore_checkpoint(&mut self, name: &str) -> Result<(), SentinelError> {
+        let checkpoint = self
+            .checkpoints
+            .get(name)
+            .ok_or_else(|| SentinelError::NotFound {
+                resource: format!("Checkpoint {}", name),
+            })?;
+        self.messages = checkpoint.messages.clone();
+        self.token_count = checkpoint.token_count;
+        Ok(())
+    }
+
+    /// Delete a checkpoint
+    ///
+    /// # Returns
+    /// `true` if a checkpoint with this name existed
+    pub fn delete_checkpoint(&mut self, name: &str) -> bool {
+        self.checkpoints.remove(name).is_some()
+    }
+
+    /// Get the names of all checkpoints, sorted
+    pub fn checkpoint_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.checkpoints.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
     /// Get the current number of messages
     pub fn message_count(&self) -> usize {
         self.messages.len()
@@ -224,7 +570,7 @@ mod tests {
                 Role::User,
                 "This is a test message that has some tokens".to_string(),
             );
-            let msg_tokens = approximate_tokens(&msg.content);
+            let msg_tokens = SimpleTokenCounter.count_message(&msg);
             if total_tokens + msg_tokens <= 100 {
                 memory.append_message(msg).unwrap();
                 total_tokens = memory.token_count();
@@ -269,6 +615,119 @@ mod tests {
         assert_eq!(recent.len(), 2);
     }
 
+    #[test]
+    fn test_restore_checkpoint_discards_later_messages() {
+        let mut memory = ShortTermMemory::new();
+        memory
+            .append_message(CanonicalMessage::new(Role::User, "shared".to_string()))
+            .unwrap();
+        memory.checkpoint("fork").unwrap();
+        let tokens_at_fork = memory.token_count();
+
+        memory
+            .append_message(CanonicalMessage::new(Role::User, "branch a".to_string()))
+            .unwrap();
+        memory
+            .append_message(CanonicalMessage::new(
+                Role::Assistant,
+                "reply a".to_string(),
+            ))
+            .unwrap();
+        assert_eq!(memory.message_count(), 3);
+
+        memory.restore_checkpoint("fork").unwrap();
+        assert_eq!(memory.message_count(), 1);
+        assert_eq!(memory.get_messages()[0].content, "shared");
+        assert_eq!(memory.token_count(), tokens_at_fork);
+
+        // The checkpoint survives a restore, so another branch can start from it
+        memory
+            .append_message(CanonicalMessage::new(Role::User, "branch b".to_string()))
+            .unwrap();
+        memory.restore_checkpoint("fork").unwrap();
+        assert_eq!(memory.message_count(), 1);
+        assert_eq!(memory.checkpoint_names(), vec!["fork".to_string()]);
+    }
+
+    #[test]
+    fn test_restore_unknown_checkpoint_leaves_memory_unchanged() {
+        let mut memory = ShortTermMemory::new();
+        memory
+            .append_message(CanonicalMessage::new(Role::User, "kept".to_string()))
+            .unwrap();
+
+        let result = memory.restore_checkpoint("missing");
+        assert_eq!(
+            result,
+            Err(SentinelError::NotFound {
+                resource: "Checkpoint missing".to_string(),
+            })
+        );
+        assert_eq!(memory.message_count(), 1);
+    }
+
+    #[test]
+    fn test_checkpoints_replaced_deleted_and_cleared() {
+        let mut memory = ShortTermMemory::new();
+        memory.checkpoint("start").unwrap();
+        memory
+            .append_message(CanonicalMessage::new(Role::User, "one".to_string()))
+            .unwrap();
+        memory.checkpoint("start").unwrap();
+        memory.checkpoint("other").unwrap();
+
+        memory.restore_checkpoint("start").unwrap();
+        assert_eq!(memory.message_count(), 1);
+
+        assert!(memory.delete_checkpoint("other"));
+        assert!(!memory.delete_checkpoint("other"));
+
+        memory.clear().unwrap();
+        assert!(memory.checkpoint_names().is_empty());
+        assert!(memory.restore_checkpoint("start").is_err());
+    }
+
+    #[test]
+    fn test_checkpoint_limit_rejects_new_names_only() {
+        let mut memory = ShortTermMemory::new().with_max_checkpoints(2);
+        memory.checkpoint("a").unwrap();
+        memory.checkpoint("b").unwrap();
+
+        let result = memory.checkpoint("c");
+        assert!(matches!(result, Err(SentinelError::DomainViolation { .. })));
+        assert_eq!(
+            memory.checkpoint_names(),
+            vec!["a".to_string(), "b".to_string()]
+        );
+
+        // Replacing an existing checkpoint, or making room first, is still allowed
+        memory
+            .append_message(CanonicalMessage::new(Role::User, "later".to_string()))
+            .unwrap();
+        memory.checkpoint("a").unwrap();
+        assert!(memory.delete_checkpoint("b"));
+        memory.checkpoint("c").unwrap();
+        memory.restore_checkpoint("a").unwrap();
+        assert_eq!(memory.message_count(), 1);
+    }
+
+    #[test]
+    fn test_remove_messages_keeps_later_messages() {
+        let mut memory = ShortTermMemory::new();
+        let old = CanonicalMessage::new(Role::User, "summarized".to_string());
+        let new = CanonicalMessage::new(Role::User, "arrived during summarization".to_string());
+        memory.append_message(old.clone()).unwrap();
+        memory.checkpoint("before").unwrap();
+        memory.append_message(new.clone()).unwrap();
+
+        assert_eq!(memory.remove_messages(&HashSet::from([old.id])), 1);
+
+        assert_eq!(memory.get_messages(), vec![new.clone()]);
+        assert_eq!(memory.token_count(), SimpleTokenCounter.count_message(&new));
+        assert!(memory.checkpoint_names().is_empty());
+        assert_eq!(memory.remove_messages(&HashSet::from([old.id])), 0);
+    }
+
     #[test]
     fn test_clear() {
         let mut memory = ShortTermMemory::new();
@@ -383,4 +842,307 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_eviction_keeps_most_recent_messages() {
+        let mut memory = ShortTermMemory::with_eviction(3, DEFAULT_MAX_TOKENS);
+        for i in 0..5 {
+            let msg = CanonicalMessage::new(Role::User, format!("message {}", i));
+            memory.append_message(msg).unwrap();
+        }
+
+        let contents: Vec<String> = memory
+            .get_messages()
+            .into_iter()
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(contents, vec!["message 2", "message 3", "message 4"]);
+    }
+
+    #[test]
+    fn test_eviction_token_count_consistent() {
+        // Each 40-char message is 10 tokens; budget holds 2.5 of them
+        let mut memory = ShortTermMemory::with_eviction(100, 25);
+        for i in 0..4 {
+            let msg = CanonicalMessage::new(Role::User, format!("{}{}", i, "x".repeat(39)));
+            memory.append_message(msg).unwrap();
+        }
+
+        assert_eq!(memory.message_count(), 2);
+        assert_eq!(memory.token_count(), 20);
+        let recomputed: u64 = memory
+            .get_messages()
+            .iter()
+            .map(|m| SimpleTokenCounter.count_message(m))
+            .sum();
+        assert_eq!(memory.token_count(), recomputed);
+        assert!(memory.get_messages()[0].content.starts_with('2'));
+    }
+
+    #[test]
+    fn test_eviction_rejects_message_larger_than_budget() {
+        let mut memory = ShortTermMemory::with_eviction(10, 10);
+        memory
+            .append_message(CanonicalMessage::new(Role::User, "short".to_string()))
+            .unwrap();
+
+        let huge = CanonicalMessage::new(Role::User, "x".repeat(100));
+        let result = memory.append_message(huge);
+        assert!(matches!(result, Err(SentinelError::DomainViolation { .. })));
+        // Existing history is left untouched
+        assert_eq!(memory.message_count(), 1);
+    }
+
+    #[test]
+    fn test_default_memory_does_not_evict() {
+        let mut memory = ShortTermMemory::with_limits(1, DEFAULT_MAX_TOKENS, 10);
+        assert!(!memory.is_evicting());
+        memory
+            .append_message(CanonicalMessage::new(Role::User, "a".to_string()))
+            .unwrap();
+        assert!(memory
+            .append_message(CanonicalMessage::new(Role::User, "b".to_string()))
+            .is_err());
+    }
+
+    /// Build a memory holding a system prompt followed by alternating turns
+    fn mixed_role_memory() -> ShortTermMemory {
+        let mut memory = ShortTermMemory::new();
+        for (role, content) in [
+            (Role::System, "system prompt"),
+            (Role::User, "question 1"),
+            (Role::Assistant, "answer 1"),
+            (Role::User, "question 2"),
+            (Role::Assistant, "answer 2"),
+        ] {
+            memory
+                .append_message(CanonicalMessage::new(role, content.to_string()))
+                .unwrap();
+        }
+        memory
+    }
+
+    #[test]
+    fn test_get_messages_by_single_role() {
+        let memory = mixed_role_memory();
+
+        let contents: Vec<String> = memory
+            .get_messages_by_role(&[Role::User])
+            .into_iter()
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(contents, vec!["question 1", "question 2"]);
+    }
+
+    #[test]
+    fn test_get_messages_by_multiple_roles_preserves_order() {
+        let memory = mixed_role_memory();
+
+        let contents: Vec<String> = memory
+            .get_messages_by_role(&[Role::Assistant, Role::User])
+            .into_iter()
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(
+            contents,
+            vec!["question 1", "answer 1", "question 2", "answer 2"]
+        );
+    }
+
+    #[test]
+    fn test_get_messages_by_empty_role_filter() {
+        let memory = mixed_role_memory();
+        assert!(memory.get_messages_by_role(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_search_content_case_sensitivity_toggle() {
+        let memory = mixed_role_memory();
+
+        assert!(memory.search_content("QUESTION", false).is_empty());
+
+        let contents: Vec<String> = memory
+            .search_content("QUESTION", true)
+            .into_iter()
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(contents, vec!["question 1", "question 2"]);
+    }
+
+    #[test]
+    fn test_search_content_unicode() {
+        let mut memory = ShortTermMemory::new();
+        for content in ["Größe der Straße", "ÉCOLE d'été", "日本語のテキスト 🚀"] {
+            memory
+                .append_message(CanonicalMessage::new(Role::User, content.to_string()))
+                .unwrap();
+        }
+
+        assert_eq!(memory.search_content("straße", true).len(), 1);
+        assert_eq!(memory.search_content("école", true).len(), 1);
+        assert!(memory.search_content("école", false).is_empty());
+        assert_eq!(memory.search_content("テキスト 🚀", false).len(), 1);
+    }
+
+    #[test]
+    fn test_search_content_empty_substring_matches_nothing() {
+        let memory = mixed_role_memory();
+        assert!(memory.search_content("", false).is_empty());
+        assert!(memory.search_content("", true).is_empty());
+    }
+
+    #[test]
+    fn test_export_openai_maps_roles_to_lowercase() {
+        let memory = mixed_role_memory();
+        let exported = memory.export_openai();
+        let messages = exported["messages"].as_array().unwrap();
+
+        assert_eq!(messages.len(), memory.message_count());
+        for (exported, original) in messages.iter().zip(memory.get_messages()) {
+            let role = match original.role {
+                Role::User => "user",
+                Role::Assistant => "assistant",
+                Role::System => "system",
+            };
+            assert_eq!(exported["role"], role);
+            assert_eq!(exported["content"], original.content);
+        }
+    }
+
+    #[test]
+    fn test_export_openai_excludes_internal_fields() {
+        let mut memory = ShortTermMemory::new();
+        let metadata = std::collections::HashMap::from([("source".to_string(), "cli".to_string())]);
+        memory
+            .append_message(CanonicalMessage::with_metadata(
+                Role::User,
+                "Hello".to_string(),
+                metadata,
+            ))
+            .unwrap();
+
+        let exported = memory.export_openai();
+        assert_eq!(
+            exported,
+            serde_json::json!({ "messages": [{ "role": "user", "content": "Hello" }] })
+        );
+        assert_eq!(
+            ShortTermMemory::new().export_openai(),
+            serde_json::json!({ "messages": [] })
+        );
+    }
+
+    #[test]
+    fn test_import_messages_appends_in_order_and_counts_tokens() {
+        let mut memory = ShortTermMemory::new();
+        memory
+            .append_message(CanonicalMessage::new(Role::System, "existing".to_string()))
+            .unwrap();
+        let batch = vec![
+            CanonicalMessage::new(Role::User, "first question".to_string()),
+            CanonicalMessage::new(Role::Assistant, "first answer".to_string()),
+            CanonicalMessage::new(Role::User, "second question".to_string()),
+        ];
+        let expected_tokens: u64 = std::iter::once("existing")
+            .chain(batch.iter().map(|msg| msg.content.as_str()))
+            .map(|text| SimpleTokenCounter.count_tokens(text))
+            .sum();
+
+        memory.import_messages(batch.clone()).unwrap();
+
+        let messages = memory.get_messages();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0].content, "existing");
+        assert_eq!(&messages[1..], &batch[..]);
+        assert_eq!(memory.token_count(), expected_tokens);
+    }
+
+    #[test]
+    fn test_import_messages_over_limit_leaves_memory_unchanged() {
+        let mut memory = ShortTermMemory::with_limits(3, 1000, 500);
+        memory
+            .append_message(CanonicalMessage::new(Role::User, "keep me".to_string()))
+            .unwrap();
+        let before = memory.get_messages();
+        let tokens_before = memory.token_count();
+
+        let too_many = vec![
+            CanonicalMessage::new(Role::User, "a".to_string()),
+            CanonicalMessage::new(Role::User, "b".to_string()),
+            CanonicalMessage::new(Role::User, "c".to_string()),
+        ];
+        assert!(matches!(
+            memory.import_messages(too_many),
+            Err(SentinelError::DomainViolation { .. })
+        ));
+
+        let too_large = vec![CanonicalMessage::new(Role::User, "x".repeat(4004))];
+        assert!(matches!(
+            memory.import_messages(too_large),
+            Err(SentinelError::DomainViolation { .. })
+        ));
+
+        assert_eq!(memory.get_messages(), before);
+        assert_eq!(memory.token_count(), tokens_before);
+    }
+
+    #[test]
+    fn test_import_messages_evicts_in_eviction_mode() {
+        let mut memory = ShortTermMemory::with_eviction(3, 1000);
+        memory
+            .append_message(CanonicalMessage::new(Role::User, "old".to_string()))
+            .unwrap();
+        let batch: Vec<CanonicalMessage> = (1..=4)
+            .map(|i| CanonicalMessage::new(Role::User, format!("imported {}", i)))
+            .collect();
+
+        memory.import_messages(batch.clone()).unwrap();
+        assert_eq!(memory.get_messages(), batch[1..].to_vec());
+
+        // A single oversized message still fails the whole import
+        let before = memory.get_messages();
+        let oversized = vec![
+            CanonicalMessage::new(Role::User, "fits".to_string()),
+            CanonicalMessage::new(Role::User, "x".repeat(4004)),
+        ];
+        assert!(memory.import_messages(oversized).is_err());
+        assert_eq!(memory.get_messages(), before);
+    }
+
+    /// Counter charging one token per character, four times the default estimate
+    struct CharTokenCounter;
+
+    impl TokenCounter for CharTokenCounter {
+        fn count_tokens(&self, text: &str) -> u64 {
+            text.chars().count() as u64
+        }
+    }
+
+    #[test]
+    fn test_should_consolidate_respects_injected_counter() {
+        let msg = CanonicalMessage::new(Role::User, "x".repeat(40));
+
+        let mut default_counter = ShortTermMemory::with_limits(100, 1000, 20);
+        default_counter.append_message(msg.clone()).unwrap();
+        assert_eq!(default_counter.token_count(), 10);
+        assert!(!default_counter.should_consolidate());
+
+        let mut injected = ShortTermMemory::with_limits(100, 1000, 20)
+            .with_token_counter(Box::new(CharTokenCounter));
+        injected.append_message(msg).unwrap();
+        assert_eq!(injected.token_count(), 40);
+        assert!(injected.should_consolidate());
+    }
+
+    #[test]
+    fn test_with_token_counter_recounts_existing_messages() {
+        let mut memory = ShortTermMemory::new();
+        memory
+            .append_message(CanonicalMessage::new(Role::User, "x".repeat(40)))
+            .unwrap();
+        assert_eq!(memory.token_count(), 10);
+
+        let memory = memory.with_token_counter(Box::new(CharTokenCounter));
+        assert_eq!(memory.token_count(), 40);
+    }
 }
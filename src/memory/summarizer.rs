@@ -0,0 +1,196 @@
+// Conversation summarizers
+// Extractive (concatenation) and LLM-backed implementations of the Summarizer port
+
+use crate::core::error::SentinelError;
+use crate::core::traits::{LLMProvider, Summarizer};
+use crate::core::types::{CanonicalMessage, CompletionParams, Role};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Instruction sent to the LLM before the transcript to summarize
+const SUMMARY_INSTRUCTION: &str = "Summarize the following conversation for long-term memory. \
+Keep facts, decisions, and open questions; omit greetings and filler. \
+Reply with the summary only.";
+
+/// Render messages as a `role: content` transcript, one message per line
+fn transcript(messages: &[CanonicalMessage]) -> String {
+    messages
+        .iter()
+        .map(|msg| format!("{}: {}", msg.role, msg.content))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Summarizer that concatenates messages as `role: content` lines
+///
+/// Lossless and free, but the summary grows with the conversation.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConcatSummarizer;
+
+#[async_trait]
+impl Summarizer for ConcatSummarizer {
+    async fn summarize(&self, messages: &[CanonicalMessage]) -> Result<String, SentinelError> {
+        Ok(transcript(messages))
+    }
+}
+
+/// Summarizer that asks an LLM provider to condense the conversation
+pub struct LLMSummarizer {
+    /// Provider generating the summary
+    provider: Arc<dyn LLMProvider>,
+    /// Generation parameters for summary completions
+    params: CompletionParams,
+}
+
+impl LLMSummarizer {
+    /// Create a summarizer backed by `provider`, using its default parameters
+    pub fn new(provider: Arc<dyn LLMProvider>) -> Self {
+        Self {
+            provider,
+            params: CompletionParams::default(),
+        }
+    }
+
+    /// Use specific generation parameters (e.g. a cheaper model) for summaries
+    pub fn with_params(mut self, params: CompletionParams) -> Self {
+        self.params = params;
+        self
+    }
+}
+
+#[async_trait]
+impl Summarizer for LLMSummarizer {
+    /// Summarize with one completion: the instruction as a system message, then the
+    /// transcript as a user message
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Trimmed summary (empty for no messages, without calling the LLM)
+    /// * `Err(SentinelError)` - The completion fails or returns an empty summary
+    async fn summarize(&self, messages: &[CanonicalMessage]) -> Result<String, SentinelError> {
+        if messages.is_empty() {
+            return Ok(String::new());
+        }
+
+        let prompt = vec![
+            CanonicalMessage::new(Role::System, SUMMARY_INSTRUCTION.to_string()),
+            CanonicalMessage::new(Role::User, transcript(messages)),
+        ];
+        let response = self
+            .provider
+            .complete_with_params(prompt, self.params.clone())
+            .await?;
+
+        let summary = response.content.trim();
+        if summary.is_empty() {
+            return Err(SentinelError::ProviderError {
+                provider: "summarizer".to_string(),
+                detail: "LLM returned an empty summary".to_string(),
+            });
+        }
+        Ok(summary.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn conversation() -> Vec<CanonicalMessage> {
+        vec![
+            CanonicalMessage::new(Role::User, "Where is the Eiffel Tower?".to_string()),
+            CanonicalMessage::new(Role::Assistant, "In Paris.".to_string()),
+        ]
+    }
+
+    /// Provider that records the prompt it receives and replies with a fixed text
+    struct ScriptedProvider {
+        reply: &'static str,
+        prompts: Mutex<Vec<(Vec<CanonicalMessage>, CompletionParams)>>,
+    }
+
+    impl ScriptedProvider {
+        fn new(reply: &'static str) -> Arc<Self> {
+            Arc::new(Self {
+                reply,
+                prompts: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl LLMProvider for ScriptedProvider {
+        async fn complete(
+            &self,
+            messages: Vec<CanonicalMessage>,
+        ) -> Result<CanonicalMessage, SentinelError> {
+            self.complete_with_params(messages, CompletionParams::default())
+                .await
+        }
+
+        async fn complete_with_params(
+            &self,
+            messages: Vec<CanonicalMessage>,
+            params: CompletionParams,
+        ) -> Result<CanonicalMessage, SentinelError> {
+            self.prompts.lock().unwrap().push((messages, params));
+            Ok(CanonicalMessage::new(
+                Role::Assistant,
+                self.reply.to_string(),
+            ))
+        }
+
+        async fn stream(
+            &self,
+            _messages: Vec<CanonicalMessage>,
+        ) -> Result<
+            Box<dyn futures::Stream<Item = Result<String, SentinelError>> + Send + Unpin>,
+            SentinelError,
+        > {
+            unimplemented!("not used by these tests")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concat_summarizer_joins_role_prefixed_lines() {
+        let summary = ConcatSummarizer.summarize(&conversation()).await.unwrap();
+        assert_eq!(
+            summary,
+            "user: Where is the Eiffel Tower?\nassistant: In Paris."
+        );
+        assert_eq!(ConcatSummarizer.summarize(&[]).await.unwrap(), "");
+    }
+
+    #[tokio::test]
+    async fn test_llm_summarizer_sends_transcript_and_trims_reply() {
+        let provider = ScriptedProvider::new("  The Eiffel Tower is in Paris.\n");
+        let params = CompletionParams {
+            model: Some("gpt-4o-mini".to_string()),
+            ..CompletionParams::default()
+        };
+        let summarizer = LLMSummarizer::new(provider.clone()).with_params(params.clone());
+
+        let summary = summarizer.summarize(&conversation()).await.unwrap();
+        assert_eq!(summary, "The Eiffel Tower is in Paris.");
+
+        let prompts = provider.prompts.lock().unwrap();
+        assert_eq!(prompts.len(), 1);
+        let (prompt, sent_params) = &prompts[0];
+        assert_eq!(sent_params, &params);
+        assert_eq!(prompt[0].role, Role::System);
+        assert_eq!(prompt[1].role, Role::User);
+        assert!(prompt[1].content.contains("assistant: In Paris."));
+    }
+
+    #[tokio::test]
+    async fn test_llm_summarizer_skips_empty_input_and_rejects_empty_reply() {
+        let provider = ScriptedProvider::new("   ");
+        let summarizer = LLMSummarizer::new(provider.clone());
+
+        assert_eq!(summarizer.summarize(&[]).await.unwrap(), "");
+        assert!(provider.prompts.lock().unwrap().is_empty());
+
+        let error = summarizer.summarize(&conversation()).await.unwrap_err();
+        assert!(matches!(error, SentinelError::ProviderError { .. }));
+    }
+}
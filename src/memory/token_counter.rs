@@ -1,6 +1,8 @@
 // Token counting implementation for accurate memory management
 // Supports multiple counting strategies (simple approximation, accurate tokenization)
 
+#[cfg(feature = "tiktoken")]
+use crate::core::error::SentinelError;
 use crate::core::types::CanonicalMessage;
 
 /// Trait for token counting strategies
@@ -38,6 +40,22 @@ pub trait TokenCounter: Send + Sync {
     }
 }
 
+/// Shared counters count like the counter they wrap, so one strategy can be handed to
+/// several short-term memories
+impl<T: TokenCounter + ?Sized> TokenCounter for std::sync::Arc<T> {
+    fn count_tokens(&self, text: &str) -> u64 {
+        (**self).count_tokens(text)
+    }
+
+    fn count_message(&self, msg: &CanonicalMessage) -> u64 {
+        (**self).count_message(msg)
+    }
+
+    fn count_messages(&self, messages: &[CanonicalMessage]) -> u64 {
+        (**self).count_messages(messages)
+    }
+}
+
 /// Simple token counter using character approximation
 /// Tokens ≈ characters / 4 (rough approximation for English text)
 /// This is fast but not accurate for all languages or tokenization schemes
@@ -58,8 +76,8 @@ impl Default for SimpleTokenCounter {
 }
 
 /// Accurate token counter (placeholder for future implementation)
-/// This would use tiktoken or similar library for accurate tokenization
-/// For now, it uses the same simple approximation
+/// For now, it uses the same simple approximation; enable the `tiktoken` feature and
+/// use `TiktokenCounter` for real BPE counts
 pub struct AccurateTokenCounter {
     // Future: tokenizer instance
     // For now, we'll use simple approximation
@@ -92,6 +110,51 @@ impl Default for AccurateTokenCounter {
     }
 }
 
+/// Token counter using OpenAI's BPE tokenizers (requires the `tiktoken` feature)
+/// Counts exactly what the model is billed for, at the cost of encoding the text
+#[cfg(feature = "tiktoken")]
+pub struct TiktokenCounter {
+    bpe: tiktoken_rs::CoreBPE,
+}
+
+#[cfg(feature = "tiktoken")]
+impl TiktokenCounter {
+    /// Create a counter using the `cl100k_base` encoding (GPT-4 and GPT-3.5 models)
+    ///
+    /// # Returns
+    /// * `Ok(TiktokenCounter)` - Counter ready to use
+    /// * `Err(SentinelError)` - Error if the encoding cannot be loaded
+    pub fn new() -> Result<Self, SentinelError> {
+        let bpe = tiktoken_rs::cl100k_base().map_err(|e| SentinelError::DomainViolation {
+            rule: format!("Failed to load cl100k_base tokenizer: {}", e),
+        })?;
+        Ok(Self { bpe })
+    }
+
+    /// Create a counter using the encoding of a specific model
+    ///
+    /// # Arguments
+    /// * `model` - OpenAI model name (e.g. "gpt-4o")
+    ///
+    /// # Returns
+    /// * `Ok(TiktokenCounter)` - Counter for the model's encoding
+    /// * `Err(SentinelError)` - Error if the model is unknown
+    pub fn with_model(model: &str) -> Result<Self, SentinelError> {
+        let bpe =
+            tiktoken_rs::get_bpe_from_model(model).map_err(|e| SentinelError::DomainViolation {
+                rule: format!("No tokenizer for model {}: {}", model, e),
+            })?;
+        Ok(Self { bpe })
+    }
+}
+
+#[cfg(feature = "tiktoken")]
+impl TokenCounter for TiktokenCounter {
+    fn count_tokens(&self, text: &str) -> u64 {
+        self.bpe.encode_ordinary(text).len() as u64
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,4 +225,22 @@ mod tests {
         let tokens = counter.count_tokens("Hello");
         assert!(tokens > 0);
     }
+
+    #[cfg(feature = "tiktoken")]
+    #[test]
+    fn test_tiktoken_counter_differs_from_approximation() {
+        let tiktoken = TiktokenCounter::new().unwrap();
+
+        // Common English words encode to one token each, well above chars / 4
+        let text = "the cat sat on a mat";
+        assert_eq!(tiktoken.count_tokens(text), 6);
+        assert_eq!(SimpleTokenCounter.count_tokens(text), 5);
+
+        // A long run of one character compresses far below chars / 4
+        let repeated = "a".repeat(400);
+        assert!(tiktoken.count_tokens(&repeated) < SimpleTokenCounter.count_tokens(&repeated));
+
+        assert!(TiktokenCounter::with_model("gpt-4o").is_ok());
+        assert!(TiktokenCounter::with_model("not-a-model").is_err());
+    }
 }
@@ -13,7 +13,7 @@ pub struct ConsolidationConfig {
     pub short_term_message_threshold: usize,
     /// Number of summaries before medium-term consolidation
     pub medium_term_summary_threshold: usize,
-    /// Age threshold for medium-term summaries (older summaries prioritized)
+    /// Age beyond which medium-term summaries are consolidated regardless of count
     pub medium_term_age_threshold: Duration,
     /// Enable automatic consolidation in background
     pub enable_auto_consolidation: bool,
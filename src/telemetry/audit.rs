@@ -0,0 +1,23 @@
+// Request/response audit log settings
+// Shared by configuration loading and the audit middleware
+
+/// Default number of bytes of each body kept in an audit log line
+pub const DEFAULT_AUDIT_BODY_BYTES: usize = 2048;
+
+/// Settings for the request/response audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditLogConfig {
+    /// Also log request and response bodies
+    pub log_bodies: bool,
+    /// Bytes of each body kept before it is truncated
+    pub max_body_bytes: usize,
+}
+
+impl Default for AuditLogConfig {
+    fn default() -> Self {
+        Self {
+            log_bodies: false,
+            max_body_bytes: DEFAULT_AUDIT_BODY_BYTES,
+        }
+    }
+}
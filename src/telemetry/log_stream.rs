@@ -0,0 +1,183 @@
+// In-process log stream for live debugging
+// A tracing layer keeps recent events in a bounded ring buffer and broadcasts new ones
+
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tokio::sync::broadcast;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+/// Default number of recent log lines kept for new subscribers
+pub const DEFAULT_LOG_BUFFER_CAPACITY: usize = 500;
+
+/// Number of lines a slow subscriber may fall behind before it skips ahead
+const BROADCAST_CAPACITY: usize = 1024;
+
+/// Recent log lines plus a broadcast channel of new ones
+#[derive(Debug)]
+pub struct LogStream {
+    /// Most recent lines, oldest first
+    recent: Mutex<VecDeque<String>>,
+    /// Maximum number of recent lines kept
+    capacity: usize,
+    /// Live feed of new lines
+    sender: broadcast::Sender<String>,
+}
+
+impl LogStream {
+    /// Create a log stream keeping up to `capacity` recent lines
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(BROADCAST_CAPACITY);
+        Self {
+            recent: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+            sender,
+        }
+    }
+
+    /// Record a line, evicting the oldest once the buffer is full
+    pub fn publish(&self, line: String) {
+        {
+            let mut recent = self.recent();
+            if self.capacity > 0 {
+                if recent.len() == self.capacity {
+                    recent.pop_front();
+                }
+                recent.push_back(line.clone());
+            }
+        }
+        // No subscribers is not an error
+        let _ = self.sender.send(line);
+    }
+
+    /// Subscribe to the stream
+    ///
+    /// # Returns
+    /// The buffered recent lines and a receiver for lines published afterwards
+    pub fn subscribe(&self) -> (Vec<String>, broadcast::Receiver<String>) {
+        // Hold the buffer lock so no line is both in the backlog and the live feed
+        let recent = self.recent();
+        (recent.iter().cloned().collect(), self.sender.subscribe())
+    }
+
+    /// Lock the buffer, recovering it if a holder panicked
+    ///
+    /// Publishing is called for every log event, so a poisoned buffer must not panic
+    /// the logging caller; the buffered lines are still valid.
+    fn recent(&self) -> MutexGuard<'_, VecDeque<String>> {
+        self.recent.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for LogStream {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOG_BUFFER_CAPACITY)
+    }
+}
+
+/// Tracing layer publishing each event to a [`LogStream`] as one text line
+///
+/// Lines look like `[12:00:01] WARN sentinel::engine::actor: message key=value`.
+pub struct LogStreamLayer {
+    stream: Arc<LogStream>,
+}
+
+impl LogStreamLayer {
+    /// Create a layer publishing to `stream`
+    pub fn new(stream: Arc<LogStream>) -> Self {
+        Self { stream }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogStreamLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = LineVisitor::default();
+        event.record(&mut visitor);
+
+        let mut line = format!(
+            "[{}] {} {}: {}",
+            chrono::Utc::now().format("%H:%M:%S"),
+            metadata.level(),
+            metadata.target(),
+            visitor.message
+        );
+        line.push_str(&visitor.fields);
+        self.stream.publish(line);
+    }
+}
+
+/// Collects an event's message and remaining fields
+#[derive(Default)]
+struct LineVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for LineVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_buffer_keeps_most_recent_lines() {
+        let stream = LogStream::new(2);
+        stream.publish("one".to_string());
+        stream.publish("two".to_string());
+        stream.publish("three".to_string());
+
+        let (recent, _) = stream.subscribe();
+        assert_eq!(recent, ["two", "three"]);
+    }
+
+    #[test]
+    fn test_poisoned_buffer_keeps_publishing() {
+        let stream = LogStream::new(2);
+        stream.publish("one".to_string());
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _recent = stream.recent.lock().unwrap();
+            panic!("poison the buffer");
+        }));
+        assert!(stream.recent.is_poisoned());
+
+        stream.publish("two".to_string());
+        assert_eq!(stream.subscribe().0, ["one", "two"]);
+    }
+
+    #[test]
+    fn test_layer_formats_events_and_broadcasts() {
+        let stream = Arc::new(LogStream::default());
+        let (_, mut rx) = stream.subscribe();
+        let subscriber = tracing_subscriber::registry().with(LogStreamLayer::new(stream.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(agent_id = "a-1", "queue almost full");
+        });
+
+        let line = rx.try_recv().unwrap();
+        assert!(line.contains("WARN"), "{}", line);
+        assert!(line.contains("queue almost full agent_id=a-1"), "{}", line);
+        assert_eq!(stream.subscribe().0, [line]);
+    }
+}
@@ -0,0 +1,124 @@
+// Request metrics exposed in the Prometheus text format
+// Counts authenticated requests by the caller's auth level and matched route
+
+use crate::core::auth::AuthLevel;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Name of the authenticated request counter
+pub const REQUESTS_TOTAL: &str = "sentinel_requests_total";
+
+/// Counters of authenticated requests labeled by auth level and route
+#[derive(Debug, Default)]
+pub struct RequestMetrics {
+    /// Request count per (auth level, route template)
+    requests: Mutex<BTreeMap<(AuthLevel, String), u64>>,
+}
+
+impl RequestMetrics {
+    /// Create an empty set of counters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one request
+    ///
+    /// # Arguments
+    /// * `auth_level` - Auth level of the caller's API key
+    /// * `route` - Matched route template (e.g. `/v1/agents/:agent_id`), not the raw
+    ///   path, so label cardinality stays bounded
+    pub fn record(&self, auth_level: AuthLevel, route: &str) {
+        *self
+            .requests()
+            .entry((auth_level, route.to_string()))
+            .or_insert(0) += 1;
+    }
+
+    /// Get the number of requests counted for an auth level and route
+    pub fn count(&self, auth_level: AuthLevel, route: &str) -> u64 {
+        self.requests()
+            .get(&(auth_level, route.to_string()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Render the counters in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let requests = self.requests();
+        let mut output = format!(
+            "# HELP {name} Authenticated requests by auth level and route\n# TYPE {name} counter\n",
+            name = REQUESTS_TOTAL
+        );
+        for ((auth_level, route), count) in requests.iter() {
+            // Writing to a String cannot fail
+            let _ = writeln!(
+                output,
+                "{}{{auth_level=\"{}\",route=\"{}\"}} {}",
+                REQUESTS_TOTAL,
+                auth_level.as_str(),
+                escape_label_value(route),
+                count
+            );
+        }
+        output
+    }
+
+    /// Lock the counters, recovering them if a holder panicked
+    ///
+    /// A counter update cannot be left half done, so poisoned counters are still valid.
+    fn requests(&self) -> MutexGuard<'_, BTreeMap<(AuthLevel, String), u64>> {
+        self.requests.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Escape a label value for the Prometheus text format
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counts_per_level_and_route() {
+        let metrics = RequestMetrics::new();
+        metrics.record(AuthLevel::Read, "/v1/agents/status");
+        metrics.record(AuthLevel::Read, "/v1/agents/status");
+        metrics.record(AuthLevel::Write, "/v1/chat/completions");
+
+        assert_eq!(metrics.count(AuthLevel::Read, "/v1/agents/status"), 2);
+        assert_eq!(metrics.count(AuthLevel::Admin, "/v1/agents/status"), 0);
+        assert_eq!(
+            metrics.render(),
+            "# HELP sentinel_requests_total Authenticated requests by auth level and route\n\
+             # TYPE sentinel_requests_total counter\n\
+             sentinel_requests_total{auth_level=\"read\",route=\"/v1/agents/status\"} 2\n\
+             sentinel_requests_total{auth_level=\"write\",route=\"/v1/chat/completions\"} 1\n"
+        );
+    }
+
+    #[test]
+    fn test_poisoned_counters_keep_counting() {
+        let metrics = RequestMetrics::new();
+        metrics.record(AuthLevel::Read, "/health");
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _requests = metrics.requests.lock().unwrap();
+            panic!("poison the counters");
+        }));
+        assert!(metrics.requests.is_poisoned());
+
+        metrics.record(AuthLevel::Read, "/health");
+        assert_eq!(metrics.count(AuthLevel::Read, "/health"), 2);
+        assert!(metrics.render().contains("route=\"/health\"} 2"));
+    }
+
+    #[test]
+    fn test_escape_label_value() {
+        assert_eq!(escape_label_value(r#"a"b\c"#), r#"a\"b\\c"#);
+    }
+}
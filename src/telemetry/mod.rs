@@ -1 +1,230 @@
 // Tracing and observability setup
+// Installs the global tracing subscriber in text or JSON format
+
+pub mod audit;
+pub mod log_stream;
+pub mod metrics;
+
+use log_stream::{LogStream, LogStreamLayer};
+use std::str::FromStr;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::Subscriber;
+use tracing_subscriber::filter::ParseError;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::{SubscriberInitExt, TryInitError};
+use tracing_subscriber::{EnvFilter, Layer};
+
+/// Error installing the global tracing subscriber
+#[derive(Error, Debug)]
+pub enum TracingInitError {
+    /// The filter directives could not be parsed
+    #[error("Invalid log filter '{filter}'")]
+    InvalidFilter {
+        /// The rejected directive string
+        filter: String,
+        /// Why parsing failed
+        #[source]
+        source: ParseError,
+    },
+
+    /// A global subscriber was already installed
+    #[error(transparent)]
+    AlreadyInstalled(#[from] TryInitError),
+}
+
+/// Output format for log events
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable text (development default)
+    #[default]
+    Text,
+    /// One JSON object per event with level, target, message and span fields
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "Invalid log format '{}': expected 'text' or 'json'",
+                other
+            )),
+        }
+    }
+}
+
+/// Build the formatting layer for a log format
+///
+/// # Arguments
+/// * `format` - Text or JSON output
+/// * `writer` - Destination for formatted events
+///
+/// # Returns
+/// A boxed layer; the JSON layer flattens event fields to the top level and
+/// includes the current span and span list, so request and key IDs are attached
+pub fn fmt_layer<S, W>(format: LogFormat, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => tracing_subscriber::fmt::layer().with_writer(writer).boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(true)
+            .with_writer(writer)
+            .boxed(),
+    }
+}
+
+/// Parse `EnvFilter` directives (e.g. `info,sentinel=debug`)
+///
+/// # Arguments
+/// * `filter` - Directive string, as set in `RUST_LOG`
+///
+/// # Returns
+/// * `Ok(EnvFilter)` - The parsed filter
+/// * `Err(TracingInitError::InvalidFilter)` - A directive could not be parsed
+pub fn parse_filter(filter: &str) -> Result<EnvFilter, TracingInitError> {
+    EnvFilter::try_new(filter).map_err(|source| TracingInitError::InvalidFilter {
+        filter: filter.to_string(),
+        source,
+    })
+}
+
+/// Install the global tracing subscriber writing to stdout
+///
+/// # Arguments
+/// * `format` - Text or JSON output (from `Config.log_format`)
+/// * `filter` - `EnvFilter` directives (from `Config.rust_log`)
+///
+/// # Returns
+/// * `Ok(())` - Subscriber installed
+/// * `Err(TracingInitError)` - The filter is invalid, or a global subscriber was
+///   already installed
+pub fn init_tracing(format: LogFormat, filter: &str) -> Result<(), TracingInitError> {
+    install_subscriber(format, filter, None)
+}
+
+/// Install the global tracing subscriber, also publishing events to a log stream
+///
+/// Same as [`init_tracing`], with every event that passes the filter additionally
+/// published to `log_stream` (served at `/v1/logs/stream`).
+pub fn init_tracing_with_log_stream(
+    format: LogFormat,
+    filter: &str,
+    log_stream: Arc<LogStream>,
+) -> Result<(), TracingInitError> {
+    install_subscriber(format, filter, Some(log_stream))
+}
+
+fn install_subscriber(
+    format: LogFormat,
+    filter: &str,
+    log_stream: Option<Arc<LogStream>>,
+) -> Result<(), TracingInitError> {
+    let filter = parse_filter(filter)?;
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt_layer(format, std::io::stdout))
+        .with(log_stream.map(LogStreamLayer::new))
+        .try_init()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    /// Writer collecting formatted output in memory
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Buffer;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    /// Log one event inside a request span and return the formatted output
+    fn capture(format: LogFormat) -> String {
+        let buffer = Buffer::default();
+        let subscriber = tracing_subscriber::registry().with(fmt_layer(format, buffer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request", request_id = "req-1", key_id = "key-1");
+            let _entered = span.enter();
+            tracing::info!("handled request");
+        });
+
+        let output = buffer.0.lock().unwrap().clone();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_parse_log_format() {
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!(" TEXT ".parse::<LogFormat>().unwrap(), LogFormat::Text);
+        assert!("yaml".parse::<LogFormat>().is_err());
+        assert_eq!(LogFormat::default(), LogFormat::Text);
+    }
+
+    #[test]
+    fn test_invalid_filter_is_rejected() {
+        assert!(parse_filter("info,sentinel=debug").is_ok());
+
+        let err = parse_filter("sentinel=loud").unwrap_err();
+        assert!(matches!(err, TracingInitError::InvalidFilter { .. }));
+        assert_eq!(err.to_string(), "Invalid log filter 'sentinel=loud'");
+        // The global subscriber is never touched for an invalid filter
+        assert!(matches!(
+            init_tracing(LogFormat::Text, "sentinel=loud"),
+            Err(TracingInitError::InvalidFilter { .. })
+        ));
+    }
+
+    #[test]
+    fn test_json_format_emits_structured_events() {
+        let output = capture(LogFormat::Json);
+
+        let event: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(event["level"], "INFO");
+        assert_eq!(event["message"], "handled request");
+        assert!(event["target"].as_str().unwrap().contains("telemetry"));
+        assert_eq!(event["span"]["request_id"], "req-1");
+        assert_eq!(event["span"]["key_id"], "key-1");
+    }
+
+    #[test]
+    fn test_text_format_is_not_json() {
+        let output = capture(LogFormat::Text);
+
+        assert!(output.contains("handled request"));
+        assert!(output.contains("request_id"));
+        assert!(serde_json::from_str::<serde_json::Value>(output.trim()).is_err());
+    }
+}
@@ -1,9 +1,11 @@
 // Integration tests for adapter boundary verification
 // Ensures strict hexagonal architecture boundaries are maintained
 
-// Note: These types need to be public in their modules to be imported here
-// For now, we verify trait implementation through compilation
-use sentinel::core::traits::{LLMProvider, VectorStore};
+use sentinel::adapters::qdrant::QdrantStore;
+use sentinel::core::traits::VectorStore;
+
+/// Compile-time assertion that `T` implements `VectorStore`
+fn assert_vector_store<T: VectorStore>() {}
 
 /// Verify that adapters implement their traits
 /// This is verified at compile time - if the adapters don't implement
@@ -14,9 +16,9 @@ fn test_adapters_implement_traits() {
     // 1. OpenAI adapter implements LLMProvider (checked via compilation)
     // 2. Qdrant adapter implements VectorStore (checked via compilation)
     // 3. All adapters use SentinelError (checked via compilation)
-    
+
     // If this test compiles and runs, the trait implementations are correct
-    assert!(true);
+    assert_vector_store::<QdrantStore>();
 }
 
 /// Verify core module has no external dependencies
@@ -25,6 +27,5 @@ fn test_core_no_external_deps() {
     // This is a compile-time check
     // If core imports external crates, this will fail
     // Manual verification: Check src/core/ imports
-    assert!(true); // Placeholder - actual check done via cargo tree
+    // Placeholder - actual check done via cargo tree
 }
-
@@ -31,10 +31,12 @@ async fn make_chat_request(
         temperature: Some(0.7),
         max_tokens: Some(1000),
         stream: false,
+        conversation_id: None,
+        session_id: None,
     };
 
     client
-        .post(&format!("{}/v1/chat/completions", api_base_url()))
+        .post(format!("{}/v1/chat/completions", api_base_url()))
         .header("Authorization", format!("Bearer {}", api_key()))
         .header("Content-Type", "application/json")
         .json(&request)
@@ -60,10 +62,7 @@ async fn test_openai_provider_simulation() {
 
     let result: serde_json::Value = response.json().await.unwrap();
     assert!(result.get("message").is_some());
-    assert_eq!(
-        result["message"]["role"].as_str().unwrap(),
-        "assistant"
-    );
+    assert_eq!(result["message"]["role"].as_str().unwrap(), "assistant");
 }
 
 /// Test simulating Anthropic-style provider requests
@@ -93,10 +92,9 @@ async fn test_google_provider_simulation() {
 
     // Simulate Google-style request with metadata
     let mut message = create_test_message(Role::User, "Explain quantum computing.");
-    message.metadata.insert(
-        "provider".to_string(),
-        "google".to_string(),
-    );
+    message
+        .metadata
+        .insert("provider".to_string(), "google".to_string());
 
     let response = make_chat_request(&client, vec![message]).await.unwrap();
     assert_eq!(response.status(), reqwest::StatusCode::OK);
@@ -169,8 +167,9 @@ async fn test_rate_limiting() {
         sleep(Duration::from_millis(100)).await;
     }
 
-    // Should have some successful requests
+    // Should have some successful requests, and every request is accounted for
     assert!(success_count > 0);
+    assert!(success_count + rate_limited_count <= 20);
 }
 
 /// Test vector storage operations through chat completions
@@ -194,13 +193,12 @@ async fn test_vector_storage_operations() {
             Role::User,
             "Store this information: The Eiffel Tower is located in Paris, France.",
         ),
-        create_test_message(
-            Role::User,
-            "Where is the Eiffel Tower located?",
-        ),
+        create_test_message(Role::User, "Where is the Eiffel Tower located?"),
     ];
 
-    let response = make_chat_request(&client, follow_up_messages).await.unwrap();
+    let response = make_chat_request(&client, follow_up_messages)
+        .await
+        .unwrap();
     assert_eq!(response.status(), reqwest::StatusCode::OK);
 
     // The response should potentially reference stored information
@@ -209,11 +207,9 @@ async fn test_vector_storage_operations() {
         .as_str()
         .unwrap()
         .to_lowercase();
-    
+
     // Verify response contains relevant information (basic check)
-    assert!(
-        content.contains("paris") || content.contains("france") || content.len() > 0
-    );
+    assert!(content.contains("paris") || content.contains("france") || !content.is_empty());
 }
 
 /// Test multi-turn conversation with vector storage
@@ -253,7 +249,7 @@ async fn test_error_handling() {
 
     // Test with empty messages
     let response = client
-        .post(&format!("{}/v1/chat/completions", api_base_url()))
+        .post(format!("{}/v1/chat/completions", api_base_url()))
         .header("Authorization", format!("Bearer {}", api_key()))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
@@ -276,7 +272,7 @@ async fn test_invalid_authentication() {
     let messages = vec![create_test_message(Role::User, "Test")];
 
     let response = client
-        .post(&format!("{}/v1/chat/completions", api_base_url()))
+        .post(format!("{}/v1/chat/completions", api_base_url()))
         .header("Authorization", "Bearer invalid-key")
         .header("Content-Type", "application/json")
         .json(&ChatCompletionRequest {
@@ -285,6 +281,8 @@ async fn test_invalid_authentication() {
             temperature: None,
             max_tokens: None,
             stream: false,
+            conversation_id: None,
+            session_id: None,
         })
         .send()
         .await
@@ -300,7 +298,7 @@ async fn test_health_check() {
     let client = reqwest::Client::new();
 
     let response = client
-        .get(&format!("{}/health", api_base_url()))
+        .get(format!("{}/health", api_base_url()))
         .send()
         .await
         .unwrap();
@@ -318,14 +316,16 @@ async fn test_readiness_check() {
     let client = reqwest::Client::new();
 
     let response = client
-        .get(&format!("{}/health/ready", api_base_url()))
+        .get(format!("{}/health/ready", api_base_url()))
         .send()
         .await
         .unwrap();
 
     // Should be 200 if ready, 503 if not
-    assert!(response.status() == reqwest::StatusCode::OK 
-         || response.status() == reqwest::StatusCode::SERVICE_UNAVAILABLE);
+    assert!(
+        response.status() == reqwest::StatusCode::OK
+            || response.status() == reqwest::StatusCode::SERVICE_UNAVAILABLE
+    );
 }
 
 /// Test liveness endpoint
@@ -335,7 +335,7 @@ async fn test_liveness_check() {
     let client = reqwest::Client::new();
 
     let response = client
-        .get(&format!("{}/health/live", api_base_url()))
+        .get(format!("{}/health/live", api_base_url()))
         .send()
         .await
         .unwrap();
@@ -343,4 +343,3 @@ async fn test_liveness_check() {
     // Liveness should always return 200 if service is running
     assert_eq!(response.status(), reqwest::StatusCode::OK);
 }
-
@@ -1,37 +1,178 @@
 // Integration tests for Sentinel Orchestrator API
 // These tests verify the full HTTP stack including authentication, routing, and responses
 
+use async_trait::async_trait;
 use axum::{
     body::Body,
     http::{header, Request, StatusCode},
 };
 use sentinel::api::middleware::ApiKeyStore;
-use sentinel::api::routes::create_router;
+use sentinel::api::routes::{create_router, AppState};
 use sentinel::core::auth::{ApiKeyId, AuthLevel};
+use sentinel::core::error::SentinelError;
+use sentinel::core::traits::{LLMProvider, VectorStore};
 use sentinel::core::types::{
-    CanonicalMessage, ChatCompletionRequest, ChatCompletionResponse, HealthState,
-    HealthStatus, Role,
+    AgentId, AgentState, CanonicalMessage, ChatCompletionRequest, ChatCompletionResponse,
+    CompletionParams, ConversationId, ErrorResponse, HealthState, HealthStatus, MaintenanceMode,
+    MessageId, ModelParams, Role, SpawnAgentResponse, TerminateAgentsResponse,
 };
+use sentinel::engine::actor::MessageProcessor;
+use sentinel::engine::channels::ActorMessage;
+use sentinel::engine::supervisor::{SpawnHook, Supervisor};
+use sentinel::memory::conversation_budget::ConversationBudgets;
+use sentinel::memory::manager::MemoryManager;
 use std::sync::Arc;
+use tokio::sync::RwLock;
 use tower::ServiceExt;
 
+/// Echo provider that answers every conversation with a fixed assistant message
+struct EchoProvider;
+
+#[async_trait]
+impl LLMProvider for EchoProvider {
+    async fn complete(
+        &self,
+        messages: Vec<CanonicalMessage>,
+    ) -> Result<CanonicalMessage, SentinelError> {
+        let last = messages
+            .last()
+            .map(|msg| msg.content.clone())
+            .unwrap_or_default();
+        Ok(CanonicalMessage::new(
+            Role::Assistant,
+            format!("echo: {}", last),
+        ))
+    }
+
+    async fn stream(
+        &self,
+        _messages: Vec<CanonicalMessage>,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = Result<String, SentinelError>> + Send + Unpin>,
+        SentinelError,
+    > {
+        Ok(Box::new(futures::stream::empty()))
+    }
+}
+
+/// Provider that logs the start and end of each completion, pausing in between
+struct RecordingProvider {
+    log: Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl LLMProvider for RecordingProvider {
+    async fn complete(
+        &self,
+        messages: Vec<CanonicalMessage>,
+    ) -> Result<CanonicalMessage, SentinelError> {
+        let last = messages
+            .last()
+            .map(|msg| msg.content.clone())
+            .unwrap_or_default();
+        self.log.lock().unwrap().push(format!("start:{}", last));
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        self.log.lock().unwrap().push(format!("end:{}", last));
+        Ok(CanonicalMessage::new(Role::Assistant, last))
+    }
+
+    async fn stream(
+        &self,
+        _messages: Vec<CanonicalMessage>,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = Result<String, SentinelError>> + Send + Unpin>,
+        SentinelError,
+    > {
+        Ok(Box::new(futures::stream::empty()))
+    }
+}
+
+/// Provider that records the completion parameters it receives
+#[derive(Default)]
+struct ParamsProvider {
+    params: Arc<std::sync::Mutex<Vec<CompletionParams>>>,
+}
+
+#[async_trait]
+impl LLMProvider for ParamsProvider {
+    async fn complete(
+        &self,
+        messages: Vec<CanonicalMessage>,
+    ) -> Result<CanonicalMessage, SentinelError> {
+        self.complete_with_params(messages, CompletionParams::default())
+            .await
+    }
+
+    async fn complete_with_params(
+        &self,
+        _messages: Vec<CanonicalMessage>,
+        params: CompletionParams,
+    ) -> Result<CanonicalMessage, SentinelError> {
+        self.params.lock().unwrap().push(params);
+        Ok(CanonicalMessage::new(Role::Assistant, "ok".to_string()))
+    }
+
+    async fn stream(
+        &self,
+        _messages: Vec<CanonicalMessage>,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = Result<String, SentinelError>> + Send + Unpin>,
+        SentinelError,
+    > {
+        Ok(Box::new(futures::stream::empty()))
+    }
+}
+
 /// Helper to create a test router with API key store
 fn create_test_router() -> (axum::Router, Arc<ApiKeyStore>) {
     let key_store = Arc::new(ApiKeyStore::new());
-    let app = create_router(key_store.clone());
+    let llm_provider: Arc<dyn LLMProvider> = Arc::new(EchoProvider);
+    let supervisor = Arc::new(RwLock::new(Supervisor::new()));
+    let app_state = AppState::new(key_store.clone(), llm_provider, Some(supervisor));
+    let app = create_router(app_state);
     (app, key_store)
 }
 
+/// Helper to create a test router with a small per-conversation token budget
+fn create_budgeted_test_router(max_tokens: u64) -> (axum::Router, Arc<ApiKeyStore>) {
+    let key_store = Arc::new(ApiKeyStore::new());
+    let llm_provider: Arc<dyn LLMProvider> = Arc::new(EchoProvider);
+    let app_state = AppState::new(key_store.clone(), llm_provider, None)
+        .with_conversation_budgets(Arc::new(ConversationBudgets::new(max_tokens)));
+    let app = create_router(app_state);
+    (app, key_store)
+}
+
+/// Helper to create a test router that also exposes its supervisor
+fn create_supervised_test_router() -> (axum::Router, Arc<ApiKeyStore>, Arc<RwLock<Supervisor>>) {
+    let key_store = Arc::new(ApiKeyStore::new());
+    let llm_provider: Arc<dyn LLMProvider> = Arc::new(EchoProvider);
+    let supervisor = Arc::new(RwLock::new(Supervisor::new()));
+    let app_state = AppState::new(key_store.clone(), llm_provider, Some(supervisor.clone()));
+    let app = create_router(app_state);
+    (app, key_store, supervisor)
+}
+
+/// Helper to build a chat request body for a conversation
+fn conversation_request_body(conversation_id: &str, content: &str) -> String {
+    let request = ChatCompletionRequest {
+        messages: vec![CanonicalMessage::new(Role::User, content.to_string())],
+        model: None,
+        temperature: None,
+        max_tokens: None,
+        stream: false,
+        conversation_id: Some(ConversationId::new(conversation_id.to_string())),
+        session_id: None,
+    };
+    serde_json::to_string(&request).unwrap()
+}
+
 /// Helper to add a test API key
-async fn add_test_key(
-    key_store: &Arc<ApiKeyStore>,
-    key: &str,
-    key_id: &str,
-    level: AuthLevel,
-) {
+async fn add_test_key(key_store: &Arc<ApiKeyStore>, key: &str, key_id: &str, level: AuthLevel) {
     key_store
         .add_key(key.to_string(), ApiKeyId::new(key_id.to_string()), level)
-        .await;
+        .await
+        .unwrap();
 }
 
 /// Helper to make a GET request
@@ -68,11 +209,7 @@ async fn make_post_request(
 
     let response = router
         .clone()
-        .oneshot(
-            request_builder
-                .body(Body::from(body.to_string()))
-                .unwrap(),
-        )
+        .oneshot(request_builder.body(Body::from(body.to_string())).unwrap())
         .await
         .unwrap();
 
@@ -117,6 +254,8 @@ async fn test_chat_completion_requires_authentication() {
         temperature: None,
         max_tokens: None,
         stream: false,
+        conversation_id: None,
+        session_id: None,
     };
 
     // Request without authentication should fail
@@ -145,17 +284,26 @@ async fn test_chat_completion_with_valid_auth() {
         temperature: None,
         max_tokens: None,
         stream: false,
+        conversation_id: None,
+        session_id: None,
     };
 
     let body_json = serde_json::to_string(&request).unwrap();
     let auth_header = format!("Bearer {}", api_key);
-    let (status, body) = make_post_request(&router, "/v1/chat/completions", &body_json, Some(&auth_header)).await;
+    let (status, body) = make_post_request(
+        &router,
+        "/v1/chat/completions",
+        &body_json,
+        Some(&auth_header),
+    )
+    .await;
 
     assert_eq!(status, StatusCode::OK);
     let completion: ChatCompletionResponse = serde_json::from_slice(&body).unwrap();
     assert_eq!(completion.message.role, Role::Assistant);
     assert!(!completion.message.content.is_empty());
-    assert_eq!(completion.model, "sentinel-orchestrator");
+    // No model configured on the state, so the built-in default is reported
+    assert_eq!(completion.model, "gpt-4o-mini");
 }
 
 #[tokio::test]
@@ -171,11 +319,19 @@ async fn test_chat_completion_requires_write_access() {
         temperature: None,
         max_tokens: None,
         stream: false,
+        conversation_id: None,
+        session_id: None,
     };
 
     let body_json = serde_json::to_string(&request).unwrap();
     let auth_header = format!("Bearer {}", api_key);
-    let (status, body) = make_post_request(&router, "/v1/chat/completions", &body_json, Some(&auth_header)).await;
+    let (status, body) = make_post_request(
+        &router,
+        "/v1/chat/completions",
+        &body_json,
+        Some(&auth_header),
+    )
+    .await;
 
     assert_eq!(status, StatusCode::FORBIDDEN);
     // Middleware returns error in nested format
@@ -200,11 +356,19 @@ async fn test_chat_completion_with_admin_access() {
         temperature: None,
         max_tokens: None,
         stream: false,
+        conversation_id: None,
+        session_id: None,
     };
 
     let body_json = serde_json::to_string(&request).unwrap();
     let auth_header = format!("Bearer {}", api_key);
-    let (status, _) = make_post_request(&router, "/v1/chat/completions", &body_json, Some(&auth_header)).await;
+    let (status, _) = make_post_request(
+        &router,
+        "/v1/chat/completions",
+        &body_json,
+        Some(&auth_header),
+    )
+    .await;
 
     assert_eq!(status, StatusCode::OK);
 }
@@ -219,11 +383,19 @@ async fn test_chat_completion_invalid_api_key() {
         temperature: None,
         max_tokens: None,
         stream: false,
+        conversation_id: None,
+        session_id: None,
     };
 
     // Use a key that doesn't exist
     let body_json = serde_json::to_string(&request).unwrap();
-    let (status, body) = make_post_request(&router, "/v1/chat/completions", &body_json, Some("Bearer sk-invalid-key-1234567890123456")).await;
+    let (status, body) = make_post_request(
+        &router,
+        "/v1/chat/completions",
+        &body_json,
+        Some("Bearer sk-invalid-key-1234567890123456"),
+    )
+    .await;
 
     assert_eq!(status, StatusCode::UNAUTHORIZED);
     // Middleware returns error in nested format: {"error": {"code": "...", "message": "...", "type": "..."}}
@@ -239,17 +411,25 @@ async fn test_chat_completion_bearer_token_format() {
     add_test_key(&key_store, api_key, "test-key", AuthLevel::Write).await;
 
     let request = ChatCompletionRequest {
-        messages: vec![],
+        messages: vec![CanonicalMessage::new(Role::User, "Hello".to_string())],
         model: None,
         temperature: None,
         max_tokens: None,
         stream: false,
+        conversation_id: None,
+        session_id: None,
     };
 
     // Test with "Bearer " prefix
     let body_json = serde_json::to_string(&request).unwrap();
     let auth_header = format!("Bearer {}", api_key);
-    let (status, _) = make_post_request(&router, "/v1/chat/completions", &body_json, Some(&auth_header)).await;
+    let (status, _) = make_post_request(
+        &router,
+        "/v1/chat/completions",
+        &body_json,
+        Some(&auth_header),
+    )
+    .await;
 
     assert_eq!(status, StatusCode::OK);
 }
@@ -356,11 +536,19 @@ async fn test_chat_completion_request_validation() {
         temperature: Some(0.7),
         max_tokens: Some(100),
         stream: false,
+        conversation_id: None,
+        session_id: None,
     };
 
     let body_json = serde_json::to_string(&request).unwrap();
     let auth_header = format!("Bearer {}", api_key);
-    let (status, body) = make_post_request(&router, "/v1/chat/completions", &body_json, Some(&auth_header)).await;
+    let (status, body) = make_post_request(
+        &router,
+        "/v1/chat/completions",
+        &body_json,
+        Some(&auth_header),
+    )
+    .await;
 
     assert_eq!(status, StatusCode::OK);
     let completion: ChatCompletionResponse = serde_json::from_slice(&body).unwrap();
@@ -392,15 +580,23 @@ async fn test_multiple_api_keys() {
 
     // Read key should NOT work for chat completion
     let request = ChatCompletionRequest {
-        messages: vec![],
+        messages: vec![CanonicalMessage::new(Role::User, "Hello".to_string())],
         model: None,
         temperature: None,
         max_tokens: None,
         stream: false,
+        conversation_id: None,
+        session_id: None,
     };
     let body_json = serde_json::to_string(&request).unwrap();
     let auth_header = format!("Bearer {}", read_key);
-    let (status, _) = make_post_request(&router, "/v1/chat/completions", &body_json, Some(&auth_header)).await;
+    let (status, _) = make_post_request(
+        &router,
+        "/v1/chat/completions",
+        &body_json,
+        Some(&auth_header),
+    )
+    .await;
     assert_eq!(status, StatusCode::FORBIDDEN);
 
     // Write key should work for both
@@ -418,7 +614,13 @@ async fn test_multiple_api_keys() {
     assert_eq!(response.status(), StatusCode::OK);
 
     let auth_header = format!("Bearer {}", write_key);
-    let (status, _) = make_post_request(&router, "/v1/chat/completions", &body_json, Some(&auth_header)).await;
+    let (status, _) = make_post_request(
+        &router,
+        "/v1/chat/completions",
+        &body_json,
+        Some(&auth_header),
+    )
+    .await;
     assert_eq!(status, StatusCode::OK);
 }
 
@@ -445,19 +647,24 @@ async fn test_chat_completion_response_structure() {
     add_test_key(&key_store, api_key, "test-key", AuthLevel::Write).await;
 
     let request = ChatCompletionRequest {
-        messages: vec![CanonicalMessage::new(
-            Role::User,
-            "Hello".to_string(),
-        )],
+        messages: vec![CanonicalMessage::new(Role::User, "Hello".to_string())],
         model: None,
         temperature: None,
         max_tokens: None,
         stream: false,
+        conversation_id: None,
+        session_id: None,
     };
 
     let body_json = serde_json::to_string(&request).unwrap();
     let auth_header = format!("Bearer {}", api_key);
-    let (status, body) = make_post_request(&router, "/v1/chat/completions", &body_json, Some(&auth_header)).await;
+    let (status, body) = make_post_request(
+        &router,
+        "/v1/chat/completions",
+        &body_json,
+        Some(&auth_header),
+    )
+    .await;
 
     assert_eq!(status, StatusCode::OK);
     let completion: ChatCompletionResponse = serde_json::from_slice(&body).unwrap();
@@ -469,3 +676,786 @@ async fn test_chat_completion_response_structure() {
     // Usage is optional and may be None
 }
 
+#[tokio::test]
+async fn test_conversation_budget_exceeded() {
+    // Each exchange costs ~21 tokens (10 prompt + 11 echoed completion)
+    let (router, key_store) = create_budgeted_test_router(30);
+    let api_key = "sk-test123456789012345678901234567890";
+    add_test_key(&key_store, api_key, "test-key", AuthLevel::Write).await;
+    let auth_header = format!("Bearer {}", api_key);
+    let body = conversation_request_body("conv-runaway", &"x".repeat(40));
+
+    // First two completions fit under the budget (0 -> 21 -> 42)
+    for _ in 0..2 {
+        let (status, _) =
+            make_post_request(&router, "/v1/chat/completions", &body, Some(&auth_header)).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    // Budget is now exhausted
+    let (status, body_bytes) =
+        make_post_request(&router, "/v1/chat/completions", &body, Some(&auth_header)).await;
+    assert_eq!(status, StatusCode::PAYMENT_REQUIRED);
+    let error_json: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(error_json["code"], "conversation_budget_exceeded");
+    assert_eq!(error_json["details"]["conversation_id"], "conv-runaway");
+    assert_eq!(error_json["details"]["limit"], "30");
+
+    // Other conversations are unaffected
+    let other = conversation_request_body("conv-other", &"x".repeat(40));
+    let (status, _) =
+        make_post_request(&router, "/v1/chat/completions", &other, Some(&auth_header)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Requests without a conversation ID are never budgeted
+    let stateless = serde_json::to_string(&ChatCompletionRequest {
+        messages: vec![CanonicalMessage::new(Role::User, "x".repeat(40))],
+        model: None,
+        temperature: None,
+        max_tokens: None,
+        stream: false,
+        conversation_id: None,
+        session_id: None,
+    })
+    .unwrap();
+    let (status, _) = make_post_request(
+        &router,
+        "/v1/chat/completions",
+        &stateless,
+        Some(&auth_header),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_conversation_budget_admin_reset() {
+    let (router, key_store) = create_budgeted_test_router(30);
+    let write_key = "sk-write123456789012345678901234567890";
+    let admin_key = "sk-admin123456789012345678901234567890";
+    add_test_key(&key_store, write_key, "write-key", AuthLevel::Write).await;
+    add_test_key(&key_store, admin_key, "admin-key", AuthLevel::Admin).await;
+    let write_auth = format!("Bearer {}", write_key);
+    let admin_auth = format!("Bearer {}", admin_key);
+    let body = conversation_request_body("conv-reset", &"x".repeat(80));
+
+    // A single large exchange exhausts the budget
+    let (status, _) =
+        make_post_request(&router, "/v1/chat/completions", &body, Some(&write_auth)).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) =
+        make_post_request(&router, "/v1/chat/completions", &body, Some(&write_auth)).await;
+    assert_eq!(status, StatusCode::PAYMENT_REQUIRED);
+
+    // Write access is not enough to reset
+    let reset_uri = "/v1/conversations/conv-reset/budget/reset";
+    let (status, _) = make_post_request(&router, reset_uri, "", Some(&write_auth)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Admin reset restores the conversation
+    let (status, _) = make_post_request(&router, reset_uri, "", Some(&admin_auth)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) =
+        make_post_request(&router, "/v1/chat/completions", &body, Some(&write_auth)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_agent_message_idle_agent_accepted() {
+    let (router, key_store, supervisor) = create_supervised_test_router();
+    let key = "sk-write123456789012345678901234567890";
+    add_test_key(&key_store, key, "write-key", AuthLevel::Write).await;
+    let auth_header = format!("Bearer {}", key);
+    let agent_id = supervisor.write().await.spawn_agent().unwrap();
+    let body =
+        serde_json::to_string(&CanonicalMessage::new(Role::User, "Hello".to_string())).unwrap();
+
+    let uri = format!("/v1/agents/{}/messages?wait=false", agent_id.0);
+    let (status, _) = make_post_request(&router, &uri, &body, Some(&auth_header)).await;
+
+    assert_eq!(status, StatusCode::ACCEPTED);
+}
+
+/// Processor holding each message until the test adds a permit to `gate`
+struct GatedProcessor {
+    started: tokio::sync::mpsc::UnboundedSender<()>,
+    gate: tokio::sync::Semaphore,
+}
+
+#[async_trait]
+impl MessageProcessor for GatedProcessor {
+    async fn process(&self, _agent_id: AgentId, _msg: ActorMessage) -> anyhow::Result<()> {
+        let _ = self.started.send(());
+        self.gate.acquire().await?.forget();
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_agent_message_busy_agent_rejected_without_wait() {
+    let key_store = Arc::new(ApiKeyStore::new());
+    let (started, mut started_rx) = tokio::sync::mpsc::unbounded_channel();
+    let processor = Arc::new(GatedProcessor {
+        started,
+        gate: tokio::sync::Semaphore::new(0),
+    });
+    let supervisor = Arc::new(RwLock::new(
+        Supervisor::new().with_message_processor(processor.clone()),
+    ));
+    let router = create_router(AppState::new(
+        key_store.clone(),
+        Arc::new(EchoProvider),
+        Some(supervisor.clone()),
+    ));
+    let key = "sk-write123456789012345678901234567890";
+    add_test_key(&key_store, key, "write-key", AuthLevel::Write).await;
+    let auth_header = format!("Bearer {}", key);
+    let (agent_id, mut state_rx) = {
+        let mut supervisor = supervisor.write().await;
+        let agent_id = supervisor.spawn_agent().unwrap();
+        let state_rx = supervisor.subscribe_agent_state(agent_id).unwrap();
+        (agent_id, state_rx)
+    };
+    let body =
+        serde_json::to_string(&CanonicalMessage::new(Role::User, "Hello".to_string())).unwrap();
+    let fail_fast_uri = format!("/v1/agents/{}/messages?wait=false", agent_id.0);
+
+    // The first message is held in flight by the processor
+    let (status, _) = make_post_request(&router, &fail_fast_uri, &body, Some(&auth_header)).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    started_rx.recv().await.unwrap();
+
+    // wait=false fails fast while a message is being processed
+    let (status, response) =
+        make_post_request(&router, &fail_fast_uri, &body, Some(&auth_header)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let error: ErrorResponse = serde_json::from_slice(&response).unwrap();
+    assert_eq!(error.code, "agent_busy");
+
+    // The default still queues behind current work
+    let uri = format!("/v1/agents/{}/messages", agent_id.0);
+    let (status, _) = make_post_request(&router, &uri, &body, Some(&auth_header)).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+
+    // Once both finish the agent is left Reflecting, yet it is no longer busy
+    processor.gate.add_permits(2);
+    state_rx
+        .wait_for(|state| *state == AgentState::Reflecting)
+        .await
+        .unwrap();
+    let (status, _) = make_post_request(&router, &fail_fast_uri, &body, Some(&auth_header)).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+}
+
+#[tokio::test]
+async fn test_agent_message_unknown_agent() {
+    let (router, key_store, _supervisor) = create_supervised_test_router();
+    let key = "sk-write123456789012345678901234567890";
+    add_test_key(&key_store, key, "write-key", AuthLevel::Write).await;
+    let auth_header = format!("Bearer {}", key);
+    let body =
+        serde_json::to_string(&CanonicalMessage::new(Role::User, "Hello".to_string())).unwrap();
+
+    let uri = format!("/v1/agents/{}/messages", uuid::Uuid::new_v4());
+    let (status, response) = make_post_request(&router, &uri, &body, Some(&auth_header)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let error: ErrorResponse = serde_json::from_slice(&response).unwrap();
+    assert_eq!(error.code, "agent_not_found");
+}
+
+#[tokio::test]
+async fn test_terminate_all_agents() {
+    let (router, key_store, supervisor) = create_supervised_test_router();
+    let key = "sk-admin123456789012345678901234567890";
+    add_test_key(&key_store, key, "admin-key", AuthLevel::Admin).await;
+    let auth_header = format!("Bearer {}", key);
+    {
+        let mut supervisor = supervisor.write().await;
+        for _ in 0..4 {
+            supervisor.spawn_agent().unwrap();
+        }
+        assert_eq!(supervisor.agent_count(), 4);
+    }
+
+    let (status, response) = make_post_request(
+        &router,
+        "/v1/agents/terminate-all",
+        r#"{"deadline_ms": 5000}"#,
+        Some(&auth_header),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let report: TerminateAgentsResponse = serde_json::from_slice(&response).unwrap();
+    assert_eq!(report.terminated, 4);
+    assert_eq!(report.failed, 0);
+    assert_eq!(supervisor.read().await.agent_count(), 0);
+}
+
+#[tokio::test]
+async fn test_terminate_all_agents_filters_by_name_and_requires_admin() {
+    let (router, key_store, supervisor) = create_supervised_test_router();
+    let write_key = "sk-write123456789012345678901234567890";
+    let admin_key = "sk-admin123456789012345678901234567890";
+    add_test_key(&key_store, write_key, "write-key", AuthLevel::Write).await;
+    add_test_key(&key_store, admin_key, "admin-key", AuthLevel::Admin).await;
+    let keep = {
+        let mut supervisor = supervisor.write().await;
+        supervisor
+            .spawn_named_agent(Some("batch-1".to_string()))
+            .unwrap();
+        supervisor
+            .spawn_named_agent(Some("batch-2".to_string()))
+            .unwrap();
+        supervisor
+            .spawn_named_agent(Some("keeper".to_string()))
+            .unwrap()
+    };
+    let body = r#"{"name_prefix": "batch-"}"#;
+
+    let (status, _) = make_post_request(
+        &router,
+        "/v1/agents/terminate-all",
+        body,
+        Some(&format!("Bearer {}", write_key)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, response) = make_post_request(
+        &router,
+        "/v1/agents/terminate-all",
+        body,
+        Some(&format!("Bearer {}", admin_key)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let report: TerminateAgentsResponse = serde_json::from_slice(&response).unwrap();
+    assert_eq!(report.terminated, 2);
+    assert_eq!(supervisor.read().await.agent_ids(), vec![keep]);
+}
+
+#[tokio::test]
+async fn test_maintenance_mode_rejects_writes_but_serves_reads() {
+    let (router, key_store) = create_test_router();
+    let admin_key = "sk-admin123456789012345678901234567890";
+    add_test_key(&key_store, admin_key, "admin-key", AuthLevel::Admin).await;
+    let admin_auth = format!("Bearer {}", admin_key);
+
+    let (status, body) = make_post_request(
+        &router,
+        "/v1/admin/maintenance",
+        r#"{"enabled":true}"#,
+        Some(&admin_auth),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let mode: MaintenanceMode = serde_json::from_slice(&body).unwrap();
+    assert!(mode.enabled);
+
+    // Writes are rejected
+    let body = conversation_request_body("conv-maintenance", "Hello");
+    let (status, body) =
+        make_post_request(&router, "/v1/chat/completions", &body, Some(&admin_auth)).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"]["code"], "maintenance");
+
+    // Reads and health keep working
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/v1/agents/status")
+                .header(header::AUTHORIZATION, &admin_auth)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let (status, _) = make_get_request(&router, "/health").await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Switching maintenance off restores writes
+    let (status, _) = make_post_request(
+        &router,
+        "/v1/admin/maintenance",
+        r#"{"enabled":false}"#,
+        Some(&admin_auth),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let body = conversation_request_body("conv-maintenance", "Hello");
+    let (status, _) =
+        make_post_request(&router, "/v1/chat/completions", &body, Some(&admin_auth)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_maintenance_mode_from_config_and_toggle_requires_admin() {
+    let key_store = Arc::new(ApiKeyStore::new());
+    let app_state =
+        AppState::new(key_store.clone(), Arc::new(EchoProvider), None).with_maintenance_mode(true);
+    let router = create_router(app_state);
+    let write_key = "sk-write123456789012345678901234567890";
+    add_test_key(&key_store, write_key, "write-key", AuthLevel::Write).await;
+    let write_auth = format!("Bearer {}", write_key);
+
+    let body = conversation_request_body("conv-maintenance", "Hello");
+    let (status, _) =
+        make_post_request(&router, "/v1/chat/completions", &body, Some(&write_auth)).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+
+    // Unauthenticated writes are still reported as 401, not 503
+    let (status, _) = make_post_request(&router, "/v1/chat/completions", &body, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = make_post_request(
+        &router,
+        "/v1/admin/maintenance",
+        r#"{"enabled":false}"#,
+        Some(&write_auth),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+/// Fire two chat requests concurrently and return the provider's event log
+async fn concurrent_completions(first: (&str, &str), second: (&str, &str)) -> Vec<String> {
+    let key_store = Arc::new(ApiKeyStore::new());
+    let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let provider = RecordingProvider { log: log.clone() };
+    let router = create_router(AppState::new(key_store.clone(), Arc::new(provider), None));
+    let key = "sk-write123456789012345678901234567890";
+    add_test_key(&key_store, key, "write-key", AuthLevel::Write).await;
+    let auth_header = format!("Bearer {}", key);
+    let first_body = conversation_request_body(first.0, first.1);
+    let second_body = conversation_request_body(second.0, second.1);
+
+    let (first_result, second_result) = tokio::join!(
+        make_post_request(
+            &router,
+            "/v1/chat/completions",
+            &first_body,
+            Some(&auth_header)
+        ),
+        make_post_request(
+            &router,
+            "/v1/chat/completions",
+            &second_body,
+            Some(&auth_header)
+        ),
+    );
+    assert_eq!(first_result.0, StatusCode::OK);
+    assert_eq!(second_result.0, StatusCode::OK);
+
+    let log = log.lock().unwrap().clone();
+    log
+}
+
+#[tokio::test]
+async fn test_concurrent_completions_for_one_conversation_do_not_interleave() {
+    let log = concurrent_completions(("conv-locked", "first"), ("conv-locked", "second")).await;
+
+    assert_eq!(log.len(), 4);
+    for turn in log.chunks(2) {
+        let content = turn[0].strip_prefix("start:").unwrap();
+        assert_eq!(turn[1], format!("end:{}", content));
+    }
+}
+
+#[tokio::test]
+async fn test_concurrent_completions_for_different_conversations_run_in_parallel() {
+    let log = concurrent_completions(("conv-a", "first"), ("conv-b", "second")).await;
+
+    assert_eq!(log.len(), 4);
+    assert!(log[0].starts_with("start:"));
+    assert!(log[1].starts_with("start:"));
+}
+
+#[tokio::test]
+async fn test_chat_completion_passes_params_to_provider() {
+    let key_store = Arc::new(ApiKeyStore::new());
+    let provider = Arc::new(ParamsProvider::default());
+    let defaults = std::collections::HashMap::from([(
+        "gpt-4o".to_string(),
+        ModelParams {
+            temperature: Some(0.9),
+            max_tokens: Some(64),
+        },
+    )]);
+    let app_state =
+        AppState::new(key_store.clone(), provider.clone(), None).with_model_defaults(defaults);
+    let router = create_router(app_state);
+    let key = "sk-write123456789012345678901234567890";
+    add_test_key(&key_store, key, "write-key", AuthLevel::Write).await;
+
+    let request = ChatCompletionRequest {
+        messages: vec![CanonicalMessage::new(Role::User, "Hello".to_string())],
+        model: Some("gpt-4o".to_string()),
+        temperature: Some(0.3),
+        max_tokens: None,
+        stream: false,
+        conversation_id: None,
+        session_id: None,
+    };
+    let (status, _) = make_post_request(
+        &router,
+        "/v1/chat/completions",
+        &serde_json::to_string(&request).unwrap(),
+        Some(&format!("Bearer {}", key)),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    // Client values win; unset values come from the model defaults
+    assert_eq!(
+        provider.params.lock().unwrap().as_slice(),
+        [CompletionParams {
+            model: Some("gpt-4o".to_string()),
+            temperature: Some(0.3),
+            max_tokens: Some(64),
+        }]
+    );
+}
+
+/// Helper to build a router restricted to the given models and return a write auth header
+async fn create_model_restricted_router(allowed: &[&str]) -> (axum::Router, String) {
+    let key_store = Arc::new(ApiKeyStore::new());
+    let llm_provider: Arc<dyn LLMProvider> = Arc::new(EchoProvider);
+    let app_state = AppState::new(key_store.clone(), llm_provider, None)
+        .with_allowed_models(allowed.iter().map(|model| model.to_string()));
+    let key = "sk-write123456789012345678901234567890";
+    add_test_key(&key_store, key, "write-key", AuthLevel::Write).await;
+    (create_router(app_state), format!("Bearer {}", key))
+}
+
+/// Helper to send a chat completion naming a model
+async fn chat_with_model(
+    router: &axum::Router,
+    auth_header: &str,
+    model: &str,
+) -> (StatusCode, Vec<u8>) {
+    let request = ChatCompletionRequest {
+        messages: vec![CanonicalMessage::new(Role::User, "Hello".to_string())],
+        model: Some(model.to_string()),
+        temperature: None,
+        max_tokens: None,
+        stream: false,
+        conversation_id: None,
+        session_id: None,
+    };
+    make_post_request(
+        router,
+        "/v1/chat/completions",
+        &serde_json::to_string(&request).unwrap(),
+        Some(auth_header),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_model_allow_list_permits_listed_model() {
+    let (router, auth_header) = create_model_restricted_router(&["gpt-4o", "gpt-4o-mini"]).await;
+
+    let (status, _) = chat_with_model(&router, &auth_header, "gpt-4o-mini").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_model_allow_list_rejects_unlisted_model() {
+    let (router, auth_header) = create_model_restricted_router(&["gpt-4o"]).await;
+
+    let (status, body) = chat_with_model(&router, &auth_header, "gpt-3.5-turbo").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let error: ErrorResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error.code, "model_not_allowed");
+    assert_eq!(
+        error.details.unwrap().get("model").unwrap(),
+        "gpt-3.5-turbo"
+    );
+}
+
+#[tokio::test]
+async fn test_no_model_allow_list_permits_any_model() {
+    let (router, auth_header) = create_model_restricted_router(&[]).await;
+
+    let (status, _) = chat_with_model(&router, &auth_header, "any-model-at-all").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+/// Provider that replies with the number of messages it was sent
+struct MessageCountProvider;
+
+#[async_trait]
+impl LLMProvider for MessageCountProvider {
+    async fn complete(
+        &self,
+        messages: Vec<CanonicalMessage>,
+    ) -> Result<CanonicalMessage, SentinelError> {
+        Ok(CanonicalMessage::new(
+            Role::Assistant,
+            format!("{} messages", messages.len()),
+        ))
+    }
+
+    async fn stream(
+        &self,
+        _messages: Vec<CanonicalMessage>,
+    ) -> Result<
+        Box<dyn futures::Stream<Item = Result<String, SentinelError>> + Send + Unpin>,
+        SentinelError,
+    > {
+        Ok(Box::new(futures::stream::empty()))
+    }
+}
+
+/// Vector store that stores nothing
+struct NullVectorStore;
+
+#[async_trait]
+impl VectorStore for NullVectorStore {
+    async fn upsert(
+        &self,
+        _id: MessageId,
+        _embedding: Vec<f32>,
+        _metadata: std::collections::HashMap<String, String>,
+    ) -> Result<(), SentinelError> {
+        Ok(())
+    }
+
+    async fn search_filtered(
+        &self,
+        _query_embedding: Vec<f32>,
+        _limit: usize,
+        _filters: Option<std::collections::HashMap<String, String>>,
+    ) -> Result<Vec<(MessageId, f32)>, SentinelError> {
+        Ok(Vec::new())
+    }
+
+    async fn delete(&self, _id: MessageId) -> Result<(), SentinelError> {
+        Ok(())
+    }
+}
+
+/// Helper to send a chat completion with one user message in a session
+async fn chat_in_session(
+    router: &axum::Router,
+    auth_header: &str,
+    session_id: Option<&str>,
+    content: &str,
+) -> ChatCompletionResponse {
+    let request = ChatCompletionRequest {
+        messages: vec![CanonicalMessage::new(Role::User, content.to_string())],
+        model: None,
+        temperature: None,
+        max_tokens: None,
+        stream: false,
+        conversation_id: None,
+        session_id: session_id.map(str::to_string),
+    };
+    let (status, body) = make_post_request(
+        router,
+        "/v1/chat/completions",
+        &serde_json::to_string(&request).unwrap(),
+        Some(auth_header),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_chat_session_accumulates_messages() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let memory_manager = Arc::new(
+        MemoryManager::new(temp_dir.path().join("sled"), Arc::new(NullVectorStore)).unwrap(),
+    );
+    let key_store = Arc::new(ApiKeyStore::new());
+    let app_state = AppState::new(key_store.clone(), Arc::new(MessageCountProvider), None)
+        .with_memory_manager(memory_manager.clone());
+    let router = create_router(app_state);
+    let key = "sk-write123456789012345678901234567890";
+    add_test_key(&key_store, key, "write-key", AuthLevel::Write).await;
+    let auth_header = format!("Bearer {}", key);
+
+    let first = chat_in_session(&router, &auth_header, Some("session-1"), "Hello").await;
+    assert_eq!(first.message.content, "1 messages");
+    assert_eq!(first.session_id.as_deref(), Some("session-1"));
+
+    // The second call sees the first exchange plus the new message
+    let second = chat_in_session(&router, &auth_header, Some("session-1"), "Again").await;
+    assert_eq!(second.message.content, "3 messages");
+
+    let history = memory_manager.session_history("session-1").await;
+    let contents: Vec<&str> = history.iter().map(|msg| msg.content.as_str()).collect();
+    assert_eq!(contents, ["Hello", "1 messages", "Again", "3 messages"]);
+
+    // Without a session the request stays stateless
+    let stateless = chat_in_session(&router, &auth_header, None, "Hello").await;
+    assert_eq!(stateless.message.content, "1 messages");
+    assert!(stateless.session_id.is_none());
+}
+
+#[tokio::test]
+async fn test_conversation_id_cannot_collide_with_session_lock() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let memory_manager = Arc::new(
+        MemoryManager::new(temp_dir.path().join("sled"), Arc::new(NullVectorStore)).unwrap(),
+    );
+    let key_store = Arc::new(ApiKeyStore::new());
+    let app_state = AppState::new(key_store.clone(), Arc::new(MessageCountProvider), None)
+        .with_memory_manager(memory_manager);
+    let router = create_router(app_state);
+    let key = "sk-write123456789012345678901234567890";
+    add_test_key(&key_store, key, "write-key", AuthLevel::Write).await;
+
+    // A conversation ID spelled like the session's lock name must not lock it twice
+    let request = ChatCompletionRequest {
+        messages: vec![CanonicalMessage::new(Role::User, "Hello".to_string())],
+        model: None,
+        temperature: None,
+        max_tokens: None,
+        stream: false,
+        conversation_id: Some(ConversationId::new("session:abc".to_string())),
+        session_id: Some("abc".to_string()),
+    };
+    let (status, _) = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        make_post_request(
+            &router,
+            "/v1/chat/completions",
+            &serde_json::to_string(&request).unwrap(),
+            Some(&format!("Bearer {}", key)),
+        ),
+    )
+    .await
+    .expect("completion deadlocked on its own locks");
+    assert_eq!(status, StatusCode::OK);
+}
+
+/// Spawn hook seeding each new agent's short-term memory with a system message
+struct PersonaHook {
+    memory_manager: Arc<MemoryManager>,
+}
+
+#[async_trait]
+impl SpawnHook for PersonaHook {
+    async fn on_spawn(&self, agent_id: AgentId) -> anyhow::Result<()> {
+        self.memory_manager
+            .append_message(
+                agent_id,
+                CanonicalMessage::new(Role::System, "You are a planner.".to_string()),
+            )
+            .await
+    }
+}
+
+#[tokio::test]
+async fn test_spawn_hook_seeds_memory_before_first_message() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let memory_manager = Arc::new(
+        MemoryManager::new(temp_dir.path().join("sled"), Arc::new(NullVectorStore)).unwrap(),
+    );
+    let supervisor = Arc::new(RwLock::new(Supervisor::new().with_spawn_hook(Arc::new(
+        PersonaHook {
+            memory_manager: memory_manager.clone(),
+        },
+    ))));
+    let key_store = Arc::new(ApiKeyStore::new());
+    let app_state = AppState::new(
+        key_store.clone(),
+        Arc::new(EchoProvider),
+        Some(supervisor.clone()),
+    );
+    let router = create_router(app_state);
+    let key = "sk-write123456789012345678901234567890";
+    add_test_key(&key_store, key, "write-key", AuthLevel::Write).await;
+
+    let (status, body) =
+        make_post_request(&router, "/v1/agents", "", Some(&format!("Bearer {}", key))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let spawned: SpawnAgentResponse = serde_json::from_slice(&body).unwrap();
+
+    // The seeded message is in memory as soon as the spawn request returns
+    let memory = memory_manager.snapshot_short_term(spawned.id).await;
+    assert_eq!(memory.len(), 1);
+    assert_eq!(memory[0].role, Role::System);
+    assert_eq!(memory[0].content, "You are a planner.");
+    assert!(supervisor.read().await.agent_ids().contains(&spawned.id));
+}
+
+/// Helper to send an authenticated GET for agent status
+async fn get_agent_status(router: &axum::Router, key: &str) -> StatusCode {
+    router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/v1/agents/status")
+                .header(header::AUTHORIZATION, format!("Bearer {}", key))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn test_metrics_count_requests_by_auth_level() {
+    let (router, key_store) = create_test_router();
+    let read_key = "sk-read123456789012345678901234567890";
+    let write_key = "sk-write123456789012345678901234567890";
+    add_test_key(&key_store, read_key, "read-key", AuthLevel::Read).await;
+    add_test_key(&key_store, write_key, "write-key", AuthLevel::Write).await;
+
+    assert_eq!(get_agent_status(&router, read_key).await, StatusCode::OK);
+    assert_eq!(get_agent_status(&router, read_key).await, StatusCode::OK);
+    assert_eq!(get_agent_status(&router, write_key).await, StatusCode::OK);
+
+    let request = ChatCompletionRequest {
+        messages: vec![CanonicalMessage::new(Role::User, "Hello".to_string())],
+        model: None,
+        temperature: None,
+        max_tokens: None,
+        stream: false,
+        conversation_id: None,
+        session_id: None,
+    };
+    let body_json = serde_json::to_string(&request).unwrap();
+    let (status, _) = make_post_request(
+        &router,
+        "/v1/chat/completions",
+        &body_json,
+        Some(&format!("Bearer {}", write_key)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Rejected by authorization, so never reaches the metrics middleware
+    let (status, _) = make_post_request(
+        &router,
+        "/v1/chat/completions",
+        &body_json,
+        Some(&format!("Bearer {}", read_key)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = make_get_request(&router, "/metrics").await;
+    assert_eq!(status, StatusCode::OK);
+    let scrape = String::from_utf8(body).unwrap();
+    let lines: Vec<&str> = scrape
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            r#"sentinel_requests_total{auth_level="read",route="/v1/agents/status"} 2"#,
+            r#"sentinel_requests_total{auth_level="write",route="/v1/agents/status"} 1"#,
+            r#"sentinel_requests_total{auth_level="write",route="/v1/chat/completions"} 1"#,
+        ]
+    );
+}
@@ -35,7 +35,7 @@ async fn test_store_messages_in_vector_db() {
 
     for message in messages {
         let response = client
-            .post(&format!("{}/v1/chat/completions", api_base_url()))
+            .post(format!("{}/v1/chat/completions", api_base_url()))
             .header("Authorization", format!("Bearer {}", api_key()))
             .header("Content-Type", "application/json")
             .json(&serde_json::json!({
@@ -46,7 +46,7 @@ async fn test_store_messages_in_vector_db() {
             .unwrap();
 
         assert_eq!(response.status(), reqwest::StatusCode::OK);
-        
+
         // Small delay to allow processing
         sleep(Duration::from_millis(500)).await;
     }
@@ -72,7 +72,7 @@ async fn test_semantic_search_retrieval() {
 
     for msg in store_messages {
         let _response = client
-            .post(&format!("{}/v1/chat/completions", api_base_url()))
+            .post(format!("{}/v1/chat/completions", api_base_url()))
             .header("Authorization", format!("Bearer {}", api_key()))
             .header("Content-Type", "application/json")
             .json(&serde_json::json!({
@@ -92,7 +92,7 @@ async fn test_semantic_search_retrieval() {
     );
 
     let response = client
-        .post(&format!("{}/v1/chat/completions", api_base_url()))
+        .post(format!("{}/v1/chat/completions", api_base_url()))
         .header("Authorization", format!("Bearer {}", api_key()))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
@@ -111,7 +111,7 @@ async fn test_semantic_search_retrieval() {
         .to_lowercase();
 
     // Response should potentially reference stored information
-    assert!(content.len() > 0);
+    assert!(!content.is_empty());
 }
 
 /// Test vector storage with multiple similar queries
@@ -121,14 +121,11 @@ async fn test_vector_storage_deduplication() {
     let client = reqwest::Client::new();
 
     // Send the same message multiple times
-    let message = create_test_message(
-        Role::User,
-        "The capital of France is Paris.",
-    );
+    let message = create_test_message(Role::User, "The capital of France is Paris.");
 
     for _ in 0..5 {
         let response = client
-            .post(&format!("{}/v1/chat/completions", api_base_url()))
+            .post(format!("{}/v1/chat/completions", api_base_url()))
             .header("Authorization", format!("Bearer {}", api_key()))
             .header("Content-Type", "application/json")
             .json(&serde_json::json!({
@@ -160,9 +157,9 @@ async fn test_bulk_vector_storage() {
 
     for fact in facts {
         let message = create_test_message(Role::User, fact);
-        
+
         let response = client
-            .post(&format!("{}/v1/chat/completions", api_base_url()))
+            .post(format!("{}/v1/chat/completions", api_base_url()))
             .header("Authorization", format!("Bearer {}", api_key()))
             .header("Content-Type", "application/json")
             .json(&serde_json::json!({
@@ -190,7 +187,7 @@ async fn test_vector_storage_message_types() {
     );
 
     let response = client
-        .post(&format!("{}/v1/chat/completions", api_base_url()))
+        .post(format!("{}/v1/chat/completions", api_base_url()))
         .header("Authorization", format!("Bearer {}", api_key()))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
@@ -209,7 +206,7 @@ async fn test_vector_storage_message_types() {
     );
 
     let response = client
-        .post(&format!("{}/v1/chat/completions", api_base_url()))
+        .post(format!("{}/v1/chat/completions", api_base_url()))
         .header("Authorization", format!("Bearer {}", api_key()))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
@@ -235,7 +232,7 @@ async fn test_vector_storage_persistence() {
     );
 
     let response1 = client
-        .post(&format!("{}/v1/chat/completions", api_base_url()))
+        .post(format!("{}/v1/chat/completions", api_base_url()))
         .header("Authorization", format!("Bearer {}", api_key()))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
@@ -249,13 +246,10 @@ async fn test_vector_storage_persistence() {
     sleep(Duration::from_secs(1)).await;
 
     // Query in second request - should retrieve stored information
-    let query_msg = create_test_message(
-        Role::User,
-        "What is my favorite color?",
-    );
+    let query_msg = create_test_message(Role::User, "What is my favorite color?");
 
     let response2 = client
-        .post(&format!("{}/v1/chat/completions", api_base_url()))
+        .post(format!("{}/v1/chat/completions", api_base_url()))
         .header("Authorization", format!("Bearer {}", api_key()))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
@@ -272,4 +266,3 @@ async fn test_vector_storage_persistence() {
 fn create_test_message(role: Role, content: &str) -> CanonicalMessage {
     CanonicalMessage::new(role, content.to_string())
 }
-